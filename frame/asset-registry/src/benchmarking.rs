@@ -38,6 +38,14 @@ benchmarks! {
    set_status {
     let user = pre_set_auth::<T>();
    }: _(RawOrigin::Signed(user), CurrencyId::Tifi, true)
+   set_withdrawal_limits {
+    let user = pre_set_auth::<T>();
+    let limits = WithdrawalLimits {
+      asset_cap: Some(1_000_000),
+      account_cap: Some(100_000),
+      delay_threshold: Some(10_000),
+    };
+   }: _(RawOrigin::Signed(user), CurrencyId::Tifi, Some(limits))
 }
 
 impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
@@ -32,6 +32,24 @@ pub use weights::*;
 // Re-export pallet items so that they can be accessed from the crate namespace.
 pub use pallet::*;
 
+use sp_runtime::DispatchError;
+use tidefi_primitives::{Balance, CurrencyId};
+
+/// Withdrawal limits traits, used by the pallets who initiate the withdrawals
+pub trait WithdrawalLimitsExt<AccountId> {
+  /// Make sure the withdrawal of `amount` by `account_id` is within the rolling caps of the
+  /// currency and record it, returns `true` when the withdrawal has to be delayed
+  fn register_withdrawal(
+    account_id: &AccountId,
+    currency_id: CurrencyId,
+    amount: Balance,
+  ) -> Result<bool, DispatchError>;
+
+  /// Remove the withdrawal of `amount` by `account_id` from the rolling usage of the currency,
+  /// when the withdrawal is cancelled or failed before reaching the quorum
+  fn unregister_withdrawal(account_id: &AccountId, currency_id: CurrencyId, amount: Balance);
+}
+
 #[frame_support::pallet]
 pub mod pallet {
   use super::*;
@@ -42,13 +60,35 @@ pub mod pallet {
     PalletId,
   };
   use frame_system::{pallet_prelude::*, RawOrigin};
-  use sp_runtime::traits::{AccountIdConversion, StaticLookup};
+  use sp_runtime::{
+    traits::{AccountIdConversion, StaticLookup},
+    Permill, RuntimeDebug, SaturatedConversion,
+  };
   use sp_std::vec;
   use tidefi_primitives::{
-    pallet::AssetRegistryExt, AssetId, Balance, BalanceInfo, CurrencyBalance, CurrencyId,
-    CurrencyMetadata,
+    pallet::AssetRegistryExt, AssetId, BalanceInfo, CurrencyBalance, CurrencyMetadata,
   };
 
+  /// Withdrawal limits of a currency, `None` disable the limit
+  #[derive(Clone, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+  pub struct WithdrawalLimits {
+    /// Maximum amount withdrawn by all the accounts over the rolling period
+    pub asset_cap: Option<Balance>,
+    /// Maximum amount withdrawn by each account over the rolling period
+    pub account_cap: Option<Balance>,
+    /// Withdrawals above this amount are held for a delay before reaching the quorum
+    pub delay_threshold: Option<Balance>,
+  }
+
+  /// Amount withdrawn over the rolling period, it decays linearly with the blocks
+  #[derive(Clone, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+  pub struct WithdrawalUsage<BlockNumber> {
+    /// Amount withdrawn, as of `updated_at`
+    pub amount: Balance,
+    /// Block of the last withdrawal
+    pub updated_at: BlockNumber,
+  }
+
   /// Asset registry configuration
   #[pallet::config]
   pub trait Config:
@@ -69,6 +109,10 @@ pub mod pallet {
       + Mutate<Self::AccountId, AssetId = CurrencyId, Balance = Balance>
       + Transfer<Self::AccountId, AssetId = CurrencyId, Balance = Balance>
       + InspectHold<Self::AccountId, AssetId = CurrencyId, Balance = Balance>;
+
+    /// The number of blocks of the rolling withdrawal caps
+    #[pallet::constant]
+    type WithdrawalPeriod: Get<Self::BlockNumber>;
   }
 
   #[pallet::pallet]
@@ -80,6 +124,31 @@ pub mod pallet {
   #[pallet::getter(fn account_id)]
   pub type AssetRegistryAccountId<T: Config> = StorageValue<_, T::AccountId, OptionQuery>;
 
+  /// Withdrawal limits of each currency
+  #[pallet::storage]
+  #[pallet::getter(fn withdrawal_limits)]
+  pub type CurrencyWithdrawalLimits<T: Config> =
+    StorageMap<_, Blake2_128Concat, CurrencyId, WithdrawalLimits>;
+
+  /// Amount withdrawn by all the accounts over the rolling period of each currency
+  #[pallet::storage]
+  #[pallet::getter(fn asset_withdrawals)]
+  pub type AssetWithdrawals<T: Config> =
+    StorageMap<_, Blake2_128Concat, CurrencyId, WithdrawalUsage<T::BlockNumber>, ValueQuery>;
+
+  /// Amount withdrawn by the account over the rolling period of each currency
+  #[pallet::storage]
+  #[pallet::getter(fn account_withdrawals)]
+  pub type AccountWithdrawals<T: Config> = StorageDoubleMap<
+    _,
+    Blake2_128Concat,
+    T::AccountId,
+    Blake2_128Concat,
+    CurrencyId,
+    WithdrawalUsage<T::BlockNumber>,
+    ValueQuery,
+  >;
+
   /// Genesis configuration
   #[pallet::genesis_config]
   pub struct GenesisConfig<T: Config> {
@@ -137,6 +206,8 @@ pub mod pallet {
     Registered(CurrencyId),
     /// Asset was updated. \[currency_id, is_enabled\]
     StatusChanged(CurrencyId, bool),
+    /// Withdrawal limits were updated. \[currency_id, limits\]
+    WithdrawalLimitsChanged(CurrencyId, Option<WithdrawalLimits>),
   }
 
   // Errors inform users that something went wrong.
@@ -152,6 +223,10 @@ pub mod pallet {
     AssetAlreadyRegistered,
     /// Invalid Currency Id
     CurrencyIdNotValid,
+    /// The withdrawal exceed the rolling cap of the currency, try again later.
+    AssetWithdrawalCapExceeded,
+    /// The withdrawal exceed the rolling cap of the account, try again later.
+    AccountWithdrawalCapExceeded,
   }
 
   #[pallet::call]
@@ -251,6 +326,41 @@ pub mod pallet {
 
       Ok(())
     }
+
+    /// Update the withdrawal limits of an asset.
+    ///
+    /// - `currency_id`: The currency ID to update
+    /// - `limits`: The rolling caps and the delay threshold, `None` remove all the limits
+    ///
+    /// Emits `WithdrawalLimitsChanged` event when successful.
+    ///
+    /// Weight: `O(1)`
+    #[pallet::weight(<T as Config>::WeightInfo::set_withdrawal_limits())]
+    pub fn set_withdrawal_limits(
+      origin: OriginFor<T>,
+      currency_id: CurrencyId,
+      limits: Option<WithdrawalLimits>,
+    ) -> DispatchResult {
+      // 1. Make sure it's signed from the asset-registry owner
+      ensure!(
+        Some(ensure_signed(origin)?) == Self::account_id(),
+        Error::<T>::AccessDenied
+      );
+
+      // 2. Make sure the currency is already registered
+      ensure!(
+        Self::is_currency_exist(currency_id),
+        Error::<T>::AssetNotRegistered
+      );
+
+      // 3. Update the limits, the usage already recorded is kept
+      CurrencyWithdrawalLimits::<T>::set(currency_id, limits.clone());
+
+      // 4. Emit the new limits
+      Self::deposit_event(<Event<T>>::WithdrawalLimitsChanged(currency_id, limits));
+
+      Ok(())
+    }
   }
 
   impl<T: Config> Pallet<T> {
@@ -371,6 +481,121 @@ pub mod pallet {
     }
   }
 
+  impl<T: Config> Pallet<T> {
+    // amount withdrawn over the rolling period, the usage decays linearly to zero in
+    // `WithdrawalPeriod` blocks, at the pace of the cap
+    fn rolling_usage(
+      usage: &WithdrawalUsage<T::BlockNumber>,
+      cap: Balance,
+      current_block: T::BlockNumber,
+    ) -> Balance {
+      let elapsed: Balance = current_block
+        .saturating_sub(usage.updated_at)
+        .saturated_into();
+      let period: Balance = T::WithdrawalPeriod::get().saturated_into();
+      if elapsed >= period {
+        return 0;
+      }
+
+      usage
+        .amount
+        .saturating_sub(Permill::from_rational(elapsed, period) * cap)
+    }
+  }
+
+  impl<T: Config> WithdrawalLimitsExt<T::AccountId> for Pallet<T> {
+    fn register_withdrawal(
+      account_id: &T::AccountId,
+      currency_id: CurrencyId,
+      amount: Balance,
+    ) -> Result<bool, DispatchError> {
+      let limits = match Self::withdrawal_limits(currency_id) {
+        Some(limits) => limits,
+        None => return Ok(false),
+      };
+      let current_block = frame_system::Pallet::<T>::block_number();
+
+      // 1. Make sure the withdrawal is within both caps before recording anything
+      let asset_usage = limits
+        .asset_cap
+        .map(|cap| {
+          let used = Self::rolling_usage(&Self::asset_withdrawals(currency_id), cap, current_block)
+            .saturating_add(amount);
+          ensure!(used <= cap, Error::<T>::AssetWithdrawalCapExceeded);
+          Ok::<Balance, Error<T>>(used)
+        })
+        .transpose()?;
+      let account_usage = limits
+        .account_cap
+        .map(|cap| {
+          let used = Self::rolling_usage(
+            &Self::account_withdrawals(account_id, currency_id),
+            cap,
+            current_block,
+          )
+          .saturating_add(amount);
+          ensure!(used <= cap, Error::<T>::AccountWithdrawalCapExceeded);
+          Ok::<Balance, Error<T>>(used)
+        })
+        .transpose()?;
+
+      // 2. Record the withdrawal in the rolling usage
+      if let Some(amount) = asset_usage {
+        AssetWithdrawals::<T>::insert(
+          currency_id,
+          WithdrawalUsage {
+            amount,
+            updated_at: current_block,
+          },
+        );
+      }
+      if let Some(amount) = account_usage {
+        AccountWithdrawals::<T>::insert(
+          account_id,
+          currency_id,
+          WithdrawalUsage {
+            amount,
+            updated_at: current_block,
+          },
+        );
+      }
+
+      // 3. Large withdrawals are delayed
+      Ok(
+        limits
+          .delay_threshold
+          .map(|threshold| amount > threshold)
+          .unwrap_or(false),
+      )
+    }
+
+    fn unregister_withdrawal(account_id: &T::AccountId, currency_id: CurrencyId, amount: Balance) {
+      let limits = match Self::withdrawal_limits(currency_id) {
+        Some(limits) => limits,
+        None => return,
+      };
+      let current_block = frame_system::Pallet::<T>::block_number();
+
+      // the usage decayed since the withdrawal, the amount is removed from the current usage
+      if let Some(cap) = limits.asset_cap {
+        AssetWithdrawals::<T>::mutate(currency_id, |usage| {
+          *usage = WithdrawalUsage {
+            amount: Self::rolling_usage(usage, cap, current_block).saturating_sub(amount),
+            updated_at: current_block,
+          };
+        });
+      }
+      if let Some(cap) = limits.account_cap {
+        AccountWithdrawals::<T>::mutate(account_id, currency_id, |usage| {
+          *usage = WithdrawalUsage {
+            amount: Self::rolling_usage(usage, cap, current_block).saturating_sub(amount),
+            updated_at: current_block,
+          };
+        });
+      }
+    }
+  }
+
   impl<T: Config> AssetRegistryExt for Pallet<T> {
     fn is_currency_enabled(currency_id: CurrencyId) -> bool {
       match currency_id {
@@ -170,11 +170,13 @@ impl pallet_asset_registry::Config for Test {
   type AssetRegistryPalletId = AssetRegistryPalletId;
   // Wrapped currency
   type CurrencyTidefi = Adapter<AccountId>;
+  type WithdrawalPeriod = WithdrawalPeriod;
 }
 
 parameter_types! {
   pub const TidefiPalletId: PalletId = PalletId(*b"wrpr*pal");
   pub const AssetRegistryPalletId: PalletId = PalletId(*b"wrpr*art");
+  pub const WithdrawalPeriod: u64 = 100;
 }
 
 // this is only the mock for benchmarking, it's implemented directly in the runtime
@@ -329,6 +329,44 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
     Ok(())
   }
 
+  /// Disallow further unprivileged transfers of the asset `id` from `who`.
+  ///
+  /// Does not check the freezer of the asset, the caller is responsible of the permission.
+  pub fn do_freeze(id: T::AssetId, who: &T::AccountId) -> DispatchResult {
+    Account::<T, I>::try_mutate(who, id, |maybe_account| -> DispatchResult {
+      maybe_account
+        .as_mut()
+        .ok_or(Error::<T, I>::NoAccount)?
+        .is_frozen = true;
+      Ok(())
+    })?;
+
+    Self::deposit_event(Event::<T, I>::Frozen {
+      asset_id: id,
+      who: who.clone(),
+    });
+    Ok(())
+  }
+
+  /// Allow unprivileged transfers of the asset `id` from `who` again.
+  ///
+  /// Does not check the admin of the asset, the caller is responsible of the permission.
+  pub fn do_thaw(id: T::AssetId, who: &T::AccountId) -> DispatchResult {
+    Account::<T, I>::try_mutate(who, id, |maybe_account| -> DispatchResult {
+      maybe_account
+        .as_mut()
+        .ok_or(Error::<T, I>::NoAccount)?
+        .is_frozen = false;
+      Ok(())
+    })?;
+
+    Self::deposit_event(Event::<T, I>::Thawed {
+      asset_id: id,
+      who: who.clone(),
+    });
+    Ok(())
+  }
+
   /// Returns a deposit, destroying an asset-account.
   pub(super) fn do_refund(id: T::AssetId, who: T::AccountId, allow_burn: bool) -> DispatchResult {
     let mut account = Account::<T, I>::get(&who, id).ok_or(Error::<T, I>::NoDeposit)?;
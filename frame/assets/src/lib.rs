@@ -823,16 +823,7 @@ pub mod pallet {
       ensure!(&origin == &d.freezer, Error::<T, I>::NoPermission);
       let who = T::Lookup::lookup(who)?;
 
-      Account::<T, I>::try_mutate(&who, id, |maybe_account| -> DispatchResult {
-        maybe_account
-          .as_mut()
-          .ok_or(Error::<T, I>::NoAccount)?
-          .is_frozen = true;
-        Ok(())
-      })?;
-
-      Self::deposit_event(Event::<T, I>::Frozen { asset_id: id, who });
-      Ok(())
+      Self::do_freeze(id, &who)
     }
 
     /// Allow unprivileged transfers from an account again.
@@ -857,16 +848,7 @@ pub mod pallet {
       ensure!(&origin == &details.admin, Error::<T, I>::NoPermission);
       let who = T::Lookup::lookup(who)?;
 
-      Account::<T, I>::try_mutate(&who, id, |maybe_account| -> DispatchResult {
-        maybe_account
-          .as_mut()
-          .ok_or(Error::<T, I>::NoAccount)?
-          .is_frozen = false;
-        Ok(())
-      })?;
-
-      Self::deposit_event(Event::<T, I>::Thawed { asset_id: id, who });
-      Ok(())
+      Self::do_thaw(id, &who)
     }
 
     /// Disallow further unprivileged transfers for the asset class.
@@ -19,7 +19,7 @@ use crate::*;
 use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
 use frame_support::traits::{fungibles::Mutate, Hooks};
 use frame_system::{self, RawOrigin};
-use tidefi_primitives::{pallet::FeesExt, CurrencyId};
+use tidefi_primitives::CurrencyId;
 
 fn _assert_last_event<T: Config>(generic_event: <T as Config>::Event) {
   frame_system::Pallet::<T>::assert_last_event(generic_event.into());
@@ -33,8 +33,36 @@ mod benchmarking;
 // Re-export pallet items so that they can be accessed from the crate namespace.
 pub use pallet::*;
 
+use sp_runtime::DispatchError;
+use tidefi_primitives::{Balance, CurrencyId, Fee};
+
 pub(crate) const LOG_TARGET: &str = "tidefi::fees";
 
+/// Value of the assets in USDT, based on the prices of the price feed
+pub trait UsdtValueExt {
+  /// Value `amount` of `currency_id` in USDT
+  fn get_usdt_value(currency_id: CurrencyId, amount: Balance) -> Result<Balance, DispatchError>;
+}
+
+/// Fees of the swaps, held and settled by the oracle
+pub trait SwapFeesExt<AccountId> {
+  /// Account receiving the swap fees
+  fn account_id() -> AccountId;
+  /// Calculate the fees of a swap, without their USDT value
+  fn calculate_swap_fees(
+    currency_id: CurrencyId,
+    total_amount_before_fees: Balance,
+    is_market_maker: bool,
+  ) -> Fee;
+  /// Register the fees of a swap for the current era and session
+  fn register_swap_fees(
+    account_id: AccountId,
+    currency_id: CurrencyId,
+    total_amount_before_fees: Balance,
+    is_market_maker: bool,
+  ) -> Result<Fee, DispatchError>;
+}
+
 // syntactic sugar for logging.
 #[macro_export]
 macro_rules! log {
@@ -60,6 +88,7 @@ pub mod pallet {
     PalletId,
   };
   use frame_system::pallet_prelude::*;
+  use pallet_price_feed::PriceFeedExt;
   use sp_arithmetic::traits::Zero;
   use sp_runtime::{
     traits::{AccountIdConversion, CheckedDiv, Saturating},
@@ -68,12 +97,12 @@ pub mod pallet {
   use sp_std::{borrow::ToOwned, vec};
   use tidefi_primitives::{
     assets::{Asset, USDT},
-    pallet::{FeesExt, SecurityExt, StakingExt},
-    ActiveEraInfo, Balance, CurrencyId, EraIndex, Fee, SessionIndex, SunriseSwapPool,
+    pallet::{SecurityExt, StakingExt},
+    ActiveEraInfo, Balance, CurrencyId, EraIndex, SessionIndex, SunriseSwapPool,
   };
 
   /// The current storage version.
-  const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+  const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 
   type BoundedAccountFees = BoundedVec<(CurrencyId, Fee), ConstU32<1_000>>;
   type BoundedSunrisePools = BoundedVec<SunriseSwapPool, ConstU32<6>>;
@@ -127,6 +156,9 @@ pub mod pallet {
     /// Tidefi stake traits
     type Staking: StakingExt<Self::AccountId>;
 
+    /// Price feed traits, used to value the fees in USDT and TIFI
+    type PriceFeed: PriceFeedExt<Self::AccountId>;
+
     /// Tidechain currency wrapper
     type CurrencyTidefi: Inspect<Self::AccountId, AssetId = CurrencyId, Balance = Balance>
       + Mutate<Self::AccountId, AssetId = CurrencyId, Balance = Balance>
@@ -170,26 +202,6 @@ pub mod pallet {
   #[pallet::getter(fn stored_sessions)]
   pub type StoredSessions<T: Config> = StorageMap<_, Blake2_128Concat, SessionIndex, ()>;
 
-  /// Tifi price of the orderbook reported by oracle every X minutes at the current market price.
-  /// We keep in sync order book of USDT values for our sunrise pool.
-  ///
-  /// CurrencyId → USDT
-  /// USDT → TIFI
-  ///
-  /// To get current TIFI USDT value;
-  ///
-  #[pallet::storage]
-  #[pallet::getter(fn order_book_price)]
-  pub type OrderBookPrice<T: Config> = StorageDoubleMap<
-    _,
-    Blake2_128Concat,
-    CurrencyId,
-    Blake2_128Concat,
-    CurrencyId,
-    FixedU128,
-    ValueQuery,
-  >;
-
   /// The total fees for the session.
   /// If total hasn't been set or has been removed then 0 stake is returned.
   #[pallet::storage]
@@ -331,7 +343,7 @@ pub mod pallet {
   #[pallet::hooks]
   impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
     fn on_runtime_upgrade() -> frame_support::weights::Weight {
-      migrations::migrate_to_v1::<T, Self>()
+      migrations::migrate_to_v1::<T, Self>().saturating_add(migrations::migrate_to_v2::<T, Self>())
     }
 
     fn on_initialize(_now: T::BlockNumber) -> Weight {
@@ -529,14 +541,18 @@ pub mod pallet {
         .try_into()
         .map_err(|_| Error::<T>::InvalidAsset)?;
 
-      // get order book price
+      if usdt_amount.is_zero() {
+        return Ok(0);
+      }
+
+      // get median price from the price feed
       let order_book_price = if currency_id == CurrencyId::Wrapped(USDT) {
         FixedU128::from(1)
       } else {
-        Self::order_book_price(currency_id, CurrencyId::Wrapped(USDT))
+        T::PriceFeed::get_price(currency_id, CurrencyId::Wrapped(USDT))?
       };
 
-      if order_book_price.is_zero() || usdt_amount.is_zero() {
+      if order_book_price.is_zero() {
         return Ok(0);
       }
 
@@ -566,7 +582,7 @@ pub mod pallet {
       let order_book_price = if currency_id == CurrencyId::Wrapped(USDT) {
         FixedU128::from(1)
       } else {
-        Self::order_book_price(currency_id, CurrencyId::Wrapped(USDT))
+        T::PriceFeed::get_price(currency_id, CurrencyId::Wrapped(USDT))?
       };
 
       if order_book_price.is_zero() {
@@ -596,7 +612,7 @@ pub mod pallet {
         .try_into()
         .map_err(|_| Error::<T>::InvalidAsset)?;
 
-      let order_book_price = Self::order_book_price(currency_id, CurrencyId::Tifi);
+      let order_book_price = T::PriceFeed::get_price(currency_id, CurrencyId::Tifi)?;
 
       if order_book_price.is_zero() {
         return Ok(0);
@@ -652,6 +668,70 @@ pub mod pallet {
       )
     }
 
+    // Based on the fee, select the highest matching sunrise pool and calculate the TIFI reward
+    pub(crate) fn try_get_sunrise_reward(
+      fee: &Fee,
+      currency_id: CurrencyId,
+    ) -> Result<Option<(SunriseSwapPool, Balance)>, DispatchError> {
+      match Self::try_select_first_eligible_sunrise_pool(fee, currency_id)? {
+        Some(sunrise_pool) => {
+          Self::calculate_tide_reward_for_pool(sunrise_pool.rebates, fee, currency_id)
+            .map(|reward| Some((sunrise_pool, reward)))
+        }
+        None => Ok(None),
+      }
+    }
+
+    /// Calculate the fees of a swap and their USDT value.
+    ///
+    /// Fails when the price of the currency is stale or not available.
+    pub fn try_calculate_swap_fees(
+      currency_id: CurrencyId,
+      total_amount_before_fees: Balance,
+      is_market_maker: bool,
+    ) -> Result<Fee, DispatchError> {
+      let fee = Self::swap_fee_amount(total_amount_before_fees, is_market_maker);
+      Ok(Fee {
+        amount: total_amount_before_fees,
+        fee,
+        fee_usdt: Self::try_get_usdt_value(currency_id, fee.into())?,
+      })
+    }
+
+    /// Simulate the fees of a swap and the Sunrise pool reward (`pool_id`, `reward`) it would earn,
+    /// without registering them. The reward is only available during an active era.
+    ///
+    /// Fails when the price of the currency is stale or not available.
+    pub fn quote_swap_fees(
+      currency_id: CurrencyId,
+      total_amount_before_fees: Balance,
+      is_market_maker: bool,
+    ) -> Result<(Fee, Option<(u8, Balance)>), DispatchError> {
+      let fee =
+        Self::try_calculate_swap_fees(currency_id, total_amount_before_fees, is_market_maker)?;
+      let sunrise_reward = match Self::active_era() {
+        Some(_) => Self::try_get_sunrise_reward(&fee, currency_id)
+          .unwrap_or_default()
+          .map(|(sunrise_pool, reward)| (sunrise_pool.id, reward)),
+        None => None,
+      };
+
+      Ok((fee, sunrise_reward))
+    }
+
+    // Fee amount of a swap, with the market maker discount
+    pub(crate) fn swap_fee_amount(
+      total_amount_before_fees: Balance,
+      is_market_maker: bool,
+    ) -> Balance {
+      let fee_amount = if is_market_maker {
+        T::MarketMakerFeeAmount::get()
+      } else {
+        T::FeeAmount::get()
+      };
+      fee_amount * total_amount_before_fees
+    }
+
     // Initialize new era
     pub fn start_era() {
       ActiveEra::<T>::mutate(|active_era| {
@@ -690,41 +770,42 @@ pub mod pallet {
     }
   }
 
-  impl<T: Config> FeesExt<T::AccountId> for Pallet<T> {
+  impl<T: Config> UsdtValueExt for Pallet<T> {
+    fn get_usdt_value(currency_id: CurrencyId, amount: Balance) -> Result<Balance, DispatchError> {
+      Self::try_get_usdt_value(currency_id, amount.into())
+    }
+  }
+
+  // the fees of the forced unstakes are sent to the fees pallet
+  impl<T: Config> pallet_tidefi_stake::StakingFeesExt<T::AccountId> for Pallet<T> {
     fn account_id() -> T::AccountId {
       T::FeesPalletId::get().into_account()
     }
 
-    // FIXME: Would probably worth moving this into his own pallet?
-    fn register_order_book_price(
-      prices: Vec<(CurrencyId, CurrencyId, Balance)>,
-    ) -> Result<(), DispatchError> {
-      for (from, to, price) in prices {
-        let asset_from: Asset = from.try_into().map_err(|_| Error::<T>::InvalidAsset)?;
-        OrderBookPrice::<T>::insert(
-          from,
-          to,
-          FixedU128::saturating_from_rational(price, asset_from.saturating_mul(1)),
-        );
+    fn get_tifi_value(currency_id: CurrencyId, amount: Balance) -> Result<Balance, DispatchError> {
+      if currency_id == CurrencyId::Tifi {
+        return Ok(amount);
       }
-      Ok(())
+      Self::try_get_tide_value(currency_id, amount.into())
     }
+  }
 
+  impl<T: Config> SwapFeesExt<T::AccountId> for Pallet<T> {
+    fn account_id() -> T::AccountId {
+      T::FeesPalletId::get().into_account()
+    }
+
+    // the oracle only use the fee amount to hold and settle the swaps, the USDT value is not
+    // priced here, see `try_calculate_swap_fees`
     fn calculate_swap_fees(
-      currency_id: CurrencyId,
+      _currency_id: CurrencyId,
       total_amount_before_fees: Balance,
       is_market_maker: bool,
     ) -> Fee {
-      let fee = if is_market_maker {
-        T::MarketMakerFeeAmount::get()
-      } else {
-        T::FeeAmount::get()
-      } * total_amount_before_fees;
-
       Fee {
         amount: total_amount_before_fees,
-        fee,
-        fee_usdt: Self::try_get_usdt_value(currency_id, fee.into()).unwrap_or_default(),
+        fee: Self::swap_fee_amount(total_amount_before_fees, is_market_maker),
+        fee_usdt: 0,
       }
     }
 
@@ -737,47 +818,69 @@ pub mod pallet {
       let fee = match Self::active_era() {
         Some(current_era) => {
           let current_session = CurrentSession::<T>::get();
-          let new_fee =
-            Self::calculate_swap_fees(currency_id, total_amount_before_fees, is_market_maker);
+          // the USDT value and the sunrise reward require a fresh price, the trade is settled and
+          // its fees are registered even without it
+          let (new_fee, sunrise_pool_with_reward) = match Self::try_calculate_swap_fees(
+            currency_id,
+            total_amount_before_fees,
+            is_market_maker,
+          ) {
+            Ok(new_fee) => {
+              let sunrise_pool_with_reward = Self::try_get_sunrise_reward(&new_fee, currency_id)
+                .unwrap_or_else(|err| {
+                  log!(warn, "Sunrise reward skipped {:?}", err);
+                  None
+                });
+              (new_fee, sunrise_pool_with_reward)
+            }
+            Err(err) => {
+              log!(warn, "USDT value and sunrise reward skipped {:?}", err);
+              (
+                Self::calculate_swap_fees(currency_id, total_amount_before_fees, is_market_maker),
+                None,
+              )
+            }
+          };
 
-          if let Some(sunrise_pool_available) =
-            Self::try_select_first_eligible_sunrise_pool(&new_fee, currency_id)?
+          if let Some((sunrise_pool_available, real_fees_in_tide_with_rebates)) =
+            sunrise_pool_with_reward
           {
-            let real_fees_in_tide_with_rebates = Self::calculate_tide_reward_for_pool(
-              sunrise_pool_available.rebates,
-              &new_fee,
-              currency_id,
-            )?;
             // Update sunrise pool
-            SunrisePools::<T>::try_mutate::<(), DispatchError, _>(|pools| {
-              let sunrise_pool = pools
-                .iter_mut()
-                .find(|pool| pool.id == sunrise_pool_available.id)
-                .ok_or(Error::<T>::InvalidSunrisePool)?;
-
-              // Reduce pool balance
-              sunrise_pool.balance = sunrise_pool
-                .balance
-                .saturating_sub(real_fees_in_tide_with_rebates);
-
-              // Reduce number of transactions remaining for this pool
-              sunrise_pool.transactions_remaining -= 1;
-
-              Ok(())
-            })?;
-
-            // Increment reward for the account
-            SunriseRewards::<T>::mutate(account_id.clone(), current_era.index, |rewards| {
-              *rewards = rewards.saturating_add(real_fees_in_tide_with_rebates);
-            });
+            let sunrise_pool_updated =
+              SunrisePools::<T>::try_mutate::<(), DispatchError, _>(|pools| {
+                let sunrise_pool = pools
+                  .iter_mut()
+                  .find(|pool| pool.id == sunrise_pool_available.id)
+                  .ok_or(Error::<T>::InvalidSunrisePool)?;
 
-            // Emit event
-            Self::deposit_event(Event::<T>::SunriseRewarded {
-              era_index: current_era.index,
-              pool_id: sunrise_pool_available.id,
-              account_id: account_id.clone(),
-              reward: real_fees_in_tide_with_rebates,
-            });
+                // Reduce pool balance
+                sunrise_pool.balance = sunrise_pool
+                  .balance
+                  .saturating_sub(real_fees_in_tide_with_rebates);
+
+                // Reduce number of transactions remaining for this pool
+                sunrise_pool.transactions_remaining -= 1;
+
+                Ok(())
+              });
+
+            match sunrise_pool_updated {
+              Ok(()) => {
+                // Increment reward for the account
+                SunriseRewards::<T>::mutate(account_id.clone(), current_era.index, |rewards| {
+                  *rewards = rewards.saturating_add(real_fees_in_tide_with_rebates);
+                });
+
+                // Emit event
+                Self::deposit_event(Event::<T>::SunriseRewarded {
+                  era_index: current_era.index,
+                  pool_id: sunrise_pool_available.id,
+                  account_id: account_id.clone(),
+                  reward: real_fees_in_tide_with_rebates,
+                });
+              }
+              Err(err) => log!(warn, "Sunrise reward skipped {:?}", err),
+            }
           }
 
           // Update fees pool for the current era / currency
@@ -17,8 +17,10 @@
 use super::*;
 use frame_support::{
   log,
+  migration::storage_iter,
   traits::{Get, GetStorageVersion, PalletInfoAccess, StorageVersion},
 };
+use sp_runtime::FixedU128;
 use tidefi_primitives::{pallet::SecurityExt, ActiveEraInfo};
 
 /// Migrate the pallet storage to v1.
@@ -66,3 +68,46 @@ pub fn migrate_to_v1<T: Config, P: GetStorageVersion + PalletInfoAccess>(
     T::DbWeight::get().reads(1)
   }
 }
+
+/// Migrate the pallet storage to v2.
+///
+/// The order book prices are now reported to `pallet_price_feed`, clear the deprecated storage.
+pub fn migrate_to_v2<T: Config, P: GetStorageVersion + PalletInfoAccess>(
+) -> frame_support::weights::Weight {
+  let on_chain_storage_version = <P as GetStorageVersion>::on_chain_storage_version();
+  log!(
+    info,
+    "Running migration storage v2 with storage version {:?}",
+    on_chain_storage_version,
+  );
+
+  if on_chain_storage_version < 2 {
+    // drain the deprecated `OrderBookPrice` storage
+    let removed_prices = storage_iter::<FixedU128>(
+      <P as PalletInfoAccess>::name().as_bytes(),
+      b"OrderBookPrice",
+    )
+    .drain()
+    .count();
+
+    // update on-chain storage version
+    StorageVersion::new(2).put::<P>();
+    log!(
+      info,
+      "Running migration storage v2 with storage version {:?} was complete",
+      on_chain_storage_version,
+    );
+    // return migration weights
+    T::DbWeight::get().reads_writes(
+      (removed_prices as u64).saturating_add(1),
+      (removed_prices as u64).saturating_add(1),
+    )
+  } else {
+    log!(
+      info,
+      "Attempted to apply migration to v2 but failed because storage version is {:?}",
+      on_chain_storage_version,
+    );
+    T::DbWeight::get().reads(1)
+  }
+}
@@ -91,6 +91,7 @@ frame_support::construct_runtime!(
     Fees: pallet_fees::{Pallet, Call, Config<T>, Storage, Event<T>},
     Security: pallet_security::{Pallet, Call, Config, Storage, Event<T>},
     TidefiStaking: pallet_tidefi_stake::{Pallet, Call, Config<T>, Storage, Event<T>},
+    PriceFeed: pallet_price_feed::{Pallet, Call, Config<T>, Storage, Event<T>},
   }
 );
 
@@ -182,6 +183,7 @@ parameter_types! {
   pub const TidefiPalletId: PalletId = PalletId(*b"wrpr*pal");
   pub const QuorumPalletId: PalletId = PalletId(*b"qurm*pal");
   pub const AssetRegistryPalletId: PalletId = PalletId(*b"asst*pal");
+  pub const WithdrawalPeriod: u64 = 100;
   pub const StakePalletId: PalletId = PalletId(*b"stak*pal");
   pub const PeriodBasis: BlockNumber = 1000u32;
   pub const SessionsPerEra: SessionIndex = 10;
@@ -197,6 +199,8 @@ parameter_types! {
   // Maximum number of staking period the chain can support
   pub const StakingRewardCap: u32 = 10;
   pub const BlocksSunriseClaims: BlockNumber = 10;
+  pub const MaxReporters: u32 = 5;
+  pub const PriceValidity: u64 = 100;
 }
 
 impl pallet_fees::Config for Test {
@@ -214,6 +218,17 @@ impl pallet_fees::Config for Test {
   type MarketMakerFeeAmount = MarketMakerFeeAmount;
   type BlocksSunriseClaims = BlocksSunriseClaims;
   type Staking = TidefiStaking;
+  type PriceFeed = PriceFeed;
+}
+
+impl pallet_price_feed::Config for Test {
+  type Event = Event;
+  type WeightInfo = pallet_price_feed::weights::SubstrateWeight<Test>;
+  type Security = Security;
+  type MaxReporters = MaxReporters;
+  type PriceValidity = PriceValidity;
+  type ForceOrigin = EnsureRoot<Self::AccountId>;
+  type InitialReporter = ();
 }
 
 impl pallet_tidefi_stake::Config for Test {
@@ -226,7 +241,9 @@ impl pallet_tidefi_stake::Config for Test {
   type BlocksForceUnstake = BlocksForceUnstake;
   type AssetRegistry = AssetRegistry;
   type StakingRewardCap = StakingRewardCap;
+  type ForceOrigin = EnsureRoot<Self::AccountId>;
   type Security = Security;
+  type Fees = Fees;
 }
 
 impl pallet_asset_registry::Config for Test {
@@ -234,6 +251,7 @@ impl pallet_asset_registry::Config for Test {
   type WeightInfo = pallet_asset_registry::weights::SubstrateWeight<Test>;
   type AssetRegistryPalletId = AssetRegistryPalletId;
   type CurrencyTidefi = Adapter<AccountId>;
+  type WithdrawalPeriod = WithdrawalPeriod;
 }
 
 impl pallet_security::Config for Test {
@@ -16,11 +16,12 @@
 
 use crate::{
   mock::{new_test_ext, AccountId, Fees, Test},
-  OrderBookPrice,
+  EraTotalFees, SwapFeesExt,
 };
-use frame_support::assert_ok;
+use frame_support::{assert_noop, assert_ok};
+use pallet_price_feed::Prices;
 use sp_runtime::{FixedPointNumber, FixedU128};
-use tidefi_primitives::{pallet::FeesExt, CurrencyId};
+use tidefi_primitives::CurrencyId;
 
 #[test]
 pub fn check_genesis_config() {
@@ -33,10 +34,10 @@ pub fn check_genesis_config() {
 pub fn calculate_trading_fees() {
   new_test_ext().execute_with(|| {
     // 1 USDT = 1 TIFI
-    OrderBookPrice::<Test>::insert(
+    Prices::<Test>::insert(
       CurrencyId::Tifi,
       CurrencyId::Wrapped(4),
-      FixedU128::saturating_from_rational(1, 1),
+      (FixedU128::saturating_from_rational(1, 1), 0),
     );
 
     // 100 tifi @ 2% should cost 2 TIFIs
@@ -54,10 +55,10 @@ pub fn calculate_trading_fees() {
 pub fn register_swap_fees() {
   new_test_ext().execute_with(|| {
     // 1 USDT = 1 TIFI
-    OrderBookPrice::<Test>::insert(
+    Prices::<Test>::insert(
       CurrencyId::Tifi,
       CurrencyId::Wrapped(4),
-      FixedU128::saturating_from_rational(1, 1),
+      (FixedU128::saturating_from_rational(1, 1), 0),
     );
 
     let current_era = Fees::active_era().unwrap().index;
@@ -96,17 +97,76 @@ pub fn register_swap_fees() {
   });
 }
 
+#[test]
+pub fn register_swap_fees_without_price() {
+  new_test_ext().execute_with(|| {
+    let current_era = Fees::active_era().unwrap().index;
+
+    // the fees are registered without their USDT value nor the sunrise reward
+    let registered_fee =
+      Fees::register_swap_fees(3u64.into(), CurrencyId::Tifi, 100_000_000_000_000, false).unwrap();
+    assert_eq!(registered_fee.amount, 100_000_000_000_000);
+    assert_eq!(registered_fee.fee, 2_000_000_000_000);
+    assert_eq!(registered_fee.fee_usdt, 0);
+    assert_eq!(
+      EraTotalFees::<Test>::get(current_era, CurrencyId::Tifi).fee,
+      2_000_000_000_000
+    );
+    assert_eq!(Fees::sunrise_rewards(AccountId(3u64), current_era), 0);
+
+    // the quote still requires a price
+    assert_noop!(
+      Fees::quote_swap_fees(CurrencyId::Tifi, 100_000_000_000_000, false),
+      pallet_price_feed::Error::<Test>::PriceNotAvailable
+    );
+  });
+}
+
+#[test]
+pub fn quote_swap_fees() {
+  new_test_ext().execute_with(|| {
+    // 1 USDT = 1 TIFI
+    Prices::<Test>::insert(
+      CurrencyId::Tifi,
+      CurrencyId::Wrapped(4),
+      (FixedU128::saturating_from_rational(1, 1), 0),
+    );
+
+    // same fees as the registered ones, with the market maker discount
+    let (quoted_fee, sunrise_reward) =
+      Fees::quote_swap_fees(CurrencyId::Tifi, 100_000_000_000_000, false).unwrap();
+    let calculated_fee =
+      Fees::try_calculate_swap_fees(CurrencyId::Tifi, 100_000_000_000_000, false).unwrap();
+    assert_eq!(quoted_fee.fee, calculated_fee.fee);
+    assert_eq!(quoted_fee.fee_usdt, calculated_fee.fee_usdt);
+    assert_eq!(quoted_fee.fee, 2_000_000_000_000);
+    assert_eq!(sunrise_reward, None);
+
+    let (quoted_fee, _) =
+      Fees::quote_swap_fees(CurrencyId::Tifi, 100_000_000_000_000, true).unwrap();
+    assert_eq!(quoted_fee.fee, 1_000_000_000_000);
+
+    // nothing is registered
+    let current_era = Fees::active_era().unwrap().index;
+    assert!(Fees::account_fees(current_era, AccountId(3u64)).is_empty());
+    assert!(!EraTotalFees::<Test>::contains_key(
+      current_era,
+      CurrencyId::Tifi
+    ));
+  });
+}
+
 #[test]
 pub fn test_calc_reward() {
   new_test_ext().execute_with(|| {
     // 0.7 USDT = 1 TIFI
-    OrderBookPrice::<Test>::insert(
+    Prices::<Test>::insert(
       CurrencyId::Wrapped(4),
       CurrencyId::Tifi,
-      FixedU128::saturating_from_rational(700_000, 1_000_000),
+      (FixedU128::saturating_from_rational(700_000, 1_000_000), 0),
     );
 
-    let fee = Fees::calculate_swap_fees(CurrencyId::Wrapped(4), 100_000_000, false);
+    let fee = Fees::try_calculate_swap_fees(CurrencyId::Wrapped(4), 100_000_000, false).unwrap();
     assert_eq!(
       Fees::calculate_tide_reward_for_pool(
         // 125%
@@ -127,13 +187,13 @@ pub fn test_calc_reward() {
 pub fn test_calc_reward_small_numbers() {
   new_test_ext().execute_with(|| {
     // 0.5 USDT = 1 TIFI
-    OrderBookPrice::<Test>::insert(
+    Prices::<Test>::insert(
       CurrencyId::Wrapped(4),
       CurrencyId::Tifi,
-      FixedU128::saturating_from_rational(500_000, 1_000_000),
+      (FixedU128::saturating_from_rational(500_000, 1_000_000), 0),
     );
 
-    let fee = Fees::calculate_swap_fees(CurrencyId::Wrapped(4), 1_000_000, false);
+    let fee = Fees::try_calculate_swap_fees(CurrencyId::Wrapped(4), 1_000_000, false).unwrap();
 
     // We should receive 0.15625 TIFI in reward
     assert_eq!(
@@ -156,20 +216,20 @@ pub fn test_calc_reward_small_numbers() {
 pub fn test_calc_reward_other_assets() {
   new_test_ext().execute_with(|| {
     // 10_000 USDT = 1 BTC
-    OrderBookPrice::<Test>::insert(
+    Prices::<Test>::insert(
       CurrencyId::Wrapped(2),
       CurrencyId::Wrapped(4),
-      FixedU128::saturating_from_rational(1, 10_000),
+      (FixedU128::saturating_from_rational(1, 10_000), 0),
     );
 
     // 100_000 TIFI = 1 BTC
-    OrderBookPrice::<Test>::insert(
+    Prices::<Test>::insert(
       CurrencyId::Wrapped(2),
       CurrencyId::Tifi,
-      FixedU128::saturating_from_rational(1, 100_000),
+      (FixedU128::saturating_from_rational(1, 100_000), 0),
     );
 
-    let fee = Fees::calculate_swap_fees(CurrencyId::Wrapped(2), 100_000_000, false);
+    let fee = Fees::try_calculate_swap_fees(CurrencyId::Wrapped(2), 100_000_000, false).unwrap();
     // We should receive 2500 TIFI in reward
     assert_eq!(
       Fees::calculate_tide_reward_for_pool(
@@ -200,7 +260,8 @@ pub fn test_calc_reward_other_assets() {
 
     // 1_000 BTC transaction
     // worth 10_000_000 USDT
-    let fee = Fees::calculate_swap_fees(CurrencyId::Wrapped(2), 100_000_000_000, false);
+    let fee =
+      Fees::try_calculate_swap_fees(CurrencyId::Wrapped(2), 100_000_000_000, false).unwrap();
 
     // We should receive 125_000 TIFI in reward
     assert_eq!(
@@ -18,10 +18,17 @@
 use super::*;
 
 use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, vec, whitelisted_caller};
-use frame_support::traits::fungibles::Mutate;
+use frame_support::{
+  traits::{fungibles::Mutate, EnsureOrigin, UnfilteredDispatchable},
+  weights::Weight,
+};
 use frame_system::{self, RawOrigin};
-use sp_runtime::{traits::StaticLookup, Permill};
-use tidefi_primitives::{pallet::OracleExt, CurrencyId, SwapConfirmation, SwapType};
+use pallet_price_feed::PriceFeedExt;
+use sp_runtime::{
+  traits::{Saturating, StaticLookup, Zero},
+  FixedU128, Permill,
+};
+use tidefi_primitives::{assets::USDT, pallet::OracleExt, CurrencyId, SwapConfirmation, SwapType};
 
 const SEED: u32 = 0;
 const ADMIN_ID: u32 = 1;
@@ -42,6 +49,27 @@ fn pre_set_auth<T: Config>() -> T::AccountId {
   user
 }
 
+fn pre_set_market_maker<T: Config>(account_id: T::AccountId) {
+  T::CurrencyTidefi::mint_into(
+    CurrencyId::Tifi,
+    &account_id,
+    T::MarketMakerBond::get().saturating_mul(2),
+  )
+  .expect("Unable to mint token");
+  Pallet::<T>::register_market_maker(RawOrigin::Signed(account_id).into(), vec![0_u8; 8])
+    .expect("Unable to register market maker");
+}
+
+// the swap fees are registered with their USDT value
+fn pre_set_prices<T: Config>() {
+  for currency_id in [
+    CurrencyId::Wrapped(TEST_TOKEN),
+    CurrencyId::Wrapped(TEST_TOKEN2),
+  ] {
+    T::PriceFeed::set_price(currency_id, CurrencyId::Wrapped(USDT), FixedU128::from(1));
+  }
+}
+
 benchmarks! {
    set_status {
       let user = pre_set_auth::<T>();
@@ -50,17 +78,59 @@ benchmarks! {
       let user = pre_set_auth::<T>();
       let caller: T::AccountId = whitelisted_caller();
    }: _(RawOrigin::Signed(user), caller)
+   register_market_maker {
+      let caller: T::AccountId = whitelisted_caller();
+      T::CurrencyTidefi::mint_into(CurrencyId::Tifi, &caller, T::MarketMakerBond::get().saturating_mul(2)).expect("Unable to mint token");
+      let name = vec![0_u8; T::MarketMakerNameLimit::get() as usize];
+   }: _(RawOrigin::Signed(caller), name)
    add_market_maker {
       let user = pre_set_auth::<T>();
       let caller: T::AccountId = whitelisted_caller();
-   }: _(RawOrigin::Signed(user), caller)
+      pre_set_market_maker::<T>(caller.clone());
+      let pairs = vec![(CurrencyId::Wrapped(TEST_TOKEN), CurrencyId::Wrapped(TEST_TOKEN2), 1_000_000_000_000); T::MarketMakerPairsLimit::get() as usize];
+   }: _(RawOrigin::Signed(user), caller, pairs)
    remove_market_maker {
       let user = pre_set_auth::<T>();
       let caller: T::AccountId = whitelisted_caller();
+      pre_set_market_maker::<T>(caller.clone());
       MarketMakers::<T>::insert(caller.clone(), true);
    }: _(RawOrigin::Signed(user), caller)
+   slash_market_maker {
+      let caller: T::AccountId = whitelisted_caller();
+      pre_set_market_maker::<T>(caller.clone());
+      MarketMakers::<T>::insert(caller.clone(), true);
+      let origin = T::ForceOrigin::successful_origin();
+      let call = Call::<T>::slash_market_maker { account_id: caller, amount: T::MarketMakerBond::get() };
+   }: { call.dispatch_bypass_filter(origin)? }
+   withdraw_market_maker_bond {
+      let user = pre_set_auth::<T>();
+      let caller: T::AccountId = whitelisted_caller();
+      pre_set_market_maker::<T>(caller.clone());
+      Pallet::<T>::remove_market_maker(RawOrigin::Signed(user).into(), caller.clone())
+         .expect("Unable to remove market maker");
+      MarketMakerUnbonding::<T>::insert(caller.clone(), (T::MarketMakerBond::get(), T::BlockNumber::zero()));
+   }: _(RawOrigin::Signed(caller))
+   set_order_book_pair {
+      let user = pre_set_auth::<T>();
+   }: _(RawOrigin::Signed(user), CurrencyId::Wrapped(TEST_TOKEN), CurrencyId::Wrapped(TEST_TOKEN2), true)
+   match_order_book {
+      let o in 0 .. T::OrderBookLimit::get();
+      pre_set_auth::<T>();
+      Pallet::<T>::update_last_seen();
+      OrderBookPairs::<T>::insert((CurrencyId::Wrapped(TEST_TOKEN), CurrencyId::Wrapped(TEST_TOKEN2)), true);
+      // the asks don't cross any bid, the whole order book is decoded without match
+      let asks: BoundedVec<(Hash, FixedU128), T::OrderBookLimit> = (0..o)
+         .map(|i| (Hash::from_low_u64_be(i.into()), FixedU128::from(2)))
+         .collect::<Vec<_>>()
+         .try_into()
+         .expect("Unable to create the order book");
+      OrderBook::<T>::insert((CurrencyId::Wrapped(TEST_TOKEN), CurrencyId::Wrapped(TEST_TOKEN2)), asks);
+   }: {
+      Pallet::<T>::match_order_books_with_max_weight(Weight::MAX);
+   }
    confirm_swap {
       let user = pre_set_auth::<T>();
+      pre_set_prices::<T>();
       let account_id: T::AccountId = account("user", USER_ID, SEED);
       let mm_account_id: T::AccountId = account("mm", MM_ID, SEED);
       let caller_lookup = T::Lookup::unlookup(user.clone());
@@ -69,6 +139,10 @@ benchmarks! {
       T::CurrencyTidefi::mint_into(CurrencyId::Wrapped(TEST_TOKEN), &account_id, 2_000_000_000_000).expect("Unable to mint token");
       T::CurrencyTidefi::mint_into(CurrencyId::Wrapped(TEST_TOKEN2), &mm_account_id, 2_000_000_000_000).expect("Unable to mint token");
 
+      // the completed limit orders are removed from full order books
+      Pallet::<T>::set_full_order_book(CurrencyId::Wrapped(TEST_TOKEN), CurrencyId::Wrapped(TEST_TOKEN2));
+      Pallet::<T>::set_full_order_book(CurrencyId::Wrapped(TEST_TOKEN2), CurrencyId::Wrapped(TEST_TOKEN));
+
       // create requests
       let user_request = Pallet::<T>::add_new_swap_in_queue(account_id,
          CurrencyId::Wrapped(TEST_TOKEN),
@@ -101,6 +175,41 @@ benchmarks! {
       ).unwrap();
 
    }: _(RawOrigin::Signed(user), user_request.0, vec![SwapConfirmation { request_id: mm_request.0, amount_to_receive: 1_000_000_000_000, amount_to_send: 1_000_000_000_000 }])
+   confirm_swap_route {
+      let l in 1 .. 1;
+      let user = pre_set_auth::<T>();
+      pre_set_prices::<T>();
+      let account_id: T::AccountId = account("user", USER_ID, SEED);
+      let mm_account_id: T::AccountId = account("mm", MM_ID, SEED);
+
+      // mint tokens
+      T::CurrencyTidefi::mint_into(CurrencyId::Wrapped(TEST_TOKEN), &account_id, 2_000_000_000_000).expect("Unable to mint token");
+      T::CurrencyTidefi::mint_into(CurrencyId::Wrapped(TEST_TOKEN2), &mm_account_id, 2_000_000_000_000).expect("Unable to mint token");
+
+      // create requests
+      let mm_request = Pallet::<T>::add_new_swap_in_queue(mm_account_id,
+         CurrencyId::Wrapped(TEST_TOKEN2),
+         1_000_000_000_000,
+         CurrencyId::Wrapped(TEST_TOKEN),
+         1_000_000_000_000,
+         <frame_system::Pallet<T>>::block_number(),
+         [0; 32],
+         false,
+         SwapType::Limit,
+         Permill::from_percent(2),
+      ).unwrap();
+
+      let route_request = Pallet::<T>::add_new_route_in_queue(account_id,
+         vec![CurrencyId::Wrapped(TEST_TOKEN), CurrencyId::Wrapped(TEST_TOKEN2)],
+         1_000_000_000_000,
+         1_000_000_000_000,
+         <frame_system::Pallet<T>>::block_number(),
+         [0; 32],
+         false,
+         Permill::from_percent(2),
+      ).unwrap();
+
+   }: _(RawOrigin::Signed(user), route_request, vec![vec![SwapConfirmation { request_id: mm_request.0, amount_to_receive: 1_000_000_000_000, amount_to_send: 1_000_000_000_000 }]])
 }
 
 impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
@@ -22,36 +22,271 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(test)]
+mod property_tests;
+
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+mod migrations;
+
 pub mod weights;
 pub use weights::*;
 
 // Re-export pallet items so that they can be accessed from the crate namespace.
 pub use pallet::*;
 
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+  inherent::Vec, traits::Get, BoundedVec, CloneNoBound, EqNoBound, PartialEqNoBound,
+  RuntimeDebugNoBound,
+};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{DispatchError, Permill, RuntimeDebug};
+use tidefi_primitives::{Balance, BalanceInfo, CurrencyId, Hash, Swap, SwapStatus, SwapType};
+
+pub(crate) const LOG_TARGET: &str = "tidefi::oracle";
+
+/// The maximum number of open swaps returned by page
+pub const OPEN_SWAPS_PAGE_LIMIT: u32 = 500;
+
+// syntactic sugar for logging.
+#[macro_export]
+macro_rules! log {
+	($level:tt, $patter:expr $(, $values:expr)* $(,)?) => {
+		log::$level!(
+			target: crate::LOG_TARGET,
+			concat!("[{:?}] 💸 ", $patter), T::Security::get_current_block_count() $(, $values)*
+		)
+	};
+}
+
+/// Multi-hop swaps traits, used by the pallets who route the swaps through intermediate assets
+pub trait SwapRouteExt<AccountId, BlockNumber> {
+  /// Hold the funds and add the swap routed through the `path` in queue, returns the request ID
+  #[allow(clippy::too_many_arguments)]
+  fn add_new_route_in_queue(
+    account_id: AccountId,
+    path: Vec<CurrencyId>,
+    amount_from: Balance,
+    min_amount_to: Balance,
+    block_number: BlockNumber,
+    extrinsic_hash: [u8; 32],
+    is_market_maker: bool,
+    slippage: Permill,
+  ) -> Result<Hash, DispatchError>;
+  /// Remove the routed swap from the queue and release the funds
+  fn remove_route_from_queue(requester: AccountId, request_id: Hash) -> Result<(), DispatchError>;
+  /// Enable the order book of the pair and fill the `currency_from` side up to one order below
+  /// the limit, used by the benchmarks of the swap callers
+  #[cfg(feature = "runtime-benchmarks")]
+  fn set_full_order_book(currency_from: CurrencyId, currency_to: CurrencyId);
+}
+
+/// Partial fill of a swap, from the point of view of the swap owner
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct SwapFill<BlockNumber> {
+  /// Request ID of the counterparty swap
+  pub counterparty_request_id: Hash,
+  /// Amount of `token_from` sent to the counterparty
+  pub amount_from: Balance,
+  /// Amount of `token_to` received from the counterparty
+  pub amount_to: Balance,
+  /// Network fee charged to the swap owner, in `token_from`
+  pub fee: Balance,
+  /// Block of the fill
+  pub block_number: BlockNumber,
+}
+
+/// Partial fill of a swap with the amounts wrapped in `BalanceInfo`, used by the RPC
+#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct SwapFillInfo<BlockNumber> {
+  /// Request ID of the counterparty swap
+  pub counterparty_request_id: Hash,
+  /// Amount of `token_from` sent to the counterparty
+  pub amount_from: BalanceInfo,
+  /// Amount of `token_to` received from the counterparty
+  pub amount_to: BalanceInfo,
+  /// Network fee charged to the swap owner, in `token_from`
+  pub fee: BalanceInfo,
+  /// Block of the fill
+  pub block_number: BlockNumber,
+}
+
+impl<BlockNumber> From<SwapFill<BlockNumber>> for SwapFillInfo<BlockNumber> {
+  fn from(fill: SwapFill<BlockNumber>) -> Self {
+    Self {
+      counterparty_request_id: fill.counterparty_request_id,
+      amount_from: BalanceInfo {
+        amount: fill.amount_from,
+      },
+      amount_to: BalanceInfo {
+        amount: fill.amount_to,
+      },
+      fee: BalanceInfo { amount: fill.fee },
+      block_number: fill.block_number,
+    }
+  }
+}
+
+/// Pending swap with the amounts wrapped in `BalanceInfo`, used by the RPC
+#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct SwapInfo<AccountId, BlockNumber> {
+  /// Account ID of the swap owner
+  pub account_id: AccountId,
+  /// Currency sent by the swap owner
+  pub token_from: CurrencyId,
+  /// Amount of `token_from` requested
+  pub amount_from: BalanceInfo,
+  /// Amount of `token_from` already sent to the counterparties
+  pub amount_from_filled: BalanceInfo,
+  /// Currency received by the swap owner
+  pub token_to: CurrencyId,
+  /// Amount of `token_to` requested
+  pub amount_to: BalanceInfo,
+  /// Amount of `token_to` already received from the counterparties
+  pub amount_to_filled: BalanceInfo,
+  /// Swap status
+  pub status: SwapStatus,
+  /// Swap type
+  pub swap_type: SwapType,
+  /// Slippage tolerance
+  pub slippage: Permill,
+  /// Block of the swap request
+  pub block_number: BlockNumber,
+  /// Extrinsic hash of the swap request
+  pub extrinsic_hash: [u8; 32],
+  /// The swap owner is a market maker
+  pub is_market_maker: bool,
+}
+
+impl<AccountId, BlockNumber> From<Swap<AccountId, BlockNumber>>
+  for SwapInfo<AccountId, BlockNumber>
+{
+  fn from(swap: Swap<AccountId, BlockNumber>) -> Self {
+    Self {
+      account_id: swap.account_id,
+      token_from: swap.token_from,
+      amount_from: BalanceInfo {
+        amount: swap.amount_from,
+      },
+      amount_from_filled: BalanceInfo {
+        amount: swap.amount_from_filled,
+      },
+      token_to: swap.token_to,
+      amount_to: BalanceInfo {
+        amount: swap.amount_to,
+      },
+      amount_to_filled: BalanceInfo {
+        amount: swap.amount_to_filled,
+      },
+      status: swap.status,
+      swap_type: swap.swap_type,
+      slippage: swap.slippage,
+      block_number: swap.block_number,
+      extrinsic_hash: swap.extrinsic_hash,
+      is_market_maker: swap.is_market_maker,
+    }
+  }
+}
+
+/// Side of the open swaps of a pair (`base`, `quote`)
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum SwapSide {
+  /// Swaps from `base` to `quote`
+  Sell,
+  /// Swaps from `quote` to `base`
+  Buy,
+}
+
+/// Market maker registration, with its bonded collateral and the supported pairs
+#[derive(
+  CloneNoBound,
+  Encode,
+  Decode,
+  EqNoBound,
+  PartialEqNoBound,
+  RuntimeDebugNoBound,
+  TypeInfo,
+  MaxEncodedLen,
+)]
+#[codec(mel_bound())]
+#[scale_info(skip_type_params(NameLimit, PairsLimit))]
+pub struct MarketMakerInfo<NameLimit: Get<u32>, PairsLimit: Get<u32>> {
+  /// Display name of the market maker
+  pub name: BoundedVec<u8, NameLimit>,
+  /// Amount of TIFI bonded, can be slashed by the governance
+  pub bond: Balance,
+  /// Supported pairs (`currency_from`, `currency_to`, `quota`), where `quota` is the maximum
+  /// amount of `currency_from` in the open swaps of the market maker
+  pub pairs: BoundedVec<(CurrencyId, CurrencyId, Balance), PairsLimit>,
+}
+
 #[frame_support::pallet]
 pub mod pallet {
   use super::*;
   use frame_support::{
     inherent::Vec,
+    log,
     pallet_prelude::*,
-    traits::fungibles::{Inspect, InspectHold, Mutate, MutateHold, Transfer},
-    PalletId,
+    traits::{
+      fungibles::{Inspect, InspectHold, Mutate, MutateHold, Transfer},
+      StorageVersion,
+    },
+    transactional, PalletId,
   };
   use frame_system::pallet_prelude::*;
-  #[cfg(feature = "std")]
-  use sp_runtime::traits::AccountIdConversion;
-  use sp_runtime::Permill;
+  use pallet_price_feed::PriceFeedExt;
+  use sp_runtime::{
+    helpers_128bit::multiply_by_rational,
+    traits::{
+      AccountIdConversion, Bounded, CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, One,
+      Saturating, Zero,
+    },
+    FixedPointNumber, FixedU128, Permill,
+  };
   use sp_std::vec;
   use tidefi_primitives::{
     assets::USDT,
-    pallet::{FeesExt, OracleExt, SecurityExt},
+    pallet::{OracleExt, SecurityExt},
     AssetId, Balance, CurrencyId, Hash, OracleImAlive, Swap, SwapConfirmation, SwapStatus,
     SwapType,
   };
 
+  type MarketMakerInfoOf<T> =
+    MarketMakerInfo<<T as Config>::MarketMakerNameLimit, <T as Config>::MarketMakerPairsLimit>;
+  type BoundedOrderBook<T> = BoundedVec<(Hash, FixedU128), <T as Config>::OrderBookLimit>;
+
+  /// Swap routed through intermediate assets, every leg of the `path` is settled at once
+  #[derive(
+    CloneNoBound, Encode, Decode, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo,
+  )]
+  #[scale_info(skip_type_params(T))]
+  pub struct SwapRoute<T: Config> {
+    /// Account ID of the swap owner
+    pub account_id: T::AccountId,
+    /// Currencies of the route, from the currency sent to the currency received
+    pub path: BoundedVec<CurrencyId, T::SwapPathLimit>,
+    /// Amount of the first currency of the path to send
+    pub amount_from: Balance,
+    /// Minimum amount of the last currency of the path to receive
+    pub min_amount_to: Balance,
+    /// Slippage tolerance of the amount sent
+    pub slippage: Permill,
+    /// Block of the swap request
+    pub block_number: T::BlockNumber,
+    /// Extrinsic hash of the swap request
+    pub extrinsic_hash: [u8; 32],
+    /// The swap owner is a market maker
+    pub is_market_maker: bool,
+  }
+
   /// Oracle configuration
   #[pallet::config]
   pub trait Config:
@@ -74,8 +309,56 @@ pub mod pallet {
     #[pallet::constant]
     type SwapLimitByAccount: Get<u32>;
 
+    /// The number of blocks a swap stay in queue before being expired and the funds released
+    #[pallet::constant]
+    type SwapLifetime: Get<Self::BlockNumber>;
+
+    /// The maximum number of limit orders in the order book for each side of a pair
+    #[pallet::constant]
+    type OrderBookLimit: Get<u32>;
+
+    /// The maximum number of fills kept in the history of each swap
+    #[pallet::constant]
+    type SwapFillLimit: Get<u32>;
+
+    /// The number of blocks the fills history of a swap is kept after the swap is closed
+    #[pallet::constant]
+    type SwapFillRetention: Get<Self::BlockNumber>;
+
+    /// The number of blocks without heartbeat before the oracle is considered unresponsive
+    /// and the trading is paused
+    #[pallet::constant]
+    type MaxOracleSilence: Get<Self::BlockNumber>;
+
+    /// The maximum number of currencies in the path of a routed swap
+    #[pallet::constant]
+    type SwapPathLimit: Get<u32>;
+
+    /// The amount of TIFI bonded by a market maker when registered
+    #[pallet::constant]
+    type MarketMakerBond: Get<Balance>;
+
+    /// The number of blocks the bond of a removed market maker is locked, it can still be
+    /// slashed until withdrawn
+    #[pallet::constant]
+    type MarketMakerUnbondingPeriod: Get<Self::BlockNumber>;
+
+    /// The maximum length of a market maker name
+    #[pallet::constant]
+    type MarketMakerNameLimit: Get<u32>;
+
+    /// The maximum number of pairs supported by a market maker
+    #[pallet::constant]
+    type MarketMakerPairsLimit: Get<u32>;
+
+    /// The origin which may slash the market makers bond
+    type ForceOrigin: EnsureOrigin<Self::Origin>;
+
     /// Fees traits
-    type Fees: FeesExt<Self::AccountId>;
+    type Fees: pallet_fees::SwapFeesExt<Self::AccountId>;
+
+    /// Price feed traits
+    type PriceFeed: PriceFeedExt<Self::AccountId>;
 
     /// Tidechain currency wrapper
     type CurrencyTidefi: Inspect<Self::AccountId, AssetId = CurrencyId, Balance = Balance>
@@ -85,8 +368,12 @@ pub mod pallet {
       + MutateHold<Self::AccountId, AssetId = CurrencyId, Balance = Balance>;
   }
 
+  /// The current storage version.
+  const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
   #[pallet::pallet]
   #[pallet::generate_store(pub (super) trait Store)]
+  #[pallet::storage_version(STORAGE_VERSION)]
   pub struct Pallet<T>(_);
 
   /// Oracle is enabled
@@ -99,6 +386,11 @@ pub mod pallet {
   #[pallet::getter(fn last_seen)]
   pub(super) type LastSeen<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
 
+  /// Block the oracle was flagged as unresponsive, cleared on the next heartbeat
+  #[pallet::storage]
+  #[pallet::getter(fn unresponsive_since)]
+  pub(super) type UnresponsiveSince<T: Config> = StorageValue<_, T::BlockNumber, OptionQuery>;
+
   /// Oracle Account ID
   #[pallet::storage]
   #[pallet::getter(fn account_id)]
@@ -120,11 +412,95 @@ pub mod pallet {
     BoundedVec<(Hash, SwapStatus), T::SwapLimitByAccount>,
   >;
 
+  /// History of the partial fills of the swaps, kept `SwapFillRetention` blocks after the swap is
+  /// closed. The oldest fill is dropped with a `SwapFillDropped` event when full
+  #[pallet::storage]
+  #[pallet::getter(fn swap_fills)]
+  pub type SwapFills<T: Config> = StorageMap<
+    _,
+    Blake2_128Concat,
+    Hash,
+    BoundedVec<SwapFill<T::BlockNumber>, T::SwapFillLimit>,
+    ValueQuery,
+  >;
+
+  /// Closed swaps with a fills history, with their closing block, by closing order
+  #[pallet::storage]
+  #[pallet::getter(fn closed_swap_fills)]
+  pub type ClosedSwapFills<T: Config> =
+    StorageMap<_, Twox64Concat, u64, (Hash, T::BlockNumber), OptionQuery>;
+
+  /// Head and tail of `ClosedSwapFills`, the fills history are pruned from the head
+  #[pallet::storage]
+  #[pallet::getter(fn closed_swap_fills_bounds)]
+  pub type ClosedSwapFillsBounds<T: Config> = StorageValue<_, (u64, u64), ValueQuery>;
+
+  /// Last swap checked by the expiration sweeper, the next sweep resume from there
+  #[pallet::storage]
+  #[pallet::getter(fn swap_expiration_cursor)]
+  pub(super) type SwapExpirationCursor<T: Config> = StorageValue<_, Hash, OptionQuery>;
+
+  /// Set of pairs matched on-chain by the order book, without the oracle confirmation
+  #[pallet::storage]
+  #[pallet::getter(fn order_book_pairs)]
+  pub type OrderBookPairs<T: Config> =
+    StorageMap<_, Blake2_128Concat, (CurrencyId, CurrencyId), bool>;
+
+  /// Limit orders of the pair `(token_from, token_to)` with their price (`amount_to / amount_from`),
+  /// sorted by price-time priority
+  #[pallet::storage]
+  #[pallet::getter(fn order_book)]
+  pub type OrderBook<T: Config> =
+    StorageMap<_, Blake2_128Concat, (CurrencyId, CurrencyId), BoundedOrderBook<T>, ValueQuery>;
+
+  /// Order book pairs enabled while limit swaps of the pair were already queued, with the last
+  /// swap visited. The pair is matched once all the queued swaps are in the order book.
+  #[pallet::storage]
+  #[pallet::getter(fn order_book_backfill)]
+  pub type OrderBookBackfill<T: Config> =
+    StorageMap<_, Blake2_128Concat, (CurrencyId, CurrencyId), Option<Hash>>;
+
+  /// Swaps routed through intermediate assets, waiting for the oracle to settle all the legs
+  #[pallet::storage]
+  #[pallet::getter(fn swap_routes)]
+  pub type SwapRoutes<T: Config> = StorageMap<_, Blake2_128Concat, Hash, SwapRoute<T>>;
+
+  /// Last routed swap checked by the expiration sweeper, the next sweep resume from there
+  #[pallet::storage]
+  #[pallet::getter(fn swap_route_expiration_cursor)]
+  pub(super) type SwapRouteExpirationCursor<T: Config> = StorageValue<_, Hash, OptionQuery>;
+
   /// Set of active market makers
   #[pallet::storage]
   #[pallet::getter(fn market_makers)]
   pub type MarketMakers<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, bool>;
 
+  /// Registration of the market makers, with their bond, name and supported pairs
+  #[pallet::storage]
+  #[pallet::getter(fn market_maker_info)]
+  pub type MarketMakerRegistry<T: Config> =
+    StorageMap<_, Blake2_128Concat, T::AccountId, MarketMakerInfoOf<T>>;
+
+  /// Bond of the removed market makers, with the block it can be withdrawn
+  #[pallet::storage]
+  #[pallet::getter(fn market_maker_unbonding)]
+  pub type MarketMakerUnbonding<T: Config> =
+    StorageMap<_, Blake2_128Concat, T::AccountId, (Balance, T::BlockNumber)>;
+
+  /// Amount of `currency_from` in the open swaps of a registered market maker for the pair
+  /// (`currency_from`, `currency_to`)
+  #[pallet::storage]
+  #[pallet::getter(fn market_maker_quota_used)]
+  pub type MarketMakerQuotaUsed<T: Config> = StorageDoubleMap<
+    _,
+    Blake2_128Concat,
+    T::AccountId,
+    Blake2_128Concat,
+    (CurrencyId, CurrencyId),
+    Balance,
+    ValueQuery,
+  >;
+
   /// Genesis configuration
   #[pallet::genesis_config]
   pub struct GenesisConfig<T: Config> {
@@ -133,7 +509,7 @@ pub mod pallet {
     /// Oracle Account ID. Multisig is supported.
     /// This account will be able to confirm trades on-chain.
     pub account: T::AccountId,
-    // List of active market makers
+    // List of market makers, bonded from their TIFI balance and waiting for the oracle approval
     pub market_makers: Vec<T::AccountId>,
   }
 
@@ -158,7 +534,23 @@ pub mod pallet {
       OracleAccountId::<T>::put(self.account.clone());
 
       for account_id in self.market_makers.clone() {
-        MarketMakers::<T>::insert(account_id, true);
+        let bond = T::MarketMakerBond::get();
+        T::CurrencyTidefi::transfer(
+          CurrencyId::Tifi,
+          &account_id,
+          &Pallet::<T>::bond_account_id(),
+          bond,
+          true,
+        )
+        .expect("Genesis market maker can't bond");
+        MarketMakerRegistry::<T>::insert(
+          account_id,
+          MarketMakerInfo {
+            name: BoundedVec::default(),
+            bond,
+            pairs: BoundedVec::default(),
+          },
+        );
       }
     }
   }
@@ -174,6 +566,27 @@ pub mod pallet {
     MarketMakerAdded { account_id: T::AccountId },
     /// Oracle removed a market maker
     MarketMakerRemoved { account_id: T::AccountId },
+    /// Account bonded TIFI and registered as market maker, waiting for the oracle approval
+    MarketMakerRegistered {
+      account_id: T::AccountId,
+      bond: Balance,
+    },
+    /// Governance slashed the bond of a market maker
+    MarketMakerSlashed {
+      account_id: T::AccountId,
+      amount: Balance,
+    },
+    /// The bond of a removed market maker is locked until `unlock_at`
+    MarketMakerUnbonding {
+      account_id: T::AccountId,
+      amount: Balance,
+      unlock_at: T::BlockNumber,
+    },
+    /// Removed market maker withdrew its unlocked bond
+    MarketMakerBondWithdrawn {
+      account_id: T::AccountId,
+      amount: Balance,
+    },
     /// Oracle processed the initial swap
     SwapProcessed {
       request_id: Hash,
@@ -185,8 +598,49 @@ pub mod pallet {
       currency_amount_to: Balance,
       initial_extrinsic_hash: [u8; 32],
     },
+    /// Swap partially or completely filled by a counterparty swap
+    SwapFilled {
+      request_id: Hash,
+      counterparty_request_id: Hash,
+      amount_from: Balance,
+      amount_to: Balance,
+      fee: Balance,
+      counterparty_fee: Balance,
+    },
     /// Oracle cancelled the initial swap and released the funds
     SwapCancelled { request_id: Hash },
+    /// The swap reached its lifetime without being completed and the funds were released
+    SwapExpired { request_id: Hash },
+    /// The fills history of the swap is full, its oldest fill has been dropped
+    SwapFillDropped {
+      request_id: Hash,
+      fill: SwapFill<T::BlockNumber>,
+    },
+    /// Oracle enabled or disabled the on-chain order book for a pair
+    OrderBookPairChanged {
+      currency_from: CurrencyId,
+      currency_to: CurrencyId,
+      is_enabled: bool,
+    },
+    /// The orders can't be matched, they are removed from the order book and stay in queue
+    OrderBookMatchFailed {
+      request_id: Hash,
+      counterparty_request_id: Hash,
+      error: DispatchError,
+    },
+    /// Oracle settled all the legs of a routed swap
+    SwapRouteProcessed {
+      request_id: Hash,
+      account_id: T::AccountId,
+      path: Vec<CurrencyId>,
+      amount_from: Balance,
+      amount_to: Balance,
+      fee: Balance,
+    },
+    /// Oracle didn't send any heartbeat for more than `MaxOracleSilence` blocks, trading is paused
+    OracleUnresponsive { last_seen: T::BlockNumber },
+    /// Oracle sent a new heartbeat after being unresponsive, trading is resumed
+    OracleRecovered { unresponsive_since: T::BlockNumber },
   }
 
   // Errors inform users that something went wrong.
@@ -226,12 +680,78 @@ pub mod pallet {
     MarketMakerNoFunds,
     /// Market Makers cannot deposit source funds of the trade
     MarketMakerCantDeposit,
+    /// The account is already registered as market maker
+    MarketMakerAlreadyRegistered,
+    /// The account is not registered as market maker
+    MarketMakerNotRegistered,
+    /// The market maker name is too long
+    MarketMakerNameTooLong,
+    /// Market maker supported pairs cap reached
+    MarketMakerPairsOverflow,
+    /// The pair is not supported by the market maker
+    MarketMakerPairNotSupported,
+    /// The market maker quota of the pair is exceeded
+    MarketMakerQuotaExceeded,
+    /// The account has no market maker bond to withdraw
+    MarketMakerNoUnbonding,
+    /// The market maker bond is still locked
+    MarketMakerBondLocked,
     /// Swaps cap reached for this account id
     SwapOverflow,
+    /// Order book cap reached for this pair
+    OrderBookOverflow,
+    /// The swap path must contain at least two different currencies.
+    InvalidSwapPath,
+    /// The legs doesn't match the swap path.
+    InvalidSwapRouteLegs,
+    /// The amount received is lower than the minimum of the routed swap.
+    SwapRouteBelowMinimum,
+    /// The amounts of the swap can't be zero.
+    InvalidSwapAmount,
     /// Unknown Error.
     UnknownError,
   }
 
+  #[pallet::hooks]
+  impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+    fn on_runtime_upgrade() -> Weight {
+      migrations::migrate_to_v1::<T, Self>()
+    }
+
+    /// Flag the oracle as unresponsive when the last heartbeat is too old
+    fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+      let db_weight = <T as frame_system::Config>::DbWeight::get();
+      // read the oracle status, the last seen, the current block and the unresponsive flag
+      let weight_used = db_weight.reads(4);
+
+      if Self::status() && Self::unresponsive_since().is_none() && Self::is_oracle_silent() {
+        let last_seen = Self::last_seen();
+        UnresponsiveSince::<T>::put(T::Security::get_current_block_count());
+        log!(warn, "Oracle unresponsive since block {:?}", last_seen);
+        Self::deposit_event(Event::<T>::OracleUnresponsive { last_seen });
+        return weight_used.saturating_add(db_weight.writes(1));
+      }
+
+      weight_used
+    }
+
+    /// Try to compute when chain is idle
+    fn on_idle(n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+      let mut weight_used = Self::clean_swap_queue_with_max_weight(n, remaining_weight);
+      weight_used = weight_used.saturating_add(Self::clean_swap_routes_with_max_weight(
+        n,
+        remaining_weight.saturating_sub(weight_used),
+      ));
+      weight_used = weight_used.saturating_add(Self::prune_swap_fills_with_max_weight(
+        n,
+        remaining_weight.saturating_sub(weight_used),
+      ));
+      weight_used.saturating_add(Self::match_order_books_with_max_weight(
+        remaining_weight.saturating_sub(weight_used),
+      ))
+    }
+  }
+
   #[pallet::call]
   impl<T: Config> Pallet<T> {
     /// Oracle have confirmation and confirm the trade.
@@ -276,27 +796,26 @@ pub mod pallet {
                 .map_err(|_| Error::<T>::InvalidMarketMakerRequest)?;
 
               // validate user slippage tolerance
-              let pay_per_token = trade.amount_from as f64 / trade.amount_to as f64;
-              let pay_per_token_offered = mm.amount_to_receive as f64 / mm.amount_to_send as f64;
-              let allowed_slippage = trade.slippage.deconstruct() as f64 / 1_000_000_f64;
-              let minimum_per_token = pay_per_token - (allowed_slippage * pay_per_token);
-              let maximum_per_token = pay_per_token + (allowed_slippage * pay_per_token);
-              ensure!(minimum_per_token <= pay_per_token_offered, Error::Overflow);
-              ensure!(maximum_per_token >= pay_per_token_offered, Error::Overflow);
-
-              // validate mm slippage tolerance
-              let pay_per_token =
-                mm_trade_request.amount_from as f64 / mm_trade_request.amount_to as f64;
-              let pay_per_token_offered = mm.amount_to_send as f64 / mm.amount_to_receive as f64;
-              let allowed_slippage = mm_trade_request.slippage.deconstruct() as f64 / 1_000_000_f64;
-              let minimum_per_token = pay_per_token - (allowed_slippage * pay_per_token);
-              let maximum_per_token = pay_per_token + (allowed_slippage * pay_per_token);
               ensure!(
-                minimum_per_token <= pay_per_token_offered,
-                Error::MarketMakerOverflow
+                Self::is_within_slippage(
+                  trade.amount_from,
+                  trade.amount_to,
+                  mm.amount_to_receive,
+                  mm.amount_to_send,
+                  trade.slippage,
+                ),
+                Error::Overflow
               );
+
+              // validate mm slippage tolerance
               ensure!(
-                maximum_per_token >= pay_per_token_offered,
+                Self::is_within_slippage(
+                  mm_trade_request.amount_from,
+                  mm_trade_request.amount_to,
+                  mm.amount_to_send,
+                  mm.amount_to_receive,
+                  mm_trade_request.slippage,
+                ),
                 Error::MarketMakerOverflow
               );
 
@@ -417,6 +936,7 @@ pub mod pallet {
                       mm.amount_to_receive,
                       trade.is_market_maker,
                     );
+                    let requester_fee = amount_and_fee.fee;
 
                     if T::CurrencyTidefi::transfer_held(
                       trade.token_from,
@@ -500,6 +1020,38 @@ pub mod pallet {
                     )
                     .map_err(|_| Error::<T>::FeesFailed)?;
 
+                    // 12. d) Keep the fill in both swaps history
+                    let block_number = <frame_system::Pallet<T>>::block_number();
+                    Self::record_swap_fill(
+                      request_id,
+                      SwapFill {
+                        counterparty_request_id: mm.request_id,
+                        amount_from: mm.amount_to_receive,
+                        amount_to: mm.amount_to_send,
+                        fee: requester_fee,
+                        block_number,
+                      },
+                    );
+                    Self::record_swap_fill(
+                      mm.request_id,
+                      SwapFill {
+                        counterparty_request_id: request_id,
+                        amount_from: mm.amount_to_send,
+                        amount_to: mm.amount_to_receive,
+                        fee: amount_and_fee.fee,
+                        block_number,
+                      },
+                    );
+
+                    Self::deposit_event(Event::<T>::SwapFilled {
+                      request_id,
+                      counterparty_request_id: mm.request_id,
+                      amount_from: mm.amount_to_receive,
+                      amount_to: mm.amount_to_send,
+                      fee: requester_fee,
+                      counterparty_fee: amount_and_fee.fee,
+                    });
+
                     // 13. Emit market maker trade event on chain
                     Self::deposit_event(Event::<T>::SwapProcessed {
                       request_id: mm.request_id,
@@ -523,7 +1075,7 @@ pub mod pallet {
                         mm.request_id,
                       )
                       .map_err(|_| Error::<T>::UnknownError)?;
-                      Self::swap_release_funds(market_maker_trade_intent)
+                      Self::swap_release_funds(mm.request_id, market_maker_trade_intent)
                         .map_err(|_| Error::<T>::ReleaseFailed)?;
                       *mm_trade_request = None;
                     } else {
@@ -557,7 +1109,7 @@ pub mod pallet {
             if trade.status == SwapStatus::Completed || trade.swap_type == SwapType::Market {
               Self::try_delete_account_swap(&trade.account_id, request_id)
                 .map_err(|_| Error::<T>::UnknownError)?;
-              Self::swap_release_funds(trade).map_err(|_| Error::<T>::ReleaseFailed)?;
+              Self::swap_release_funds(request_id, trade).map_err(|_| Error::<T>::ReleaseFailed)?;
 
               *trade_request = None;
             } else {
@@ -575,7 +1127,7 @@ pub mod pallet {
       })?;
 
       // 15. Update last seen
-      LastSeen::<T>::put(T::Security::get_current_block_count());
+      Self::update_last_seen();
 
       // don't take tx fees on success
       Ok(Pays::No.into())
@@ -590,21 +1142,68 @@ pub mod pallet {
     /// Weight: `O(1)`
     #[pallet::weight(<T as pallet::Config>::WeightInfo::confirm_swap())]
     pub fn cancel_swap(origin: OriginFor<T>, request_id: Hash) -> DispatchResultWithPostInfo {
+      // 1. Make sure this is signed by `account_id`, the cancellation stay open while the
+      // oracle is unresponsive
+      let sender = ensure_signed(origin)?;
+      let is_oracle = Some(sender.clone()) == Self::account_id();
+
+      // 2. Remove swap from queue
+      Self::remove_swap_from_queue(sender, request_id)?;
+
+      // 3. Emit event on chain
+      Self::deposit_event(Event::<T>::SwapCancelled { request_id });
+
+      // 4. Update last seen, only the oracle calls are heartbeats
+      if is_oracle {
+        Self::update_last_seen();
+      }
+
+      Ok(Pays::No.into())
+    }
+
+    /// Oracle confirm all the legs of a swap routed through intermediate assets.
+    ///
+    /// The legs are settled atomically, if any leg fails, nothing is committed and
+    /// the routed swap stay in queue.
+    ///
+    /// - `request_id`: Unique request ID of the routed swap.
+    /// - `legs`: Vector of `SwapConfirmation` for each hop of the path, the amount received by
+    ///   a leg is the amount sent by the next leg.
+    ///
+    /// Emits `SwapRouteProcessed` event when successful.
+    ///
+    /// Weight: `O(n)` where `n` is the number of market makers in all the legs
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::confirm_swap_route(
+      legs.iter().map(|leg| leg.len() as u32).sum()
+    ))]
+    pub fn confirm_swap_route(
+      origin: OriginFor<T>,
+      request_id: Hash,
+      legs: Vec<Vec<SwapConfirmation>>,
+    ) -> DispatchResultWithPostInfo {
       // 1. Make sure the oracle/chain is not paused
       Self::ensure_not_paused()?;
 
       // 2. Make sure this is signed by `account_id`
       let sender = ensure_signed(origin)?;
+      ensure!(Some(sender) == Self::account_id(), Error::<T>::AccessDenied);
 
-      // 3. Remove swap from queue
-      Self::remove_swap_from_queue(sender, request_id)?;
+      // 3. Make sure the `request_id` exist
+      let route = Self::swap_routes(request_id).ok_or(Error::<T>::InvalidRequestId)?;
 
-      // 4. Emit event on chain
-      Self::deposit_event(Event::<T>::SwapCancelled { request_id });
+      // 4. Make sure we have a leg for each hop of the path
+      ensure!(
+        legs.len().saturating_add(1) == route.path.len(),
+        Error::<T>::InvalidSwapRouteLegs
+      );
 
-      // 5. Update last seen
-      LastSeen::<T>::put(T::Security::get_current_block_count());
+      // 5. Settle all the legs
+      Self::settle_swap_route(request_id, route, legs)?;
+
+      // 6. Update last seen
+      Self::update_last_seen();
 
+      // don't take tx fees on success
       Ok(Pays::No.into())
     }
 
@@ -636,7 +1235,7 @@ pub mod pallet {
       });
 
       // 4. Update last seen
-      LastSeen::<T>::put(T::Security::get_current_block_count());
+      Self::update_last_seen();
 
       // don't take tx fees on success
       Ok(Pays::No.into())
@@ -662,7 +1261,7 @@ pub mod pallet {
       Self::deposit_event(Event::<T>::StatusChanged { is_enabled });
 
       // 4. Update last seen
-      LastSeen::<T>::put(T::Security::get_current_block_count());
+      Self::update_last_seen();
 
       // don't take tx fees on success
       Ok(Pays::No.into())
@@ -679,7 +1278,10 @@ pub mod pallet {
     pub fn im_alive(origin: OriginFor<T>, im_alive: OracleImAlive) -> DispatchResultWithPostInfo {
       // 1. Make sure this is signed by `account_id`
       let sender = ensure_signed(origin)?;
-      ensure!(Some(sender) == Self::account_id(), Error::<T>::AccessDenied);
+      ensure!(
+        Some(sender.clone()) == Self::account_id(),
+        Error::<T>::AccessDenied
+      );
 
       // 2. Build final price vector
       let mut all_prices = Vec::new();
@@ -694,48 +1296,116 @@ pub mod pallet {
 
       if !all_prices.is_empty() {
         // Update only if we provided at least one price
-        T::Fees::register_order_book_price(all_prices)?;
+        T::PriceFeed::submit_prices(&sender, all_prices)?;
       }
 
       // 3. Update last seen
-      LastSeen::<T>::put(T::Security::get_current_block_count());
+      Self::update_last_seen();
 
       // don't take tx fees on success
       Ok(Pays::No.into())
     }
 
-    /// Add market maker to the local storage
+    /// Bond `MarketMakerBond` TIFI and register as market maker.
     ///
-    /// Emits `StatusChanged` event when successful.
+    /// The market maker is active once approved by the oracle with `add_market_maker`.
+    ///
+    /// - `name`: Display name of the market maker.
+    ///
+    /// Emits `MarketMakerRegistered` event when successful.
+    ///
+    /// Weight: `O(1)`
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::register_market_maker())]
+    pub fn register_market_maker(
+      origin: OriginFor<T>,
+      name: Vec<u8>,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure this is signed
+      let account_id = ensure_signed(origin)?;
+
+      // 2. Make sure the account is not already registered
+      ensure!(
+        !MarketMakerRegistry::<T>::contains_key(&account_id),
+        Error::<T>::MarketMakerAlreadyRegistered
+      );
+      let name: BoundedVec<u8, T::MarketMakerNameLimit> = name
+        .try_into()
+        .map_err(|_| Error::<T>::MarketMakerNameTooLong)?;
+
+      // 3. Bond the TIFI in the pallet account
+      let bond = T::MarketMakerBond::get();
+      T::CurrencyTidefi::transfer(
+        CurrencyId::Tifi,
+        &account_id,
+        &Self::bond_account_id(),
+        bond,
+        true,
+      )
+      .map_err(|_| Error::<T>::TransferFailed)?;
+
+      // 4. Register the market maker, without any supported pair until approved
+      MarketMakerRegistry::<T>::insert(
+        &account_id,
+        MarketMakerInfo {
+          name,
+          bond,
+          pairs: BoundedVec::default(),
+        },
+      );
+
+      // 5. Emit event on chain
+      Self::deposit_event(Event::<T>::MarketMakerRegistered { account_id, bond });
+
+      Ok(().into())
+    }
+
+    /// Approve a registered market maker, or update its supported pairs.
+    ///
+    /// - `account_id`: The registered market maker.
+    /// - `pairs`: Vector of (`currency_from`, `currency_to`, `quota`) supported by the market
+    /// maker, where `quota` is the maximum amount of `currency_from` in its open swaps.
+    ///
+    /// Emits `MarketMakerAdded` event when successful.
     ///
     /// Weight: `O(1)`
     #[pallet::weight(<T as pallet::Config>::WeightInfo::add_market_maker())]
     pub fn add_market_maker(
       origin: OriginFor<T>,
       account_id: T::AccountId,
+      pairs: Vec<(CurrencyId, CurrencyId, Balance)>,
     ) -> DispatchResultWithPostInfo {
       // 1. Make sure this is signed by `account_id`
       let sender = ensure_signed(origin)?;
       ensure!(Some(sender) == Self::account_id(), Error::<T>::AccessDenied);
 
-      // 2. Insert and make the account ID as a market maker (overwrite if already exist)
+      // 2. Set the supported pairs of the registered market maker
+      MarketMakerRegistry::<T>::try_mutate(&account_id, |info| match info {
+        Some(info) => {
+          info.pairs = pairs
+            .try_into()
+            .map_err(|_| Error::<T>::MarketMakerPairsOverflow)?;
+          Ok(())
+        }
+        None => Err(Error::<T>::MarketMakerNotRegistered),
+      })?;
+
+      // 3. Insert and make the account ID as a market maker (overwrite if already exist)
       MarketMakers::<T>::insert(account_id.clone(), true);
 
-      // 3. Emit event on chain
+      // 4. Emit event on chain
       Self::deposit_event(Event::<T>::MarketMakerAdded { account_id });
 
-      // 4. Update last seen
-      LastSeen::<T>::put(T::Security::get_current_block_count());
+      // 5. Update last seen
+      Self::update_last_seen();
 
       // don't take tx fees on success
       Ok(Pays::No.into())
     }
 
-    /// Remove market maker to the local storage
+    /// Remove market maker from the local storage and lock its remaining bond for
+    /// `MarketMakerUnbondingPeriod` blocks, the bond can still be slashed until withdrawn.
     ///
-    /// - `delete_orders`: Should we delete all existing swaps on chain for this user?
-    ///
-    /// Emits `StatusChanged` event when successful.
+    /// Emits `MarketMakerRemoved` event when successful.
     ///
     /// Weight: `O(1)`
     #[pallet::weight(<T as pallet::Config>::WeightInfo::remove_market_maker())]
@@ -747,67 +1417,1507 @@ pub mod pallet {
       let sender = ensure_signed(origin)?;
       ensure!(Some(sender) == Self::account_id(), Error::<T>::AccessDenied);
 
-      // 2. Remove the market makers from the chain storage
+      // 2. Lock the remaining bond of the registered market maker until the end of the unbonding
+      if let Some(info) = MarketMakerRegistry::<T>::take(&account_id) {
+        if !info.bond.is_zero() {
+          let unlock_at = T::Security::get_current_block_count()
+            .saturating_add(T::MarketMakerUnbondingPeriod::get());
+          MarketMakerUnbonding::<T>::mutate(&account_id, |unbonding| {
+            let amount = unbonding
+              .map(|(amount, _)| amount)
+              .unwrap_or_default()
+              .saturating_add(info.bond);
+            *unbonding = Some((amount, unlock_at));
+          });
+          Self::deposit_event(Event::<T>::MarketMakerUnbonding {
+            account_id: account_id.clone(),
+            amount: info.bond,
+            unlock_at,
+          });
+        }
+      }
+
+      // 3. Remove the market makers from the chain storage
       MarketMakers::<T>::remove(account_id.clone());
 
-      // 3. Emit event on chain
+      // 4. Emit event on chain
       Self::deposit_event(Event::<T>::MarketMakerRemoved { account_id });
 
-      // 4. Update last seen
-      LastSeen::<T>::put(T::Security::get_current_block_count());
+      // 5. Update last seen
+      Self::update_last_seen();
 
       // don't take tx fees on success
       Ok(Pays::No.into())
     }
-  }
 
-  // helper functions (not dispatchable)
-  impl<T: Config> Pallet<T> {
-    fn swap_release_funds(trade: &Swap<T::AccountId, T::BlockNumber>) -> Result<(), DispatchError> {
-      // release the remaining funds
-      let amount_with_max_slippage = trade
-        .amount_from
-        .saturating_add(trade.slippage * trade.amount_from);
-      // real fees required
-      let real_fees_amount = T::Fees::calculate_swap_fees(
-        trade.token_from,
-        trade.amount_from_filled,
-        trade.is_market_maker,
-      );
-      let fees_with_slippage = T::Fees::calculate_swap_fees(
-        trade.token_from,
-        amount_with_max_slippage,
-        trade.is_market_maker,
+    /// Slash the bond of a market maker who failed to deliver the funds of a swap.
+    ///
+    /// The slashed amount is sent to the fees pallet account, the market maker is disabled
+    /// when its remaining bond is lower than `MarketMakerBond`. The bond of a removed market
+    /// maker is slashed until withdrawn.
+    ///
+    /// - `account_id`: The registered or removed market maker.
+    /// - `amount`: The amount to slash, capped to the bond and the unbonding bond.
+    ///
+    /// Emits `MarketMakerSlashed` event when successful.
+    ///
+    /// Weight: `O(1)`
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::slash_market_maker())]
+    pub fn slash_market_maker(
+      origin: OriginFor<T>,
+      account_id: T::AccountId,
+      amount: Balance,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure this is called by the force origin
+      T::ForceOrigin::ensure_origin(origin)?;
+
+      // 2. Make sure the account has a bond to slash
+      ensure!(
+        MarketMakerRegistry::<T>::contains_key(&account_id)
+          || MarketMakerUnbonding::<T>::contains_key(&account_id),
+        Error::<T>::MarketMakerNotRegistered
       );
 
-      let amount_to_release = trade
-        .amount_from
-        // slippage
-        .saturating_add(trade.slippage * trade.amount_from)
-        // reduce filled amount
-        .saturating_sub(trade.amount_from_filled)
-        // reduce un-needed locked fee
-        .saturating_add(fees_with_slippage.fee.saturating_sub(real_fees_amount.fee));
+      // 3. Reduce the bond of the market maker, then its unbonding bond
+      let mut remaining_amount = amount;
+      let mut remaining_bond = Zero::zero();
+      MarketMakerRegistry::<T>::mutate(&account_id, |info| {
+        if let Some(info) = info {
+          let slashed = remaining_amount.min(info.bond);
+          info.bond = info.bond.saturating_sub(slashed);
+          remaining_amount = remaining_amount.saturating_sub(slashed);
+          remaining_bond = info.bond;
+        }
+      });
+      MarketMakerUnbonding::<T>::mutate_exists(&account_id, |unbonding| {
+        *unbonding = unbonding
+          .map(|(unbonding_amount, unlock_at)| {
+            let slashed = remaining_amount.min(unbonding_amount);
+            remaining_amount = remaining_amount.saturating_sub(slashed);
+            (unbonding_amount.saturating_sub(slashed), unlock_at)
+          })
+          .filter(|(unbonding_amount, _)| !unbonding_amount.is_zero());
+      });
+      let amount = amount.saturating_sub(remaining_amount);
+
+      // 4. Send the slashed amount to the fees pallet account
+      if !amount.is_zero() {
+        T::CurrencyTidefi::transfer(
+          CurrencyId::Tifi,
+          &Self::bond_account_id(),
+          &T::Fees::account_id(),
+          amount,
+          false,
+        )
+        .map_err(|_| Error::<T>::TransferFailed)?;
+      }
 
-      T::CurrencyTidefi::release(
-        trade.token_from,
-        &trade.account_id,
-        amount_to_release,
-        false,
-      )
-      .map_err(|_| Error::<T>::ReleaseFailed)?;
+      // 5. Disable the market maker when the bond is not enough anymore
+      if remaining_bond < T::MarketMakerBond::get() {
+        MarketMakers::<T>::remove(&account_id);
+      }
 
-      Ok(())
+      // 6. Emit event on chain
+      Self::deposit_event(Event::<T>::MarketMakerSlashed { account_id, amount });
+
+      Ok(().into())
     }
 
-    fn ensure_not_paused() -> Result<(), DispatchError> {
-      if Self::is_oracle_enabled() {
-        Ok(())
+    /// Withdraw the bond of a removed market maker, once `MarketMakerUnbondingPeriod` blocks
+    /// passed since its removal.
+    ///
+    /// Emits `MarketMakerBondWithdrawn` event when successful.
+    ///
+    /// Weight: `O(1)`
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::withdraw_market_maker_bond())]
+    pub fn withdraw_market_maker_bond(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+      // 1. Make sure this is signed
+      let account_id = ensure_signed(origin)?;
+
+      // 2. Make sure the bond is unlocked
+      let (amount, unlock_at) =
+        Self::market_maker_unbonding(&account_id).ok_or(Error::<T>::MarketMakerNoUnbonding)?;
+      ensure!(
+        unlock_at <= T::Security::get_current_block_count(),
+        Error::<T>::MarketMakerBondLocked
+      );
+
+      // 3. Release the bond
+      T::CurrencyTidefi::transfer(
+        CurrencyId::Tifi,
+        &Self::bond_account_id(),
+        &account_id,
+        amount,
+        false,
+      )
+      .map_err(|_| Error::<T>::ReleaseFailed)?;
+      MarketMakerUnbonding::<T>::remove(&account_id);
+
+      // 4. Emit event on chain
+      Self::deposit_event(Event::<T>::MarketMakerBondWithdrawn { account_id, amount });
+
+      Ok(().into())
+    }
+
+    /// Enable or disable the on-chain order book for a pair.
+    ///
+    /// When enabled, the limit swaps of the pair (in both directions) are added to the
+    /// order book and crossing orders are matched when the chain is idle. The limit swaps
+    /// already in queue are added when the chain is idle, before the pair get matched.
+    /// When disabled, the order book of the pair is cleared and the swaps are
+    /// only confirmed by the oracle.
+    ///
+    /// - `currency_from`: The first currency of the pair.
+    /// - `currency_to`: The second currency of the pair.
+    /// - `is_enabled`: Is the order book enabled for this pair?
+    ///
+    /// Emits `OrderBookPairChanged` event when successful.
+    ///
+    /// Weight: `O(1)`
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::set_order_book_pair())]
+    pub fn set_order_book_pair(
+      origin: OriginFor<T>,
+      currency_from: CurrencyId,
+      currency_to: CurrencyId,
+      is_enabled: bool,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure this is signed by `account_id`
+      let sender = ensure_signed(origin)?;
+      ensure!(Some(sender) == Self::account_id(), Error::<T>::AccessDenied);
+
+      // 2. Make sure the pair is valid
+      ensure!(currency_from != currency_to, Error::<T>::Conflict);
+
+      // 3. Update the pair, the pair is stored once for both directions
+      if is_enabled {
+        if !Self::is_order_book_pair(currency_from, currency_to) {
+          OrderBookPairs::<T>::insert((currency_from, currency_to), true);
+          OrderBookBackfill::<T>::insert((currency_from, currency_to), None::<Hash>);
+        }
+      } else {
+        OrderBookPairs::<T>::remove((currency_from, currency_to));
+        OrderBookPairs::<T>::remove((currency_to, currency_from));
+        OrderBookBackfill::<T>::remove((currency_from, currency_to));
+        OrderBookBackfill::<T>::remove((currency_to, currency_from));
+        OrderBook::<T>::remove((currency_from, currency_to));
+        OrderBook::<T>::remove((currency_to, currency_from));
+      }
+
+      // 4. Emit event on chain
+      Self::deposit_event(Event::<T>::OrderBookPairChanged {
+        currency_from,
+        currency_to,
+        is_enabled,
+      });
+
+      // 5. Update last seen
+      Self::update_last_seen();
+
+      // don't take tx fees on success
+      Ok(Pays::No.into())
+    }
+  }
+
+  // helper functions (not dispatchable)
+  impl<T: Config> Pallet<T> {
+    // the oracle didn't send any heartbeat in the last `MaxOracleSilence` blocks
+    pub fn is_oracle_silent() -> bool {
+      Self::last_seen().saturating_add(T::MaxOracleSilence::get())
+        < T::Security::get_current_block_count()
+    }
+
+    // record the oracle heartbeat and resume the trading if it was unresponsive
+    fn update_last_seen() {
+      LastSeen::<T>::put(T::Security::get_current_block_count());
+      if let Some(unresponsive_since) = UnresponsiveSince::<T>::take() {
+        Self::deposit_event(Event::<T>::OracleRecovered { unresponsive_since });
+      }
+    }
+
+    /// Make sure the price offered (`offered_from` / `offered_to`) is within the `slippage`
+    /// tolerance of the price expected (`expected_from` / `expected_to`).
+    ///
+    /// Prices are computed with `FixedU128`. When the expected price is below 1, both prices
+    /// are inverted so we keep all the precision of the fixed point number, and the bounds are
+    /// adjusted accordingly. The bounds are always rounded outward by the smallest fixed point
+    /// unit, so an offer exactly on the boundary is accepted.
+    ///
+    /// Returns `false` if any of the amounts is zero or if the price can't be represented.
+    pub fn is_within_slippage(
+      expected_from: Balance,
+      expected_to: Balance,
+      offered_from: Balance,
+      offered_to: Balance,
+      slippage: Permill,
+    ) -> bool {
+      Self::try_is_within_slippage(
+        expected_from,
+        expected_to,
+        offered_from,
+        offered_to,
+        slippage,
+      )
+      .unwrap_or(false)
+    }
+
+    fn try_is_within_slippage(
+      expected_from: Balance,
+      expected_to: Balance,
+      offered_from: Balance,
+      offered_to: Balance,
+      slippage: Permill,
+    ) -> Option<bool> {
+      let slippage = FixedU128::from(slippage);
+      let smallest_unit = FixedU128::from_inner(1);
+
+      let (price, offered_price, minimum_price, maximum_price) = if expected_from >= expected_to {
+        // price = from / to
+        // bounds = [price * (1 - slippage), price * (1 + slippage)]
+        let price = FixedU128::checked_from_rational(expected_from, expected_to)?;
+        let offered_price = FixedU128::checked_from_rational(offered_from, offered_to)?;
+        let minimum_price = price.checked_mul(&FixedU128::one().saturating_sub(slippage))?;
+        let maximum_price = price.checked_mul(&FixedU128::one().checked_add(&slippage)?)?;
+        (price, offered_price, minimum_price, maximum_price)
+      } else {
+        // inverted price = to / from
+        // bounds = [price / (1 + slippage), price / (1 - slippage)]
+        let price = FixedU128::checked_from_rational(expected_to, expected_from)?;
+        let offered_price = FixedU128::checked_from_rational(offered_to, offered_from)?;
+        let minimum_price = price.checked_div(&FixedU128::one().checked_add(&slippage)?)?;
+        let maximum_price = match FixedU128::one().checked_sub(&slippage) {
+          // 100% slippage, there is no upper bound
+          Some(divisor) if !divisor.is_zero() => price.checked_div(&divisor)?,
+          _ => FixedU128::max_value(),
+        };
+        (price, offered_price, minimum_price, maximum_price)
+      };
+
+      if price.is_zero() || offered_price.is_zero() {
+        return Some(false);
+      }
+
+      Some(
+        offered_price >= minimum_price.saturating_sub(smallest_unit)
+          && offered_price <= maximum_price.saturating_add(smallest_unit),
+      )
+    }
+
+    fn clean_swap_queue_with_max_weight(
+      current_block: T::BlockNumber,
+      max_weight: Weight,
+    ) -> Weight {
+      let db_weight = <T as frame_system::Config>::DbWeight::get();
+      // read the cursor
+      let mut weight_used = db_weight.reads(1);
+
+      // The amount of remaining weight under which we stop processing swaps
+      let threshold_weight = 100_000;
+      // Read the swap
+      let read_weight = db_weight.reads(1);
+      // Release the funds, update `AccountSwaps` and delete the swap
+      let expire_weight = db_weight.reads_writes(2, 3);
+      // Remove the limit order from a full order book
+      let order_book_weight =
+        <T as pallet::Config>::WeightInfo::match_order_book(T::OrderBookLimit::get());
+
+      let swap_lifetime = T::SwapLifetime::get();
+      let initial_cursor = Self::swap_expiration_cursor();
+      let mut cursor = initial_cursor;
+
+      // resume where the last sweep stopped, so the swaps at the beginning of the map
+      // can't prevent the rest of the queue to be processed
+      let mut swaps = match cursor {
+        Some(last_request_id) => Swaps::<T>::iter_from(Swaps::<T>::hashed_key_for(last_request_id)),
+        None => Swaps::<T>::iter(),
+      };
+
+      let mut expired_swaps = Vec::new();
+      while max_weight.saturating_sub(weight_used)
+        >= threshold_weight
+          .saturating_add(read_weight)
+          .saturating_add(expire_weight)
+          .saturating_add(order_book_weight)
+      {
+        match swaps.next() {
+          Some((request_id, swap)) => {
+            weight_used += read_weight;
+            cursor = Some(request_id);
+
+            if current_block >= swap.block_number.saturating_add(swap_lifetime) {
+              weight_used += expire_weight;
+              if swap.swap_type == SwapType::Limit {
+                weight_used += order_book_weight;
+              }
+              expired_swaps.push((request_id, swap));
+            }
+          }
+          None => {
+            // we reached the end of the queue, next sweep start from the beginning
+            cursor = None;
+            break;
+          }
+        }
+      }
+
+      for (request_id, swap) in expired_swaps {
+        match Self::swap_cancel_release_funds(request_id, &swap) {
+          Ok(_) => {
+            Swaps::<T>::remove(request_id);
+            Self::deposit_event(Event::<T>::SwapExpired { request_id });
+          }
+          Err(_) => {
+            log!(error, "Can't expire swap {}", request_id);
+          }
+        }
+      }
+
+      // Update the cursor (1 write)
+      if cursor != initial_cursor {
+        SwapExpirationCursor::<T>::set(cursor);
+        weight_used += db_weight.writes(1);
+      }
+
+      weight_used
+    }
+
+    fn clean_swap_routes_with_max_weight(
+      current_block: T::BlockNumber,
+      max_weight: Weight,
+    ) -> Weight {
+      let db_weight = <T as frame_system::Config>::DbWeight::get();
+      // read the cursor
+      let mut weight_used = db_weight.reads(1);
+
+      // The amount of remaining weight under which we stop processing routed swaps
+      let threshold_weight = 100_000;
+      // Read the routed swap
+      let read_weight = db_weight.reads(1);
+      // Release the funds, update `AccountSwaps` and delete the routed swap
+      let expire_weight = db_weight.reads_writes(2, 3);
+
+      let swap_lifetime = T::SwapLifetime::get();
+      let initial_cursor = Self::swap_route_expiration_cursor();
+      let mut cursor = initial_cursor;
+
+      let mut routes = match cursor {
+        Some(last_request_id) => {
+          SwapRoutes::<T>::iter_from(SwapRoutes::<T>::hashed_key_for(last_request_id))
+        }
+        None => SwapRoutes::<T>::iter(),
+      };
+
+      let mut expired_routes = Vec::new();
+      while max_weight.saturating_sub(weight_used)
+        >= threshold_weight
+          .saturating_add(read_weight)
+          .saturating_add(expire_weight)
+      {
+        match routes.next() {
+          Some((request_id, route)) => {
+            weight_used += read_weight;
+            cursor = Some(request_id);
+
+            if current_block >= route.block_number.saturating_add(swap_lifetime) {
+              weight_used += expire_weight;
+              expired_routes.push((request_id, route));
+            }
+          }
+          None => {
+            // we reached the end of the routed swaps, next sweep start from the beginning
+            cursor = None;
+            break;
+          }
+        }
+      }
+
+      for (request_id, route) in expired_routes {
+        match Self::swap_route_release_funds(request_id, &route) {
+          Ok(_) => Self::deposit_event(Event::<T>::SwapExpired { request_id }),
+          Err(_) => {
+            log!(error, "Can't expire routed swap {}", request_id);
+          }
+        }
+      }
+
+      // Update the cursor (1 write)
+      if cursor != initial_cursor {
+        SwapRouteExpirationCursor::<T>::set(cursor);
+        weight_used += db_weight.writes(1);
+      }
+
+      weight_used
+    }
+
+    // prune the fills history of the swaps closed for more than `SwapFillRetention` blocks
+    fn prune_swap_fills_with_max_weight(
+      current_block: T::BlockNumber,
+      max_weight: Weight,
+    ) -> Weight {
+      let db_weight = <T as frame_system::Config>::DbWeight::get();
+      // read the bounds
+      let mut weight_used = db_weight.reads(1);
+
+      // Read the closed swap, delete it with its fills history
+      let prune_weight = db_weight.reads_writes(1, 2);
+
+      let (initial_head, tail) = Self::closed_swap_fills_bounds();
+      let mut head = initial_head;
+      while head < tail && max_weight.saturating_sub(weight_used) >= prune_weight {
+        weight_used += db_weight.reads(1);
+        if let Some((request_id, closed_at)) = Self::closed_swap_fills(head) {
+          // the swaps are closed in order, stop at the first one in retention
+          if closed_at.saturating_add(T::SwapFillRetention::get()) > current_block {
+            break;
+          }
+          SwapFills::<T>::remove(request_id);
+          weight_used += db_weight.writes(1);
+        }
+        ClosedSwapFills::<T>::remove(head);
+        weight_used += db_weight.writes(1);
+        head = head.saturating_add(1);
+      }
+
+      // Update the bounds (1 write)
+      if head != initial_head {
+        ClosedSwapFillsBounds::<T>::put((head, tail));
+        weight_used += db_weight.writes(1);
+      }
+
+      weight_used
+    }
+
+    // keep the fills history of the closed swap until the end of the retention
+    fn schedule_swap_fills_pruning(request_id: Hash) {
+      if !SwapFills::<T>::contains_key(request_id) {
+        return;
+      }
+      ClosedSwapFillsBounds::<T>::mutate(|(_, tail)| {
+        ClosedSwapFills::<T>::insert(
+          *tail,
+          (request_id, <frame_system::Pallet<T>>::block_number()),
+        );
+        *tail = tail.saturating_add(1);
+      });
+    }
+
+    fn match_order_books_with_max_weight(max_weight: Weight) -> Weight {
+      let db_weight = <T as frame_system::Config>::DbWeight::get();
+      // read the chain status, the oracle status and its last heartbeat
+      let mut weight_used = db_weight.reads(4);
+
+      if !Self::is_oracle_enabled() {
+        return weight_used;
+      }
+
+      // The amount of remaining weight under which we stop processing orders
+      let threshold_weight = 100_000;
+      // Read the size of both sides of the order book and the backfill cursor
+      let read_weight = db_weight.reads(3);
+      // A match touch the same storage as an oracle confirmation with a single market maker
+      let match_weight = <T as pallet::Config>::WeightInfo::confirm_swap();
+
+      for ((currency_from, currency_to), _) in OrderBookPairs::<T>::iter() {
+        weight_used += db_weight.reads(1);
+
+        if max_weight.saturating_sub(weight_used) < threshold_weight.saturating_add(read_weight) {
+          return weight_used;
+        }
+        weight_used += read_weight;
+
+        // both sides of the order book are decoded once and written back once
+        let orders = OrderBook::<T>::decode_len((currency_from, currency_to))
+          .unwrap_or_default()
+          .saturating_add(
+            OrderBook::<T>::decode_len((currency_to, currency_from)).unwrap_or_default(),
+          );
+        let order_book_weight = <T as pallet::Config>::WeightInfo::match_order_book(orders as u32);
+        if max_weight.saturating_sub(weight_used)
+          < threshold_weight.saturating_add(order_book_weight)
+        {
+          return weight_used;
+        }
+        weight_used += order_book_weight;
+
+        let mut asks = Self::order_book((currency_from, currency_to));
+        let mut bids = Self::order_book((currency_to, currency_from));
+        let mut is_updated = false;
+
+        // add the limit swaps queued before the pair was enabled, the pair is not matched
+        // until all the queue has been visited
+        let is_backfilled = match Self::order_book_backfill((currency_from, currency_to)) {
+          Some(cursor) => {
+            let (new_cursor, added_orders, backfill_weight) = Self::backfill_order_book(
+              currency_from,
+              currency_to,
+              cursor,
+              &mut asks,
+              &mut bids,
+              max_weight
+                .saturating_sub(weight_used)
+                .saturating_sub(threshold_weight),
+            );
+            weight_used += backfill_weight.saturating_add(db_weight.writes(1));
+            is_updated = added_orders > 0;
+
+            match new_cursor {
+              Some(cursor) => {
+                OrderBookBackfill::<T>::insert((currency_from, currency_to), cursor);
+                false
+              }
+              None => {
+                OrderBookBackfill::<T>::remove((currency_from, currency_to));
+                true
+              }
+            }
+          }
+          None => true,
+        };
+
+        while is_backfilled
+          && max_weight.saturating_sub(weight_used) >= threshold_weight.saturating_add(match_weight)
+        {
+          if !Self::try_match_order_book(&mut asks, &mut bids) {
+            break;
+          }
+          weight_used += match_weight;
+          is_updated = true;
+        }
+
+        if is_updated {
+          OrderBook::<T>::insert((currency_from, currency_to), asks);
+          OrderBook::<T>::insert((currency_to, currency_from), bids);
+        }
+      }
+
+      weight_used
+    }
+
+    // add the queued limit swaps of the pair in the order book, resume after the `cursor`.
+    // returns the new cursor (`None` once the whole queue has been visited), the number of
+    // orders added and the weight used
+    fn backfill_order_book(
+      currency_from: CurrencyId,
+      currency_to: CurrencyId,
+      cursor: Option<Hash>,
+      asks: &mut BoundedOrderBook<T>,
+      bids: &mut BoundedOrderBook<T>,
+      max_weight: Weight,
+    ) -> (Option<Option<Hash>>, u32, Weight) {
+      let db_weight = <T as frame_system::Config>::DbWeight::get();
+      let mut weight_used: Weight = 0;
+      let mut added_orders: u32 = 0;
+      // Read the swap and write back the new order
+      let read_weight = db_weight.reads(1).saturating_add(
+        <T as pallet::Config>::WeightInfo::match_order_book(1)
+          .saturating_sub(<T as pallet::Config>::WeightInfo::match_order_book(0)),
+      );
+
+      let mut cursor = cursor;
+      let mut swaps = match cursor {
+        Some(last_request_id) => Swaps::<T>::iter_from(Swaps::<T>::hashed_key_for(last_request_id)),
+        None => Swaps::<T>::iter(),
+      };
+
+      while max_weight.saturating_sub(weight_used) >= read_weight {
+        let (request_id, swap) = match swaps.next() {
+          Some(next_swap) => next_swap,
+          // we reached the end of the queue
+          None => return (None, added_orders, weight_used),
+        };
+        weight_used += read_weight;
+        cursor = Some(request_id);
+
+        if swap.swap_type != SwapType::Limit
+          || swap.amount_from.is_zero()
+          || swap.amount_to.is_zero()
+        {
+          continue;
+        }
+
+        let order_book = if (swap.token_from, swap.token_to) == (currency_from, currency_to) {
+          &mut *asks
+        } else if (swap.token_from, swap.token_to) == (currency_to, currency_from) {
+          &mut *bids
+        } else {
+          continue;
+        };
+
+        // the swaps added after the pair was enabled are already in the order book
+        if order_book
+          .iter()
+          .any(|(order_id, _)| *order_id == request_id)
+        {
+          continue;
+        }
+
+        match Self::try_insert_order(order_book, request_id, &swap) {
+          Ok(_) => added_orders += 1,
+          Err(error) => log!(
+            error,
+            "Can't add swap {:?} in the order book: {:?}",
+            request_id,
+            error
+          ),
+        }
+      }
+
+      (Some(cursor), added_orders, weight_used)
+    }
+
+    // match the best orders of both sides of the pair if they cross,
+    // returns `true` if the order book has been updated
+    fn try_match_order_book(
+      asks: &mut BoundedOrderBook<T>,
+      bids: &mut BoundedOrderBook<T>,
+    ) -> bool {
+      // asks are selling `currency_from` for `currency_to`
+      let (ask_id, ask_price) = match asks.first() {
+        Some(best_ask) => *best_ask,
+        None => return false,
+      };
+      // bids are selling `currency_to` for `currency_from`
+      let (bid_id, bid_price) = match bids.first() {
+        Some(best_bid) => *best_bid,
+        None => return false,
+      };
+
+      // both prices are expressed in the currency received by each side, the orders cross
+      // when the ask price is lower or equal to the inverse of the bid price
+      if ask_price.saturating_mul(bid_price) > FixedU128::one() {
+        return false;
+      }
+
+      match Self::try_match_orders(ask_id, bid_id) {
+        Ok((is_ask_closed, is_bid_closed)) => {
+          if is_ask_closed {
+            asks.remove(0);
+          }
+          if is_bid_closed {
+            bids.remove(0);
+          }
+        }
+        Err(error) => {
+          // the failing orders can't stall the pair, they stay in queue for the oracle
+          log!(
+            error,
+            "Can't match orders {:?} / {:?}: {:?}",
+            ask_id,
+            bid_id,
+            error
+          );
+          asks.remove(0);
+          bids.remove(0);
+          Self::deposit_event(Event::<T>::OrderBookMatchFailed {
+            request_id: ask_id,
+            counterparty_request_id: bid_id,
+            error,
+          });
+        }
+      }
+
+      true
+    }
+
+    // match the `ask` and the `bid` orders at the price of the oldest one,
+    // returns if each order has to be removed from the order book
+    fn try_match_orders(ask_id: Hash, bid_id: Hash) -> Result<(bool, bool), DispatchError> {
+      // remove the orders who are not in queue anymore
+      let ask = match Swaps::<T>::get(ask_id) {
+        Some(ask) => ask,
+        None => return Ok((true, false)),
+      };
+      let bid = match Swaps::<T>::get(bid_id) {
+        Some(bid) => bid,
+        None => return Ok((false, true)),
+      };
+
+      // the oldest order set the price (`currency_to` per `currency_from`)
+      let (price_numerator, price_denominator) = if bid.block_number < ask.block_number {
+        (bid.amount_from, bid.amount_to)
+      } else {
+        (ask.amount_to, ask.amount_from)
+      };
+
+      let ask_remaining = ask.amount_from.saturating_sub(ask.amount_from_filled);
+      let bid_remaining = bid.amount_from.saturating_sub(bid.amount_from_filled);
+
+      // how much `currency_from` the bid can buy at this price
+      let bid_capacity = multiply_by_rational(bid_remaining, price_denominator, price_numerator)
+        .map_err(|_| Error::<T>::Overflow)?;
+
+      let amount_from = ask_remaining.min(bid_capacity);
+      let amount_to = multiply_by_rational(amount_from, price_numerator, price_denominator)
+        .map_err(|_| Error::<T>::Overflow)?;
+
+      if amount_from.is_zero() || amount_to.is_zero() {
+        // the remaining amount of one of the order is too small to be filled at this price,
+        // remove it from the order book, it stays in queue for the oracle
+        return Ok((bid_capacity >= ask_remaining, bid_capacity < ask_remaining));
+      }
+
+      Self::settle_order_book_match(ask_id, bid_id, amount_from, amount_to)
+    }
+
+    // settle a match of the order book, the `ask` send `amount_from` and the `bid` send
+    // `amount_to`, returns if each order is completed. Nothing is committed if one of the
+    // transfer fails.
+    #[transactional]
+    fn settle_order_book_match(
+      ask_id: Hash,
+      bid_id: Hash,
+      amount_from: Balance,
+      amount_to: Balance,
+    ) -> Result<(bool, bool), DispatchError> {
+      let ask = Swaps::<T>::get(ask_id).ok_or(Error::<T>::InvalidRequestId)?;
+      let bid = Swaps::<T>::get(bid_id).ok_or(Error::<T>::InvalidRequestId)?;
+
+      let ask_account_id = ask.account_id.clone();
+      let bid_account_id = bid.account_id.clone();
+
+      let (ask_fee, is_ask_completed) =
+        Self::fill_order_book_order(ask_id, ask, bid_id, &bid_account_id, amount_from, amount_to)?;
+      let (bid_fee, is_bid_completed) =
+        Self::fill_order_book_order(bid_id, bid, ask_id, &ask_account_id, amount_to, amount_from)?;
+
+      Self::deposit_event(Event::<T>::SwapFilled {
+        request_id: ask_id,
+        counterparty_request_id: bid_id,
+        amount_from,
+        amount_to,
+        fee: ask_fee,
+        counterparty_fee: bid_fee,
+      });
+
+      Ok((is_ask_completed, is_bid_completed))
+    }
+
+    // transfer the held funds of the order to the counterparty, take the network fees and
+    // close the order if it's completed, returns the fee paid and if the order is completed.
+    // A completed order is removed from the order book.
+    fn fill_order_book_order(
+      request_id: Hash,
+      mut order: Swap<T::AccountId, T::BlockNumber>,
+      counterparty_request_id: Hash,
+      counterparty: &T::AccountId,
+      amount_sent: Balance,
+      amount_received: Balance,
+    ) -> Result<(Balance, bool), DispatchError> {
+      // 1. Transfer funds to the counterparty
+      T::CurrencyTidefi::transfer_held(
+        order.token_from,
+        &order.account_id,
+        counterparty,
+        amount_sent,
+        false,
+        false,
+      )
+      .map_err(|_| Error::<T>::TransferFailed)?;
+
+      // 2. Transfer the network fees
+      let amount_and_fee =
+        T::Fees::calculate_swap_fees(order.token_from, amount_sent, order.is_market_maker);
+
+      T::CurrencyTidefi::transfer_held(
+        order.token_from,
+        &order.account_id,
+        &T::Fees::account_id(),
+        amount_and_fee.fee,
+        false,
+        false,
+      )
+      .map_err(|_| Error::<T>::TransferFailed)?;
+
+      // 3. Register a new trading fees associated with the account.
+      // A percentage of the network profits will be re-distributed to the account at the end of the era.
+      T::Fees::register_swap_fees(
+        order.account_id.clone(),
+        order.token_from,
+        amount_sent,
+        order.is_market_maker,
+      )
+      .map_err(|_| Error::<T>::FeesFailed)?;
+
+      // 4. Update the order status
+      order.amount_from_filled = order.amount_from_filled.saturating_add(amount_sent);
+      order.amount_to_filled = order.amount_to_filled.saturating_add(amount_received);
+
+      let amount_from_with_min_slippage = order
+        .amount_from
+        .saturating_sub(order.slippage * order.amount_from);
+
+      if order.amount_from_filled >= amount_from_with_min_slippage {
+        order.status = SwapStatus::Completed;
+      } else {
+        order.status = SwapStatus::PartiallyFilled;
+      }
+
+      // 5. Keep the fill in the swap history
+      Self::record_swap_fill(
+        request_id,
+        SwapFill {
+          counterparty_request_id,
+          amount_from: amount_sent,
+          amount_to: amount_received,
+          fee: amount_and_fee.fee,
+          block_number: <frame_system::Pallet<T>>::block_number(),
+        },
+      );
+
+      // 6. Emit event on chain
+      Self::deposit_event(Event::<T>::SwapProcessed {
+        request_id,
+        initial_extrinsic_hash: order.extrinsic_hash,
+        status: order.status.clone(),
+        account_id: order.account_id.clone(),
+        currency_from: order.token_from,
+        currency_amount_from: amount_sent,
+        currency_to: order.token_to,
+        currency_amount_to: amount_received,
+      });
+
+      // 7. Close the order if it's completed
+      let is_completed = order.status == SwapStatus::Completed;
+      if is_completed {
+        Self::try_delete_account_swap(&order.account_id, request_id)
+          .map_err(|_| Error::<T>::UnknownError)?;
+        Self::swap_release_held_funds(request_id, &order).map_err(|_| Error::<T>::ReleaseFailed)?;
+        Self::try_delete_order_book_entry(order.token_from, order.token_to, request_id);
+        Swaps::<T>::remove(request_id);
+      } else {
+        Self::try_update_account_swap_status(&order.account_id, request_id, order.status.clone())
+          .map_err(|_| Error::<T>::UnknownError)?;
+        Swaps::<T>::insert(request_id, order);
+      }
+
+      Ok((amount_and_fee.fee, is_completed))
+    }
+
+    // settle all the legs of a routed swap, the proceeds of each leg are held to pay the next
+    // one. The network fee is paid once, on the first currency of the path. Nothing is
+    // committed if one of the legs fails.
+    #[transactional]
+    fn settle_swap_route(
+      request_id: Hash,
+      route: SwapRoute<T>,
+      legs: Vec<Vec<SwapConfirmation>>,
+    ) -> Result<(), DispatchError> {
+      let mut amount_sent: Balance = 0;
+      let mut amount_received: Balance = 0;
+
+      for (index, leg) in legs.iter().enumerate() {
+        let currency_from = route.path[index];
+        let currency_to = route.path[index + 1];
+        ensure!(!leg.is_empty(), Error::<T>::InvalidSwapRouteLegs);
+
+        let mut total_from: Balance = 0;
+        let mut total_to: Balance = 0;
+
+        for mm in leg.iter() {
+          let mm_swap =
+            Swaps::<T>::get(mm.request_id).ok_or(Error::<T>::InvalidMarketMakerRequest)?;
+
+          // a) Make sure the market maker swap is still valid
+          ensure!(
+            mm_swap.status == SwapStatus::Pending || mm_swap.status == SwapStatus::PartiallyFilled,
+            Error::<T>::InvalidRequestStatus
+          );
+
+          // b) Make sure the currencies match the hop
+          ensure!(
+            mm_swap.token_from == currency_to && mm_swap.token_to == currency_from,
+            Error::<T>::InvalidMarketMakerRequest
+          );
+
+          // c) Validate market maker slippage tolerance
+          ensure!(
+            Self::is_within_slippage(
+              mm_swap.amount_from,
+              mm_swap.amount_to,
+              mm.amount_to_send,
+              mm.amount_to_receive,
+              mm_swap.slippage,
+            ),
+            Error::<T>::MarketMakerOverflow
+          );
+
+          // d) Make sure the market maker have enough funds in the swap
+          let available_funds = mm_swap
+            .amount_from
+            .saturating_sub(mm_swap.amount_from_filled);
+          ensure!(
+            available_funds.saturating_add(mm_swap.slippage * available_funds) >= mm.amount_to_send,
+            Error::<T>::InvalidMarketMakerRequest
+          );
+
+          // e) Transfer funds from the requester to the market maker
+          T::CurrencyTidefi::transfer_held(
+            currency_from,
+            &route.account_id,
+            &mm_swap.account_id,
+            mm.amount_to_receive,
+            false,
+            false,
+          )
+          .map_err(|_| Error::<T>::NoFunds)?;
+
+          // f) Transfer funds from the market maker to the requester
+          let swap_type = mm_swap.swap_type.clone();
+          Self::fill_order_book_order(
+            mm.request_id,
+            mm_swap,
+            request_id,
+            &route.account_id,
+            mm.amount_to_send,
+            mm.amount_to_receive,
+          )?;
+
+          // g) Market orders are closed after the first fill
+          if swap_type == SwapType::Market {
+            if let Some(mm_swap) = Swaps::<T>::take(mm.request_id) {
+              Self::try_delete_account_swap(&mm_swap.account_id, mm.request_id)
+                .map_err(|_| Error::<T>::UnknownError)?;
+              Self::swap_release_funds(mm.request_id, &mm_swap)
+                .map_err(|_| Error::<T>::ReleaseFailed)?;
+            }
+          }
+
+          total_from = total_from
+            .checked_add(mm.amount_to_receive)
+            .ok_or(Error::<T>::Overflow)?;
+          total_to = total_to
+            .checked_add(mm.amount_to_send)
+            .ok_or(Error::<T>::Overflow)?;
+        }
+
+        if index == 0 {
+          // the first leg is within the slippage tolerance of the amount requested
+          ensure!(
+            total_from
+              >= route
+                .amount_from
+                .saturating_sub(route.slippage * route.amount_from)
+              && total_from
+                <= route
+                  .amount_from
+                  .saturating_add(route.slippage * route.amount_from),
+            Error::<T>::Overflow
+          );
+          amount_sent = total_from;
+        } else {
+          // the next legs send exactly what was received by the previous leg
+          ensure!(
+            total_from == amount_received,
+            Error::<T>::InvalidSwapRouteLegs
+          );
+        }
+
+        // hold the proceeds, they are sent by the next leg
+        if index + 1 < legs.len() {
+          T::CurrencyTidefi::hold(currency_to, &route.account_id, total_to)?;
+        }
+
+        amount_received = total_to;
+      }
+
+      // Make sure the requester receive at least the minimum of the route
+      ensure!(
+        amount_received >= route.min_amount_to,
+        Error::<T>::SwapRouteBelowMinimum
+      );
+
+      // Transfer the network fees of the amount sent
+      let currency_from = route.path[0];
+      let amount_and_fee =
+        T::Fees::calculate_swap_fees(currency_from, amount_sent, route.is_market_maker);
+
+      T::CurrencyTidefi::transfer_held(
+        currency_from,
+        &route.account_id,
+        &T::Fees::account_id(),
+        amount_and_fee.fee,
+        false,
+        false,
+      )
+      .map_err(|_| Error::<T>::TransferFailed)?;
+
+      // Register a new trading fees associated with the account.
+      T::Fees::register_swap_fees(
+        route.account_id.clone(),
+        currency_from,
+        amount_sent,
+        route.is_market_maker,
+      )
+      .map_err(|_| Error::<T>::FeesFailed)?;
+
+      // Release the remaining funds
+      let amount_to_release = Self::swap_amount_to_hold(
+        currency_from,
+        route.amount_from,
+        route.slippage,
+        route.is_market_maker,
+      )
+      .saturating_sub(amount_sent)
+      .saturating_sub(amount_and_fee.fee);
+
+      T::CurrencyTidefi::release(currency_from, &route.account_id, amount_to_release, false)
+        .map_err(|_| Error::<T>::ReleaseFailed)?;
+
+      SwapRoutes::<T>::remove(request_id);
+      Self::try_delete_account_swap(&route.account_id, request_id)?;
+
+      Self::deposit_event(Event::<T>::SwapRouteProcessed {
+        request_id,
+        account_id: route.account_id,
+        path: route.path.into_inner(),
+        amount_from: amount_sent,
+        amount_to: amount_received,
+        fee: amount_and_fee.fee,
+      });
+
+      Ok(())
+    }
+
+    fn is_order_book_pair(currency_from: CurrencyId, currency_to: CurrencyId) -> bool {
+      OrderBookPairs::<T>::contains_key((currency_from, currency_to))
+        || OrderBookPairs::<T>::contains_key((currency_to, currency_from))
+    }
+
+    // add the limit order in the order book, after all the orders with the same price
+    fn try_insert_order_book_entry(
+      request_id: Hash,
+      swap: &Swap<T::AccountId, T::BlockNumber>,
+    ) -> Result<(), DispatchError> {
+      OrderBook::<T>::try_mutate((swap.token_from, swap.token_to), |order_book| {
+        Self::try_insert_order(order_book, request_id, swap)
+      })
+    }
+
+    // insert the limit order in its side of the order book, by price-time priority
+    fn try_insert_order(
+      order_book: &mut BoundedOrderBook<T>,
+      request_id: Hash,
+      swap: &Swap<T::AccountId, T::BlockNumber>,
+    ) -> Result<(), DispatchError> {
+      let price = FixedU128::checked_from_rational(swap.amount_to, swap.amount_from)
+        .ok_or(Error::<T>::Overflow)?;
+
+      let index = order_book.partition_point(|(_, order_price)| *order_price <= price);
+      order_book
+        .try_insert(index, (request_id, price))
+        .map_err(|_| Error::<T>::OrderBookOverflow)?;
+
+      Ok(())
+    }
+
+    fn try_delete_order_book_entry(
+      currency_from: CurrencyId,
+      currency_to: CurrencyId,
+      request_id: Hash,
+    ) {
+      if OrderBook::<T>::contains_key((currency_from, currency_to)) {
+        OrderBook::<T>::mutate((currency_from, currency_to), |order_book| {
+          order_book.retain(|(order_id, _)| *order_id != request_id)
+        });
+      }
+    }
+
+    // release the remaining funds and the network fee of a swap who didn't
+    // get completed and delete it from the `AccountSwaps`
+    fn swap_cancel_release_funds(
+      request_id: Hash,
+      swap_intent: &Swap<T::AccountId, T::BlockNumber>,
+    ) -> Result<(), Error<T>> {
+      let amount_and_fee = T::Fees::calculate_swap_fees(
+        swap_intent.token_from,
+        swap_intent.amount_from,
+        swap_intent.is_market_maker,
+      );
+      let amount_to_release = swap_intent
+        .amount_from
+        // amount filled
+        .saturating_sub(swap_intent.amount_from_filled)
+        // initial slippage extra
+        .saturating_add(swap_intent.slippage * swap_intent.amount_from);
+
+      // FIXME: Should we refund the swap fee?
+      // swap fee
+      let real_amount_to_release = if swap_intent.amount_from_filled == 0 {
+        amount_to_release.saturating_add(amount_and_fee.fee)
+      } else {
+        let amount_with_max_slippage = swap_intent
+          .amount_from
+          .saturating_add(swap_intent.slippage * swap_intent.amount_from);
+        // real fees required
+        let real_fees_amount = T::Fees::calculate_swap_fees(
+          swap_intent.token_from,
+          swap_intent.amount_from_filled,
+          swap_intent.is_market_maker,
+        );
+        let fees_with_slippage = T::Fees::calculate_swap_fees(
+          swap_intent.token_from,
+          amount_with_max_slippage,
+          swap_intent.is_market_maker,
+        );
+
+        amount_to_release
+          .saturating_add(fees_with_slippage.fee.saturating_sub(real_fees_amount.fee))
+      };
+
+      T::CurrencyTidefi::release(
+        swap_intent.token_from,
+        &swap_intent.account_id,
+        real_amount_to_release,
+        false,
+      )
+      .map_err(|_| Error::<T>::ReleaseFailed)?;
+
+      // delete the swap from the storage
+      Self::try_delete_account_swap(&swap_intent.account_id, request_id)
+        .map_err(|_| Error::<T>::UnknownError)?;
+
+      // remove the swap from the order book
+      Self::try_delete_order_book_entry(swap_intent.token_from, swap_intent.token_to, request_id);
+
+      // the fills history is kept until the end of the retention
+      Self::schedule_swap_fills_pruning(request_id);
+
+      Self::release_market_maker_quota(swap_intent);
+
+      Ok(())
+    }
+
+    fn swap_release_funds(
+      request_id: Hash,
+      trade: &Swap<T::AccountId, T::BlockNumber>,
+    ) -> Result<(), DispatchError> {
+      Self::swap_release_held_funds(request_id, trade)?;
+
+      // remove the swap from the order book
+      Self::try_delete_order_book_entry(trade.token_from, trade.token_to, request_id);
+
+      Ok(())
+    }
+
+    // release the remaining funds of a completed swap, without updating the order book
+    fn swap_release_held_funds(
+      request_id: Hash,
+      trade: &Swap<T::AccountId, T::BlockNumber>,
+    ) -> Result<(), DispatchError> {
+      // release the remaining funds
+      let amount_with_max_slippage = trade
+        .amount_from
+        .saturating_add(trade.slippage * trade.amount_from);
+      // real fees required
+      let real_fees_amount = T::Fees::calculate_swap_fees(
+        trade.token_from,
+        trade.amount_from_filled,
+        trade.is_market_maker,
+      );
+      let fees_with_slippage = T::Fees::calculate_swap_fees(
+        trade.token_from,
+        amount_with_max_slippage,
+        trade.is_market_maker,
+      );
+
+      let amount_to_release = trade
+        .amount_from
+        // slippage
+        .saturating_add(trade.slippage * trade.amount_from)
+        // reduce filled amount
+        .saturating_sub(trade.amount_from_filled)
+        // reduce un-needed locked fee
+        .saturating_add(fees_with_slippage.fee.saturating_sub(real_fees_amount.fee));
+
+      T::CurrencyTidefi::release(
+        trade.token_from,
+        &trade.account_id,
+        amount_to_release,
+        false,
+      )
+      .map_err(|_| Error::<T>::ReleaseFailed)?;
+
+      // the fills history is kept until the end of the retention
+      Self::schedule_swap_fills_pruning(request_id);
+
+      Self::release_market_maker_quota(trade);
+
+      Ok(())
+    }
+
+    /// Account holding the bond of the market makers, apart from the pallet account
+    pub fn bond_account_id() -> T::AccountId {
+      T::OraclePalletId::get().into_sub_account(b"bond")
+    }
+
+    // registered market makers can only trade their supported pairs, up to the quota of the pair.
+    // returns the new quota used by the market maker, `None` when the account is not registered
+    fn ensure_market_maker_quota(
+      account_id: &T::AccountId,
+      currency_from: CurrencyId,
+      currency_to: CurrencyId,
+      amount: Balance,
+    ) -> Result<Option<Balance>, DispatchError> {
+      // accounts who are not registered don't trade as market maker
+      let info = match Self::market_maker_info(account_id) {
+        Some(info) => info,
+        None => return Ok(None),
+      };
+
+      let (_, _, quota) = info
+        .pairs
+        .iter()
+        .find(|(from, to, _)| *from == currency_from && *to == currency_to)
+        .ok_or(Error::<T>::MarketMakerPairNotSupported)?;
+
+      let new_used = Self::market_maker_quota_used(account_id, (currency_from, currency_to))
+        .checked_add(amount)
+        .filter(|new_used| new_used <= quota)
+        .ok_or(Error::<T>::MarketMakerQuotaExceeded)?;
+
+      Ok(Some(new_used))
+    }
+
+    fn try_use_market_maker_quota(
+      account_id: &T::AccountId,
+      currency_from: CurrencyId,
+      currency_to: CurrencyId,
+      amount: Balance,
+    ) -> Result<(), DispatchError> {
+      if let Some(new_used) =
+        Self::ensure_market_maker_quota(account_id, currency_from, currency_to, amount)?
+      {
+        MarketMakerQuotaUsed::<T>::insert(account_id, (currency_from, currency_to), new_used);
+      }
+      Ok(())
+    }
+
+    /// Amount of a new swap with the slippage, the network fee is charged on this amount
+    pub fn swap_amount_with_slippage(amount_from: Balance, slippage: Permill) -> Balance {
+      amount_from.saturating_add(slippage * amount_from)
+    }
+
+    // amount of `asset_id_from` held by a new swap, with the slippage and the network fee
+    fn swap_amount_to_hold(
+      asset_id_from: CurrencyId,
+      amount_from: Balance,
+      slippage: Permill,
+      is_market_maker: bool,
+    ) -> Balance {
+      let amount_from_with_slippage = Self::swap_amount_with_slippage(amount_from, slippage);
+      let amount_and_fee =
+        T::Fees::calculate_swap_fees(asset_id_from, amount_from_with_slippage, is_market_maker);
+      amount_from_with_slippage.saturating_add(amount_and_fee.fee)
+    }
+
+    /// Simulate the amount of `currency_from` held when the swap is requested, including the
+    /// slippage and the network fee. Fails if one of the amounts is zero, or if the market maker
+    /// quota of the pair is exceeded.
+    pub fn quote_swap_hold(
+      account_id: &T::AccountId,
+      currency_from: CurrencyId,
+      amount_from: Balance,
+      currency_to: CurrencyId,
+      amount_to: Balance,
+      slippage: Permill,
+      is_market_maker: bool,
+    ) -> Result<Balance, DispatchError> {
+      ensure!(
+        !amount_from.is_zero() && !amount_to.is_zero(),
+        Error::<T>::InvalidSwapAmount
+      );
+      if is_market_maker {
+        Self::ensure_market_maker_quota(account_id, currency_from, currency_to, amount_from)?;
+      }
+      Ok(Self::swap_amount_to_hold(
+        currency_from,
+        amount_from,
+        slippage,
+        is_market_maker,
+      ))
+    }
+
+    // the quota is used until the swap is closed, even when partially filled
+    fn release_market_maker_quota(swap: &Swap<T::AccountId, T::BlockNumber>) {
+      if swap.is_market_maker {
+        MarketMakerQuotaUsed::<T>::mutate_exists(
+          &swap.account_id,
+          (swap.token_from, swap.token_to),
+          |used| {
+            *used = used
+              .map(|used| used.saturating_sub(swap.amount_from))
+              .filter(|used| !used.is_zero());
+          },
+        );
+      }
+    }
+
+    // keep the fill in the swap history, the oldest fill is dropped with an event when the
+    // history is full
+    fn record_swap_fill(request_id: Hash, fill: SwapFill<T::BlockNumber>) {
+      SwapFills::<T>::mutate(request_id, |fills| {
+        if !fills.is_empty() && fills.len() as u32 >= T::SwapFillLimit::get() {
+          let dropped_fill = fills.remove(0);
+          Self::deposit_event(Event::<T>::SwapFillDropped {
+            request_id,
+            fill: dropped_fill,
+          });
+        }
+        if fills.try_push(fill).is_err() {
+          log!(warn, "Can't record fill of swap {}", request_id);
+        }
+      });
+    }
+
+    /// Get a swap with its fills history, the swap is `None` once closed and its fills history
+    /// is kept `SwapFillRetention` blocks
+    pub fn get_swap_with_fills(
+      request_id: Hash,
+    ) -> (
+      Option<SwapInfo<T::AccountId, T::BlockNumber>>,
+      Vec<SwapFillInfo<T::BlockNumber>>,
+    ) {
+      (
+        Self::get_swap(request_id),
+        Self::swap_fills(request_id)
+          .into_iter()
+          .map(Into::into)
+          .collect(),
+      )
+    }
+
+    /// Get the pending swaps of an account
+    pub fn get_account_swaps(
+      account_id: &T::AccountId,
+    ) -> Vec<(Hash, SwapInfo<T::AccountId, T::BlockNumber>)> {
+      Self::account_swaps(account_id)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|(request_id, _)| Self::get_swap(request_id).map(|swap| (request_id, swap)))
+        .collect()
+    }
+
+    /// Get a pending swap, or a pending routed swap from the first to the last currency of its
+    /// path
+    pub fn get_swap(request_id: Hash) -> Option<SwapInfo<T::AccountId, T::BlockNumber>> {
+      Self::swaps(request_id).map(Into::into).or_else(|| {
+        Self::swap_routes(request_id).map(|route| SwapInfo {
+          account_id: route.account_id,
+          token_from: route.path[0],
+          amount_from: BalanceInfo {
+            amount: route.amount_from,
+          },
+          amount_from_filled: BalanceInfo { amount: 0 },
+          token_to: route.path[route.path.len() - 1],
+          amount_to: BalanceInfo {
+            amount: route.min_amount_to,
+          },
+          amount_to_filled: BalanceInfo { amount: 0 },
+          status: SwapStatus::Pending,
+          swap_type: SwapType::Market,
+          slippage: route.slippage,
+          block_number: route.block_number,
+          extrinsic_hash: route.extrinsic_hash,
+          is_market_maker: route.is_market_maker,
+        })
+      })
+    }
+
+    /// Get a page of the open swaps of the pair (`base`, `quote`) for the `side`, starting
+    /// after the `cursor` request ID. Returns the swaps with the cursor of the next page,
+    /// if any.
+    pub fn get_open_swaps(
+      pair: (CurrencyId, CurrencyId),
+      side: SwapSide,
+      limit: u32,
+      cursor: Option<Hash>,
+    ) -> (
+      Vec<(Hash, SwapInfo<T::AccountId, T::BlockNumber>)>,
+      Option<Hash>,
+    ) {
+      let (token_from, token_to) = match side {
+        SwapSide::Sell => pair,
+        SwapSide::Buy => (pair.1, pair.0),
+      };
+      let limit = limit.min(OPEN_SWAPS_PAGE_LIMIT) as usize;
+
+      let swaps = match cursor {
+        Some(last_request_id) => Swaps::<T>::iter_from(Swaps::<T>::hashed_key_for(last_request_id)),
+        None => Swaps::<T>::iter(),
+      };
+
+      let mut open_swaps: Vec<(Hash, SwapInfo<T::AccountId, T::BlockNumber>)> = swaps
+        .filter(|(_, swap)| swap.token_from == token_from && swap.token_to == token_to)
+        .take(limit.saturating_add(1))
+        .map(|(request_id, swap)| (request_id, swap.into()))
+        .collect();
+
+      // we fetched one more swap to know if there is a next page
+      let next_cursor = if open_swaps.len() > limit {
+        open_swaps.truncate(limit);
+        open_swaps.last().map(|(request_id, _)| *request_id)
+      } else {
+        None
+      };
+
+      (open_swaps, next_cursor)
+    }
+
+    fn ensure_not_paused() -> Result<(), DispatchError> {
+      if Self::is_oracle_enabled() {
+        Ok(())
+      } else {
+        Err(Error::<T>::OraclePaused.into())
+      }
+    }
+
+    // the swaps can be cancelled and their funds released while the oracle is unresponsive
+    fn ensure_cancel_allowed() -> Result<(), DispatchError> {
+      if T::Security::is_chain_running() && Self::status() {
+        Ok(())
       } else {
         Err(Error::<T>::OraclePaused.into())
       }
     }
 
+    // add the pending swap in the `AccountSwaps` storage, limited to `SwapLimitByAccount`
+    fn try_add_account_swap(account_id: &T::AccountId, request_id: Hash) -> DispatchResult {
+      AccountSwaps::<T>::try_mutate(account_id, |account_swaps| match account_swaps {
+        Some(swaps) => swaps
+          .try_push((request_id, SwapStatus::Pending))
+          .map_err(|_| Error::<T>::SwapOverflow),
+        None => {
+          let empty_bounded_vec: BoundedVec<(Hash, SwapStatus), T::SwapLimitByAccount> =
+            vec![(request_id, SwapStatus::Pending)]
+              .try_into()
+              .map_err(|_| Error::<T>::UnknownError)?;
+
+          *account_swaps = Some(empty_bounded_vec);
+          Ok(())
+        }
+      })?;
+      Ok(())
+    }
+
+    // release the funds and the network fee of a routed swap who didn't get settled, and delete
+    // it from the `AccountSwaps`
+    fn swap_route_release_funds(request_id: Hash, route: &SwapRoute<T>) -> DispatchResult {
+      T::CurrencyTidefi::release(
+        route.path[0],
+        &route.account_id,
+        Self::swap_amount_to_hold(
+          route.path[0],
+          route.amount_from,
+          route.slippage,
+          route.is_market_maker,
+        ),
+        false,
+      )
+      .map_err(|_| Error::<T>::ReleaseFailed)?;
+
+      SwapRoutes::<T>::remove(request_id);
+      Self::try_delete_account_swap(&route.account_id, request_id)
+    }
+
     // delete the `AccountSwaps` storage where the tidefi
     // app subscribe to get latest trade status
     fn try_delete_account_swap(
@@ -847,8 +2957,8 @@ pub mod pallet {
   // implement the `OracleExt` functions
   impl<T: Config> OracleExt<T::AccountId, T::BlockNumber> for Pallet<T> {
     fn is_oracle_enabled() -> bool {
-      // make sure the chain and the oracle pallet are enabled
-      T::Security::is_chain_running() && Self::status()
+      // make sure the chain and the oracle pallet are enabled, and the oracle is still alive
+      T::Security::is_chain_running() && Self::status() && !Self::is_oracle_silent()
     }
 
     fn is_market_maker(account_id: T::AccountId) -> Result<bool, DispatchError> {
@@ -884,33 +2994,30 @@ pub mod pallet {
         slippage,
       };
 
-      // 6. Freeze asset
-      let amount_from_with_slippage = amount_from.saturating_add(slippage * amount_from);
-      let amount_and_fee =
-        T::Fees::calculate_swap_fees(asset_id_from, amount_from_with_slippage, is_market_maker);
+      // make sure the market maker quota of the pair is not exceeded
+      if is_market_maker {
+        Self::try_use_market_maker_quota(&account_id, asset_id_from, asset_id_to, amount_from)?;
+      }
 
+      // 6. Freeze asset
       T::CurrencyTidefi::hold(
         asset_id_from,
         &account_id,
-        amount_from_with_slippage.saturating_add(amount_and_fee.fee),
+        Self::swap_amount_to_hold(asset_id_from, amount_from, slippage, is_market_maker),
       )?;
 
       Swaps::<T>::insert(request_id, swap.clone());
 
-      AccountSwaps::<T>::try_mutate(account_id, |account_swaps| match account_swaps {
-        Some(swaps) => swaps
-          .try_push((request_id, SwapStatus::Pending))
-          .map_err(|_| Error::<T>::SwapOverflow),
-        None => {
-          let empty_bounded_vec: BoundedVec<(Hash, SwapStatus), T::SwapLimitByAccount> =
-            vec![(request_id, SwapStatus::Pending)]
-              .try_into()
-              .map_err(|_| Error::<T>::UnknownError)?;
+      // add the limit orders in the order book when the pair is matched on-chain
+      if swap.swap_type == SwapType::Limit
+        && !amount_from.is_zero()
+        && !amount_to.is_zero()
+        && Self::is_order_book_pair(asset_id_from, asset_id_to)
+      {
+        Self::try_insert_order_book_entry(request_id, &swap)?;
+      }
 
-          *account_swaps = Some(empty_bounded_vec);
-          Ok(())
-        }
-      })?;
+      Self::try_add_account_swap(&account_id, request_id)?;
 
       Ok((request_id, swap))
     }
@@ -919,6 +3026,8 @@ pub mod pallet {
       requester: T::AccountId,
       request_id: Hash,
     ) -> Result<(), DispatchError> {
+      Self::ensure_cancel_allowed()?;
+
       Swaps::<T>::try_mutate_exists(request_id, |swap| match swap {
         None => Err(Error::<T>::InvalidRequestId),
         Some(swap_intent) => {
@@ -929,53 +3038,7 @@ pub mod pallet {
           );
 
           // release the remaining funds and the network fee
-          let amount_and_fee = T::Fees::calculate_swap_fees(
-            swap_intent.token_from,
-            swap_intent.amount_from,
-            swap_intent.is_market_maker,
-          );
-          let amount_to_release = swap_intent
-            .amount_from
-            // amount filled
-            .saturating_sub(swap_intent.amount_from_filled)
-            // initial slippage extra
-            .saturating_add(swap_intent.slippage * swap_intent.amount_from);
-
-          // FIXME: Should we refund the swap fee?
-          // swap fee
-          let real_amount_to_release = if swap_intent.amount_from_filled == 0 {
-            amount_to_release.saturating_add(amount_and_fee.fee)
-          } else {
-            let amount_with_max_slippage = swap_intent
-              .amount_from
-              .saturating_add(swap_intent.slippage * swap_intent.amount_from);
-            // real fees required
-            let real_fees_amount = T::Fees::calculate_swap_fees(
-              swap_intent.token_from,
-              swap_intent.amount_from_filled,
-              swap_intent.is_market_maker,
-            );
-            let fees_with_slippage = T::Fees::calculate_swap_fees(
-              swap_intent.token_from,
-              amount_with_max_slippage,
-              swap_intent.is_market_maker,
-            );
-
-            amount_to_release
-              .saturating_add(fees_with_slippage.fee.saturating_sub(real_fees_amount.fee))
-          };
-
-          T::CurrencyTidefi::release(
-            swap_intent.token_from,
-            &swap_intent.account_id,
-            real_amount_to_release,
-            false,
-          )
-          .map_err(|_| Error::<T>::ReleaseFailed)?;
-
-          // delete the swap from the storage
-          Self::try_delete_account_swap(&swap_intent.account_id, request_id)
-            .map_err(|_| Error::<T>::UnknownError)?;
+          Self::swap_cancel_release_funds(request_id, swap_intent)?;
 
           *swap = None;
 
@@ -986,4 +3049,91 @@ pub mod pallet {
       Ok(())
     }
   }
+
+  // implement the `SwapRouteExt` functions
+  impl<T: Config> SwapRouteExt<T::AccountId, T::BlockNumber> for Pallet<T> {
+    fn add_new_route_in_queue(
+      account_id: T::AccountId,
+      path: Vec<CurrencyId>,
+      amount_from: Balance,
+      min_amount_to: Balance,
+      block_number: T::BlockNumber,
+      extrinsic_hash: [u8; 32],
+      is_market_maker: bool,
+      slippage: Permill,
+    ) -> Result<Hash, DispatchError> {
+      // make sure the path have at least one hop, and no currency is visited twice
+      ensure!(path.len() >= 2, Error::<T>::InvalidSwapPath);
+      ensure!(
+        path
+          .iter()
+          .enumerate()
+          .all(|(index, currency_id)| !path[index + 1..].contains(currency_id)),
+        Error::<T>::InvalidSwapPath
+      );
+      ensure!(!amount_from.is_zero(), Error::<T>::InvalidSwapPath);
+
+      let path: BoundedVec<CurrencyId, T::SwapPathLimit> =
+        path.try_into().map_err(|_| Error::<T>::InvalidSwapPath)?;
+
+      let request_id = T::Security::get_unique_id(account_id.clone());
+
+      // freeze the first asset of the path
+      T::CurrencyTidefi::hold(
+        path[0],
+        &account_id,
+        Self::swap_amount_to_hold(path[0], amount_from, slippage, is_market_maker),
+      )?;
+
+      Self::try_add_account_swap(&account_id, request_id)?;
+
+      SwapRoutes::<T>::insert(
+        request_id,
+        SwapRoute {
+          account_id,
+          path,
+          amount_from,
+          min_amount_to,
+          slippage,
+          block_number,
+          extrinsic_hash,
+          is_market_maker,
+        },
+      );
+
+      Ok(request_id)
+    }
+
+    fn remove_route_from_queue(
+      requester: T::AccountId,
+      request_id: Hash,
+    ) -> Result<(), DispatchError> {
+      Self::ensure_cancel_allowed()?;
+
+      let route = Self::swap_routes(request_id).ok_or(Error::<T>::InvalidRequestId)?;
+
+      // allow oracle or the requester to cancel the routed swap
+      ensure!(
+        Some(requester.clone()) == Self::account_id() || route.account_id == requester,
+        Error::<T>::AccessDenied
+      );
+
+      // release the funds and the network fee
+      Self::swap_route_release_funds(request_id, &route)
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn set_full_order_book(currency_from: CurrencyId, currency_to: CurrencyId) {
+      if !Self::is_order_book_pair(currency_from, currency_to) {
+        OrderBookPairs::<T>::insert((currency_from, currency_to), true);
+      }
+      // the orders don't cross the new swaps, the whole side is decoded and shifted
+      let orders: BoundedOrderBook<T> = (1..T::OrderBookLimit::get())
+        .map(|i| (Hash::from_low_u64_be(i.into()), FixedU128::from(2)))
+        .collect::<Vec<_>>()
+        .try_into()
+        .expect("the orders are below the limit; qed");
+      OrderBook::<T>::insert((currency_from, currency_to), orders);
+    }
+  }
 }
@@ -0,0 +1,67 @@
+// Copyright 2021-2022 Semantic Network Ltd.
+// This file is part of Tidechain.
+
+// Tidechain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tidechain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use frame_support::{
+  log,
+  traits::{Get, GetStorageVersion, PalletInfoAccess, StorageVersion},
+};
+use tidefi_primitives::pallet::SecurityExt;
+
+/// Migrate the pallet storage to v1.
+///
+/// The market makers set in the genesis or added before the registry have no bond and no quota,
+/// they are disabled until they register.
+pub fn migrate_to_v1<T: Config, P: GetStorageVersion + PalletInfoAccess>(
+) -> frame_support::weights::Weight {
+  let on_chain_storage_version = <P as GetStorageVersion>::on_chain_storage_version();
+  log!(
+    info,
+    "Running migration storage v1 with storage version {:?}",
+    on_chain_storage_version,
+  );
+
+  if on_chain_storage_version < 1 {
+    // disable the market makers without registration
+    let legacy_market_makers = MarketMakers::<T>::iter_keys()
+      .filter(|account_id| !MarketMakerRegistry::<T>::contains_key(account_id))
+      .collect::<Vec<_>>();
+    for account_id in legacy_market_makers.iter() {
+      MarketMakers::<T>::remove(account_id);
+    }
+
+    // update on-chain storage version
+    StorageVersion::new(1).put::<P>();
+    log!(
+      info,
+      "Running migration storage v1 with storage version {:?} was complete",
+      on_chain_storage_version,
+    );
+    // return migration weights
+    let legacy_market_makers = legacy_market_makers.len() as u64;
+    T::DbWeight::get().reads_writes(
+      legacy_market_makers.saturating_mul(2).saturating_add(1),
+      legacy_market_makers.saturating_add(1),
+    )
+  } else {
+    log!(
+      info,
+      "Attempted to apply migration to v1 but failed because storage version is {:?}",
+      on_chain_storage_version,
+    );
+    T::DbWeight::get().reads(1)
+  }
+}
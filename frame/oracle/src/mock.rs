@@ -33,7 +33,7 @@ use sp_core::H256;
 use sp_runtime::{
   testing::Header,
   traits::{BlakeTwo256, IdentityLookup},
-  DispatchError, DispatchResult, Permill,
+  DispatchError, DispatchResult, FixedU128, Permill,
 };
 use std::marker::PhantomData;
 use system::EnsureRoot;
@@ -60,6 +60,7 @@ frame_support::construct_runtime!(
     Fees: pallet_fees::{Pallet, Storage, Event<T>},
     Security: pallet_security::{Pallet, Call, Config, Storage, Event<T>},
     TidefiStaking: pallet_tidefi_stake::{Pallet, Call, Config<T>, Storage, Event<T>},
+    PriceFeed: pallet_price_feed::{Pallet, Call, Config<T>, Storage, Event<T>},
   }
 );
 
@@ -142,6 +143,7 @@ parameter_types! {
   pub const TidefiPalletId: PalletId = PalletId(*b"wrpr*pal");
   pub const FeesPalletId: PalletId = PalletId(*b"wrpr*pab");
   pub const AssetRegistryPalletId: PalletId = PalletId(*b"asst*pal");
+  pub const WithdrawalPeriod: u64 = 100;
   pub const StakePalletId: PalletId = PalletId(*b"stak*pal");
   pub const MinimumPeriod: u64 = 5;
   pub const SessionsPerEra: SessionIndex = 10;
@@ -158,6 +160,18 @@ parameter_types! {
   pub const DistributionPercentage: Permill = Permill::from_percent(20);
   // The number of swap each account can have in queue
   pub const SwapLimitByAccount: u32 = 100;
+  pub const SwapLifetime: u64 = 100;
+  pub const OrderBookLimit: u32 = 100;
+  pub const SwapFillLimit: u32 = 10;
+  pub const SwapFillRetention: u64 = 10;
+  pub const MaxOracleSilence: u64 = 100;
+  pub const SwapPathLimit: u32 = 4;
+  pub const MarketMakerBond: Balance = 10 * TIFI;
+  pub const MarketMakerUnbondingPeriod: u64 = 10;
+  pub const MarketMakerNameLimit: u32 = 32;
+  pub const MarketMakerPairsLimit: u32 = 5;
+  pub const MaxReporters: u32 = 5;
+  pub const PriceValidity: u64 = 100;
   // Maximum number of staking period the chain can support
   pub const StakingRewardCap: u32 = 10;
   pub const BlocksSunriseClaims: BlockNumber = 10;
@@ -169,7 +183,19 @@ impl pallet_oracle::Config for Test {
   type CurrencyTidefi = Adapter<AccountId>;
   type Security = Security;
   type Fees = Fees;
+  type PriceFeed = PriceFeed;
   type SwapLimitByAccount = SwapLimitByAccount;
+  type SwapLifetime = SwapLifetime;
+  type OrderBookLimit = OrderBookLimit;
+  type SwapFillLimit = SwapFillLimit;
+  type SwapFillRetention = SwapFillRetention;
+  type MaxOracleSilence = MaxOracleSilence;
+  type SwapPathLimit = SwapPathLimit;
+  type MarketMakerBond = MarketMakerBond;
+  type MarketMakerUnbondingPeriod = MarketMakerUnbondingPeriod;
+  type MarketMakerNameLimit = MarketMakerNameLimit;
+  type MarketMakerPairsLimit = MarketMakerPairsLimit;
+  type ForceOrigin = EnsureRoot<Self::AccountId>;
   type WeightInfo = crate::weights::SubstrateWeight<Test>;
 }
 
@@ -187,7 +213,9 @@ impl pallet_tidefi_stake::Config for Test {
   type BlocksForceUnstake = BlocksForceUnstake;
   type AssetRegistry = AssetRegistry;
   type StakingRewardCap = StakingRewardCap;
+  type ForceOrigin = EnsureRoot<Self::AccountId>;
   type Security = Security;
+  type Fees = Fees;
 }
 
 impl pallet_asset_registry::Config for Test {
@@ -195,6 +223,7 @@ impl pallet_asset_registry::Config for Test {
   type WeightInfo = pallet_asset_registry::weights::SubstrateWeight<Test>;
   type AssetRegistryPalletId = AssetRegistryPalletId;
   type CurrencyTidefi = Adapter<AccountId>;
+  type WithdrawalPeriod = WithdrawalPeriod;
 }
 
 impl pallet_fees::Config for Test {
@@ -212,6 +241,17 @@ impl pallet_fees::Config for Test {
   type BlocksPerSession = BlocksPerSession;
   type BlocksSunriseClaims = BlocksSunriseClaims;
   type Staking = TidefiStaking;
+  type PriceFeed = PriceFeed;
+}
+
+impl pallet_price_feed::Config for Test {
+  type Event = Event;
+  type WeightInfo = pallet_price_feed::weights::SubstrateWeight<Test>;
+  type Security = Security;
+  type MaxReporters = MaxReporters;
+  type PriceValidity = PriceValidity;
+  type ForceOrigin = EnsureRoot<Self::AccountId>;
+  type InitialReporter = ();
 }
 
 impl pallet_timestamp::Config for Test {
@@ -393,6 +433,13 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
   .assimilate_storage(&mut storage)
   .unwrap();
 
+  pallet_price_feed::GenesisConfig::<Test> {
+    reporters: vec![1],
+    minimum_reports: 1,
+  }
+  .assimilate_storage(&mut storage)
+  .unwrap();
+
   pallet_asset_registry::GenesisConfig::<Test> {
     assets: vec![
       (
@@ -416,6 +463,21 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
   .unwrap();
 
   let mut ext: sp_io::TestExternalities = storage.into();
-  ext.execute_with(|| System::set_block_number(1));
+  ext.execute_with(|| {
+    System::set_block_number(1);
+    // the swap fees are registered with their USDT value
+    for currency_id in [
+      CurrencyId::Tifi,
+      CurrencyId::Wrapped(1),
+      CurrencyId::Wrapped(2),
+      CurrencyId::Wrapped(3),
+    ] {
+      pallet_price_feed::Prices::<Test>::insert(
+        currency_id,
+        CurrencyId::Wrapped(4),
+        (FixedU128::from(1), 0),
+      );
+    }
+  });
   ext
 }
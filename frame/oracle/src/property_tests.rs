@@ -0,0 +1,272 @@
+// Copyright 2021-2022 Semantic Network Ltd.
+// This file is part of Tidechain.
+
+// Tidechain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tidechain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::mock::Oracle;
+use rand_chacha::{
+  rand_core::{RngCore, SeedableRng},
+  ChaChaRng,
+};
+use sp_runtime::{
+  traits::{Saturating, Zero},
+  Permill,
+};
+use strum::IntoEnumIterator;
+use tidefi_primitives::{assets::Asset, Balance};
+
+// Number of random cases generated for each pair of assets
+const CASES_PER_PAIR: u32 = 200;
+
+// Maximum amount of whole units used in a trade
+const MAX_UNITS: u64 = 1_000_000;
+
+struct Fuzzer {
+  rng: ChaChaRng,
+}
+
+impl Fuzzer {
+  fn new(seed: u64) -> Self {
+    Self {
+      rng: ChaChaRng::seed_from_u64(seed),
+    }
+  }
+
+  // Random value in `[min, max]`
+  fn range(&mut self, min: u64, max: u64) -> u64 {
+    min + self.rng.next_u64() % (max - min + 1)
+  }
+
+  // Random amount between 1 and `MAX_UNITS` whole units of the asset, with a random fraction
+  fn amount(&mut self, exponent: u8) -> Balance {
+    let one = 10_u128.pow(exponent as u32);
+    let units = self.range(1, MAX_UNITS) as u128;
+    let fraction = self.rng.next_u64() as u128 % one;
+    units * one + fraction
+  }
+
+  // Random slippage between 1% and 50%
+  fn slippage(&mut self) -> Permill {
+    Permill::from_parts(self.range(10_000, 500_000) as u32)
+  }
+}
+
+// Run `check` for every pair of asset exponents with the random amounts and slippage
+fn for_all_pairs(seed: u64, check: impl Fn(u8, u8, Balance, Balance, Permill)) {
+  let mut fuzzer = Fuzzer::new(seed);
+  let exponents: Vec<u8> = Asset::iter().map(|asset| asset.exponent()).collect();
+  for exponent_from in exponents.iter() {
+    for exponent_to in exponents.iter() {
+      for _ in 0..CASES_PER_PAIR {
+        let amount_from = fuzzer.amount(*exponent_from);
+        let amount_to = fuzzer.amount(*exponent_to);
+        let slippage = fuzzer.slippage();
+        check(
+          *exponent_from,
+          *exponent_to,
+          amount_from,
+          amount_to,
+          slippage,
+        );
+      }
+    }
+  }
+}
+
+#[test]
+pub fn exact_price_is_always_accepted() {
+  for_all_pairs(
+    1,
+    |exponent_from, exponent_to, amount_from, amount_to, slippage| {
+      // any multiple of the initial request keep the exact same price
+      for multiplier in [1, 2, 7, 1_000] {
+        assert!(
+          Oracle::is_within_slippage(
+            amount_from,
+            amount_to,
+            amount_from * multiplier,
+            amount_to * multiplier,
+            slippage,
+          ),
+          "10^{} -> 10^{}: {} / {} (x{}) rejected with {:?}",
+          exponent_from,
+          exponent_to,
+          amount_from,
+          amount_to,
+          multiplier,
+          slippage
+        );
+      }
+      // even without any slippage tolerance
+      assert!(Oracle::is_within_slippage(
+        amount_from,
+        amount_to,
+        amount_from,
+        amount_to,
+        Permill::zero(),
+      ));
+    },
+  );
+}
+
+#[test]
+pub fn price_inside_slippage_is_accepted() {
+  for_all_pairs(
+    2,
+    |exponent_from, exponent_to, amount_from, amount_to, slippage| {
+      let half_slippage = Permill::from_parts(slippage.deconstruct() / 2);
+      let delta = half_slippage * amount_from;
+
+      // pay more than expected
+      assert!(
+        Oracle::is_within_slippage(
+          amount_from,
+          amount_to,
+          amount_from.saturating_add(delta),
+          amount_to,
+          slippage,
+        ),
+        "10^{} -> 10^{}: {} / {} (+{}) rejected with {:?}",
+        exponent_from,
+        exponent_to,
+        amount_from,
+        amount_to,
+        delta,
+        slippage
+      );
+
+      // pay less than expected
+      assert!(
+        Oracle::is_within_slippage(
+          amount_from,
+          amount_to,
+          amount_from.saturating_sub(delta),
+          amount_to,
+          slippage,
+        ),
+        "10^{} -> 10^{}: {} / {} (-{}) rejected with {:?}",
+        exponent_from,
+        exponent_to,
+        amount_from,
+        amount_to,
+        delta,
+        slippage
+      );
+    },
+  );
+}
+
+#[test]
+pub fn price_outside_slippage_is_rejected() {
+  for_all_pairs(
+    3,
+    |exponent_from, exponent_to, amount_from, amount_to, slippage| {
+      // 1% above the tolerance
+      let outside_slippage = slippage.saturating_add(Permill::from_percent(1));
+      let delta = outside_slippage * amount_from;
+
+      // pay more than the tolerance
+      assert!(
+        !Oracle::is_within_slippage(
+          amount_from,
+          amount_to,
+          amount_from.saturating_add(delta),
+          amount_to,
+          slippage,
+        ),
+        "10^{} -> 10^{}: {} / {} (+{}) accepted with {:?}",
+        exponent_from,
+        exponent_to,
+        amount_from,
+        amount_to,
+        delta,
+        slippage
+      );
+
+      // receive less than the tolerance
+      assert!(
+        !Oracle::is_within_slippage(
+          amount_from,
+          amount_to,
+          amount_from.saturating_sub(delta),
+          amount_to,
+          slippage,
+        ),
+        "10^{} -> 10^{}: {} / {} (-{}) accepted with {:?}",
+        exponent_from,
+        exponent_to,
+        amount_from,
+        amount_to,
+        delta,
+        slippage
+      );
+    },
+  );
+}
+
+#[test]
+pub fn slippage_is_symmetric_for_market_makers() {
+  // the market maker side is validated with the inverted amounts, make sure
+  // a fill at the exact price is accepted from both sides of the trade
+  for_all_pairs(4, |_, _, amount_from, amount_to, slippage| {
+    assert!(Oracle::is_within_slippage(
+      amount_from,
+      amount_to,
+      amount_from,
+      amount_to,
+      slippage,
+    ));
+    assert!(Oracle::is_within_slippage(
+      amount_to,
+      amount_from,
+      amount_to,
+      amount_from,
+      slippage,
+    ));
+  });
+}
+
+#[test]
+pub fn zero_amounts_are_rejected() {
+  for_all_pairs(5, |_, _, amount_from, amount_to, slippage| {
+    assert!(!Oracle::is_within_slippage(
+      amount_from,
+      amount_to,
+      0,
+      amount_to,
+      slippage
+    ));
+    assert!(!Oracle::is_within_slippage(
+      amount_from,
+      amount_to,
+      amount_from,
+      0,
+      slippage
+    ));
+    assert!(!Oracle::is_within_slippage(
+      0,
+      amount_to,
+      amount_from,
+      amount_to,
+      slippage
+    ));
+    assert!(!Oracle::is_within_slippage(
+      amount_from,
+      0,
+      amount_from,
+      amount_to,
+      slippage
+    ));
+  });
+}
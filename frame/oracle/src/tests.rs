@@ -16,20 +16,26 @@
 
 use crate::{
   mock::{
-    new_test_ext, Adapter, Assets, Event as MockEvent, FeeAmount, Fees, MarketMakerFeeAmount,
-    Oracle, Origin, System, Test,
+    new_test_ext, Adapter, Assets, Event as MockEvent, FeeAmount, Fees, MarketMakerBond,
+    MarketMakerFeeAmount, MarketMakerUnbondingPeriod, MaxOracleSilence, Oracle, Origin,
+    SwapFillRetention, SwapLifetime, System, Test,
   },
   pallet::*,
+  SwapFill, SwapFillInfo, SwapRouteExt, SwapSide,
 };
 use frame_support::{
   assert_noop, assert_ok,
-  traits::fungibles::{Inspect, InspectHold, Mutate},
+  traits::{
+    fungibles::{Inspect, InspectHold, Mutate},
+    Hooks,
+  },
 };
+use pallet_fees::SwapFeesExt;
 use sp_runtime::{traits::Zero, Permill};
 use std::str::FromStr;
 use tidefi_primitives::{
-  pallet::{FeesExt, OracleExt},
-  Balance, CurrencyId, Hash, SwapConfirmation, SwapStatus, SwapType,
+  pallet::OracleExt, Balance, BalanceInfo, CurrencyId, Hash, OracleImAlive, SwapConfirmation,
+  SwapStatus, SwapType,
 };
 
 #[test]
@@ -380,6 +386,43 @@ pub fn confirm_swap_partial_filling() {
     // 5 tifi
     assert_eq!(trade_request_filled.amount_to_filled, 5_000_000_000_000);
 
+    // fill event and history of both swaps
+    System::assert_has_event(MockEvent::Oracle(Event::SwapFilled {
+      request_id: trade_request_id,
+      counterparty_request_id: trade_request_mm_id,
+      amount_from: 5_000_000_000_000,
+      amount_to: partial_filling_amount_charlie,
+      fee: FeeAmount::get() * 5_000_000_000_000,
+      counterparty_fee: MarketMakerFeeAmount::get() * partial_filling_amount_charlie,
+    }));
+
+    assert_eq!(
+      Oracle::get_swap_with_fills(trade_request_id).1,
+      vec![SwapFillInfo {
+        counterparty_request_id: trade_request_mm_id,
+        amount_from: BalanceInfo {
+          amount: 5_000_000_000_000
+        },
+        amount_to: BalanceInfo {
+          amount: partial_filling_amount_charlie
+        },
+        fee: BalanceInfo {
+          amount: FeeAmount::get() * 5_000_000_000_000
+        },
+        block_number: 1,
+      }]
+    );
+    assert_eq!(
+      Oracle::swap_fills(trade_request_mm_id).into_inner(),
+      vec![SwapFill {
+        counterparty_request_id: trade_request_id,
+        amount_from: partial_filling_amount_charlie,
+        amount_to: 5_000_000_000_000,
+        fee: MarketMakerFeeAmount::get() * partial_filling_amount_charlie,
+        block_number: 1,
+      }]
+    );
+
     // another partial filling who should close the trade
     assert_ok!(Oracle::confirm_swap(
       alice.clone(),
@@ -436,6 +479,16 @@ pub fn confirm_swap_partial_filling() {
 
     // BOB: make sure the CLIENT current trade is deleted
     assert!(Oracle::swaps(trade_request_id).is_none());
+    // the fills history of the closed swap is kept until the end of the retention
+    let (swap, fills) = Oracle::get_swap_with_fills(trade_request_id);
+    assert!(swap.is_none());
+    assert_eq!(fills.len(), 2);
+    assert_eq!(Oracle::swap_fills(trade_request_mm2_id).len(), 1);
+    Oracle::on_idle(SwapFillRetention::get(), 1_000_000_000_000);
+    assert_eq!(Oracle::swap_fills(trade_request_id).len(), 2);
+    Oracle::on_idle(SwapFillRetention::get() + 1, 1_000_000_000_000);
+    assert!(Oracle::swap_fills(trade_request_id).is_empty());
+    assert_eq!(Oracle::closed_swap_fills_bounds(), (1, 1));
     let trade_request_account = Oracle::account_swaps(2u64).unwrap();
     assert_eq!(
       trade_request_account
@@ -1245,3 +1298,1201 @@ pub fn test_slippage() {
     );
   });
 }
+
+#[test]
+pub fn swap_expire_on_idle() {
+  new_test_ext().execute_with(|| {
+    let alice = Origin::signed(1u64);
+    let bob_initial_balance: Balance = 20_000_000_000_000;
+
+    assert_ok!(Oracle::set_status(alice, true));
+    assert!(Oracle::status());
+
+    // add 1 tifi to fees account to make sure account is valid
+    assert_ok!(Adapter::mint_into(
+      CurrencyId::Tifi,
+      &Fees::account_id(),
+      1_000_000_000_000
+    ));
+
+    assert_ok!(Adapter::mint_into(
+      CurrencyId::Tifi,
+      &2u64,
+      bob_initial_balance
+    ));
+
+    // BOB: 10 TIFI for 200 TEMP (20 TEMP/TIFI)
+    let (trade_request_id, _) = Oracle::add_new_swap_in_queue(
+      2u64,
+      CurrencyId::Tifi,
+      10_000_000_000_000,
+      CurrencyId::Wrapped(4),
+      20_000,
+      1,
+      [
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0,
+      ],
+      false,
+      SwapType::Limit,
+      Permill::zero(),
+    )
+    .unwrap();
+
+    assert!(!Adapter::balance_on_hold(CurrencyId::Tifi, &2u64).is_zero());
+
+    // the swap is still valid
+    Oracle::on_idle(SwapLifetime::get(), 1_000_000_000_000);
+    assert!(Oracle::swaps(trade_request_id).is_some());
+    assert_eq!(Oracle::account_swaps(2u64).unwrap_or_default().len(), 1);
+
+    // the swap reached its lifetime
+    Oracle::on_idle(SwapLifetime::get() + 1, 1_000_000_000_000);
+    assert!(Oracle::swaps(trade_request_id).is_none());
+    assert_eq!(Oracle::account_swaps(2u64).unwrap_or_default().len(), 0);
+
+    // all the funds are released
+    assert_eq!(
+      Adapter::balance_on_hold(CurrencyId::Tifi, &2u64),
+      Balance::zero()
+    );
+    assert_eq!(
+      Adapter::reducible_balance(CurrencyId::Tifi, &2u64, true),
+      bob_initial_balance
+    );
+
+    System::assert_has_event(MockEvent::Oracle(Event::SwapExpired {
+      request_id: trade_request_id,
+    }));
+  });
+}
+
+#[test]
+pub fn swap_expire_on_idle_respect_weight() {
+  new_test_ext().execute_with(|| {
+    assert_ok!(Adapter::mint_into(
+      CurrencyId::Tifi,
+      &2u64,
+      20_000_000_000_000
+    ));
+
+    let (trade_request_id, _) = Oracle::add_new_swap_in_queue(
+      2u64,
+      CurrencyId::Tifi,
+      10_000_000_000_000,
+      CurrencyId::Wrapped(4),
+      20_000,
+      1,
+      [
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0,
+      ],
+      false,
+      SwapType::Limit,
+      Permill::zero(),
+    )
+    .unwrap();
+
+    // not enough weight available to process the queue
+    Oracle::on_idle(SwapLifetime::get() + 1, 0);
+    assert!(Oracle::swaps(trade_request_id).is_some());
+
+    Oracle::on_idle(SwapLifetime::get() + 1, 1_000_000_000_000);
+    assert!(Oracle::swaps(trade_request_id).is_none());
+  });
+}
+
+#[test]
+pub fn order_book_match_crossing_limit_orders() {
+  new_test_ext().execute_with(|| {
+    let alice = Origin::signed(1u64);
+    let temp_asset_id = 4;
+
+    let bob_initial_balance: Balance = 20_000_000_000_000;
+    let charlie_initial_wrapped_balance: Balance = 1_000_000;
+
+    assert_ok!(Oracle::set_status(alice.clone(), true));
+
+    // only the oracle can enable the order book
+    assert_noop!(
+      Oracle::set_order_book_pair(
+        Origin::signed(2u64),
+        CurrencyId::Tifi,
+        CurrencyId::Wrapped(temp_asset_id),
+        true
+      ),
+      Error::<Test>::AccessDenied
+    );
+    assert_ok!(Oracle::set_order_book_pair(
+      alice.clone(),
+      CurrencyId::Tifi,
+      CurrencyId::Wrapped(temp_asset_id),
+      true
+    ));
+
+    // add 1 tifi to fees account to make sure account is valid
+    assert_ok!(Adapter::mint_into(
+      CurrencyId::Tifi,
+      &Fees::account_id(),
+      1_000_000_000_000
+    ));
+    assert_ok!(Adapter::mint_into(
+      CurrencyId::Tifi,
+      &2u64,
+      bob_initial_balance
+    ));
+    assert_ok!(Adapter::mint_into(
+      CurrencyId::Tifi,
+      &3u64,
+      1_000_000_000_000
+    ));
+
+    // create TEMP asset
+    assert_ok!(Assets::force_create(
+      Origin::root(),
+      temp_asset_id,
+      1u64,
+      true,
+      1
+    ));
+    assert_ok!(Assets::mint(
+      alice,
+      temp_asset_id,
+      3u64,
+      charlie_initial_wrapped_balance
+    ));
+
+    // BOB: 10 TIFI for 200 TEMP
+    let (bob_request_id, _) = Oracle::add_new_swap_in_queue(
+      2u64,
+      CurrencyId::Tifi,
+      10_000_000_000_000,
+      CurrencyId::Wrapped(temp_asset_id),
+      20_000,
+      1,
+      [
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0,
+      ],
+      false,
+      SwapType::Limit,
+      Permill::zero(),
+    )
+    .unwrap();
+
+    // CHARLIE: 400 TEMP for 20 TIFI
+    let (charlie_request_id, _) = Oracle::add_new_swap_in_queue(
+      3u64,
+      CurrencyId::Wrapped(temp_asset_id),
+      40_000,
+      CurrencyId::Tifi,
+      20_000_000_000_000,
+      2,
+      [
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 1,
+      ],
+      false,
+      SwapType::Limit,
+      Permill::zero(),
+    )
+    .unwrap();
+
+    assert_eq!(
+      Oracle::order_book((CurrencyId::Tifi, CurrencyId::Wrapped(temp_asset_id))).len(),
+      1
+    );
+    assert_eq!(
+      Oracle::order_book((CurrencyId::Wrapped(temp_asset_id), CurrencyId::Tifi)).len(),
+      1
+    );
+
+    Oracle::on_idle(3, 1_000_000_000_000);
+
+    // bob is completely filled at his price
+    assert!(Oracle::swaps(bob_request_id).is_none());
+    assert!(Oracle::order_book((CurrencyId::Tifi, CurrencyId::Wrapped(temp_asset_id))).is_empty());
+    assert_eq!(
+      Adapter::balance(CurrencyId::Wrapped(temp_asset_id), &2u64),
+      20_000
+    );
+    assert_eq!(
+      Adapter::balance(CurrencyId::Tifi, &2u64),
+      bob_initial_balance
+        .saturating_sub(10_000_000_000_000)
+        .saturating_sub(FeeAmount::get() * 10_000_000_000_000)
+    );
+
+    // charlie is partially filled and stay in the order book
+    let charlie_request = Oracle::swaps(charlie_request_id).unwrap();
+    assert_eq!(charlie_request.status, SwapStatus::PartiallyFilled);
+    assert_eq!(charlie_request.amount_from_filled, 20_000);
+    assert_eq!(charlie_request.amount_to_filled, 10_000_000_000_000);
+    assert_eq!(
+      Oracle::order_book((CurrencyId::Wrapped(temp_asset_id), CurrencyId::Tifi)).len(),
+      1
+    );
+    assert_eq!(
+      Adapter::balance(CurrencyId::Tifi, &3u64),
+      11_000_000_000_000
+    );
+
+    System::assert_has_event(MockEvent::Oracle(Event::SwapProcessed {
+      request_id: bob_request_id,
+      status: SwapStatus::Completed,
+      account_id: 2u64,
+      currency_from: CurrencyId::Tifi,
+      currency_amount_from: 10_000_000_000_000,
+      currency_to: CurrencyId::Wrapped(temp_asset_id),
+      currency_amount_to: 20_000,
+      initial_extrinsic_hash: [0; 32],
+    }));
+
+    // disabling the pair clear the order book
+    assert_ok!(Oracle::set_order_book_pair(
+      Origin::signed(1u64),
+      CurrencyId::Wrapped(temp_asset_id),
+      CurrencyId::Tifi,
+      false
+    ));
+    assert!(Oracle::order_book((CurrencyId::Wrapped(temp_asset_id), CurrencyId::Tifi)).is_empty());
+  });
+}
+
+#[test]
+pub fn order_book_do_not_match_limit_orders_not_crossing() {
+  new_test_ext().execute_with(|| {
+    let alice = Origin::signed(1u64);
+    let temp_asset_id = 4;
+
+    assert_ok!(Oracle::set_status(alice.clone(), true));
+    assert_ok!(Oracle::set_order_book_pair(
+      alice.clone(),
+      CurrencyId::Tifi,
+      CurrencyId::Wrapped(temp_asset_id),
+      true
+    ));
+
+    assert_ok!(Adapter::mint_into(
+      CurrencyId::Tifi,
+      &2u64,
+      20_000_000_000_000
+    ));
+    assert_ok!(Assets::force_create(
+      Origin::root(),
+      temp_asset_id,
+      1u64,
+      true,
+      1
+    ));
+    assert_ok!(Assets::mint(alice, temp_asset_id, 3u64, 1_000_000));
+
+    // BOB: 10 TIFI for 200 TEMP
+    let (bob_request_id, _) = Oracle::add_new_swap_in_queue(
+      2u64,
+      CurrencyId::Tifi,
+      10_000_000_000_000,
+      CurrencyId::Wrapped(temp_asset_id),
+      20_000,
+      1,
+      [
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0,
+      ],
+      false,
+      SwapType::Limit,
+      Permill::zero(),
+    )
+    .unwrap();
+
+    // CHARLIE: 100 TEMP for 10 TIFI
+    let (charlie_request_id, _) = Oracle::add_new_swap_in_queue(
+      3u64,
+      CurrencyId::Wrapped(temp_asset_id),
+      10_000,
+      CurrencyId::Tifi,
+      10_000_000_000_000,
+      1,
+      [
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 1,
+      ],
+      false,
+      SwapType::Limit,
+      Permill::zero(),
+    )
+    .unwrap();
+
+    Oracle::on_idle(2, 1_000_000_000_000);
+
+    assert_eq!(
+      Oracle::swaps(bob_request_id).unwrap().status,
+      SwapStatus::Pending
+    );
+    assert_eq!(
+      Oracle::swaps(charlie_request_id).unwrap().status,
+      SwapStatus::Pending
+    );
+
+    // cancelling the swap remove it from the order book
+    assert_ok!(Oracle::cancel_swap(Origin::signed(2u64), bob_request_id));
+    assert!(Oracle::order_book((CurrencyId::Tifi, CurrencyId::Wrapped(temp_asset_id))).is_empty());
+  });
+}
+
+// add the crossing limit swaps of bob (10 TIFI for 200 TEMP) and charlie (400 TEMP for 20 TIFI)
+fn add_crossing_limit_swaps(temp_asset_id: u32) -> (Hash, Hash) {
+  assert_ok!(Adapter::mint_into(
+    CurrencyId::Tifi,
+    &Fees::account_id(),
+    1_000_000_000_000
+  ));
+  assert_ok!(Adapter::mint_into(
+    CurrencyId::Tifi,
+    &2u64,
+    20_000_000_000_000
+  ));
+  assert_ok!(Adapter::mint_into(
+    CurrencyId::Tifi,
+    &3u64,
+    1_000_000_000_000
+  ));
+  assert_ok!(Assets::force_create(
+    Origin::root(),
+    temp_asset_id,
+    1u64,
+    true,
+    1
+  ));
+  assert_ok!(Assets::mint(
+    Origin::signed(1u64),
+    temp_asset_id,
+    3u64,
+    1_000_000
+  ));
+
+  let (bob_request_id, _) = Oracle::add_new_swap_in_queue(
+    2u64,
+    CurrencyId::Tifi,
+    10_000_000_000_000,
+    CurrencyId::Wrapped(temp_asset_id),
+    20_000,
+    1,
+    [0; 32],
+    false,
+    SwapType::Limit,
+    Permill::zero(),
+  )
+  .unwrap();
+
+  let (charlie_request_id, _) = Oracle::add_new_swap_in_queue(
+    3u64,
+    CurrencyId::Wrapped(temp_asset_id),
+    40_000,
+    CurrencyId::Tifi,
+    20_000_000_000_000,
+    2,
+    [1; 32],
+    false,
+    SwapType::Limit,
+    Permill::zero(),
+  )
+  .unwrap();
+
+  (bob_request_id, charlie_request_id)
+}
+
+#[test]
+pub fn order_book_add_queued_limit_swaps() {
+  new_test_ext().execute_with(|| {
+    let temp_asset_id = 4;
+    assert_ok!(Oracle::set_status(Origin::signed(1u64), true));
+
+    // the swaps are queued before the pair is enabled
+    let (bob_request_id, charlie_request_id) = add_crossing_limit_swaps(temp_asset_id);
+    assert_ok!(Oracle::set_order_book_pair(
+      Origin::signed(1u64),
+      CurrencyId::Tifi,
+      CurrencyId::Wrapped(temp_asset_id),
+      true
+    ));
+    assert!(Oracle::order_book((CurrencyId::Tifi, CurrencyId::Wrapped(temp_asset_id))).is_empty());
+    assert_eq!(
+      Oracle::order_book_backfill((CurrencyId::Tifi, CurrencyId::Wrapped(temp_asset_id))),
+      Some(None)
+    );
+
+    // the queued swaps are added in the order book, then matched
+    Oracle::on_idle(3, 1_000_000_000_000);
+
+    assert_eq!(
+      Oracle::order_book_backfill((CurrencyId::Tifi, CurrencyId::Wrapped(temp_asset_id))),
+      None
+    );
+    assert!(Oracle::swaps(bob_request_id).is_none());
+    assert_eq!(
+      Oracle::swaps(charlie_request_id).unwrap().status,
+      SwapStatus::PartiallyFilled
+    );
+    assert_eq!(
+      Oracle::order_book((CurrencyId::Wrapped(temp_asset_id), CurrencyId::Tifi))
+        .into_inner()
+        .into_iter()
+        .map(|(request_id, _)| request_id)
+        .collect::<Vec<_>>(),
+      vec![charlie_request_id]
+    );
+  });
+}
+
+#[test]
+pub fn order_book_drop_failing_orders() {
+  new_test_ext().execute_with(|| {
+    let temp_asset_id = 4;
+    assert_ok!(Oracle::set_status(Origin::signed(1u64), true));
+    assert_ok!(Oracle::set_order_book_pair(
+      Origin::signed(1u64),
+      CurrencyId::Tifi,
+      CurrencyId::Wrapped(temp_asset_id),
+      true
+    ));
+    let (bob_request_id, charlie_request_id) = add_crossing_limit_swaps(temp_asset_id);
+
+    // the funds of bob are not held anymore, the match fails
+    Swaps::<Test>::mutate(bob_request_id, |swap| {
+      if let Some(swap) = swap {
+        swap.account_id = 5u64;
+      }
+    });
+
+    Oracle::on_idle(3, 1_000_000_000_000);
+
+    // both orders are dropped from the order book and stay in queue for the oracle
+    assert!(Oracle::order_book((CurrencyId::Tifi, CurrencyId::Wrapped(temp_asset_id))).is_empty());
+    assert!(Oracle::order_book((CurrencyId::Wrapped(temp_asset_id), CurrencyId::Tifi)).is_empty());
+    assert_eq!(
+      Oracle::swaps(bob_request_id).unwrap().status,
+      SwapStatus::Pending
+    );
+    assert_eq!(
+      Oracle::swaps(charlie_request_id).unwrap().status,
+      SwapStatus::Pending
+    );
+    System::assert_has_event(MockEvent::Oracle(Event::OrderBookMatchFailed {
+      request_id: bob_request_id,
+      counterparty_request_id: charlie_request_id,
+      error: Error::<Test>::TransferFailed.into(),
+    }));
+  });
+}
+
+#[test]
+pub fn oracle_unresponsive_pause_trading() {
+  new_test_ext().execute_with(|| {
+    assert_ok!(Oracle::set_status(Origin::signed(1u64), true));
+    assert!(Oracle::is_oracle_enabled());
+
+    // still inside the silence window
+    pallet_security::CurrentBlockCount::<Test>::put(Oracle::last_seen() + MaxOracleSilence::get());
+    Oracle::on_initialize(2);
+    assert!(Oracle::is_oracle_enabled());
+    assert_eq!(Oracle::unresponsive_since(), None);
+
+    // the oracle is silent for too long
+    pallet_security::CurrentBlockCount::<Test>::put(
+      Oracle::last_seen() + MaxOracleSilence::get() + 1,
+    );
+    Oracle::on_initialize(3);
+    assert!(!Oracle::is_oracle_enabled());
+    assert_eq!(
+      Oracle::unresponsive_since(),
+      Some(Oracle::last_seen() + MaxOracleSilence::get() + 1)
+    );
+    System::assert_last_event(MockEvent::Oracle(Event::OracleUnresponsive {
+      last_seen: Oracle::last_seen(),
+    }));
+
+    // the event is emitted only once
+    let events = System::events().len();
+    Oracle::on_initialize(4);
+    assert_eq!(System::events().len(), events);
+
+    // the next heartbeat resume the trading
+    let unresponsive_since = Oracle::unresponsive_since().unwrap();
+    assert_ok!(Oracle::im_alive(
+      Origin::signed(1u64),
+      OracleImAlive {
+        usdt_value: Vec::new(),
+        tifi_value: Vec::new(),
+      }
+    ));
+    assert!(Oracle::is_oracle_enabled());
+    assert_eq!(Oracle::unresponsive_since(), None);
+    System::assert_last_event(MockEvent::Oracle(Event::OracleRecovered {
+      unresponsive_since,
+    }));
+  });
+}
+
+#[test]
+pub fn cancel_swap_while_oracle_unresponsive() {
+  new_test_ext().execute_with(|| {
+    assert_ok!(Oracle::set_status(Origin::signed(1u64), true));
+    assert_ok!(Adapter::mint_into(
+      CurrencyId::Tifi,
+      &2u64,
+      100_000_000_000_000
+    ));
+    let (request_id, _) = Oracle::add_new_swap_in_queue(
+      2u64,
+      CurrencyId::Tifi,
+      10_000_000_000_000,
+      CurrencyId::Wrapped(4),
+      20_000,
+      1,
+      [
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0,
+      ],
+      false,
+      SwapType::Limit,
+      Permill::zero(),
+    )
+    .unwrap();
+
+    pallet_security::CurrentBlockCount::<Test>::put(
+      Oracle::last_seen() + MaxOracleSilence::get() + 1,
+    );
+    Oracle::on_initialize(2);
+    assert!(!Oracle::is_oracle_enabled());
+
+    // the user can still cancel the swap, it isn't a heartbeat of the oracle
+    assert_ok!(Oracle::cancel_swap(Origin::signed(2u64), request_id));
+    assert!(Oracle::swaps(request_id).is_none());
+    assert!(!Oracle::is_oracle_enabled());
+    assert!(Oracle::unresponsive_since().is_some());
+  });
+}
+
+#[test]
+pub fn market_maker_registry_with_bond_and_quota() {
+  new_test_ext().execute_with(|| {
+    let alice = Origin::signed(1u64);
+    let market_maker = 3u64;
+    let market_maker_initial_balance: Balance = 100_000_000_000_000;
+    let quota: Balance = 10_000_000_000_000;
+
+    assert_ok!(Oracle::set_status(alice.clone(), true));
+    assert_ok!(Adapter::mint_into(
+      CurrencyId::Tifi,
+      &market_maker,
+      market_maker_initial_balance
+    ));
+
+    // only registered accounts can be approved
+    assert_noop!(
+      Oracle::add_market_maker(alice.clone(), market_maker, Vec::new()),
+      Error::<Test>::MarketMakerNotRegistered
+    );
+
+    // the bond is transferred to the pallet account
+    assert_ok!(Oracle::register_market_maker(
+      Origin::signed(market_maker),
+      "Market Maker".into()
+    ));
+    assert_eq!(
+      Adapter::balance(CurrencyId::Tifi, &market_maker),
+      market_maker_initial_balance - MarketMakerBond::get()
+    );
+    assert_eq!(
+      Adapter::balance(CurrencyId::Tifi, &Oracle::bond_account_id()),
+      MarketMakerBond::get()
+    );
+    System::assert_last_event(MockEvent::Oracle(Event::MarketMakerRegistered {
+      account_id: market_maker,
+      bond: MarketMakerBond::get(),
+    }));
+    assert_noop!(
+      Oracle::register_market_maker(Origin::signed(market_maker), "Market Maker".into()),
+      Error::<Test>::MarketMakerAlreadyRegistered
+    );
+    assert!(!Oracle::is_market_maker(market_maker).unwrap());
+
+    // only the oracle can approve the market maker
+    assert_noop!(
+      Oracle::add_market_maker(
+        Origin::signed(2u64),
+        market_maker,
+        vec![(CurrencyId::Tifi, CurrencyId::Wrapped(4), quota)]
+      ),
+      Error::<Test>::AccessDenied
+    );
+    assert_ok!(Oracle::add_market_maker(
+      alice.clone(),
+      market_maker,
+      vec![(CurrencyId::Tifi, CurrencyId::Wrapped(4), quota)]
+    ));
+    assert!(Oracle::is_market_maker(market_maker).unwrap());
+
+    let add_swap = |currency_to: CurrencyId, amount_from: Balance| {
+      Oracle::add_new_swap_in_queue(
+        market_maker,
+        CurrencyId::Tifi,
+        amount_from,
+        currency_to,
+        20_000,
+        1,
+        [
+          0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+          0, 0,
+        ],
+        true,
+        SwapType::Limit,
+        Permill::zero(),
+      )
+    };
+
+    // the pair is not supported by the market maker
+    assert_noop!(
+      add_swap(CurrencyId::Wrapped(2), 1_000_000_000_000),
+      Error::<Test>::MarketMakerPairNotSupported
+    );
+
+    // the open swaps can't exceed the quota of the pair
+    let (request_id, _) = add_swap(CurrencyId::Wrapped(4), 6_000_000_000_000).unwrap();
+    assert_eq!(
+      Oracle::market_maker_quota_used(market_maker, (CurrencyId::Tifi, CurrencyId::Wrapped(4))),
+      6_000_000_000_000
+    );
+    assert_noop!(
+      add_swap(CurrencyId::Wrapped(4), 5_000_000_000_000),
+      Error::<Test>::MarketMakerQuotaExceeded
+    );
+
+    // the quota is released when the swap is closed
+    assert_ok!(Oracle::remove_swap_from_queue(market_maker, request_id));
+    assert_eq!(
+      Oracle::market_maker_quota_used(market_maker, (CurrencyId::Tifi, CurrencyId::Wrapped(4))),
+      0
+    );
+    assert_ok!(add_swap(CurrencyId::Wrapped(4), quota));
+
+    // the bond is locked during the unbonding period when the market maker is removed
+    assert_ok!(Oracle::remove_market_maker(alice, market_maker));
+    assert!(Oracle::market_maker_info(market_maker).is_none());
+    assert!(!Oracle::is_market_maker(market_maker).unwrap());
+    let unlock_at = pallet_security::CurrentBlockCount::<Test>::get()
+      .saturating_add(MarketMakerUnbondingPeriod::get());
+    assert_eq!(
+      Oracle::market_maker_unbonding(market_maker),
+      Some((MarketMakerBond::get(), unlock_at))
+    );
+    assert_noop!(
+      Oracle::withdraw_market_maker_bond(Origin::signed(market_maker)),
+      Error::<Test>::MarketMakerBondLocked
+    );
+    assert_noop!(
+      Oracle::withdraw_market_maker_bond(Origin::signed(2u64)),
+      Error::<Test>::MarketMakerNoUnbonding
+    );
+
+    // the bond is released at the end of the unbonding period
+    pallet_security::CurrentBlockCount::<Test>::put(unlock_at);
+    assert_ok!(Oracle::withdraw_market_maker_bond(Origin::signed(
+      market_maker
+    )));
+    System::assert_last_event(MockEvent::Oracle(Event::MarketMakerBondWithdrawn {
+      account_id: market_maker,
+      amount: MarketMakerBond::get(),
+    }));
+    assert!(Oracle::market_maker_unbonding(market_maker).is_none());
+    assert_eq!(
+      Adapter::balance(CurrencyId::Tifi, &Oracle::bond_account_id()),
+      0
+    );
+  });
+}
+
+#[test]
+pub fn slash_market_maker_bond() {
+  new_test_ext().execute_with(|| {
+    let alice = Origin::signed(1u64);
+    let market_maker = 3u64;
+    let market_maker_initial_balance: Balance = 100_000_000_000_000;
+    let slash_amount: Balance = 4_000_000_000_000;
+
+    assert_ok!(Oracle::set_status(alice.clone(), true));
+    assert_ok!(Adapter::mint_into(
+      CurrencyId::Tifi,
+      &market_maker,
+      market_maker_initial_balance
+    ));
+    assert_ok!(Oracle::register_market_maker(
+      Origin::signed(market_maker),
+      "Market Maker".into()
+    ));
+    assert_ok!(Oracle::add_market_maker(
+      alice.clone(),
+      market_maker,
+      vec![(CurrencyId::Tifi, CurrencyId::Wrapped(4), 1_000_000_000_000)]
+    ));
+
+    // only the force origin can slash the bond
+    assert_noop!(
+      Oracle::slash_market_maker(alice.clone(), market_maker, slash_amount),
+      sp_runtime::DispatchError::BadOrigin
+    );
+    assert_noop!(
+      Oracle::slash_market_maker(Origin::root(), 2u64, slash_amount),
+      Error::<Test>::MarketMakerNotRegistered
+    );
+
+    // the slashed amount is sent to the fees account and the market maker is disabled
+    assert_ok!(Oracle::slash_market_maker(
+      Origin::root(),
+      market_maker,
+      slash_amount
+    ));
+    assert_eq!(
+      Adapter::balance(CurrencyId::Tifi, &Fees::account_id()),
+      slash_amount
+    );
+    assert_eq!(
+      Oracle::market_maker_info(market_maker).unwrap().bond,
+      MarketMakerBond::get() - slash_amount
+    );
+    assert!(!Oracle::is_market_maker(market_maker).unwrap());
+    System::assert_last_event(MockEvent::Oracle(Event::MarketMakerSlashed {
+      account_id: market_maker,
+      amount: slash_amount,
+    }));
+
+    // the remaining bond can still be slashed during the unbonding period
+    assert_ok!(Oracle::remove_market_maker(alice, market_maker));
+    assert_ok!(Oracle::slash_market_maker(
+      Origin::root(),
+      market_maker,
+      slash_amount
+    ));
+    assert_eq!(
+      Adapter::balance(CurrencyId::Tifi, &Fees::account_id()),
+      slash_amount * 2
+    );
+    assert_eq!(
+      Oracle::market_maker_unbonding(market_maker).unwrap().0,
+      MarketMakerBond::get() - slash_amount * 2
+    );
+
+    // the remaining bond is released at the end of the unbonding period
+    pallet_security::CurrentBlockCount::<Test>::put(
+      pallet_security::CurrentBlockCount::<Test>::get()
+        .saturating_add(MarketMakerUnbondingPeriod::get()),
+    );
+    assert_ok!(Oracle::withdraw_market_maker_bond(Origin::signed(
+      market_maker
+    )));
+    assert_eq!(
+      Adapter::balance(CurrencyId::Tifi, &market_maker),
+      market_maker_initial_balance - slash_amount * 2
+    );
+  });
+}
+
+#[test]
+pub fn get_open_swaps_paged() {
+  new_test_ext().execute_with(|| {
+    assert_ok!(Adapter::mint_into(
+      CurrencyId::Tifi,
+      &2u64,
+      100_000_000_000_000
+    ));
+
+    let mut request_ids = Vec::new();
+    for _ in 0..3 {
+      let (request_id, _) = Oracle::add_new_swap_in_queue(
+        2u64,
+        CurrencyId::Tifi,
+        10_000_000_000_000,
+        CurrencyId::Wrapped(4),
+        20_000,
+        1,
+        [
+          0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+          0, 0,
+        ],
+        false,
+        SwapType::Limit,
+        Permill::zero(),
+      )
+      .unwrap();
+      request_ids.push(request_id);
+    }
+
+    // the amounts are wrapped in `BalanceInfo`
+    let swap = Oracle::get_swap(request_ids[0]).unwrap();
+    assert_eq!(swap.amount_from.amount, 10_000_000_000_000);
+    assert_eq!(swap.amount_to.amount, 20_000);
+    assert_eq!(swap.amount_from_filled.amount, 0);
+    assert_eq!(Oracle::get_account_swaps(&2u64).len(), 3);
+
+    // first page
+    let (first_page, cursor) = Oracle::get_open_swaps(
+      (CurrencyId::Tifi, CurrencyId::Wrapped(4)),
+      SwapSide::Sell,
+      2,
+      None,
+    );
+    assert_eq!(first_page.len(), 2);
+    assert!(cursor.is_some());
+
+    // last page
+    let (last_page, cursor) = Oracle::get_open_swaps(
+      (CurrencyId::Tifi, CurrencyId::Wrapped(4)),
+      SwapSide::Sell,
+      2,
+      cursor,
+    );
+    assert_eq!(last_page.len(), 1);
+    assert!(cursor.is_none());
+
+    let mut all_request_ids: Vec<Hash> = first_page
+      .iter()
+      .chain(last_page.iter())
+      .map(|(request_id, _)| *request_id)
+      .collect();
+    all_request_ids.sort();
+    request_ids.sort();
+    assert_eq!(all_request_ids, request_ids);
+
+    // no swap on the buy side
+    let (buy_side, cursor) = Oracle::get_open_swaps(
+      (CurrencyId::Tifi, CurrencyId::Wrapped(4)),
+      SwapSide::Buy,
+      2,
+      None,
+    );
+    assert!(buy_side.is_empty());
+    assert!(cursor.is_none());
+  });
+}
+
+#[test]
+pub fn quote_swap_hold_match_held_amount() {
+  new_test_ext().execute_with(|| {
+    assert_ok!(Adapter::mint_into(
+      CurrencyId::Tifi,
+      &2u64,
+      100_000_000_000_000
+    ));
+
+    // the amount to receive can't be zero
+    assert_noop!(
+      Oracle::quote_swap_hold(
+        &2u64,
+        CurrencyId::Tifi,
+        10_000_000_000_000,
+        CurrencyId::Wrapped(4),
+        0,
+        Permill::from_percent(2),
+        false,
+      ),
+      Error::<Test>::InvalidSwapAmount
+    );
+
+    let quoted_amount = Oracle::quote_swap_hold(
+      &2u64,
+      CurrencyId::Tifi,
+      10_000_000_000_000,
+      CurrencyId::Wrapped(4),
+      20_000,
+      Permill::from_percent(2),
+      false,
+    )
+    .unwrap();
+
+    assert_ok!(Oracle::add_new_swap_in_queue(
+      2u64,
+      CurrencyId::Tifi,
+      10_000_000_000_000,
+      CurrencyId::Wrapped(4),
+      20_000,
+      1,
+      [
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0,
+      ],
+      false,
+      SwapType::Limit,
+      Permill::from_percent(2),
+    ));
+
+    assert_eq!(
+      Adapter::balance_on_hold(CurrencyId::Tifi, &2u64),
+      quoted_amount
+    );
+
+    // the network fee is charged on the amount with the slippage
+    let amount_with_slippage =
+      Oracle::swap_amount_with_slippage(10_000_000_000_000, Permill::from_percent(2));
+    assert_eq!(amount_with_slippage, 10_200_000_000_000);
+    assert_eq!(
+      quoted_amount,
+      amount_with_slippage + FeeAmount::get() * amount_with_slippage
+    );
+  });
+}
+
+#[test]
+pub fn confirm_swap_route_settle_all_legs() {
+  new_test_ext().execute_with(|| {
+    let alice = Origin::signed(1u64);
+    let temp_asset_id = 4;
+    let zemp_asset_id = 1;
+
+    let bob_initial_balance: Balance = 20_000_000_000_000;
+
+    assert_ok!(Oracle::set_status(alice.clone(), true));
+
+    // add 1 tifi to fees account to make sure account is valid
+    assert_ok!(Adapter::mint_into(
+      CurrencyId::Tifi,
+      &Fees::account_id(),
+      1_000_000_000_000
+    ));
+    assert_ok!(Adapter::mint_into(
+      CurrencyId::Tifi,
+      &2u64,
+      bob_initial_balance
+    ));
+
+    // create TEMP and ZEMP assets
+    for asset_id in [temp_asset_id, zemp_asset_id] {
+      assert_ok!(Assets::force_create(
+        Origin::root(),
+        asset_id,
+        1u64,
+        true,
+        1
+      ));
+    }
+    assert_ok!(Assets::mint(alice.clone(), temp_asset_id, 3u64, 1_000_000));
+    assert_ok!(Assets::mint(alice, zemp_asset_id, 4u64, 1_000_000));
+
+    // CHARLIE: 200 TEMP for 10 TIFI
+    let (charlie_request_id, _) = Oracle::add_new_swap_in_queue(
+      3u64,
+      CurrencyId::Wrapped(temp_asset_id),
+      20_000,
+      CurrencyId::Tifi,
+      10_000_000_000_000,
+      1,
+      [0; 32],
+      false,
+      SwapType::Market,
+      Permill::zero(),
+    )
+    .unwrap();
+
+    // the limit order of dave is in the order book
+    assert_ok!(Oracle::set_order_book_pair(
+      Origin::signed(1u64),
+      CurrencyId::Wrapped(zemp_asset_id),
+      CurrencyId::Wrapped(temp_asset_id),
+      true
+    ));
+
+    // DAVE: 400 ZEMP for 200 TEMP
+    let (dave_request_id, _) = Oracle::add_new_swap_in_queue(
+      4u64,
+      CurrencyId::Wrapped(zemp_asset_id),
+      40_000,
+      CurrencyId::Wrapped(temp_asset_id),
+      20_000,
+      1,
+      [0; 32],
+      false,
+      SwapType::Limit,
+      Permill::zero(),
+    )
+    .unwrap();
+    assert_eq!(
+      Oracle::order_book((
+        CurrencyId::Wrapped(zemp_asset_id),
+        CurrencyId::Wrapped(temp_asset_id)
+      ))
+      .len(),
+      1
+    );
+
+    let legs = vec![
+      vec![SwapConfirmation {
+        request_id: charlie_request_id,
+        amount_to_receive: 10_000_000_000_000,
+        amount_to_send: 20_000,
+      }],
+      vec![SwapConfirmation {
+        request_id: dave_request_id,
+        amount_to_receive: 20_000,
+        amount_to_send: 40_000,
+      }],
+    ];
+
+    // BOB: 10 TIFI for at least 500 ZEMP, through TEMP
+    let request_id = Oracle::add_new_route_in_queue(
+      2u64,
+      vec![
+        CurrencyId::Tifi,
+        CurrencyId::Wrapped(temp_asset_id),
+        CurrencyId::Wrapped(zemp_asset_id),
+      ],
+      10_000_000_000_000,
+      50_000,
+      1,
+      [0; 32],
+      false,
+      Permill::zero(),
+    )
+    .unwrap();
+
+    // one leg is missing
+    assert_noop!(
+      Oracle::confirm_swap_route(Origin::signed(1u64), request_id, vec![legs[0].clone()]),
+      Error::<Test>::InvalidSwapRouteLegs
+    );
+
+    // nothing is committed when the minimum is not received
+    assert_noop!(
+      Oracle::confirm_swap_route(Origin::signed(1u64), request_id, legs.clone()),
+      Error::<Test>::SwapRouteBelowMinimum
+    );
+
+    // the route is listed with the swaps of bob
+    assert_eq!(
+      Oracle::account_swaps(2u64).unwrap(),
+      vec![(request_id, SwapStatus::Pending)]
+    );
+    let swap = Oracle::get_swap(request_id).unwrap();
+    assert_eq!(swap.token_from, CurrencyId::Tifi);
+    assert_eq!(swap.token_to, CurrencyId::Wrapped(zemp_asset_id));
+    assert_eq!(swap.amount_to.amount, 50_000);
+
+    // only bob or the oracle can cancel the route
+    assert_noop!(
+      Oracle::remove_route_from_queue(3u64, request_id),
+      Error::<Test>::AccessDenied
+    );
+    assert_ok!(Oracle::remove_route_from_queue(2u64, request_id));
+    assert!(Oracle::swap_routes(request_id).is_none());
+    assert!(Oracle::account_swaps(2u64).unwrap_or_default().is_empty());
+    assert!(Adapter::balance_on_hold(CurrencyId::Tifi, &2u64).is_zero());
+
+    // BOB: 10 TIFI for at least 300 ZEMP, through TEMP
+    let request_id = Oracle::add_new_route_in_queue(
+      2u64,
+      vec![
+        CurrencyId::Tifi,
+        CurrencyId::Wrapped(temp_asset_id),
+        CurrencyId::Wrapped(zemp_asset_id),
+      ],
+      10_000_000_000_000,
+      30_000,
+      1,
+      [0; 32],
+      false,
+      Permill::zero(),
+    )
+    .unwrap();
+
+    assert_ok!(Oracle::confirm_swap_route(
+      Origin::signed(1u64),
+      request_id,
+      legs
+    ));
+
+    // the route and the market makers swaps are closed
+    assert!(Oracle::swap_routes(request_id).is_none());
+    assert!(Oracle::account_swaps(2u64).unwrap_or_default().is_empty());
+    assert!(Oracle::swaps(charlie_request_id).is_none());
+    assert!(Oracle::swaps(dave_request_id).is_none());
+    assert!(Oracle::order_book((
+      CurrencyId::Wrapped(zemp_asset_id),
+      CurrencyId::Wrapped(temp_asset_id)
+    ))
+    .is_empty());
+
+    // bob paid the network fee once, and nothing is left on hold
+    let fee = FeeAmount::get() * 10_000_000_000_000;
+    assert_eq!(
+      Adapter::balance(CurrencyId::Tifi, &2u64),
+      bob_initial_balance
+        .saturating_sub(10_000_000_000_000)
+        .saturating_sub(fee)
+    );
+    assert!(Adapter::balance(CurrencyId::Wrapped(temp_asset_id), &2u64).is_zero());
+    assert_eq!(
+      Adapter::balance(CurrencyId::Wrapped(zemp_asset_id), &2u64),
+      40_000
+    );
+    assert!(Adapter::balance_on_hold(CurrencyId::Tifi, &2u64).is_zero());
+    assert!(Adapter::balance_on_hold(CurrencyId::Wrapped(temp_asset_id), &2u64).is_zero());
+
+    System::assert_has_event(MockEvent::Oracle(Event::SwapRouteProcessed {
+      request_id,
+      account_id: 2u64,
+      path: vec![
+        CurrencyId::Tifi,
+        CurrencyId::Wrapped(temp_asset_id),
+        CurrencyId::Wrapped(zemp_asset_id),
+      ],
+      amount_from: 10_000_000_000_000,
+      amount_to: 40_000,
+      fee,
+    }));
+  });
+}
+
+#[test]
+pub fn expire_swap_route() {
+  new_test_ext().execute_with(|| {
+    let bob_initial_balance: Balance = 20_000_000_000_000;
+    assert_ok!(Oracle::set_status(Origin::signed(1u64), true));
+    assert_ok!(Adapter::mint_into(
+      CurrencyId::Tifi,
+      &2u64,
+      bob_initial_balance
+    ));
+
+    // BOB: 10 TIFI for at least 500 ZEMP, through TEMP
+    let request_id = Oracle::add_new_route_in_queue(
+      2u64,
+      vec![
+        CurrencyId::Tifi,
+        CurrencyId::Wrapped(4),
+        CurrencyId::Wrapped(1),
+      ],
+      10_000_000_000_000,
+      50_000,
+      1,
+      [0; 32],
+      false,
+      Permill::zero(),
+    )
+    .unwrap();
+    assert!(!Adapter::balance_on_hold(CurrencyId::Tifi, &2u64).is_zero());
+
+    // the route is still valid
+    Oracle::on_idle(SwapLifetime::get(), 1_000_000_000_000);
+    assert!(Oracle::swap_routes(request_id).is_some());
+
+    // the route reached its lifetime, all the funds are released
+    Oracle::on_idle(SwapLifetime::get() + 1, 1_000_000_000_000);
+    assert!(Oracle::swap_routes(request_id).is_none());
+    assert!(Oracle::account_swaps(2u64).unwrap_or_default().is_empty());
+    assert!(Adapter::balance_on_hold(CurrencyId::Tifi, &2u64).is_zero());
+    assert_eq!(
+      Adapter::reducible_balance(CurrencyId::Tifi, &2u64, true),
+      bob_initial_balance
+    );
+    System::assert_has_event(MockEvent::Oracle(Event::SwapExpired { request_id }));
+  });
+}
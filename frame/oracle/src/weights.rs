@@ -26,9 +26,15 @@ pub trait WeightInfo {
    fn set_status() -> Weight;
    fn set_account_id() -> Weight;
    fn confirm_swap() -> Weight;
+   fn confirm_swap_route(l: u32) -> Weight;
    fn add_market_maker() -> Weight;
    fn remove_market_maker() -> Weight;
+   fn register_market_maker() -> Weight;
+   fn slash_market_maker() -> Weight;
+   fn withdraw_market_maker_bond() -> Weight;
    fn im_alive() -> Weight;
+   fn set_order_book_pair() -> Weight;
+   fn match_order_book(o: u32) -> Weight;
 }
 
 /// Weights for `pallet_tidefi` using the Substrate node and recommended hardware.
@@ -49,19 +55,52 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
          .saturating_add(T::DbWeight::get().reads(6_u64))
          .saturating_add(T::DbWeight::get().writes(5_u64))
    }
+   fn register_market_maker() -> Weight {
+      58_000_300_u64
+         .saturating_add(T::DbWeight::get().reads(3_u64))
+         .saturating_add(T::DbWeight::get().writes(3_u64))
+   }
+   fn slash_market_maker() -> Weight {
+      58_000_300_u64
+         .saturating_add(T::DbWeight::get().reads(5_u64))
+         .saturating_add(T::DbWeight::get().writes(5_u64))
+   }
+   fn withdraw_market_maker_bond() -> Weight {
+      58_000_300_u64
+         .saturating_add(T::DbWeight::get().reads(3_u64))
+         .saturating_add(T::DbWeight::get().writes(3_u64))
+   }
   fn set_account_id() -> Weight {
       62_000_300_u64
        .saturating_add(T::DbWeight::get().reads(6_u64))
        .saturating_add(T::DbWeight::get().writes(5_u64))
    }
    fn confirm_swap() -> Weight {
+      86_000_400_u64
+         .saturating_add(T::DbWeight::get().reads(8_u64))
+         .saturating_add(T::DbWeight::get().writes(7_u64))
+   }
+   fn confirm_swap_route(l: u32) -> Weight {
       63_000_400_u64
-         .saturating_add(T::DbWeight::get().reads(6_u64))
-         .saturating_add(T::DbWeight::get().writes(5_u64))
+         .saturating_add(120_000_000_u64.saturating_mul(l as Weight))
+         .saturating_add(T::DbWeight::get().reads(4_u64))
+         .saturating_add(T::DbWeight::get().reads((8_u64).saturating_mul(l as Weight)))
+         .saturating_add(T::DbWeight::get().writes((7_u64).saturating_mul(l as Weight)))
    }
    fn im_alive() -> Weight {
       64_000_400_u64
          .saturating_add(T::DbWeight::get().reads(6_u64))
          .saturating_add(T::DbWeight::get().writes(5_u64))
    }
+   fn set_order_book_pair() -> Weight {
+      61_000_300_u64
+         .saturating_add(T::DbWeight::get().reads(3_u64))
+         .saturating_add(T::DbWeight::get().writes(5_u64))
+   }
+   fn match_order_book(o: u32) -> Weight {
+      20_000_000_u64
+         .saturating_add(45_000_u64.saturating_mul(o as Weight))
+         .saturating_add(T::DbWeight::get().reads(2_u64))
+         .saturating_add(T::DbWeight::get().writes(2_u64))
+   }
 }
@@ -0,0 +1,54 @@
+// Copyright 2021-2022 Semantic Network Ltd.
+// This file is part of Tidechain.
+
+// Tidechain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tidechain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(feature = "runtime-benchmarks")]
+use super::*;
+
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, vec};
+use frame_support::BoundedVec;
+use frame_system::{self, RawOrigin};
+use tidefi_primitives::{assets::USDT, CurrencyId};
+
+const SEED: u32 = 0;
+const REPORTER_ID: u32 = 1;
+
+fn pre_set_reporter<T: Config>() -> T::AccountId {
+  let reporter: T::AccountId = account("reporter", REPORTER_ID, SEED);
+  let reporters: BoundedVec<T::AccountId, T::MaxReporters> =
+    vec![reporter.clone()].try_into().unwrap();
+  Reporters::<T>::put(reporters);
+  reporter
+}
+
+benchmarks! {
+   submit_prices {
+      let p in 1 .. 100;
+      let reporter = pre_set_reporter::<T>();
+      let prices = (0..p).map(|_| (CurrencyId::Tifi, CurrencyId::Wrapped(USDT), 1_000_000)).collect::<Vec<_>>();
+   }: _(RawOrigin::Signed(reporter), prices)
+   add_reporter {
+      let reporter: T::AccountId = account("reporter", REPORTER_ID, SEED);
+   }: _(RawOrigin::Root, reporter)
+   remove_reporter {
+      let reporter = pre_set_reporter::<T>();
+      Reporters::<T>::try_append(account::<T::AccountId>("reporter", REPORTER_ID + 1, SEED)).unwrap();
+   }: _(RawOrigin::Root, reporter)
+   set_minimum_reports {
+      pre_set_reporter::<T>();
+   }: _(RawOrigin::Root, 1)
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
@@ -0,0 +1,464 @@
+// Copyright 2021-2022 Semantic Network Ltd.
+// This file is part of Tidechain.
+
+// Tidechain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tidechain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+pub use weights::*;
+
+// Re-export pallet items so that they can be accessed from the crate namespace.
+pub use pallet::*;
+
+mod migrations;
+
+pub(crate) const LOG_TARGET: &str = "tidefi::price-feed";
+
+use frame_support::inherent::Vec;
+use sp_runtime::{DispatchError, FixedU128};
+use tidefi_primitives::{Balance, CurrencyId};
+
+/// Price feed traits, used by the pallets who need to report or consume the prices
+pub trait PriceFeedExt<AccountId> {
+  /// Submit the prices (`currency_from`, `currency_to`, `price`) reported by `account_id`
+  fn submit_prices(
+    account_id: &AccountId,
+    prices: Vec<(CurrencyId, CurrencyId, Balance)>,
+  ) -> Result<(), DispatchError>;
+  /// Get the median price of the pair, fails if the price is stale or not available
+  fn get_price(
+    currency_from: CurrencyId,
+    currency_to: CurrencyId,
+  ) -> Result<FixedU128, DispatchError>;
+  /// Set a fresh price of the pair, used by the benchmarks of the price consumers
+  #[cfg(feature = "runtime-benchmarks")]
+  fn set_price(currency_from: CurrencyId, currency_to: CurrencyId, price: FixedU128);
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+  use super::*;
+  use frame_support::{pallet_prelude::*, traits::StorageVersion};
+  use frame_system::pallet_prelude::*;
+  use sp_runtime::{
+    traits::{CheckedDiv, Saturating},
+    FixedPointNumber,
+  };
+  use tidefi_primitives::{assets::Asset, pallet::SecurityExt};
+
+  /// The current storage version.
+  const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+  type BoundedReporters<T> =
+    BoundedVec<<T as frame_system::Config>::AccountId, <T as Config>::MaxReporters>;
+  type BoundedReports<T> = BoundedVec<
+    (
+      <T as frame_system::Config>::AccountId,
+      FixedU128,
+      <T as frame_system::Config>::BlockNumber,
+    ),
+    <T as Config>::MaxReporters,
+  >;
+
+  /// Price feed configuration
+  #[pallet::config]
+  pub trait Config: frame_system::Config {
+    /// Events
+    type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+    /// Weights
+    type WeightInfo: WeightInfo;
+
+    /// Security traits
+    type Security: SecurityExt<Self::AccountId, Self::BlockNumber>;
+
+    /// The maximum number of price reporters
+    #[pallet::constant]
+    type MaxReporters: Get<u32>;
+
+    /// The number of blocks a report is used in the median, and a price can be consumed
+    #[pallet::constant]
+    type PriceValidity: Get<Self::BlockNumber>;
+
+    /// The origin which may add or remove price reporters
+    type ForceOrigin: EnsureOrigin<Self::Origin>;
+
+    /// The reporter seeded by the migration when the chain has no reporters
+    type InitialReporter: Get<Option<Self::AccountId>>;
+  }
+
+  #[pallet::pallet]
+  #[pallet::generate_store(pub (super) trait Store)]
+  #[pallet::storage_version(STORAGE_VERSION)]
+  pub struct Pallet<T>(_);
+
+  #[pallet::type_value]
+  pub fn DefaultMinimumReports() -> u32 {
+    1
+  }
+
+  /// Minimum number of fresh reports required to update the median price of a pair
+  #[pallet::storage]
+  #[pallet::getter(fn minimum_reports)]
+  pub type MinimumReports<T: Config> = StorageValue<_, u32, ValueQuery, DefaultMinimumReports>;
+
+  /// Set of accounts allowed to submit prices
+  #[pallet::storage]
+  #[pallet::getter(fn reporters)]
+  pub type Reporters<T: Config> = StorageValue<_, BoundedReporters<T>, ValueQuery>;
+
+  /// Latest report of each reporter for the pair (`currency_from`, `currency_to`)
+  #[pallet::storage]
+  #[pallet::getter(fn reports)]
+  pub type Reports<T: Config> = StorageDoubleMap<
+    _,
+    Blake2_128Concat,
+    CurrencyId,
+    Blake2_128Concat,
+    CurrencyId,
+    BoundedReports<T>,
+    ValueQuery,
+  >;
+
+  /// Median price of the fresh reports for the pair (`currency_from`, `currency_to`),
+  /// with the block it was last updated.
+  ///
+  /// CurrencyId → USDT
+  /// CurrencyId → TIFI
+  #[pallet::storage]
+  #[pallet::getter(fn prices)]
+  pub type Prices<T: Config> = StorageDoubleMap<
+    _,
+    Blake2_128Concat,
+    CurrencyId,
+    Blake2_128Concat,
+    CurrencyId,
+    (FixedU128, T::BlockNumber),
+  >;
+
+  /// Genesis configuration
+  #[pallet::genesis_config]
+  pub struct GenesisConfig<T: Config> {
+    /// Accounts allowed to submit prices
+    pub reporters: Vec<T::AccountId>,
+    /// Minimum number of fresh reports required to update a price
+    pub minimum_reports: u32,
+  }
+
+  #[cfg(feature = "std")]
+  impl<T: Config> Default for GenesisConfig<T> {
+    fn default() -> Self {
+      Self {
+        reporters: Vec::new(),
+        minimum_reports: 1,
+      }
+    }
+  }
+
+  #[pallet::genesis_build]
+  impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+    fn build(&self) {
+      let bounded_reporters: BoundedReporters<T> = self
+        .reporters
+        .clone()
+        .try_into()
+        .expect("Too many price reporters");
+      assert!(
+        self.minimum_reports >= 1 && self.minimum_reports as usize <= self.reporters.len().max(1),
+        "Invalid minimum reports"
+      );
+      Reporters::<T>::put(bounded_reporters);
+      MinimumReports::<T>::put(self.minimum_reports);
+    }
+  }
+
+  #[pallet::hooks]
+  impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+    fn on_runtime_upgrade() -> frame_support::weights::Weight {
+      migrations::migrate_to_v1::<T, Self>()
+    }
+  }
+
+  #[pallet::event]
+  #[pallet::generate_deposit(pub (super) fn deposit_event)]
+  pub enum Event<T: Config> {
+    /// Reporter submitted new prices
+    PricesSubmitted { account_id: T::AccountId },
+    /// Reporter added to the set
+    ReporterAdded { account_id: T::AccountId },
+    /// Reporter removed from the set
+    ReporterRemoved { account_id: T::AccountId },
+    /// The minimum number of fresh reports has been updated
+    MinimumReportsSet { minimum_reports: u32 },
+  }
+
+  // Errors inform users that something went wrong.
+  #[pallet::error]
+  pub enum Error<T> {
+    /// The access to the price feed is not allowed for this account ID.
+    AccessDenied,
+    /// The account is already a reporter.
+    ReporterAlreadyExist,
+    /// The account is not a reporter.
+    ReporterNotFound,
+    /// Reporters cap reached.
+    ReportersOverflow,
+    /// Unknown Asset.
+    UnknownAsset,
+    /// There is no price available for this pair.
+    PriceNotAvailable,
+    /// The latest price of this pair is older than the validity window.
+    PriceStale,
+    /// The minimum reports must be at least one, and can't exceed the number of reporters.
+    InvalidMinimumReports,
+  }
+
+  #[pallet::call]
+  impl<T: Config> Pallet<T> {
+    /// Reporter submit the latest prices.
+    ///
+    /// - `prices`: Vector of (`currency_from`, `currency_to`, `price`).
+    ///
+    /// Emits `PricesSubmitted` event when successful.
+    ///
+    /// Weight: `O(n)` where `n` is the number of prices
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::submit_prices(prices.len() as u32))]
+    pub fn submit_prices(
+      origin: OriginFor<T>,
+      prices: Vec<(CurrencyId, CurrencyId, Balance)>,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure this is signed
+      let sender = ensure_signed(origin)?;
+
+      // 2. Submit the prices, only reporters are allowed
+      <Self as PriceFeedExt<T::AccountId>>::submit_prices(&sender, prices)?;
+
+      // don't take tx fees on success
+      Ok(Pays::No.into())
+    }
+
+    /// Add a new price reporter.
+    ///
+    /// - `account_id`: The account allowed to submit prices.
+    ///
+    /// Emits `ReporterAdded` event when successful.
+    ///
+    /// Weight: `O(1)`
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::add_reporter())]
+    pub fn add_reporter(
+      origin: OriginFor<T>,
+      account_id: T::AccountId,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure this is called by the force origin
+      T::ForceOrigin::ensure_origin(origin)?;
+
+      // 2. Add the reporter
+      Reporters::<T>::try_mutate(|reporters| {
+        ensure!(
+          !reporters.contains(&account_id),
+          Error::<T>::ReporterAlreadyExist
+        );
+        reporters
+          .try_push(account_id.clone())
+          .map_err(|_| Error::<T>::ReportersOverflow)
+      })?;
+
+      // 3. Emit event on chain
+      Self::deposit_event(Event::<T>::ReporterAdded { account_id });
+
+      Ok(().into())
+    }
+
+    /// Remove a price reporter, its reports are ignored from now on.
+    ///
+    /// - `account_id`: The account to remove.
+    ///
+    /// Emits `ReporterRemoved` event when successful.
+    ///
+    /// Weight: `O(1)`
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::remove_reporter())]
+    pub fn remove_reporter(
+      origin: OriginFor<T>,
+      account_id: T::AccountId,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure this is called by the force origin
+      T::ForceOrigin::ensure_origin(origin)?;
+
+      // 2. Remove the reporter, the remaining reporters must reach the minimum reports
+      Reporters::<T>::try_mutate(|reporters| {
+        ensure!(
+          reporters.contains(&account_id),
+          Error::<T>::ReporterNotFound
+        );
+        reporters.retain(|reporter| *reporter != account_id);
+        ensure!(
+          reporters.len() >= Self::minimum_reports() as usize,
+          Error::<T>::InvalidMinimumReports
+        );
+        Ok::<(), Error<T>>(())
+      })?;
+
+      // 3. Emit event on chain
+      Self::deposit_event(Event::<T>::ReporterRemoved { account_id });
+
+      Ok(().into())
+    }
+
+    /// Set the minimum number of fresh reports required to update the price of a pair.
+    ///
+    /// - `minimum_reports`: The number of reports, between one and the number of reporters.
+    ///
+    /// Emits `MinimumReportsSet` event when successful.
+    ///
+    /// Weight: `O(1)`
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::set_minimum_reports())]
+    pub fn set_minimum_reports(
+      origin: OriginFor<T>,
+      minimum_reports: u32,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure this is called by the force origin
+      T::ForceOrigin::ensure_origin(origin)?;
+
+      // 2. Make sure the reporters can reach the minimum reports
+      ensure!(
+        minimum_reports >= 1 && minimum_reports as usize <= Self::reporters().len(),
+        Error::<T>::InvalidMinimumReports
+      );
+      MinimumReports::<T>::put(minimum_reports);
+
+      // 3. Emit event on chain
+      Self::deposit_event(Event::<T>::MinimumReportsSet { minimum_reports });
+
+      Ok(().into())
+    }
+  }
+
+  // helper functions (not dispatchable)
+  impl<T: Config> Pallet<T> {
+    pub fn is_reporter(account_id: &T::AccountId) -> bool {
+      Self::reporters().contains(account_id)
+    }
+
+    // replace the previous report of the reporter and update the median price of the pair
+    fn try_submit_price(
+      account_id: &T::AccountId,
+      reporters: &BoundedReporters<T>,
+      currency_from: CurrencyId,
+      currency_to: CurrencyId,
+      price: Balance,
+    ) -> Result<(), DispatchError> {
+      let asset_from: Asset = currency_from
+        .try_into()
+        .map_err(|_| Error::<T>::UnknownAsset)?;
+      let price = FixedU128::saturating_from_rational(price, asset_from.saturating_mul(1));
+      let current_block = T::Security::get_current_block_count();
+      let price_validity = T::PriceValidity::get();
+
+      Reports::<T>::try_mutate(currency_from, currency_to, |reports| {
+        // keep only the fresh reports of the active reporters
+        reports.retain(|(reporter, _, reported_at)| {
+          reporter != account_id
+            && reporters.contains(reporter)
+            && reported_at.saturating_add(price_validity) >= current_block
+        });
+        reports
+          .try_push((account_id.clone(), price, current_block))
+          .map_err(|_| Error::<T>::ReportersOverflow)?;
+
+        // a single reporter can't set the price, the previous price get stale without quorum
+        if reports.len() >= Self::minimum_reports() as usize {
+          let median = Self::median(reports.iter().map(|(_, price, _)| *price).collect());
+          Prices::<T>::insert(currency_from, currency_to, (median, current_block));
+        }
+
+        Ok(())
+      })
+    }
+
+    // median of the prices, the average of the two middle values for an even number of prices
+    pub(crate) fn median(mut prices: Vec<FixedU128>) -> FixedU128 {
+      if prices.is_empty() {
+        return FixedU128::default();
+      }
+
+      prices.sort();
+      let middle = prices.len() / 2;
+
+      if prices.len() % 2 == 0 {
+        prices[middle - 1]
+          .saturating_add(prices[middle])
+          .checked_div(&FixedU128::from(2))
+          .unwrap_or_default()
+      } else {
+        prices[middle]
+      }
+    }
+  }
+
+  // implement the `PriceFeedExt` functions
+  impl<T: Config> PriceFeedExt<T::AccountId> for Pallet<T> {
+    fn submit_prices(
+      account_id: &T::AccountId,
+      prices: Vec<(CurrencyId, CurrencyId, Balance)>,
+    ) -> Result<(), DispatchError> {
+      let reporters = Self::reporters();
+      ensure!(reporters.contains(account_id), Error::<T>::AccessDenied);
+
+      for (currency_from, currency_to, price) in prices {
+        Self::try_submit_price(account_id, &reporters, currency_from, currency_to, price)?;
+      }
+
+      Self::deposit_event(Event::<T>::PricesSubmitted {
+        account_id: account_id.clone(),
+      });
+
+      Ok(())
+    }
+
+    fn get_price(
+      currency_from: CurrencyId,
+      currency_to: CurrencyId,
+    ) -> Result<FixedU128, DispatchError> {
+      let (price, last_updated) =
+        Self::prices(currency_from, currency_to).ok_or(Error::<T>::PriceNotAvailable)?;
+
+      ensure!(
+        last_updated.saturating_add(T::PriceValidity::get())
+          >= T::Security::get_current_block_count(),
+        Error::<T>::PriceStale
+      );
+
+      Ok(price)
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn set_price(currency_from: CurrencyId, currency_to: CurrencyId, price: FixedU128) {
+      Prices::<T>::insert(
+        currency_from,
+        currency_to,
+        (price, T::Security::get_current_block_count()),
+      );
+    }
+  }
+}
@@ -0,0 +1,67 @@
+// Copyright 2021-2022 Semantic Network Ltd.
+// This file is part of Tidechain.
+
+// Tidechain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tidechain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use frame_support::{
+  log,
+  traits::{Get, GetStorageVersion, PalletInfoAccess, StorageVersion},
+  BoundedVec,
+};
+use sp_std::vec;
+
+/// Migrate the pallet storage to v1, the chains launched before the price feed have no
+/// reporters, so the oracle heartbeat can't submit its prices.
+pub fn migrate_to_v1<T: Config, P: GetStorageVersion + PalletInfoAccess>(
+) -> frame_support::weights::Weight {
+  let on_chain_storage_version = <P as GetStorageVersion>::on_chain_storage_version();
+  log::info!(
+    target: LOG_TARGET,
+    "Running migration storage v1 with storage version {:?}",
+    on_chain_storage_version,
+  );
+
+  if on_chain_storage_version < 1 {
+    let mut writes = 1;
+
+    // seed the reporters with the oracle account
+    if Reporters::<T>::get().is_empty() {
+      if let Some(initial_reporter) = T::InitialReporter::get() {
+        let bounded_reporters: BoundedVec<T::AccountId, T::MaxReporters> = vec![initial_reporter]
+          .try_into()
+          .expect("too much reporters");
+        Reporters::<T>::put(bounded_reporters);
+        writes += 1;
+      }
+    }
+
+    // update on-chain storage version
+    StorageVersion::new(1).put::<P>();
+    log::info!(
+      target: LOG_TARGET,
+      "Running migration storage v1 with storage version {:?} was complete",
+      on_chain_storage_version,
+    );
+    // return migration weights
+    T::DbWeight::get().reads_writes(2, writes)
+  } else {
+    log::info!(
+      target: LOG_TARGET,
+      "Attempted to apply migration to v1 but failed because storage version is {:?}",
+      on_chain_storage_version,
+    );
+    T::DbWeight::get().reads(1)
+  }
+}
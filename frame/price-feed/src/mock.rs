@@ -0,0 +1,114 @@
+// Copyright 2021-2022 Semantic Network Ltd.
+// This file is part of Tidechain.
+
+// Tidechain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tidechain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::pallet as pallet_price_feed;
+use frame_support::{
+  parameter_types,
+  traits::{ConstU32, GenesisBuild},
+};
+use frame_system as system;
+use sp_core::H256;
+use sp_runtime::{
+  testing::Header,
+  traits::{BlakeTwo256, IdentityLookup},
+};
+use system::EnsureRoot;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+type AccountId = u64;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+  pub enum Test where
+    Block = Block,
+    NodeBlock = Block,
+    UncheckedExtrinsic = UncheckedExtrinsic,
+  {
+    System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+    Security: pallet_security::{Pallet, Call, Config, Storage, Event<T>},
+    PriceFeed: pallet_price_feed::{Pallet, Call, Config<T>, Storage, Event<T>},
+  }
+);
+
+parameter_types! {
+  pub const BlockHashCount: u64 = 250;
+  pub const SS58Prefix: u8 = 42;
+}
+
+impl system::Config for Test {
+  type BaseCallFilter = frame_support::traits::Everything;
+  type BlockWeights = ();
+  type BlockLength = ();
+  type DbWeight = ();
+  type Origin = Origin;
+  type Call = Call;
+  type Index = u64;
+  type BlockNumber = u64;
+  type Hash = H256;
+  type Hashing = BlakeTwo256;
+  type AccountId = AccountId;
+  type Lookup = IdentityLookup<Self::AccountId>;
+  type Header = Header;
+  type Event = Event;
+  type BlockHashCount = BlockHashCount;
+  type Version = ();
+  type PalletInfo = PalletInfo;
+  type AccountData = ();
+  type OnNewAccount = ();
+  type OnKilledAccount = ();
+  type SystemWeightInfo = ();
+  type SS58Prefix = SS58Prefix;
+  type OnSetCode = ();
+  type MaxConsumers = ConstU32<16>;
+}
+
+parameter_types! {
+  pub const MaxReporters: u32 = 5;
+  pub const PriceValidity: u64 = 10;
+  pub const InitialReporter: Option<AccountId> = Some(1);
+}
+
+impl pallet_price_feed::Config for Test {
+  type Event = Event;
+  type WeightInfo = crate::weights::SubstrateWeight<Test>;
+  type Security = Security;
+  type MaxReporters = MaxReporters;
+  type PriceValidity = PriceValidity;
+  type ForceOrigin = EnsureRoot<Self::AccountId>;
+  type InitialReporter = InitialReporter;
+}
+
+impl pallet_security::Config for Test {
+  type Event = Event;
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+  let mut storage = system::GenesisConfig::default()
+    .build_storage::<Test>()
+    .unwrap();
+  pallet_price_feed::GenesisConfig::<Test> {
+    reporters: vec![1, 2, 3],
+    minimum_reports: 1,
+  }
+  .assimilate_storage(&mut storage)
+  .unwrap();
+
+  let mut ext: sp_io::TestExternalities = storage.into();
+  ext.execute_with(|| System::set_block_number(1));
+  ext
+}
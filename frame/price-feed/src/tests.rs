@@ -0,0 +1,260 @@
+// Copyright 2021-2022 Semantic Network Ltd.
+// This file is part of Tidechain.
+
+// Tidechain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tidechain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+  mock::{new_test_ext, Origin, PriceFeed, Test},
+  pallet::*,
+  PriceFeedExt,
+};
+use frame_support::{
+  assert_noop, assert_ok,
+  traits::{GetStorageVersion, StorageVersion},
+  BoundedVec,
+};
+use sp_runtime::{traits::BadOrigin, FixedPointNumber, FixedU128};
+use tidefi_primitives::{
+  assets::{Asset, USDT},
+  pallet::SecurityExt,
+};
+
+fn set_current_block(block_number: u64) {
+  pallet_security::CurrentBlockCount::<Test>::put(block_number);
+}
+
+fn submit_btc_price(reporter: u64, usdt_per_btc: u128) {
+  assert_ok!(PriceFeed::submit_prices(
+    Origin::signed(reporter),
+    vec![(
+      Asset::Bitcoin.currency_id(),
+      USDT,
+      Asset::Tether.saturating_mul(usdt_per_btc)
+    )],
+  ));
+}
+
+#[test]
+pub fn check_genesis_config() {
+  new_test_ext().execute_with(|| {
+    assert_eq!(PriceFeed::reporters().into_inner(), vec![1, 2, 3]);
+  });
+}
+
+#[test]
+pub fn median_price() {
+  new_test_ext().execute_with(|| {
+    assert_eq!(PriceFeed::median(Vec::new()), FixedU128::default());
+    assert_eq!(
+      PriceFeed::median(vec![
+        FixedU128::from(3),
+        FixedU128::from(1),
+        FixedU128::from(2)
+      ]),
+      FixedU128::from(2)
+    );
+    assert_eq!(
+      PriceFeed::median(vec![
+        FixedU128::from(4),
+        FixedU128::from(1),
+        FixedU128::from(2),
+        FixedU128::from(3)
+      ]),
+      FixedU128::saturating_from_rational(5, 2)
+    );
+  });
+}
+
+#[test]
+pub fn submit_prices_use_median_of_reporters() {
+  new_test_ext().execute_with(|| {
+    submit_btc_price(1, 20_000);
+    submit_btc_price(2, 21_000);
+    // an outlier can't move the price
+    submit_btc_price(3, 1);
+
+    let expected_price = FixedU128::saturating_from_rational(
+      Asset::Tether.saturating_mul(20_000),
+      Asset::Bitcoin.saturating_mul(1),
+    );
+    assert_eq!(
+      <PriceFeed as PriceFeedExt<u64>>::get_price(Asset::Bitcoin.currency_id(), USDT),
+      Ok(expected_price)
+    );
+    assert_eq!(
+      PriceFeed::reports(Asset::Bitcoin.currency_id(), USDT).len(),
+      3
+    );
+
+    // a new report replace the previous one of the same reporter
+    submit_btc_price(3, 20_000);
+    assert_eq!(
+      PriceFeed::reports(Asset::Bitcoin.currency_id(), USDT).len(),
+      3
+    );
+  });
+}
+
+#[test]
+pub fn submit_prices_ignore_stale_reports() {
+  new_test_ext().execute_with(|| {
+    submit_btc_price(1, 1);
+
+    set_current_block(pallet_security::Pallet::<Test>::get_current_block_count() + 11);
+    submit_btc_price(2, 20_000);
+
+    assert_eq!(
+      PriceFeed::reports(Asset::Bitcoin.currency_id(), USDT).len(),
+      1
+    );
+    assert_eq!(
+      <PriceFeed as PriceFeedExt<u64>>::get_price(Asset::Bitcoin.currency_id(), USDT),
+      Ok(FixedU128::saturating_from_rational(
+        Asset::Tether.saturating_mul(20_000),
+        Asset::Bitcoin.saturating_mul(1)
+      ))
+    );
+  });
+}
+
+#[test]
+pub fn get_price_fails_when_stale_or_missing() {
+  new_test_ext().execute_with(|| {
+    assert_noop!(
+      <PriceFeed as PriceFeedExt<u64>>::get_price(Asset::Bitcoin.currency_id(), USDT),
+      Error::<Test>::PriceNotAvailable
+    );
+
+    submit_btc_price(1, 20_000);
+    set_current_block(pallet_security::Pallet::<Test>::get_current_block_count() + 11);
+
+    assert_noop!(
+      <PriceFeed as PriceFeedExt<u64>>::get_price(Asset::Bitcoin.currency_id(), USDT),
+      Error::<Test>::PriceStale
+    );
+  });
+}
+
+#[test]
+pub fn submit_prices_only_by_reporters() {
+  new_test_ext().execute_with(|| {
+    assert_noop!(
+      PriceFeed::submit_prices(
+        Origin::signed(4),
+        vec![(
+          Asset::Bitcoin.currency_id(),
+          USDT,
+          Asset::Tether.saturating_mul(20_000)
+        )],
+      ),
+      Error::<Test>::AccessDenied
+    );
+  });
+}
+
+#[test]
+pub fn add_and_remove_reporter() {
+  new_test_ext().execute_with(|| {
+    assert_noop!(PriceFeed::add_reporter(Origin::signed(1), 4), BadOrigin);
+    assert_noop!(
+      PriceFeed::add_reporter(Origin::root(), 1),
+      Error::<Test>::ReporterAlreadyExist
+    );
+    assert_ok!(PriceFeed::add_reporter(Origin::root(), 4));
+    assert!(PriceFeed::is_reporter(&4));
+
+    submit_btc_price(4, 20_000);
+    assert_ok!(PriceFeed::remove_reporter(Origin::root(), 4));
+    assert!(!PriceFeed::is_reporter(&4));
+    assert_noop!(
+      PriceFeed::remove_reporter(Origin::root(), 4),
+      Error::<Test>::ReporterNotFound
+    );
+
+    // the report of the removed reporter is dropped on the next submission
+    submit_btc_price(1, 20_000);
+    assert_eq!(
+      PriceFeed::reports(Asset::Bitcoin.currency_id(), USDT).len(),
+      1
+    );
+  });
+}
+
+#[test]
+pub fn minimum_reports_quorum() {
+  new_test_ext().execute_with(|| {
+    assert_noop!(
+      PriceFeed::set_minimum_reports(Origin::signed(1), 2),
+      BadOrigin
+    );
+    assert_noop!(
+      PriceFeed::set_minimum_reports(Origin::root(), 0),
+      Error::<Test>::InvalidMinimumReports
+    );
+    assert_noop!(
+      PriceFeed::set_minimum_reports(Origin::root(), 4),
+      Error::<Test>::InvalidMinimumReports
+    );
+    assert_ok!(PriceFeed::set_minimum_reports(Origin::root(), 2));
+    assert_eq!(PriceFeed::minimum_reports(), 2);
+
+    // a single report can't set the price
+    submit_btc_price(1, 20_000);
+    assert_noop!(
+      <PriceFeed as PriceFeedExt<u64>>::get_price(Asset::Bitcoin.currency_id(), USDT),
+      Error::<Test>::PriceNotAvailable
+    );
+
+    submit_btc_price(2, 20_000);
+    assert_eq!(
+      <PriceFeed as PriceFeedExt<u64>>::get_price(Asset::Bitcoin.currency_id(), USDT),
+      Ok(FixedU128::saturating_from_rational(
+        Asset::Tether.saturating_mul(20_000),
+        Asset::Bitcoin.saturating_mul(1)
+      ))
+    );
+
+    // the previous price get stale when the quorum is lost
+    set_current_block(pallet_security::Pallet::<Test>::get_current_block_count() + 11);
+    submit_btc_price(3, 20_000);
+    assert_noop!(
+      <PriceFeed as PriceFeedExt<u64>>::get_price(Asset::Bitcoin.currency_id(), USDT),
+      Error::<Test>::PriceStale
+    );
+
+    // the reporters can't go below the minimum reports
+    assert_ok!(PriceFeed::remove_reporter(Origin::root(), 3));
+    assert_noop!(
+      PriceFeed::remove_reporter(Origin::root(), 2),
+      Error::<Test>::InvalidMinimumReports
+    );
+  });
+}
+
+#[test]
+pub fn migrate_to_v1_seed_reporters() {
+  new_test_ext().execute_with(|| {
+    StorageVersion::new(0).put::<PriceFeed>();
+    Reporters::<Test>::put(BoundedVec::default());
+
+    crate::migrations::migrate_to_v1::<Test, PriceFeed>();
+    assert_eq!(PriceFeed::reporters().into_inner(), vec![1]);
+    assert_eq!(PriceFeed::on_chain_storage_version(), 1);
+
+    // the migration run only once
+    Reporters::<Test>::put(BoundedVec::default());
+    crate::migrations::migrate_to_v1::<Test, PriceFeed>();
+    assert!(PriceFeed::reporters().is_empty());
+  });
+}
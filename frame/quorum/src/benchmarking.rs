@@ -20,14 +20,22 @@ use super::*;
 use frame_benchmarking::{
   account, benchmarks, impl_benchmark_test_suite, vec, whitelisted_caller, Vec,
 };
-use frame_support::BoundedVec;
+use frame_support::{
+  traits::{fungibles::Mutate, EnsureOrigin, UnfilteredDispatchable},
+  BoundedVec,
+};
 use frame_system::{self, RawOrigin};
+use sp_runtime::{traits::StaticLookup, Permill};
 use tidefi_primitives::{
   pallet::SecurityExt, ComplianceLevel, CurrencyId, Hash, Mint, ProposalType, ProposalVotes,
+  WatchList, WatchListAction, Withdrawal,
 };
 
 const SEED: u32 = 0;
 const ADMIN_ID: u32 = 1;
+const USER_ID: u32 = 2;
+const KEYGEN_ASSET_ID: u32 = 1;
+const WATCH_LIST_ASSET_ID: u32 = 100;
 
 fn _assert_last_event<T: Config>(generic_event: <T as Config>::Event) {
   frame_system::Pallet::<T>::assert_last_event(generic_event.into());
@@ -50,6 +58,7 @@ fn pre_set_auth<T: Config>() -> T::AccountId {
   PublicKeys::<T>::insert(1, public_keys);
 
   Threshold::<T>::put(1);
+  Thresholds::<T>::put(ProposalThresholds::from_threshold(1));
   user
 }
 
@@ -74,6 +83,153 @@ fn create_proposal<T: Config>() -> Hash {
   proposal_id
 }
 
+fn pre_set_members<T: Config>(count: u32) -> Vec<T::AccountId> {
+  let members: Vec<T::AccountId> = (0..count).map(|i| account("member", i, SEED)).collect();
+  Members::<T>::remove_all();
+  for account_id in members.iter() {
+    Members::<T>::insert(account_id, true);
+  }
+  Threshold::<T>::put(count as u16);
+  Thresholds::<T>::put(ProposalThresholds::from_threshold(count as u16));
+  members
+}
+
+fn pre_set_keygen_round<T: Config>(participants: &[T::AccountId], phase: KeygenPhase) {
+  let phase_end = T::Security::get_current_block_count() + T::KeygenPhaseTimeout::get();
+  KeygenRounds::<T>::insert(
+    KEYGEN_ASSET_ID,
+    KeygenRound {
+      round: 1,
+      phase,
+      phase_end,
+      threshold: participants.len() as u16,
+      participants: participants.to_vec().try_into().unwrap(),
+    },
+  );
+  KeygenDeadlines::<T>::insert(phase_end, KEYGEN_ASSET_ID, ());
+}
+
+fn pre_set_keygen_submissions<T: Config>(
+  participants: &[T::AccountId],
+  with_commitments: bool,
+  with_shares: bool,
+  with_confirmations: bool,
+) {
+  let payload: BoundedVec<u8, <T as pallet::Config>::KeygenPayloadLimit> =
+    vec![0_u8; T::KeygenPayloadLimit::get() as usize]
+      .try_into()
+      .unwrap();
+  let public_key: BoundedVec<u8, <T as pallet::Config>::StringLimit> =
+    "group_pubkey".as_bytes().to_vec().try_into().unwrap();
+
+  for account_id in participants.iter() {
+    if with_commitments {
+      KeygenCommitments::<T>::insert(KEYGEN_ASSET_ID, account_id, payload.clone());
+    }
+    if with_shares {
+      let shares: Vec<(
+        T::AccountId,
+        BoundedVec<u8, <T as pallet::Config>::KeygenPayloadLimit>,
+      )> = participants
+        .iter()
+        .filter(|recipient| *recipient != account_id)
+        .map(|recipient| (recipient.clone(), payload.clone()))
+        .collect();
+      KeygenShares::<T>::insert(
+        KEYGEN_ASSET_ID,
+        account_id,
+        BoundedVec::try_from(shares).unwrap(),
+      );
+    }
+    if with_confirmations {
+      KeygenConfirmations::<T>::insert(KEYGEN_ASSET_ID, account_id, public_key.clone());
+    }
+  }
+}
+
+fn pre_set_watch_list<T: Config>(
+  account_id: &T::AccountId,
+  compliance_level: ComplianceLevel,
+) -> Vec<u8> {
+  let transaction_id: BoundedVec<u8, <T as pallet::Config>::StringLimit> =
+    "transaction_id".as_bytes().to_vec().try_into().unwrap();
+  let watch_list = WatchList {
+    amount: 1_000_000_000_000,
+    block_number: T::Security::get_current_block_count(),
+    compliance_level,
+    currency_id: CurrencyId::Tifi,
+    watch_action: WatchListAction::Mint,
+    transaction_id: transaction_id.clone(),
+  };
+  AccountWatchList::<T>::insert(account_id, BoundedVec::try_from(vec![watch_list]).unwrap());
+  transaction_id.to_vec()
+}
+
+fn pre_set_wrapped_asset<T: Config>(account_id: &T::AccountId, is_frozen: bool) {
+  pre_set_wrapped_assets::<T>(account_id, WATCH_LIST_ASSET_ID, 1);
+
+  if is_frozen {
+    pallet_assets::Pallet::<T>::do_freeze(WATCH_LIST_ASSET_ID, account_id)
+      .expect("Unable to freeze token");
+    FrozenAccountAssets::<T>::insert(
+      account_id,
+      WATCH_LIST_ASSET_ID,
+      T::Security::get_current_block_count(),
+    );
+    FrozenWatchedAccounts::<T>::insert(account_id, T::Security::get_current_block_count());
+  }
+}
+
+fn pre_set_wrapped_assets<T: Config>(account_id: &T::AccountId, first_asset_id: u32, count: u32) {
+  let owner: T::AccountId = account("owner", ADMIN_ID, SEED);
+  for asset_id in first_asset_id..first_asset_id + count {
+    pallet_assets::Pallet::<T>::force_create(
+      RawOrigin::Root.into(),
+      asset_id,
+      T::Lookup::unlookup(owner.clone()),
+      true,
+      1,
+    )
+    .expect("Unable to create asset");
+    T::CurrencyTidefi::mint_into(CurrencyId::Wrapped(asset_id), account_id, 1_000_000_000_000)
+      .expect("Unable to mint token");
+  }
+}
+
+fn pre_set_burned<T: Config>(account_id: &T::AccountId) -> Hash {
+  let fee = 1_000;
+  pre_set_wrapped_asset::<T>(account_id, false);
+  T::CurrencyTidefi::mint_into(
+    CurrencyId::Wrapped(WATCH_LIST_ASSET_ID),
+    &T::WithdrawalFeesAccount::get(),
+    fee,
+  )
+  .expect("Unable to mint token");
+
+  let proposal_id = Hash::zero();
+  let block_number = T::Security::get_current_block_count();
+  let withdrawal = Withdrawal {
+    account_id: account_id.clone(),
+    asset_id: CurrencyId::Wrapped(WATCH_LIST_ASSET_ID),
+    amount: 1_000_000,
+    external_address: vec![1; 32].try_into().unwrap(),
+    block_number,
+  };
+  BurnedQueue::<T>::put(BoundedVec::try_from(vec![(proposal_id, withdrawal)]).unwrap());
+  BurnedConfirmations::<T>::insert(
+    proposal_id,
+    BurnedConfirmation {
+      burned_at: block_number,
+      fee,
+      acknowledged_by: None,
+      confirmations: BoundedVec::default(),
+      failures: BoundedVec::default(),
+      escalated: false,
+    },
+  );
+  proposal_id
+}
+
 benchmarks! {
    submit_proposal {
       let user = pre_set_auth::<T>();
@@ -108,6 +264,112 @@ benchmarks! {
       }
       let user = pre_set_auth::<T>();
    }: _(RawOrigin::Signed(user), all_keys)
+   start_keygen {
+      let members = pre_set_members::<T>(T::VotesLimit::get());
+   }: _(RawOrigin::Signed(members[0].clone()), KEYGEN_ASSET_ID)
+   submit_keygen_commitment {
+      let members = pre_set_members::<T>(T::VotesLimit::get());
+      pre_set_keygen_round::<T>(&members, KeygenPhase::Commitment);
+      pre_set_keygen_submissions::<T>(&members[1..], true, false, false);
+      let commitment = vec![0_u8; T::KeygenPayloadLimit::get() as usize];
+   }: _(RawOrigin::Signed(members[0].clone()), KEYGEN_ASSET_ID, 1, commitment)
+   submit_keygen_shares {
+      let s in 1 .. T::VotesLimit::get() - 1;
+      let members = pre_set_members::<T>(s + 1);
+      pre_set_keygen_round::<T>(&members, KeygenPhase::Share);
+      pre_set_keygen_submissions::<T>(&members, true, false, false);
+      pre_set_keygen_submissions::<T>(&members[1..], false, true, false);
+      let shares: Vec<(T::AccountId, Vec<u8>)> = members[1..]
+         .iter()
+         .map(|recipient| (recipient.clone(), vec![0_u8; T::KeygenPayloadLimit::get() as usize]))
+         .collect();
+   }: _(RawOrigin::Signed(members[0].clone()), KEYGEN_ASSET_ID, 1, shares)
+   submit_keygen_complaint {
+      let members = pre_set_members::<T>(T::VotesLimit::get());
+      pre_set_keygen_round::<T>(&members, KeygenPhase::Complaint);
+      pre_set_keygen_submissions::<T>(&members, true, true, false);
+   }: _(RawOrigin::Signed(members[0].clone()), KEYGEN_ASSET_ID, 1, members[1].clone())
+   confirm_keygen {
+      let members = pre_set_members::<T>(T::VotesLimit::get());
+      pre_set_keygen_round::<T>(&members, KeygenPhase::Complaint);
+      pre_set_keygen_submissions::<T>(&members, true, true, false);
+      pre_set_keygen_submissions::<T>(&members[1..], false, false, true);
+   }: _(RawOrigin::Signed(members[0].clone()), KEYGEN_ASSET_ID, 1, "group_pubkey".as_bytes().to_vec())
+   approve_watch_list_mint {
+      let account_id: T::AccountId = account("user", USER_ID, SEED);
+      let transaction_id = pre_set_watch_list::<T>(&account_id, ComplianceLevel::Red);
+      pre_set_wrapped_asset::<T>(&account_id, true);
+      let origin = T::ComplianceOrigin::successful_origin();
+      let call = Call::<T>::approve_watch_list_mint { account_id, currency_id: CurrencyId::Tifi, transaction_id };
+   }: { call.dispatch_bypass_filter(origin)? }
+   reject_watch_list_mint {
+      let account_id: T::AccountId = account("user", USER_ID, SEED);
+      let transaction_id = pre_set_watch_list::<T>(&account_id, ComplianceLevel::Red);
+      pre_set_wrapped_asset::<T>(&account_id, true);
+      let origin = T::ComplianceOrigin::successful_origin();
+      let call = Call::<T>::reject_watch_list_mint { account_id, currency_id: CurrencyId::Tifi, transaction_id };
+   }: { call.dispatch_bypass_filter(origin)? }
+   review_watch_list_entry {
+      let account_id: T::AccountId = account("user", USER_ID, SEED);
+      let transaction_id = pre_set_watch_list::<T>(&account_id, ComplianceLevel::Amber);
+      pre_set_wrapped_asset::<T>(&account_id, true);
+      let origin = T::ComplianceOrigin::successful_origin();
+      let call = Call::<T>::review_watch_list_entry { account_id, currency_id: CurrencyId::Tifi, transaction_id };
+   }: { call.dispatch_bypass_filter(origin)? }
+   freeze_watched_account {
+      let a in 1 .. 100;
+      let account_id: T::AccountId = account("user", USER_ID, SEED);
+      pre_set_watch_list::<T>(&account_id, ComplianceLevel::Red);
+      pre_set_wrapped_assets::<T>(&account_id, WATCH_LIST_ASSET_ID, a);
+      let origin = T::ComplianceOrigin::successful_origin();
+      let call = Call::<T>::freeze_watched_account { account_id, asset_count: a };
+   }: { call.dispatch_bypass_filter(origin)? }
+   cancel_delayed_withdrawal {
+      let account_id: T::AccountId = account("user", USER_ID, SEED);
+      pre_set_wrapped_asset::<T>(&account_id, false);
+      let (withdrawal_id, _) = Pallet::<T>::add_new_delayed_withdrawal(
+         account_id,
+         CurrencyId::Wrapped(WATCH_LIST_ASSET_ID),
+         1_000_000,
+         vec![1; 32],
+      )
+      .expect("Unable to delay withdrawal");
+      let origin = T::ForceOrigin::successful_origin();
+      let call = Call::<T>::cancel_delayed_withdrawal { withdrawal_id };
+   }: { call.dispatch_bypass_filter(origin)? }
+   submit_withdrawal_fee {
+      let user = pre_set_auth::<T>();
+      let fee = WithdrawalFee {
+         flat: 1_000,
+         percentage: Permill::from_percent(1),
+      };
+   }: _(RawOrigin::Signed(user), CurrencyId::Wrapped(WATCH_LIST_ASSET_ID), fee)
+   confirm_burned {
+      let user = pre_set_auth::<T>();
+      let account_id: T::AccountId = account("user", USER_ID, SEED);
+      let proposal_id = pre_set_burned::<T>(&account_id);
+   }: _(RawOrigin::Signed(user), proposal_id, "external_tx_id".as_bytes().to_vec())
+   fail_burned {
+      let user = pre_set_auth::<T>();
+      let account_id: T::AccountId = account("user", USER_ID, SEED);
+      let proposal_id = pre_set_burned::<T>(&account_id);
+   }: _(RawOrigin::Signed(user), proposal_id)
+   set_proposal_thresholds {
+      let thresholds = ProposalThresholds {
+         mint: 2,
+         large_mint: 3,
+         large_mint_value: 100_000_000_000,
+         withdrawal: 2,
+         update_configuration: 3,
+      };
+      let origin = T::ForceOrigin::successful_origin();
+      let call = Call::<T>::set_proposal_thresholds { thresholds };
+   }: { call.dispatch_bypass_filter(origin)? }
+   set_member_vote_weight {
+      let account_id = pre_set_auth::<T>();
+      let origin = T::ForceOrigin::successful_origin();
+      let call = Call::<T>::set_member_vote_weight { account_id, weight: Some(2) };
+   }: { call.dispatch_bypass_filter(origin)? }
 }
 
 impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
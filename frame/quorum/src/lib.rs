@@ -25,14 +25,29 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+mod migrations;
+
 pub mod weights;
 pub use weights::*;
 
 // Re-export pallet items so that they can be accessed from the crate namespace.
 pub use pallet::*;
 
+use codec::{Decode, Encode};
+use frame_support::inherent::Vec;
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{DispatchError, Permill, RuntimeDebug};
+use tidefi_primitives::{
+  AssetId, Balance, BalanceInfo, ComplianceLevel, CurrencyId, Hash, WatchListAction,
+};
+
 pub(crate) const LOG_TARGET: &str = "tidefi::quorum";
 
+/// The maximum number of archived proposals returned by page
+pub const PROPOSAL_ARCHIVE_PAGE_LIMIT: u32 = 500;
+
 // syntactic sugar for logging.
 #[macro_export]
 macro_rules! log {
@@ -44,16 +59,189 @@ macro_rules! log {
 	};
 }
 
+/// Final status of an archived proposal
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ProposalResolution {
+  /// The proposal reached the threshold and has been processed
+  Approved,
+  /// Too many members voted against the proposal
+  Rejected,
+  /// The proposal lifetime ended before the quorum resolved it
+  Expired,
+}
+
+/// Body of an archived proposal, used by the RPC
+#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ArchivedProposalBody<AccountId> {
+  /// Mint `amount` of `currency_id` to `account_id`
+  Mint {
+    account_id: AccountId,
+    currency_id: CurrencyId,
+    amount: BalanceInfo,
+    transaction_id: Vec<u8>,
+  },
+  /// Burn `amount` of `currency_id` from `account_id` and send it to `external_address`
+  Withdrawal {
+    account_id: AccountId,
+    currency_id: CurrencyId,
+    amount: BalanceInfo,
+    external_address: Vec<u8>,
+  },
+  /// Replace the quorum members and threshold
+  UpdateConfiguration {
+    members: Vec<AccountId>,
+    threshold: u16,
+  },
+}
+
+/// Finalized proposal with its votes, used by the RPC
+#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ArchivedProposalInfo<AccountId, BlockNumber> {
+  /// Proposal ID
+  pub proposal_id: Hash,
+  /// Proposal body
+  pub proposal: ArchivedProposalBody<AccountId>,
+  /// Final status of the proposal
+  pub status: ProposalResolution,
+  /// Members who voted for the proposal
+  pub votes_for: Vec<AccountId>,
+  /// Members who voted against the proposal
+  pub votes_against: Vec<AccountId>,
+  /// Block of the proposal submission
+  pub submitted_at: BlockNumber,
+  /// Block of the proposal resolution
+  pub resolved_at: BlockNumber,
+}
+
+/// Filter of the archived proposals, every filter set must match
+#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ProposalArchiveFilter<AccountId> {
+  /// Account of the mint or the withdrawal, or member who voted on the proposal
+  pub account_id: Option<AccountId>,
+  /// Currency of the mint or the withdrawal
+  pub currency_id: Option<CurrencyId>,
+  /// Final status of the proposal
+  pub status: Option<ProposalResolution>,
+}
+
+/// Compliance decision on a watch list entry
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum WatchListDecision {
+  /// The held `Red` mint has been approved and minted
+  Approved,
+  /// The held `Red` mint has been rejected, it will never be minted
+  Rejected,
+  /// The `Amber` entry has been reviewed
+  Reviewed,
+}
+
+/// Watch list entry with its compliance decision, used by the RPC
+#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct WatchListEntryInfo<BlockNumber> {
+  /// Currency of the transaction
+  pub currency_id: CurrencyId,
+  /// Amount of the transaction
+  pub amount: BalanceInfo,
+  /// Compliance level of the transaction
+  pub compliance_level: ComplianceLevel,
+  /// Action of the transaction
+  pub watch_action: WatchListAction,
+  /// Transaction ID on the external chain
+  pub transaction_id: Vec<u8>,
+  /// Block the transaction has been added to the watch list
+  pub block_number: BlockNumber,
+  /// Compliance decision with its block, `None` while the review is open
+  pub decision: Option<(WatchListDecision, BlockNumber)>,
+}
+
+/// Watch list of an account with the assets frozen by the compliance, used by the RPC
+#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct AccountWatchListInfo<BlockNumber> {
+  /// Watch list entries of the account
+  pub entries: Vec<WatchListEntryInfo<BlockNumber>>,
+  /// Wrapped assets of the account frozen while a review is open
+  pub frozen_assets: Vec<AssetId>,
+}
+
+/// Withdrawal fee of an asset, paid in the withdrawn asset to cover the external chain network fee
+#[derive(Clone, Copy, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct WithdrawalFee {
+  /// Flat fee of each withdrawal
+  pub flat: Balance,
+  /// Fee proportional to the amount withdrawn
+  pub percentage: Permill,
+}
+
+/// Votes weight needed to approve each type of proposal
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ProposalThresholds {
+  /// Votes weight needed to approve a mint
+  pub mint: u16,
+  /// Votes weight needed to approve a mint valued above `large_mint_value`
+  pub large_mint: u16,
+  /// Value in USDT above which a mint needs the `large_mint` threshold
+  pub large_mint_value: Balance,
+  /// Votes weight needed to approve a withdrawal
+  pub withdrawal: u16,
+  /// Votes weight needed to approve a configuration update
+  pub update_configuration: u16,
+}
+
+impl ProposalThresholds {
+  /// The same threshold for all the proposals
+  pub fn from_threshold(threshold: u16) -> Self {
+    Self {
+      mint: threshold,
+      large_mint: threshold,
+      large_mint_value: Balance::MAX,
+      withdrawal: threshold,
+      update_configuration: threshold,
+    }
+  }
+}
+
+/// Withdrawal fees traits, used by the pallets who initiate the withdrawals
+pub trait WithdrawalFeeExt {
+  /// Quote the fee of a withdrawal of `amount`, deducted from the amount burned
+  fn quote_withdrawal_fee(asset_id: CurrencyId, amount: Balance) -> Balance;
+}
+
+/// Delayed withdrawals traits, used by the pallets who initiate the withdrawals
+pub trait DelayedWithdrawalExt<AccountId, BlockNumber> {
+  /// Hold the funds of the withdrawal until the end of the delay, the withdrawal is then added
+  /// in the proposals queue. Returns the withdrawal ID and the block of the release.
+  fn add_new_delayed_withdrawal(
+    account_id: AccountId,
+    asset_id: CurrencyId,
+    amount: Balance,
+    external_address: Vec<u8>,
+  ) -> Result<(Hash, BlockNumber), DispatchError>;
+}
+
 #[frame_support::pallet]
 pub mod pallet {
   use super::*;
   use frame_support::{
     log,
     pallet_prelude::*,
-    traits::fungibles::{Inspect, Mutate, Transfer},
-    PalletId,
+    traits::{
+      fungibles::{Inspect, Mutate, MutateHold, Transfer},
+      StorageVersion,
+    },
+    transactional, PalletId,
   };
   use frame_system::pallet_prelude::*;
+  use pallet_asset_registry::WithdrawalLimitsExt;
+  use pallet_fees::UsdtValueExt;
   use rand_chacha::{
     rand_core::{RngCore, SeedableRng},
     ChaChaRng,
@@ -65,6 +253,189 @@ pub mod pallet {
     ProposalVotes, WatchList, WatchListAction, Withdrawal,
   };
 
+  type KeygenPayload<T> = BoundedVec<u8, <T as Config>::KeygenPayloadLimit>;
+  type KeygenParticipants<T> =
+    BoundedVec<<T as frame_system::Config>::AccountId, <T as Config>::VotesLimit>;
+
+  /// Phases of a distributed key generation round
+  #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+  pub enum KeygenPhase {
+    /// Participants publish the commitments of their secret polynomial
+    Commitment,
+    /// Participants distribute the encrypted secret shares to each other participant
+    Share,
+    /// Participants complain about the invalid shares and confirm the group public key
+    Complaint,
+  }
+
+  /// Distributed key generation round of the threshold key of an asset
+  #[derive(
+    CloneNoBound, Encode, Decode, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo,
+  )]
+  #[scale_info(skip_type_params(T))]
+  pub struct KeygenRound<T: Config> {
+    /// Round index, incremented for every new round of the asset
+    pub round: u32,
+    /// Current phase of the round
+    pub phase: KeygenPhase,
+    /// The current phase ends at this block
+    pub phase_end: T::BlockNumber,
+    /// Number of participants required to sign with the group key
+    pub threshold: u16,
+    /// Quorum members taking part of the round
+    pub participants: KeygenParticipants<T>,
+  }
+
+  /// Threshold group public key of an asset, shared by the quorum members
+  #[derive(
+    CloneNoBound, Encode, Decode, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo,
+  )]
+  #[scale_info(skip_type_params(T))]
+  pub struct GroupPublicKey<T: Config> {
+    /// Round who generated the key
+    pub round: u32,
+    /// Number of participants required to sign with the group key
+    pub threshold: u16,
+    /// The group public key
+    pub public_key: BoundedVec<u8, <T as pallet::Config>::StringLimit>,
+    /// Quorum members holding a share of the key
+    pub participants: KeygenParticipants<T>,
+    /// Block of the key generation
+    pub block_number: T::BlockNumber,
+  }
+
+  /// Finalized proposal kept in the archive with its votes
+  #[derive(
+    CloneNoBound, Encode, Decode, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo,
+  )]
+  #[scale_info(skip_type_params(T))]
+  pub struct ArchivedProposal<T: Config> {
+    /// Proposal ID
+    pub proposal_id: Hash,
+    /// Proposal body
+    pub proposal: ProposalType<
+      T::AccountId,
+      T::BlockNumber,
+      BoundedVec<u8, <T as pallet::Config>::StringLimit>,
+      BoundedVec<T::AccountId, <T as pallet::Config>::VotesLimit>,
+    >,
+    /// Final status of the proposal
+    pub status: ProposalResolution,
+    /// Members who voted for the proposal
+    pub votes_for: BoundedVec<T::AccountId, T::VotesLimit>,
+    /// Members who voted against the proposal
+    pub votes_against: BoundedVec<T::AccountId, T::VotesLimit>,
+    /// Block of the proposal submission
+    pub submitted_at: T::BlockNumber,
+    /// Block of the proposal resolution
+    pub resolved_at: T::BlockNumber,
+  }
+
+  impl<T: Config> From<ArchivedProposal<T>> for ArchivedProposalInfo<T::AccountId, T::BlockNumber> {
+    fn from(archived: ArchivedProposal<T>) -> Self {
+      let proposal = match archived.proposal {
+        ProposalType::Mint(mint) => ArchivedProposalBody::Mint {
+          account_id: mint.account_id,
+          currency_id: mint.currency_id,
+          amount: BalanceInfo {
+            amount: mint.mint_amount,
+          },
+          transaction_id: mint.transaction_id.to_vec(),
+        },
+        ProposalType::Withdrawal(withdrawal) => ArchivedProposalBody::Withdrawal {
+          account_id: withdrawal.account_id,
+          currency_id: withdrawal.asset_id,
+          amount: BalanceInfo {
+            amount: withdrawal.amount,
+          },
+          external_address: withdrawal.external_address.to_vec(),
+        },
+        ProposalType::UpdateConfiguration(members, threshold) => {
+          ArchivedProposalBody::UpdateConfiguration {
+            members: members.to_vec(),
+            threshold,
+          }
+        }
+      };
+
+      Self {
+        proposal_id: archived.proposal_id,
+        proposal,
+        status: archived.status,
+        votes_for: archived.votes_for.to_vec(),
+        votes_against: archived.votes_against.to_vec(),
+        submitted_at: archived.submitted_at,
+        resolved_at: archived.resolved_at,
+      }
+    }
+  }
+
+  /// Large withdrawal held until the end of its delay, before being added in the proposals queue
+  #[derive(
+    CloneNoBound, Encode, Decode, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo,
+  )]
+  #[scale_info(skip_type_params(T))]
+  pub struct DelayedWithdrawal<T: Config> {
+    /// Account who requested the withdrawal
+    pub account_id: T::AccountId,
+    /// Currency to withdraw
+    pub asset_id: CurrencyId,
+    /// Amount held during the delay
+    pub amount: Balance,
+    /// Withdrawal fee quoted at the request, deducted from the amount burned
+    pub fee: Balance,
+    /// External address where to send funds
+    pub external_address: BoundedVec<u8, <T as pallet::Config>::StringLimit>,
+    /// Block of the withdrawal request
+    pub requested_at: T::BlockNumber,
+    /// The withdrawal is added in the proposals queue from this block
+    pub release_at: T::BlockNumber,
+  }
+
+  /// Liveness of a quorum member, the inactive members are proposed for removal
+  #[derive(
+    CloneNoBound, Encode, Decode, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo,
+  )]
+  #[scale_info(skip_type_params(T))]
+  pub struct MemberLiveness<T: Config> {
+    /// Block of the last vote of the member, or of the start of the tracking
+    pub last_vote: T::BlockNumber,
+    /// Block of the last public keys submission of the member, or of the start of the tracking
+    pub last_keys_submission: T::BlockNumber,
+    /// Finalized proposals in a row without vote of the member
+    pub missed_proposals: u32,
+    /// Submission block of the oldest finalized proposal missed since the last activity
+    pub missed_since: Option<T::BlockNumber>,
+    /// Pending proposal to remove the member from the quorum
+    pub removal_proposal: Option<Hash>,
+  }
+
+  /// Confirmation of the external transfer of a burned withdrawal
+  #[derive(
+    CloneNoBound, Encode, Decode, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo,
+  )]
+  #[scale_info(skip_type_params(T))]
+  pub struct BurnedConfirmation<T: Config> {
+    /// Block when the funds were burned
+    pub burned_at: T::BlockNumber,
+    /// Withdrawal fee collected, refunded if the external transfer failed
+    pub fee: Balance,
+    /// Quorum member who initiated the external transfer
+    pub acknowledged_by: Option<T::AccountId>,
+    /// Members who confirmed the external transfer, with the external transaction ID
+    pub confirmations: BoundedVec<
+      (
+        T::AccountId,
+        BoundedVec<u8, <T as pallet::Config>::StringLimit>,
+      ),
+      T::VotesLimit,
+    >,
+    /// Members who reported the external transfer as failed
+    pub failures: BoundedVec<T::AccountId, T::VotesLimit>,
+    /// The confirmation timed out and can be resolved by the governance
+    pub escalated: bool,
+  }
+
   #[pallet::config]
   /// Configure the pallet by specifying the parameters and types on which it depends.
   pub trait Config:
@@ -111,17 +482,75 @@ pub mod pallet {
     #[pallet::constant]
     type PubkeyLimitPerAsset: Get<u32>;
 
+    /// The number of blocks of each phase of a key generation round
+    #[pallet::constant]
+    type KeygenPhaseTimeout: Get<Self::BlockNumber>;
+
+    /// The maximum length of the key generation commitments and shares
+    #[pallet::constant]
+    type KeygenPayloadLimit: Get<u32>;
+
+    /// The number of blocks a finalized proposal is kept in the archive
+    #[pallet::constant]
+    type ProposalArchiveRetention: Get<Self::BlockNumber>;
+
+    /// The origin which may decide on the watch list entries and freeze the watched accounts
+    type ComplianceOrigin: EnsureOrigin<Self::Origin>;
+
+    /// The number of blocks a processed mint is kept to reject the replays of its deposit
+    #[pallet::constant]
+    type MintReplayHorizon: Get<Self::BlockNumber>;
+
+    /// The number of blocks the large withdrawals are held before reaching the proposals queue
+    #[pallet::constant]
+    type WithdrawalDelay: Get<Self::BlockNumber>;
+
+    /// The number of blocks the quorum have to confirm the external transfer of a burned
+    /// withdrawal, before it's escalated to the force origin
+    #[pallet::constant]
+    type BurnedConfirmationTimeout: Get<Self::BlockNumber>;
+
+    /// The number of finalized proposals in a row a member can miss before being proposed for
+    /// removal
+    #[pallet::constant]
+    type MaxMissedProposals: Get<u32>;
+
+    /// The number of blocks without vote nor public keys submission, since the first proposal
+    /// missed, before a member is proposed for removal
+    #[pallet::constant]
+    type MemberInactivityPeriod: Get<Self::BlockNumber>;
+
+    /// The number of blocks the public keys of the removed members stay valid after a
+    /// configuration update
+    #[pallet::constant]
+    type MemberKeysGracePeriod: Get<Self::BlockNumber>;
+
+    /// The origin which may cancel the delayed withdrawals, along with the quorum members,
+    /// resolve the escalated burned withdrawals, and set the proposal thresholds and vote weights
+    type ForceOrigin: EnsureOrigin<Self::Origin>;
+
+    /// The account receiving the withdrawal fees
+    type WithdrawalFeesAccount: Get<Self::AccountId>;
+
     /// Asset registry traits
-    type AssetRegistry: AssetRegistryExt;
+    type AssetRegistry: AssetRegistryExt + WithdrawalLimitsExt<Self::AccountId>;
+
+    /// Fees traits, used to value the mints in USDT
+    type Fees: UsdtValueExt;
 
     /// Tidechain currency wrapper
     type CurrencyTidefi: Inspect<Self::AccountId, AssetId = CurrencyId, Balance = Balance>
       + Mutate<Self::AccountId, AssetId = CurrencyId, Balance = Balance>
-      + Transfer<Self::AccountId, AssetId = CurrencyId, Balance = Balance>;
+      + Transfer<Self::AccountId, AssetId = CurrencyId, Balance = Balance>
+      + MutateHold<Self::AccountId, AssetId = CurrencyId, Balance = Balance>;
   }
 
+  /// The current storage version.
+  const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
   #[pallet::pallet]
   #[pallet::generate_store(pub (super) trait Store)]
+  #[pallet::storage_version(STORAGE_VERSION)]
   pub struct Pallet<T>(_);
 
   /// Quorum status
@@ -159,11 +588,22 @@ pub mod pallet {
     >,
   >;
 
-  /// The threshold required for a proposal to process
+  /// The threshold of the last configuration update, the proposal thresholds default to it until
+  /// they are set
   #[pallet::storage]
   #[pallet::getter(fn threshold)]
   pub type Threshold<T: Config> = StorageValue<_, u16, ValueQuery>;
 
+  /// The votes weight required for each type of proposal to process
+  #[pallet::storage]
+  #[pallet::getter(fn thresholds)]
+  pub type Thresholds<T: Config> = StorageValue<_, ProposalThresholds>;
+
+  /// Vote weight of the members on the proposals, the members not listed have a weight of 1
+  #[pallet::storage]
+  #[pallet::getter(fn member_vote_weight)]
+  pub type MemberVoteWeights<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, u16>;
+
   /// Set of proposals for the Quorum
   #[pallet::storage]
   #[pallet::getter(fn proposals)]
@@ -200,6 +640,23 @@ pub mod pallet {
   #[pallet::getter(fn members)]
   pub type Members<T: Config> = CountedStorageMap<_, Blake2_128Concat, T::AccountId, bool>;
 
+  /// Liveness of the quorum members
+  #[pallet::storage]
+  #[pallet::getter(fn members_liveness)]
+  pub type MembersLiveness<T: Config> =
+    StorageMap<_, Blake2_128Concat, T::AccountId, MemberLiveness<T>>;
+
+  /// Pending proposals to remove an inactive member, with the inactive member
+  #[pallet::storage]
+  #[pallet::getter(fn inactive_member_removals)]
+  pub type InactiveMemberRemovals<T: Config> = StorageMap<_, Blake2_128Concat, Hash, T::AccountId>;
+
+  /// Members removed by a configuration update, with the block their public keys expire
+  #[pallet::storage]
+  #[pallet::getter(fn retiring_members)]
+  pub type RetiringMembers<T: Config> =
+    StorageMap<_, Blake2_128Concat, T::AccountId, T::BlockNumber>;
+
   /// Burned queue
   #[pallet::storage]
   #[pallet::getter(fn burned_queue)]
@@ -219,6 +676,203 @@ pub mod pallet {
     ValueQuery,
   >;
 
+  /// Confirmation of the burned withdrawals in queue, by proposal ID
+  #[pallet::storage]
+  #[pallet::getter(fn burned_confirmations)]
+  pub type BurnedConfirmations<T: Config> =
+    StorageMap<_, Blake2_128Concat, Hash, BurnedConfirmation<T>>;
+
+  /// Threshold group public key of each asset
+  #[pallet::storage]
+  #[pallet::getter(fn group_public_keys)]
+  pub type GroupPublicKeys<T: Config> = StorageMap<_, Blake2_128Concat, AssetId, GroupPublicKey<T>>;
+
+  /// Active key generation round of each asset
+  #[pallet::storage]
+  #[pallet::getter(fn keygen_rounds)]
+  pub type KeygenRounds<T: Config> = StorageMap<_, Blake2_128Concat, AssetId, KeygenRound<T>>;
+
+  /// Assets of the key generation rounds by the block their current phase ends
+  #[pallet::storage]
+  #[pallet::getter(fn keygen_deadlines)]
+  pub type KeygenDeadlines<T: Config> =
+    StorageDoubleMap<_, Twox64Concat, T::BlockNumber, Blake2_128Concat, AssetId, ()>;
+
+  /// Index of the last key generation round of each asset
+  #[pallet::storage]
+  #[pallet::getter(fn keygen_round_index)]
+  pub type KeygenRoundIndex<T: Config> = StorageMap<_, Blake2_128Concat, AssetId, u32, ValueQuery>;
+
+  /// Commitments published by the participants of the active round
+  #[pallet::storage]
+  #[pallet::getter(fn keygen_commitments)]
+  pub type KeygenCommitments<T: Config> = StorageDoubleMap<
+    _,
+    Blake2_128Concat,
+    AssetId,
+    Blake2_128Concat,
+    T::AccountId,
+    KeygenPayload<T>,
+  >;
+
+  /// Encrypted shares (`recipient`, `share`) distributed by the participants of the active round
+  #[pallet::storage]
+  #[pallet::getter(fn keygen_shares)]
+  pub type KeygenShares<T: Config> = StorageDoubleMap<
+    _,
+    Blake2_128Concat,
+    AssetId,
+    Blake2_128Concat,
+    T::AccountId,
+    BoundedVec<(T::AccountId, KeygenPayload<T>), T::VotesLimit>,
+  >;
+
+  /// Participants who complained about the shares of the accused participant
+  #[pallet::storage]
+  #[pallet::getter(fn keygen_complaints)]
+  pub type KeygenComplaints<T: Config> = StorageDoubleMap<
+    _,
+    Blake2_128Concat,
+    AssetId,
+    Blake2_128Concat,
+    T::AccountId,
+    KeygenParticipants<T>,
+    ValueQuery,
+  >;
+
+  /// Group public key computed by each participant of the active round
+  #[pallet::storage]
+  #[pallet::getter(fn keygen_confirmations)]
+  pub type KeygenConfirmations<T: Config> = StorageDoubleMap<
+    _,
+    Blake2_128Concat,
+    AssetId,
+    Blake2_128Concat,
+    T::AccountId,
+    BoundedVec<u8, <T as pallet::Config>::StringLimit>,
+  >;
+
+  /// Compliance decision (`decision`, `block_number`) of the watch list entries, by
+  /// (`currency_id`, `transaction_id`)
+  #[pallet::storage]
+  #[pallet::getter(fn watch_list_decisions)]
+  pub type WatchListDecisions<T: Config> = StorageDoubleMap<
+    _,
+    Blake2_128Concat,
+    T::AccountId,
+    Blake2_128Concat,
+    (
+      CurrencyId,
+      BoundedVec<u8, <T as pallet::Config>::StringLimit>,
+    ),
+    (WatchListDecision, T::BlockNumber),
+  >;
+
+  /// Accounts frozen while a review is open, with the block of the freeze. The wrapped assets
+  /// received after the freeze are locked by the `FrozenBalance` of the pallet.
+  #[pallet::storage]
+  #[pallet::getter(fn frozen_watched_accounts)]
+  pub type FrozenWatchedAccounts<T: Config> =
+    StorageMap<_, Blake2_128Concat, T::AccountId, T::BlockNumber>;
+
+  /// Wrapped assets of the account frozen while a review is open, with the block of the freeze
+  #[pallet::storage]
+  #[pallet::getter(fn frozen_account_assets)]
+  pub type FrozenAccountAssets<T: Config> =
+    StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, AssetId, T::BlockNumber>;
+
+  /// Deposits (`currency_id`, `transaction_id`) already processed, with the block of the mint
+  #[pallet::storage]
+  #[pallet::getter(fn processed_mints)]
+  pub type ProcessedMints<T: Config> = StorageDoubleMap<
+    _,
+    Blake2_128Concat,
+    CurrencyId,
+    Blake2_128Concat,
+    BoundedVec<u8, <T as pallet::Config>::StringLimit>,
+    T::BlockNumber,
+  >;
+
+  /// Processed deposits indexed by order of processing, used to prune `ProcessedMints`
+  #[pallet::storage]
+  #[pallet::getter(fn processed_mints_queue)]
+  pub type ProcessedMintsQueue<T: Config> = StorageMap<
+    _,
+    Blake2_128Concat,
+    u32,
+    (
+      CurrencyId,
+      BoundedVec<u8, <T as pallet::Config>::StringLimit>,
+    ),
+  >;
+
+  /// Index of the next processed deposit
+  #[pallet::storage]
+  #[pallet::getter(fn processed_mints_head)]
+  pub type ProcessedMintsHead<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+  /// Index of the oldest processed deposit, the previous ones have been pruned
+  #[pallet::storage]
+  #[pallet::getter(fn processed_mints_tail)]
+  pub type ProcessedMintsTail<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+  /// Archive of the finalized proposals, indexed by order of resolution
+  #[pallet::storage]
+  #[pallet::getter(fn proposal_archive)]
+  pub type ProposalArchive<T: Config> = StorageMap<_, Blake2_128Concat, u32, ArchivedProposal<T>>;
+
+  /// Index of the next archived proposal
+  #[pallet::storage]
+  #[pallet::getter(fn proposal_archive_head)]
+  pub type ProposalArchiveHead<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+  /// Index of the oldest archived proposal, the previous ones have been pruned
+  #[pallet::storage]
+  #[pallet::getter(fn proposal_archive_tail)]
+  pub type ProposalArchiveTail<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+  /// Withdrawal fee of each asset, no fee is taken when not set
+  #[pallet::storage]
+  #[pallet::getter(fn withdrawal_fees)]
+  pub type WithdrawalFees<T: Config> = StorageMap<_, Blake2_128Concat, CurrencyId, WithdrawalFee>;
+
+  /// Withdrawal fee submitted by the members for each asset, applied once the threshold agree
+  #[pallet::storage]
+  #[pallet::getter(fn withdrawal_fee_votes)]
+  pub type WithdrawalFeeVotes<T: Config> = StorageMap<
+    _,
+    Blake2_128Concat,
+    CurrencyId,
+    BoundedVec<(T::AccountId, WithdrawalFee), T::VotesLimit>,
+    ValueQuery,
+  >;
+
+  /// Withdrawal fee collected for each withdrawal proposal, refunded if it is not approved
+  #[pallet::storage]
+  #[pallet::getter(fn withdrawal_proposal_fees)]
+  pub type WithdrawalProposalFees<T: Config> = StorageMap<_, Blake2_128Concat, Hash, Balance>;
+
+  /// Large withdrawals held until the end of their delay
+  #[pallet::storage]
+  #[pallet::getter(fn delayed_withdrawals)]
+  pub type DelayedWithdrawals<T: Config> =
+    StorageMap<_, Blake2_128Concat, Hash, DelayedWithdrawal<T>>;
+
+  /// Delayed withdrawals indexed by order of request, used to release `DelayedWithdrawals`
+  #[pallet::storage]
+  #[pallet::getter(fn delayed_withdrawals_queue)]
+  pub type DelayedWithdrawalsQueue<T: Config> = StorageMap<_, Blake2_128Concat, u32, Hash>;
+
+  /// Index of the next delayed withdrawal
+  #[pallet::storage]
+  #[pallet::getter(fn delayed_withdrawals_head)]
+  pub type DelayedWithdrawalsHead<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+  /// Index of the oldest delayed withdrawal, the previous ones have been released or cancelled
+  #[pallet::storage]
+  #[pallet::getter(fn delayed_withdrawals_tail)]
+  pub type DelayedWithdrawalsTail<T: Config> = StorageValue<_, u32, ValueQuery>;
+
   /// Genesis configuration
   #[pallet::genesis_config]
   pub struct GenesisConfig<T: Config> {
@@ -226,8 +880,12 @@ pub mod pallet {
     pub enabled: bool,
     /// Quorum members
     pub members: Vec<T::AccountId>,
-    /// Quorum threshold to process a proposal
+    /// Quorum threshold of the configuration
     pub threshold: u16,
+    /// Quorum votes weight to process each type of proposal
+    pub proposal_thresholds: ProposalThresholds,
+    /// Vote weight of the members, the members not listed have a weight of 1
+    pub vote_weights: Vec<(T::AccountId, u16)>,
   }
 
   #[cfg(feature = "std")]
@@ -238,6 +896,8 @@ pub mod pallet {
         enabled: true,
         members: Vec::new(),
         threshold: 1,
+        proposal_thresholds: ProposalThresholds::from_threshold(1),
+        vote_weights: Vec::new(),
       }
     }
   }
@@ -246,10 +906,14 @@ pub mod pallet {
   impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
     fn build(&self) {
       Threshold::<T>::put(self.threshold);
+      Thresholds::<T>::put(self.proposal_thresholds);
       QuorumStatus::<T>::put(self.enabled);
       for account_id in self.members.clone() {
         Members::<T>::insert(account_id, true);
       }
+      for (account_id, weight) in self.vote_weights.iter() {
+        MemberVoteWeights::<T>::insert(account_id, weight);
+      }
     }
   }
 
@@ -285,11 +949,62 @@ pub mod pallet {
       account_id: T::AccountId,
       currency_id: CurrencyId,
       amount: Balance,
+      fee: Balance,
     },
 
     /// Quorum member acknowledged the burned and initiated the process
     BurnedAcknowledged { proposal_id: Hash },
 
+    /// Quorum member confirmed the external transfer of a burned withdrawal
+    BurnedConfirmationSubmitted {
+      proposal_id: Hash,
+      account_id: T::AccountId,
+      external_tx_id: Vec<u8>,
+    },
+
+    /// Quorum member reported the external transfer of a burned withdrawal as failed
+    BurnedFailureSubmitted {
+      proposal_id: Hash,
+      account_id: T::AccountId,
+    },
+
+    /// The external transfer of a burned withdrawal is confirmed
+    BurnedConfirmed {
+      proposal_id: Hash,
+      external_tx_id: Vec<u8>,
+    },
+
+    /// The external transfer of a burned withdrawal failed, the funds and the fee are
+    /// returned to the account
+    BurnedFailed {
+      proposal_id: Hash,
+      account_id: T::AccountId,
+      currency_id: CurrencyId,
+      amount: Balance,
+      fee: Balance,
+    },
+
+    /// The external transfer of a burned withdrawal wasn't confirmed in time, it's escalated to
+    /// the force origin
+    BurnedEscalated { proposal_id: Hash },
+
+    /// The votes weight required by each type of proposal changed
+    ProposalThresholdsChanged { thresholds: ProposalThresholds },
+
+    /// The vote weight of a member changed, `None` resets the weight to 1
+    MemberVoteWeightChanged {
+      account_id: T::AccountId,
+      weight: Option<u16>,
+    },
+
+    /// An inactive member is proposed for removal, the proposal is approved by `threshold`
+    /// active members at most
+    InactiveMemberRemovalProposed {
+      account_id: T::AccountId,
+      proposal_id: Hash,
+      threshold: u16,
+    },
+
     /// Member voted for a proposal
     VoteFor {
       account_id: T::AccountId,
@@ -314,61 +1029,301 @@ pub mod pallet {
     /// Proposal has been rejected
     ProposalRejected { proposal_id: Hash },
 
-    /// The quorum configuration has been updated, all elected members should re-submit public keys
+    /// The quorum configuration has been updated, the added members should submit their public
+    /// keys, the public keys of the removed members stay valid until the end of the grace period
     ConfigurationUpdated {
       members: Vec<T::AccountId>,
       threshold: u16,
+      added: Vec<T::AccountId>,
+      removed: Vec<T::AccountId>,
     },
-  }
 
-  // Errors inform users that something went wrong.
-  #[pallet::error]
-  pub enum Error<T> {
-    /// Asset is currently disabled or do not exist on chain
-    AssetDisabled,
-    /// The access to the Quorum pallet is not allowed for this account ID.
-    AccessDenied,
-    /// Unable to burn token.
-    BurnFailed,
-    /// Proposals cap exceeded, try again later.
-    ProposalsCapExceeded,
-    /// No proposal with the ID was found
-    ProposalDoesNotExist,
-    /// Proposal has either failed or succeeded
-    ProposalAlreadyComplete,
-    /// Lifetime of proposal has been exceeded
-    ProposalExpired,
-    /// Member already voted for this proposal
-    MemberAlreadyVoted,
-    /// Mint failed
-    MintFailed,
-    /// Invalid proposal
-    BadProposal,
-    /// Invalid public key
-    BadPublicKey,
-    /// Invalid transaction id
-    BadTransactionId,
-    /// Invalid external address
-    BadExternalAddress,
-    /// Burned queue cap reached
-    BurnedQueueOverflow,
-    /// Watchlist cap reached
-    WatchlistOverflow,
-    /// Members cap reached
-    MembersOverflow,
-    /// Votes cap reached for this proposal
+    /// The public keys of a removed member expired at the end of the grace period
+    MemberKeysExpired { account_id: T::AccountId },
+
+    /// A new key generation round started, the participants should publish their commitments
+    KeygenStarted {
+      asset_id: AssetId,
+      round: u32,
+      participants: Vec<T::AccountId>,
+      threshold: u16,
+      phase_end: T::BlockNumber,
+    },
+
+    /// The key generation round moved to the next phase
+    KeygenPhaseChanged {
+      asset_id: AssetId,
+      round: u32,
+      phase: KeygenPhase,
+      phase_end: T::BlockNumber,
+    },
+
+    /// Participant published the commitments
+    KeygenCommitmentSubmitted {
+      asset_id: AssetId,
+      round: u32,
+      account_id: T::AccountId,
+    },
+
+    /// Participant distributed the encrypted shares
+    KeygenSharesSubmitted {
+      asset_id: AssetId,
+      round: u32,
+      account_id: T::AccountId,
+    },
+
+    /// Participant complained about the shares received from `accused`
+    KeygenComplaintSubmitted {
+      asset_id: AssetId,
+      round: u32,
+      account_id: T::AccountId,
+      accused: T::AccountId,
+    },
+
+    /// Participant confirmed the group public key
+    KeygenConfirmed {
+      asset_id: AssetId,
+      round: u32,
+      account_id: T::AccountId,
+    },
+
+    /// The key generation round is completed and the group public key stored
+    KeygenCompleted {
+      asset_id: AssetId,
+      round: u32,
+      public_key: Vec<u8>,
+      participants: Vec<T::AccountId>,
+      threshold: u16,
+    },
+
+    /// The key generation round failed, the previous group public key is kept
+    KeygenFailed { asset_id: AssetId, round: u32 },
+
+    /// The held `Red` mint has been approved by the compliance and minted to the account
+    WatchListMintApproved {
+      account_id: T::AccountId,
+      currency_id: CurrencyId,
+      amount: Balance,
+      transaction_id: Vec<u8>,
+    },
+
+    /// The held `Red` mint has been rejected by the compliance
+    WatchListMintRejected {
+      account_id: T::AccountId,
+      currency_id: CurrencyId,
+      amount: Balance,
+      transaction_id: Vec<u8>,
+    },
+
+    /// The `Amber` watch list entry has been reviewed by the compliance
+    WatchListEntryReviewed {
+      account_id: T::AccountId,
+      currency_id: CurrencyId,
+      amount: Balance,
+      transaction_id: Vec<u8>,
+    },
+
+    /// The wrapped assets of the account are frozen until all the reviews are closed
+    WatchListAccountFrozen {
+      account_id: T::AccountId,
+      asset_ids: Vec<AssetId>,
+    },
+
+    /// All the reviews of the account are closed, the wrapped assets are thawed
+    WatchListAccountThawed {
+      account_id: T::AccountId,
+      asset_ids: Vec<AssetId>,
+    },
+
+    /// The delay of the withdrawal is over, the funds are released and the withdrawal is
+    /// added in the proposals queue
+    DelayedWithdrawalReleased {
+      withdrawal_id: Hash,
+      account_id: T::AccountId,
+      currency_id: CurrencyId,
+      amount: Balance,
+    },
+
+    /// Member submitted a new withdrawal fee for the asset
+    WithdrawalFeeSubmitted {
+      account_id: T::AccountId,
+      currency_id: CurrencyId,
+      fee: WithdrawalFee,
+    },
+
+    /// The threshold of members agreed on the withdrawal fee of the asset
+    WithdrawalFeeUpdated {
+      currency_id: CurrencyId,
+      fee: WithdrawalFee,
+    },
+
+    /// The delayed withdrawal has been cancelled and the funds are released
+    DelayedWithdrawalCancelled {
+      withdrawal_id: Hash,
+      account_id: T::AccountId,
+      currency_id: CurrencyId,
+      amount: Balance,
+    },
+
+    /// The delayed withdrawal can't be added in the proposals queue at the end of its delay,
+    /// the funds are released
+    DelayedWithdrawalFailed {
+      withdrawal_id: Hash,
+      account_id: T::AccountId,
+      currency_id: CurrencyId,
+      amount: Balance,
+    },
+  }
+
+  // Errors inform users that something went wrong.
+  #[pallet::error]
+  pub enum Error<T> {
+    /// Asset is currently disabled or do not exist on chain
+    AssetDisabled,
+    /// The access to the Quorum pallet is not allowed for this account ID.
+    AccessDenied,
+    /// Unable to burn token.
+    BurnFailed,
+    /// Proposals cap exceeded, try again later.
+    ProposalsCapExceeded,
+    /// No proposal with the ID was found
+    ProposalDoesNotExist,
+    /// Proposal has either failed or succeeded
+    ProposalAlreadyComplete,
+    /// Lifetime of proposal has been exceeded
+    ProposalExpired,
+    /// Member already voted for this proposal
+    MemberAlreadyVoted,
+    /// Mint failed
+    MintFailed,
+    /// Invalid proposal
+    BadProposal,
+    /// Invalid public key
+    BadPublicKey,
+    /// Invalid transaction id
+    BadTransactionId,
+    /// Invalid external address
+    BadExternalAddress,
+    /// Burned queue cap reached
+    BurnedQueueOverflow,
+    /// Watchlist cap reached
+    WatchlistOverflow,
+    /// Members cap reached
+    MembersOverflow,
+    /// Votes cap reached for this proposal
     VotesOverflow,
     /// Public keys cap reached for this asset id
     PublicKeysOverflow,
+    /// A key generation round is already in progress for this asset id
+    KeygenInProgress,
+    /// No key generation round in progress for this asset id
+    KeygenNotInProgress,
+    /// The key generation round or phase doesn't match the active round
+    KeygenBadRound,
+    /// Not enough quorum members to reach the threshold
+    KeygenNotEnoughParticipants,
+    /// The account is not a participant of the key generation round
+    KeygenNotParticipant,
+    /// The participant already submitted for this phase
+    KeygenAlreadySubmitted,
+    /// Invalid commitment or share payload
+    KeygenBadPayload,
+    /// Shares must be sent to each other participant once
+    KeygenBadShares,
+    /// No watch list entry with this transaction ID for the account
+    WatchListEntryNotFound,
+    /// The compliance already decided on this watch list entry
+    WatchListEntryAlreadyDecided,
+    /// The decision doesn't apply to the compliance level or the action of the entry
+    WatchListBadDecision,
+    /// The account has no watch list entry under review
+    WatchListNoOpenReview,
+    /// The wrapped assets of the account are already frozen
+    WatchListAccountAlreadyFrozen,
+    /// The account holds more wrapped assets than the asset count provided
+    WatchListBadAssetCount,
+    /// Unable to freeze the wrapped assets of the account
+    FreezeFailed,
+    /// The deposit has already been minted or is pending in another proposal
+    MintAlreadySubmitted,
+    /// No delayed withdrawal with this ID was found
+    DelayedWithdrawalNotFound,
+    /// Unable to hold or release the funds of the withdrawal
+    HoldFailed,
+    /// The withdrawal amount doesn't cover the withdrawal fee
+    WithdrawalBelowFee,
+    /// No burned withdrawal with this proposal ID in queue
+    BurnedNotFound,
+    /// The burned withdrawal is not escalated to the force origin yet
+    BurnedNotEscalated,
+    /// The burned withdrawal has already been acknowledged by a member
+    BurnedAlreadyAcknowledged,
+    /// The thresholds and the vote weights can't be zero, and the threshold of a configuration
+    /// can't be above the vote weight of its members
+    BadThreshold,
+    /// The account is not a quorum member
+    MemberNotFound,
+    /// The configuration contains the same member more than once
+    DuplicateMember,
     // Unknown error
     UnknownError,
   }
 
   #[pallet::hooks]
   impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+    fn on_runtime_upgrade() -> Weight {
+      migrations::migrate_to_v1::<T, Self>()
+    }
+
+    /// Move the key generation rounds to the next phase when the phase timed out
+    fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+      let db_weight = <T as frame_system::Config>::DbWeight::get();
+      let current_block = T::Security::get_current_block_count();
+      // read the current block and the rounds whose phase ends at this block
+      let mut weight_used = db_weight.reads(2);
+
+      let timed_out_assets: Vec<AssetId> = KeygenDeadlines::<T>::drain_prefix(current_block)
+        .map(|(asset_id, _)| asset_id)
+        .collect();
+
+      for asset_id in timed_out_assets {
+        // read the round and delete the deadline
+        weight_used = weight_used.saturating_add(db_weight.reads_writes(1, 1));
+        if let Some(round) = Self::keygen_rounds(asset_id) {
+          if current_block >= round.phase_end {
+            let participants = round.participants.len() as Weight;
+            Self::try_advance_keygen_round(asset_id, round, true);
+            weight_used = weight_used.saturating_add(
+              db_weight.reads_writes(participants.saturating_mul(4).saturating_add(1), 7),
+            );
+          }
+        }
+      }
+
+      weight_used
+    }
+
     /// Try to compute when chain is idle
     fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
-      Self::clean_proposal_queue_with_max_weight(remaining_weight)
+      let mut weight_used = Self::clean_proposal_queue_with_max_weight(remaining_weight);
+      weight_used = weight_used.saturating_add(Self::release_delayed_withdrawals_with_max_weight(
+        remaining_weight.saturating_sub(weight_used),
+      ));
+      weight_used = weight_used.saturating_add(Self::escalate_burned_with_max_weight(
+        remaining_weight.saturating_sub(weight_used),
+      ));
+      weight_used = weight_used.saturating_add(Self::check_members_liveness_with_max_weight(
+        remaining_weight.saturating_sub(weight_used),
+      ));
+      weight_used = weight_used.saturating_add(Self::expire_retiring_members_keys_with_max_weight(
+        remaining_weight.saturating_sub(weight_used),
+      ));
+      weight_used = weight_used.saturating_add(Self::prune_proposal_archive_with_max_weight(
+        remaining_weight.saturating_sub(weight_used),
+      ));
+      weight_used.saturating_add(Self::prune_processed_mints_with_max_weight(
+        remaining_weight.saturating_sub(weight_used),
+      ))
     }
   }
 
@@ -388,7 +1343,6 @@ pub mod pallet {
 
       // 3. Add the proposal in queue
       let current_block = T::Security::get_current_block_count();
-      let proposal_id = T::Security::get_unique_id(sender.clone());
 
       // Transform the proposal type to use bounded vector
       let proposal: ProposalType<
@@ -425,6 +1379,20 @@ pub mod pallet {
         ),
       };
 
+      // Make sure the deposit has not been minted or submitted in another proposal yet
+      if let ProposalType::Mint(mint) = &proposal {
+        ensure!(
+          !Self::is_mint_submitted(mint.currency_id, &mint.transaction_id),
+          Error::<T>::MintAlreadySubmitted
+        );
+      }
+
+      // Make sure the new configuration can be applied
+      if let ProposalType::UpdateConfiguration(members, threshold) = &proposal {
+        Self::ensure_valid_configuration(members, *threshold)?;
+      }
+
+      let proposal_id = T::Security::get_unique_id(sender);
       Proposals::<T>::try_append((proposal_id, current_block, proposal))
         .map_err(|_| Error::<T>::ProposalsCapExceeded)?;
 
@@ -436,7 +1404,8 @@ pub mod pallet {
     }
 
     /// Quorum member acknowledge to a proposal
-    #[pallet::weight(<T as pallet::Config>::WeightInfo::acknowledge_proposal())]
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::acknowledge_proposal()
+      .saturating_add(Pallet::<T>::record_missed_votes_weight()))]
     pub fn acknowledge_proposal(
       origin: OriginFor<T>,
       proposal: Hash,
@@ -455,6 +1424,9 @@ pub mod pallet {
     }
 
     /// Quorum member acknowledge a burned item and started the process.
+    ///
+    /// A burned withdrawal is acknowledged once, the members polling `BurnedQueue` skip the
+    /// withdrawals with `acknowledged_by` set in `BurnedConfirmations`.
     #[pallet::weight(<T as pallet::Config>::WeightInfo::acknowledge_burned())]
     pub fn acknowledge_burned(origin: OriginFor<T>, proposal: Hash) -> DispatchResultWithPostInfo {
       // 1. Make sure the request is signed by `account_id`
@@ -463,10 +1435,16 @@ pub mod pallet {
       // 2. Make sure this is a quorum member
       ensure!(Self::is_member_and_ready(&sender), Error::<T>::AccessDenied);
 
-      // 3. Remove from the queue
-      BurnedQueue::<T>::mutate(|burned_queue| {
-        burned_queue.retain(|(proposal_id, _)| *proposal_id != proposal);
-      });
+      // 3. Record the member who initiated the external transfer, the burned withdrawal stays in
+      // queue until the transfer is confirmed or failed
+      let mut confirmation =
+        Self::get_burned_confirmation(proposal).ok_or(Error::<T>::BurnedNotFound)?;
+      ensure!(
+        confirmation.acknowledged_by.is_none(),
+        Error::<T>::BurnedAlreadyAcknowledged
+      );
+      confirmation.acknowledged_by = Some(sender);
+      BurnedConfirmations::<T>::insert(proposal, confirmation);
 
       // 4. Emit event on chain
       Self::deposit_event(Event::<T>::BurnedAcknowledged {
@@ -478,7 +1456,8 @@ pub mod pallet {
     }
 
     /// Quorum member reject a proposal
-    #[pallet::weight(<T as pallet::Config>::WeightInfo::reject_proposal())]
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::reject_proposal()
+      .saturating_add(Pallet::<T>::record_missed_votes_weight()))]
     pub fn reject_proposal(origin: OriginFor<T>, proposal: Hash) -> DispatchResultWithPostInfo {
       // 1. Make sure the request is signed by `account_id`
       let sender = ensure_signed(origin)?;
@@ -526,107 +1505,856 @@ pub mod pallet {
         Self::add_public_keys_for_asset(&sender, asset_id, public_key)?;
       }
 
+      // 5. Record the liveness of the member
+      let current_block = T::Security::get_current_block_count();
+      let mut liveness = Self::get_member_liveness(&sender, current_block);
+      liveness.last_keys_submission = current_block;
+      liveness.missed_since = None;
+      MembersLiveness::<T>::insert(&sender, liveness);
+
       // Don't take tx fees on success
       Ok(Pays::No.into())
     }
-  }
-
-  // helper functions (not dispatchable)
-  impl<T: Config> Pallet<T> {
-    fn clean_proposal_queue_with_max_weight(max_weight: Weight) -> Weight {
-      let all_proposals = Proposals::<T>::get();
-      let mut weight_used = <T as frame_system::Config>::DbWeight::get().reads(1);
 
-      if all_proposals.len() == 0 {
-        return weight_used;
-      }
+    /// Quorum member start a key generation round of the threshold key of an asset
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::start_keygen())]
+    pub fn start_keygen(origin: OriginFor<T>, asset_id: AssetId) -> DispatchResultWithPostInfo {
+      // 1. Make sure the request is signed by `account_id`
+      let sender = ensure_signed(origin)?;
 
-      // The amount of remaining weight under which we stop processing messages
-      let threshold_weight = 100_000;
+      // 2. Make sure this is a quorum member
+      ensure!(Self::is_member(&sender), Error::<T>::AccessDenied);
 
-      // we create a shuffle of index, to prevent queue blocking
-      let mut shuffled = Self::create_shuffle(all_proposals.len());
-      let current_block = T::Security::get_current_block_count();
-      let proposal_lifetime = T::ProposalLifetime::get();
-      let mut shuffle_index = 0;
-      let mut weight_available = 0;
+      // 3. Make sure there is no round in progress
+      ensure!(
+        !KeygenRounds::<T>::contains_key(asset_id),
+        Error::<T>::KeygenInProgress
+      );
 
-      while shuffle_index < shuffled.len()
-        && max_weight.saturating_sub(weight_used) >= threshold_weight
-      {
-        let index = shuffled[shuffle_index];
-        let proposal_id = all_proposals[index].0;
-        let proposal_expiration = all_proposals[index].1 + proposal_lifetime;
+      // 4. Start the round with the current member set
+      Self::start_keygen_round(asset_id)?;
 
-        if weight_available != max_weight {
-          // The speed to which the available weight approaches the maximum weight. A lower number
-          // results in a faster progression. A value of 1 makes the entire weight available initially.
-          let weight_restrict_decay = 2;
-          // Get incrementally closer to freeing up max_weight for first round.
-          // For the second round we unlock all weight. If we come close enough
-          // on the first round to unlocking everything, then we do so.
-          if shuffle_index < all_proposals.len() {
-            weight_available += (max_weight - weight_available) / (weight_restrict_decay + 1);
-            if weight_available + threshold_weight > max_weight {
-              weight_available = max_weight;
-            }
-          } else {
-            weight_available = max_weight;
-          }
-        }
+      // Don't take tx fees on success
+      Ok(Pays::No.into())
+    }
 
-        let weight_processed = if current_block >= proposal_expiration {
-          // Delete proposal (1 write)
-          if let Err(_) = Self::delete_proposal(proposal_id) {
-            log!(error, "Can't delete proposal {}", proposal_id);
-          };
+    /// Participant publish the commitments of the key generation round
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::submit_keygen_commitment())]
+    pub fn submit_keygen_commitment(
+      origin: OriginFor<T>,
+      asset_id: AssetId,
+      round: u32,
+      commitment: Vec<u8>,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the request is signed by `account_id`
+      let sender = ensure_signed(origin)?;
 
-          // Delete all votes (1 write)
-          Votes::<T>::remove(&proposal_id);
+      // 2. Make sure this is a participant of the round in the commitment phase
+      let keygen_round =
+        Self::ensure_keygen_participant(&sender, asset_id, round, KeygenPhase::Commitment)?;
+      ensure!(
+        !KeygenCommitments::<T>::contains_key(asset_id, &sender),
+        Error::<T>::KeygenAlreadySubmitted
+      );
 
-          <T as frame_system::Config>::DbWeight::get().reads_writes(0, 2)
-        } else {
-          0
-        };
+      // 3. Store the commitment
+      let commitment: KeygenPayload<T> = commitment
+        .try_into()
+        .map_err(|_| Error::<T>::KeygenBadPayload)?;
+      KeygenCommitments::<T>::insert(asset_id, &sender, commitment);
 
-        weight_used += weight_processed;
+      // 4. Emit event on chain
+      Self::deposit_event(Event::<T>::KeygenCommitmentSubmitted {
+        asset_id,
+        round,
+        account_id: sender,
+      });
 
-        // If there are more and we're making progress, we process them after we've given the
-        // other channels a look in. If we've still not unlocked all weight, then we set them
-        // up for processing a second time anyway.
-        if current_block >= proposal_expiration
-          && (weight_processed > 0 || weight_available != max_weight)
-        {
-          if shuffle_index + 1 == shuffled.len() {
-            // Only this queue left. Just run around this loop once more.
-            continue;
-          }
-          shuffled.push(index);
-        }
-        shuffle_index += 1;
-      }
+      // 5. Move to the share phase if all the participants published their commitments
+      Self::try_advance_keygen_round(asset_id, keygen_round, false);
 
-      weight_used
+      // Don't take tx fees on success
+      Ok(Pays::No.into())
     }
 
-    // Delete all member public keys
-    fn delete_public_keys_for_account(who: &T::AccountId) -> Result<(), DispatchError> {
-      for asset_id in PublicKeys::<T>::iter_keys() {
-        PublicKeys::<T>::mutate(asset_id, |public_keys| {
-          public_keys.retain(|(account_id, _)| *account_id != *who);
-        });
+    /// Participant distribute the encrypted shares (`recipient`, `share`) to each other participant
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::submit_keygen_shares(shares.len() as u32))]
+    pub fn submit_keygen_shares(
+      origin: OriginFor<T>,
+      asset_id: AssetId,
+      round: u32,
+      shares: Vec<(T::AccountId, Vec<u8>)>,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the request is signed by `account_id`
+      let sender = ensure_signed(origin)?;
+
+      // 2. Make sure this is a participant of the round in the share phase, who published
+      // the commitments
+      let keygen_round =
+        Self::ensure_keygen_participant(&sender, asset_id, round, KeygenPhase::Share)?;
+      ensure!(
+        KeygenCommitments::<T>::contains_key(asset_id, &sender),
+        Error::<T>::KeygenNotParticipant
+      );
+      ensure!(
+        !KeygenShares::<T>::contains_key(asset_id, &sender),
+        Error::<T>::KeygenAlreadySubmitted
+      );
+
+      // 3. Make sure there is exactly one share for each other participant
+      ensure!(
+        shares.len().saturating_add(1) == keygen_round.participants.len(),
+        Error::<T>::KeygenBadShares
+      );
+      let mut bounded_shares: BoundedVec<(T::AccountId, KeygenPayload<T>), T::VotesLimit> =
+        Default::default();
+      for (recipient, share) in shares {
+        ensure!(
+          recipient != sender
+            && keygen_round.participants.contains(&recipient)
+            && !bounded_shares
+              .iter()
+              .any(|(account_id, _)| *account_id == recipient),
+          Error::<T>::KeygenBadShares
+        );
+        let share: KeygenPayload<T> = share.try_into().map_err(|_| Error::<T>::KeygenBadPayload)?;
+        bounded_shares
+          .try_push((recipient, share))
+          .map_err(|_| Error::<T>::KeygenBadShares)?;
       }
-      Ok(())
+
+      // 4. Store the shares
+      KeygenShares::<T>::insert(asset_id, &sender, bounded_shares);
+
+      // 5. Emit event on chain
+      Self::deposit_event(Event::<T>::KeygenSharesSubmitted {
+        asset_id,
+        round,
+        account_id: sender,
+      });
+
+      // 6. Move to the complaint phase if all the participants distributed their shares
+      Self::try_advance_keygen_round(asset_id, keygen_round, false);
+
+      // Don't take tx fees on success
+      Ok(Pays::No.into())
     }
 
-    // Add member public key for a specific asset id
-    fn add_public_keys_for_asset(
-      who: &T::AccountId,
+    /// Participant complain about the invalid share received from `accused`
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::submit_keygen_complaint())]
+    pub fn submit_keygen_complaint(
+      origin: OriginFor<T>,
       asset_id: AssetId,
-      public_key: Vec<u8>,
-    ) -> Result<(), DispatchError> {
-      let final_public_key = public_key
-        .try_into()
+      round: u32,
+      accused: T::AccountId,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the request is signed by `account_id`
+      let sender = ensure_signed(origin)?;
+
+      // 2. Make sure this is a participant of the round in the complaint phase
+      Self::ensure_keygen_participant(&sender, asset_id, round, KeygenPhase::Complaint)?;
+
+      // 3. Make sure the accused distributed shares
+      ensure!(
+        sender != accused && KeygenShares::<T>::contains_key(asset_id, &accused),
+        Error::<T>::KeygenNotParticipant
+      );
+
+      // 4. Record the complaint
+      KeygenComplaints::<T>::try_mutate(asset_id, &accused, |complaints| {
+        ensure!(
+          !complaints.contains(&sender),
+          Error::<T>::KeygenAlreadySubmitted
+        );
+        complaints
+          .try_push(sender.clone())
+          .map_err(|_| Error::<T>::VotesOverflow)
+      })?;
+
+      // 5. Emit event on chain
+      Self::deposit_event(Event::<T>::KeygenComplaintSubmitted {
+        asset_id,
+        round,
+        account_id: sender,
+        accused,
+      });
+
+      // Don't take tx fees on success
+      Ok(Pays::No.into())
+    }
+
+    /// Participant confirm the group public key computed from the shares received.
+    ///
+    /// The round is completed at the end of the complaint phase, the participants can still
+    /// complain after confirming the key.
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::confirm_keygen())]
+    pub fn confirm_keygen(
+      origin: OriginFor<T>,
+      asset_id: AssetId,
+      round: u32,
+      public_key: Vec<u8>,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the request is signed by `account_id`
+      let sender = ensure_signed(origin)?;
+
+      // 2. Make sure this is a participant of the round in the complaint phase, who
+      // distributed shares
+      Self::ensure_keygen_participant(&sender, asset_id, round, KeygenPhase::Complaint)?;
+      ensure!(
+        KeygenShares::<T>::contains_key(asset_id, &sender),
+        Error::<T>::KeygenNotParticipant
+      );
+      ensure!(
+        !KeygenConfirmations::<T>::contains_key(asset_id, &sender),
+        Error::<T>::KeygenAlreadySubmitted
+      );
+
+      // 3. Record the group public key
+      let public_key: BoundedVec<u8, <T as pallet::Config>::StringLimit> = public_key
+        .try_into()
+        .map_err(|_| Error::<T>::BadPublicKey)?;
+      KeygenConfirmations::<T>::insert(asset_id, &sender, public_key);
+
+      // 4. Emit event on chain
+      Self::deposit_event(Event::<T>::KeygenConfirmed {
+        asset_id,
+        round,
+        account_id: sender,
+      });
+
+      // Don't take tx fees on success
+      Ok(Pays::No.into())
+    }
+
+    /// Compliance approve a held `Red` mint of the watch list, the amount is minted to the account.
+    ///
+    /// - `account_id`: Account of the watch list entry
+    /// - `currency_id`: Currency of the mint
+    /// - `transaction_id`: Transaction ID of the mint
+    ///
+    /// Emits `WatchListMintApproved` event when successful, and `WatchListAccountThawed` when
+    /// it was the last review open of a frozen account.
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::approve_watch_list_mint())]
+    pub fn approve_watch_list_mint(
+      origin: OriginFor<T>,
+      account_id: T::AccountId,
+      currency_id: CurrencyId,
+      transaction_id: Vec<u8>,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure this is called by the compliance origin
+      T::ComplianceOrigin::ensure_origin(origin)?;
+
+      // 2. Make sure this is a `Red` mint under review
+      let (transaction_id, watch_list) = Self::ensure_open_watch_list_entry(
+        &account_id,
+        currency_id,
+        transaction_id,
+        ComplianceLevel::Red,
+      )?;
+      ensure!(
+        watch_list.watch_action == WatchListAction::Mint,
+        Error::<T>::WatchListBadDecision
+      );
+
+      // 3. Mint the held deposit
+      ensure!(
+        T::AssetRegistry::is_currency_enabled(watch_list.currency_id),
+        Error::<T>::AssetDisabled
+      );
+      T::CurrencyTidefi::mint_into(watch_list.currency_id, &account_id, watch_list.amount)
+        .map_err(|_| Error::<T>::MintFailed)?;
+
+      // 4. Record the decision
+      WatchListDecisions::<T>::insert(
+        &account_id,
+        (currency_id, &transaction_id),
+        (
+          WatchListDecision::Approved,
+          T::Security::get_current_block_count(),
+        ),
+      );
+
+      // 5. Emit event on chain
+      Self::deposit_event(Event::<T>::WatchListMintApproved {
+        account_id: account_id.clone(),
+        currency_id: watch_list.currency_id,
+        amount: watch_list.amount,
+        transaction_id: transaction_id.to_vec(),
+      });
+
+      // 6. Thaw the account if there is no more review open
+      Self::try_thaw_watched_account(&account_id);
+
+      Ok(().into())
+    }
+
+    /// Compliance reject a held `Red` mint of the watch list, the amount is never minted.
+    ///
+    /// - `account_id`: Account of the watch list entry
+    /// - `currency_id`: Currency of the mint
+    /// - `transaction_id`: Transaction ID of the mint
+    ///
+    /// Emits `WatchListMintRejected` event when successful, and `WatchListAccountThawed` when
+    /// it was the last review open of a frozen account.
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::reject_watch_list_mint())]
+    pub fn reject_watch_list_mint(
+      origin: OriginFor<T>,
+      account_id: T::AccountId,
+      currency_id: CurrencyId,
+      transaction_id: Vec<u8>,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure this is called by the compliance origin
+      T::ComplianceOrigin::ensure_origin(origin)?;
+
+      // 2. Make sure this is a `Red` mint under review
+      let (transaction_id, watch_list) = Self::ensure_open_watch_list_entry(
+        &account_id,
+        currency_id,
+        transaction_id,
+        ComplianceLevel::Red,
+      )?;
+      ensure!(
+        watch_list.watch_action == WatchListAction::Mint,
+        Error::<T>::WatchListBadDecision
+      );
+
+      // 3. Record the decision
+      WatchListDecisions::<T>::insert(
+        &account_id,
+        (currency_id, &transaction_id),
+        (
+          WatchListDecision::Rejected,
+          T::Security::get_current_block_count(),
+        ),
+      );
+
+      // 4. Emit event on chain
+      Self::deposit_event(Event::<T>::WatchListMintRejected {
+        account_id: account_id.clone(),
+        currency_id: watch_list.currency_id,
+        amount: watch_list.amount,
+        transaction_id: transaction_id.to_vec(),
+      });
+
+      // 5. Thaw the account if there is no more review open
+      Self::try_thaw_watched_account(&account_id);
+
+      Ok(().into())
+    }
+
+    /// Compliance mark an `Amber` entry of the watch list as reviewed.
+    ///
+    /// - `account_id`: Account of the watch list entry
+    /// - `currency_id`: Currency of the entry
+    /// - `transaction_id`: Transaction ID of the entry
+    ///
+    /// Emits `WatchListEntryReviewed` event when successful, and `WatchListAccountThawed` when
+    /// it was the last review open of a frozen account.
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::review_watch_list_entry())]
+    pub fn review_watch_list_entry(
+      origin: OriginFor<T>,
+      account_id: T::AccountId,
+      currency_id: CurrencyId,
+      transaction_id: Vec<u8>,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure this is called by the compliance origin
+      T::ComplianceOrigin::ensure_origin(origin)?;
+
+      // 2. Make sure this is an `Amber` entry under review
+      let (transaction_id, watch_list) = Self::ensure_open_watch_list_entry(
+        &account_id,
+        currency_id,
+        transaction_id,
+        ComplianceLevel::Amber,
+      )?;
+
+      // 3. Record the decision
+      WatchListDecisions::<T>::insert(
+        &account_id,
+        (currency_id, &transaction_id),
+        (
+          WatchListDecision::Reviewed,
+          T::Security::get_current_block_count(),
+        ),
+      );
+
+      // 4. Emit event on chain
+      Self::deposit_event(Event::<T>::WatchListEntryReviewed {
+        account_id: account_id.clone(),
+        currency_id: watch_list.currency_id,
+        amount: watch_list.amount,
+        transaction_id: transaction_id.to_vec(),
+      });
+
+      // 5. Thaw the account if there is no more review open
+      Self::try_thaw_watched_account(&account_id);
+
+      Ok(().into())
+    }
+
+    /// Compliance freeze all the wrapped assets of an account with a review open, the assets
+    /// are thawed when the last review of the account is closed. The wrapped assets received
+    /// after the freeze can't be transferred either.
+    ///
+    /// - `account_id`: Account to freeze
+    /// - `asset_count`: Upper bound of the number of wrapped assets held by the account
+    ///
+    /// Emits `WatchListAccountFrozen` event when successful.
+    ///
+    /// Weight: `O(n)` where `n` is the number of wrapped assets held by the account
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::freeze_watched_account(*asset_count))]
+    pub fn freeze_watched_account(
+      origin: OriginFor<T>,
+      account_id: T::AccountId,
+      asset_count: u32,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure this is called by the compliance origin
+      T::ComplianceOrigin::ensure_origin(origin)?;
+
+      // 2. Make sure the account has a review open and is not frozen yet
+      ensure!(
+        Self::has_open_watch_list_review(&account_id),
+        Error::<T>::WatchListNoOpenReview
+      );
+      ensure!(
+        !FrozenWatchedAccounts::<T>::contains_key(&account_id),
+        Error::<T>::WatchListAccountAlreadyFrozen
+      );
+
+      // 3. Make sure the weight paid covers all the wrapped assets held by the account
+      let asset_ids: Vec<AssetId> = pallet_assets::Account::<T>::iter_prefix(&account_id)
+        .map(|(asset_id, _)| asset_id)
+        .collect();
+      let held_assets = asset_ids.len() as u32;
+      ensure!(
+        held_assets <= asset_count,
+        Error::<T>::WatchListBadAssetCount
+      );
+
+      // 4. Freeze all the wrapped assets held by the account, the assets received later are
+      // locked by the `FrozenBalance` as long as the account is frozen
+      let current_block = T::Security::get_current_block_count();
+      for asset_id in asset_ids.iter() {
+        pallet_assets::Pallet::<T>::do_freeze(*asset_id, &account_id)
+          .map_err(|_| Error::<T>::FreezeFailed)?;
+        FrozenAccountAssets::<T>::insert(&account_id, asset_id, current_block);
+      }
+      FrozenWatchedAccounts::<T>::insert(&account_id, current_block);
+
+      // 5. Emit event on chain
+      Self::deposit_event(Event::<T>::WatchListAccountFrozen {
+        account_id,
+        asset_ids,
+      });
+
+      Ok(
+        Some(<T as pallet::Config>::WeightInfo::freeze_watched_account(
+          held_assets,
+        ))
+        .into(),
+      )
+    }
+
+    /// Quorum member submit the withdrawal fee of an asset, the fee is updated once the
+    /// configuration votes weight of members submitted the same fee.
+    ///
+    /// - `currency_id`: The asset of the fee
+    /// - `fee`: The flat and the percentage fee of the withdrawals
+    ///
+    /// Emits `WithdrawalFeeSubmitted` event when successful, and `WithdrawalFeeUpdated` when
+    /// the threshold is reached.
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::submit_withdrawal_fee())]
+    pub fn submit_withdrawal_fee(
+      origin: OriginFor<T>,
+      currency_id: CurrencyId,
+      fee: WithdrawalFee,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the request is signed by `account_id`
+      let sender = ensure_signed(origin)?;
+
+      // 2. Make sure this is a quorum member
+      ensure!(Self::is_member_and_ready(&sender), Error::<T>::AccessDenied);
+
+      // 3. Replace the previous submission of the member
+      let votes_weight = WithdrawalFeeVotes::<T>::try_mutate(currency_id, |votes| {
+        votes.retain(|(account_id, _)| *account_id != sender && Self::is_member(account_id));
+        votes
+          .try_push((sender.clone(), fee))
+          .map_err(|_| Error::<T>::VotesOverflow)?;
+        Ok::<u16, Error<T>>(Self::members_vote_weight(
+          votes
+            .iter()
+            .filter(|(_, submitted_fee)| *submitted_fee == fee)
+            .map(|(account_id, _)| account_id.clone()),
+        ))
+      })?;
+
+      Self::deposit_event(Event::<T>::WithdrawalFeeSubmitted {
+        account_id: sender,
+        currency_id,
+        fee,
+      });
+
+      // 4. Update the fee once the configuration threshold of members agree
+      if votes_weight >= Self::proposal_thresholds().update_configuration {
+        WithdrawalFees::<T>::insert(currency_id, fee);
+        WithdrawalFeeVotes::<T>::remove(currency_id);
+        Self::deposit_event(Event::<T>::WithdrawalFeeUpdated { currency_id, fee });
+      }
+
+      // Don't take tx fees on success
+      Ok(Pays::No.into())
+    }
+
+    /// Governance or quorum member cancel a delayed withdrawal before the end of its delay,
+    /// the funds are released to the account.
+    ///
+    /// - `withdrawal_id`: Delayed withdrawal ID
+    ///
+    /// Emits `DelayedWithdrawalCancelled` event when successful.
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::cancel_delayed_withdrawal())]
+    pub fn cancel_delayed_withdrawal(
+      origin: OriginFor<T>,
+      withdrawal_id: Hash,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure this is called by the force origin or a quorum member
+      if T::ForceOrigin::ensure_origin(origin.clone()).is_err() {
+        let sender = ensure_signed(origin)?;
+        ensure!(Self::is_member(&sender), Error::<T>::AccessDenied);
+      }
+
+      // 2. Make sure the withdrawal is still delayed
+      let withdrawal =
+        Self::delayed_withdrawals(withdrawal_id).ok_or(Error::<T>::DelayedWithdrawalNotFound)?;
+
+      // 3. Release the funds, the queue index is skipped once the delay is over
+      T::CurrencyTidefi::release(
+        withdrawal.asset_id,
+        &withdrawal.account_id,
+        withdrawal.amount,
+        false,
+      )
+      .map_err(|_| Error::<T>::HoldFailed)?;
+      DelayedWithdrawals::<T>::remove(withdrawal_id);
+
+      // 4. The cancelled withdrawal doesn't count in the withdrawal caps
+      T::AssetRegistry::unregister_withdrawal(
+        &withdrawal.account_id,
+        withdrawal.asset_id,
+        withdrawal.amount,
+      );
+
+      // 5. Emit event on chain
+      Self::deposit_event(Event::<T>::DelayedWithdrawalCancelled {
+        withdrawal_id,
+        account_id: withdrawal.account_id,
+        currency_id: withdrawal.asset_id,
+        amount: withdrawal.amount,
+      });
+
+      // don't take tx fees on success
+      Ok(Pays::No.into())
+    }
+
+    /// Quorum member confirm the external transfer of a burned withdrawal. The withdrawal is
+    /// removed from the burned queue once the withdrawal votes weight of members confirmed the
+    /// same external transaction ID. The force origin can confirm an escalated burned withdrawal.
+    ///
+    /// - `proposal_id`: Proposal ID of the burned withdrawal
+    /// - `external_tx_id`: Transaction ID of the transfer on the external chain
+    ///
+    /// Emits `BurnedConfirmationSubmitted` event when successful, and `BurnedConfirmed` event
+    /// once the threshold is reached.
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::confirm_burned())]
+    pub fn confirm_burned(
+      origin: OriginFor<T>,
+      proposal_id: Hash,
+      external_tx_id: Vec<u8>,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the burned withdrawal is in queue
+      let mut confirmation =
+        Self::get_burned_confirmation(proposal_id).ok_or(Error::<T>::BurnedNotFound)?;
+      let external_tx_id: BoundedVec<u8, <T as pallet::Config>::StringLimit> = external_tx_id
+        .try_into()
+        .map_err(|_| Error::<T>::BadTransactionId)?;
+
+      // 2. The force origin can confirm the escalated burned withdrawals
+      if T::ForceOrigin::ensure_origin(origin.clone()).is_ok() {
+        ensure!(confirmation.escalated, Error::<T>::BurnedNotEscalated);
+        Self::confirm_burned_withdrawal(proposal_id, external_tx_id);
+        return Ok(().into());
+      }
+
+      // 3. Make sure this is a quorum member who didn't vote yet
+      let sender = ensure_signed(origin)?;
+      ensure!(Self::is_member_and_ready(&sender), Error::<T>::AccessDenied);
+      ensure!(
+        !Self::has_voted_burned(&confirmation, &sender),
+        Error::<T>::MemberAlreadyVoted
+      );
+
+      // 4. Register the confirmation
+      confirmation
+        .confirmations
+        .try_push((sender.clone(), external_tx_id.clone()))
+        .map_err(|_| Error::<T>::VotesOverflow)?;
+
+      Self::deposit_event(Event::<T>::BurnedConfirmationSubmitted {
+        proposal_id,
+        account_id: sender,
+        external_tx_id: external_tx_id.to_vec(),
+      });
+
+      // 5. Confirm once the withdrawal threshold of members agree on the external transaction ID
+      let confirmations_weight = Self::members_vote_weight(
+        confirmation
+          .confirmations
+          .iter()
+          .filter(|(_, tx_id)| *tx_id == external_tx_id)
+          .map(|(account_id, _)| account_id.clone()),
+      );
+      if confirmations_weight >= Self::proposal_thresholds().withdrawal {
+        Self::confirm_burned_withdrawal(proposal_id, external_tx_id);
+      } else {
+        BurnedConfirmations::<T>::insert(proposal_id, confirmation);
+      }
+
+      // don't take tx fees on success
+      Ok(Pays::No.into())
+    }
+
+    /// Quorum member report the external transfer of a burned withdrawal as failed. Once the
+    /// withdrawal votes weight of members reported the failure, the burned funds are minted back
+    /// to the account and the withdrawal fee is refunded. The force origin can fail an escalated
+    /// burned withdrawal.
+    ///
+    /// - `proposal_id`: Proposal ID of the burned withdrawal
+    ///
+    /// Emits `BurnedFailureSubmitted` event when successful, and `BurnedFailed` event once the
+    /// threshold is reached.
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::fail_burned())]
+    #[transactional]
+    pub fn fail_burned(origin: OriginFor<T>, proposal_id: Hash) -> DispatchResultWithPostInfo {
+      // 1. Make sure the burned withdrawal is in queue
+      let mut confirmation =
+        Self::get_burned_confirmation(proposal_id).ok_or(Error::<T>::BurnedNotFound)?;
+
+      // 2. The force origin can fail the escalated burned withdrawals
+      if T::ForceOrigin::ensure_origin(origin.clone()).is_ok() {
+        ensure!(confirmation.escalated, Error::<T>::BurnedNotEscalated);
+        Self::fail_burned_withdrawal(proposal_id, confirmation.fee)?;
+        return Ok(().into());
+      }
+
+      // 3. Make sure this is a quorum member who didn't vote yet
+      let sender = ensure_signed(origin)?;
+      ensure!(Self::is_member_and_ready(&sender), Error::<T>::AccessDenied);
+      ensure!(
+        !Self::has_voted_burned(&confirmation, &sender),
+        Error::<T>::MemberAlreadyVoted
+      );
+
+      // 4. Register the failure
+      confirmation
+        .failures
+        .try_push(sender.clone())
+        .map_err(|_| Error::<T>::VotesOverflow)?;
+
+      Self::deposit_event(Event::<T>::BurnedFailureSubmitted {
+        proposal_id,
+        account_id: sender,
+      });
+
+      // 5. Return the funds once the withdrawal threshold of members reported the failure
+      let failures_weight = Self::members_vote_weight(confirmation.failures.iter().cloned());
+      if failures_weight >= Self::proposal_thresholds().withdrawal {
+        Self::fail_burned_withdrawal(proposal_id, confirmation.fee)?;
+      } else {
+        BurnedConfirmations::<T>::insert(proposal_id, confirmation);
+      }
+
+      // don't take tx fees on success
+      Ok(Pays::No.into())
+    }
+
+    /// Set the votes weight required by each type of proposal.
+    ///
+    /// - `thresholds`: Votes weight required by the mints, the mints valued above the large mint
+    /// value in USDT, the withdrawals and the configuration updates
+    ///
+    /// Emits `ProposalThresholdsChanged` event when successful.
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::set_proposal_thresholds())]
+    pub fn set_proposal_thresholds(
+      origin: OriginFor<T>,
+      thresholds: ProposalThresholds,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure this is called by the force origin
+      T::ForceOrigin::ensure_origin(origin)?;
+
+      // 2. Make sure every type of proposal needs at least one vote
+      ensure!(
+        thresholds.mint > 0
+          && thresholds.large_mint > 0
+          && thresholds.withdrawal > 0
+          && thresholds.update_configuration > 0,
+        Error::<T>::BadThreshold
+      );
+
+      // 3. Update the thresholds
+      Thresholds::<T>::put(thresholds);
+
+      // 4. Emit event on chain
+      Self::deposit_event(Event::<T>::ProposalThresholdsChanged { thresholds });
+
+      Ok(().into())
+    }
+
+    /// Set the vote weight of a quorum member on the proposals.
+    ///
+    /// - `account_id`: Quorum member
+    /// - `weight`: Vote weight of the member, `None` resets the weight to 1
+    ///
+    /// Emits `MemberVoteWeightChanged` event when successful.
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::set_member_vote_weight())]
+    pub fn set_member_vote_weight(
+      origin: OriginFor<T>,
+      account_id: T::AccountId,
+      weight: Option<u16>,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure this is called by the force origin
+      T::ForceOrigin::ensure_origin(origin)?;
+
+      // 2. Make sure this is a quorum member
+      ensure!(Self::is_member(&account_id), Error::<T>::MemberNotFound);
+
+      // 3. Update the vote weight
+      match weight {
+        Some(weight) => {
+          ensure!(weight > 0, Error::<T>::BadThreshold);
+          MemberVoteWeights::<T>::insert(&account_id, weight);
+        }
+        None => MemberVoteWeights::<T>::remove(&account_id),
+      }
+
+      // 4. Emit event on chain
+      Self::deposit_event(Event::<T>::MemberVoteWeightChanged { account_id, weight });
+
+      Ok(().into())
+    }
+  }
+
+  // helper functions (not dispatchable)
+  impl<T: Config> Pallet<T> {
+    fn clean_proposal_queue_with_max_weight(max_weight: Weight) -> Weight {
+      let all_proposals = Proposals::<T>::get();
+      let mut weight_used = <T as frame_system::Config>::DbWeight::get().reads(1);
+
+      if all_proposals.len() == 0 {
+        return weight_used;
+      }
+
+      // The amount of remaining weight under which we stop processing messages
+      let threshold_weight = 100_000;
+
+      // we create a shuffle of index, to prevent queue blocking
+      let mut shuffled = Self::create_shuffle(all_proposals.len());
+      let current_block = T::Security::get_current_block_count();
+      let proposal_lifetime = T::ProposalLifetime::get();
+      let mut shuffle_index = 0;
+      let mut weight_available = 0;
+
+      while shuffle_index < shuffled.len()
+        && max_weight.saturating_sub(weight_used) >= threshold_weight
+      {
+        let index = shuffled[shuffle_index];
+        let proposal_id = all_proposals[index].0;
+        let proposal_expiration = all_proposals[index].1 + proposal_lifetime;
+
+        if weight_available != max_weight {
+          // The speed to which the available weight approaches the maximum weight. A lower number
+          // results in a faster progression. A value of 1 makes the entire weight available initially.
+          let weight_restrict_decay = 2;
+          // Get incrementally closer to freeing up max_weight for first round.
+          // For the second round we unlock all weight. If we come close enough
+          // on the first round to unlocking everything, then we do so.
+          if shuffle_index < all_proposals.len() {
+            weight_available += (max_weight - weight_available) / (weight_restrict_decay + 1);
+            if weight_available + threshold_weight > max_weight {
+              weight_available = max_weight;
+            }
+          } else {
+            weight_available = max_weight;
+          }
+        }
+
+        let weight_processed = if current_block >= proposal_expiration {
+          // Refund the withdrawal fee (1 read, 3 writes)
+          Self::refund_withdrawal_fee(proposal_id);
+
+          // Archive proposal with the votes (2 reads, 2 writes)
+          let (votes_for, votes_against) = Votes::<T>::get(&proposal_id)
+            .map(|votes| (votes.votes_for, votes.votes_against))
+            .unwrap_or_default();
+          Self::archive_proposal(
+            proposal_id,
+            ProposalResolution::Expired,
+            votes_for,
+            votes_against,
+          );
+
+          // Delete proposal (1 write)
+          if let Err(_) = Self::delete_proposal(proposal_id) {
+            log!(error, "Can't delete proposal {}", proposal_id);
+          };
+
+          // Delete all votes (1 write)
+          Votes::<T>::remove(&proposal_id);
+
+          <T as frame_system::Config>::DbWeight::get()
+            .reads_writes(3, 7)
+            .saturating_add(Self::record_missed_votes_weight())
+        } else {
+          0
+        };
+
+        weight_used += weight_processed;
+
+        // If there are more and we're making progress, we process them after we've given the
+        // other channels a look in. If we've still not unlocked all weight, then we set them
+        // up for processing a second time anyway.
+        if current_block >= proposal_expiration
+          && (weight_processed > 0 || weight_available != max_weight)
+        {
+          if shuffle_index + 1 == shuffled.len() {
+            // Only this queue left. Just run around this loop once more.
+            continue;
+          }
+          shuffled.push(index);
+        }
+        shuffle_index += 1;
+      }
+
+      weight_used
+    }
+
+    // Delete all member public keys
+    fn delete_public_keys_for_account(who: &T::AccountId) -> Result<(), DispatchError> {
+      for asset_id in PublicKeys::<T>::iter_keys() {
+        PublicKeys::<T>::mutate(asset_id, |public_keys| {
+          public_keys.retain(|(account_id, _)| *account_id != *who);
+        });
+      }
+      Ok(())
+    }
+
+    // Add member public key for a specific asset id
+    fn add_public_keys_for_asset(
+      who: &T::AccountId,
+      asset_id: AssetId,
+      public_key: Vec<u8>,
+    ) -> Result<(), DispatchError> {
+      let final_public_key = public_key
+        .try_into()
         .map_err(|_| Error::<T>::BadPublicKey)?;
       PublicKeys::<T>::try_mutate(asset_id, |public_keys| {
         // Prevent duplicate for the same member / asset id pubkey
@@ -639,307 +2367,1635 @@ pub mod pallet {
       })
     }
 
-    // Create a shuffled vector the size of `len` with random keys
-    pub(crate) fn create_shuffle(len: usize) -> Vec<usize> {
-      // Create a shuffled order for use to iterate through.
-      // Not a great random seed, but good enough for our purposes.
-      let seed = frame_system::Pallet::<T>::parent_hash();
-      let seed = <[u8; 32]>::decode(&mut sp_runtime::traits::TrailingZeroInput::new(
-        seed.as_ref(),
-      ))
-      .expect("input is padded with zeroes; qed");
-      let mut rng = ChaChaRng::from_seed(seed);
-      let mut shuffled = (0..len).collect::<Vec<_>>();
-      for i in 0..len {
-        let j = (rng.next_u32() as usize) % len;
-        let a = shuffled[i];
-        shuffled[i] = shuffled[j];
-        shuffled[j] = a;
+    // Create a shuffled vector the size of `len` with random keys
+    pub(crate) fn create_shuffle(len: usize) -> Vec<usize> {
+      // Create a shuffled order for use to iterate through.
+      // Not a great random seed, but good enough for our purposes.
+      let seed = frame_system::Pallet::<T>::parent_hash();
+      let seed = <[u8; 32]>::decode(&mut sp_runtime::traits::TrailingZeroInput::new(
+        seed.as_ref(),
+      ))
+      .expect("input is padded with zeroes; qed");
+      let mut rng = ChaChaRng::from_seed(seed);
+      let mut shuffled = (0..len).collect::<Vec<_>>();
+      for i in 0..len {
+        let j = (rng.next_u32() as usize) % len;
+        let a = shuffled[i];
+        shuffled[i] = shuffled[j];
+        shuffled[j] = a;
+      }
+      shuffled
+    }
+
+    // Propose the removal of the inactive members, a member is inactive when it missed
+    // `MaxMissedProposals` proposals in a row, or didn't vote nor submit public keys for
+    // `MemberInactivityPeriod` blocks since the first proposal it missed. A single removal is
+    // pending at a time.
+    fn check_members_liveness_with_max_weight(max_weight: Weight) -> Weight {
+      let db_weight = <T as frame_system::Config>::DbWeight::get();
+      // the current block and the pending removal
+      let mut weight_used = db_weight.reads(2);
+      if max_weight < weight_used {
+        return 0;
+      }
+
+      let current_block = T::Security::get_current_block_count();
+      // the member, its liveness and its pending removal, the liveness update
+      let check_weight = db_weight.reads_writes(3, 1);
+      // the members with their vote weight, the thresholds, the unique ID and the proposals queue
+      let removal_weight = db_weight.reads_writes(
+        (T::VotesLimit::get() as u64)
+          .saturating_mul(3)
+          .saturating_add(4),
+        4,
+      );
+      let mut has_pending_removal = InactiveMemberRemovals::<T>::iter_keys().next().is_some();
+
+      let mut members = Members::<T>::iter_keys();
+      while max_weight.saturating_sub(weight_used) >= check_weight {
+        let account_id = match members.next() {
+          Some(account_id) => account_id,
+          None => break,
+        };
+        weight_used = weight_used.saturating_add(check_weight);
+
+        let mut liveness = match Self::members_liveness(&account_id) {
+          Some(liveness) => liveness,
+          // start tracking the members added before the liveness
+          None => {
+            MembersLiveness::<T>::insert(&account_id, Self::new_member_liveness(current_block));
+            continue;
+          }
+        };
+
+        // skip the members with a pending removal proposal
+        if let Some(proposal_id) = liveness.removal_proposal {
+          if InactiveMemberRemovals::<T>::contains_key(proposal_id) {
+            continue;
+          }
+        }
+
+        // the inactivity period is measured from the first proposal missed, so a quiet period
+        // without proposals doesn't make the members inactive
+        let is_inactive = liveness.missed_proposals >= T::MaxMissedProposals::get()
+          || liveness
+            .missed_since
+            .map(|missed_since| {
+              missed_since.saturating_add(T::MemberInactivityPeriod::get()) <= current_block
+            })
+            .unwrap_or(false);
+        if !is_inactive || has_pending_removal {
+          continue;
+        }
+
+        if max_weight.saturating_sub(weight_used) < removal_weight {
+          break;
+        }
+        weight_used = weight_used.saturating_add(removal_weight);
+
+        let current_members: Vec<T::AccountId> = Members::<T>::iter_keys().collect();
+        match Self::propose_member_removal(&account_id, current_members.as_slice()) {
+          Ok(proposal_id) => {
+            has_pending_removal = true;
+            liveness.removal_proposal = Some(proposal_id);
+            MembersLiveness::<T>::insert(&account_id, liveness);
+          }
+          Err(removal_error) => log!(
+            error,
+            "Can't propose the removal of the inactive member {:?}: {:?}",
+            account_id,
+            removal_error
+          ),
+        }
+      }
+
+      weight_used
+    }
+
+    // Delete the public keys of the removed members at the end of the grace period
+    fn expire_retiring_members_keys_with_max_weight(max_weight: Weight) -> Weight {
+      let db_weight = <T as frame_system::Config>::DbWeight::get();
+      let read_weight = db_weight.reads(1);
+      // the current block and the first retiring member
+      let mut weight_used = db_weight.reads(2);
+      if max_weight < weight_used {
+        return 0;
+      }
+      if RetiringMembers::<T>::iter_keys().next().is_none() {
+        return weight_used;
+      }
+
+      let current_block = T::Security::get_current_block_count();
+
+      // the public keys of every asset are updated for each expired member
+      let mut assets_count: u64 = 0;
+      for _ in PublicKeys::<T>::iter_keys() {
+        if max_weight.saturating_sub(weight_used) < read_weight {
+          return weight_used;
+        }
+        weight_used = weight_used.saturating_add(read_weight);
+        assets_count = assets_count.saturating_add(1);
+      }
+      let expire_weight = db_weight.reads_writes(
+        assets_count.saturating_add(1),
+        assets_count.saturating_add(1),
+      );
+
+      // collect the expired members within the budget, the retiring members are removed once
+      // the iteration is over
+      let mut expired = Vec::new();
+      let mut retiring_members = RetiringMembers::<T>::iter();
+      while max_weight.saturating_sub(weight_used) >= read_weight {
+        let (account_id, keys_expiry) = match retiring_members.next() {
+          Some(retiring_member) => retiring_member,
+          None => break,
+        };
+        weight_used = weight_used.saturating_add(read_weight);
+        if keys_expiry > current_block {
+          continue;
+        }
+
+        if max_weight.saturating_sub(weight_used) < expire_weight {
+          break;
+        }
+        weight_used = weight_used.saturating_add(expire_weight);
+        expired.push(account_id);
+      }
+
+      for account_id in expired {
+        if let Err(expire_error) = Self::delete_public_keys_for_account(&account_id) {
+          log!(
+            error,
+            "Can't delete the public keys of the removed member {:?}: {:?}",
+            account_id,
+            expire_error
+          );
+          continue;
+        }
+        RetiringMembers::<T>::remove(&account_id);
+        Self::deposit_event(Event::<T>::MemberKeysExpired { account_id });
+      }
+
+      weight_used
+    }
+
+    // Add a proposal to update the configuration without the inactive member, the threshold is
+    // lowered to the remaining members if needed
+    fn propose_member_removal(
+      account_id: &T::AccountId,
+      members: &[T::AccountId],
+    ) -> Result<Hash, DispatchError> {
+      let remaining_members: BoundedVec<T::AccountId, T::VotesLimit> = members
+        .iter()
+        .filter(|member| *member != account_id)
+        .cloned()
+        .collect::<Vec<T::AccountId>>()
+        .try_into()
+        .map_err(|_| Error::<T>::MembersOverflow)?;
+      ensure!(
+        !remaining_members.is_empty(),
+        Error::<T>::KeygenNotEnoughParticipants
+      );
+
+      let remaining_weight = Self::members_vote_weight(remaining_members.iter().cloned());
+      let threshold = Self::proposal_thresholds()
+        .update_configuration
+        .min(remaining_weight);
+      let proposal_id = T::Security::get_unique_id(account_id.clone());
+      Proposals::<T>::try_append((
+        proposal_id,
+        T::Security::get_current_block_count(),
+        ProposalType::UpdateConfiguration(remaining_members, threshold),
+      ))
+      .map_err(|_| Error::<T>::ProposalsCapExceeded)?;
+      InactiveMemberRemovals::<T>::insert(proposal_id, account_id.clone());
+
+      Self::deposit_event(Event::<T>::InactiveMemberRemovalProposed {
+        account_id: account_id.clone(),
+        proposal_id,
+        threshold,
+      });
+
+      Ok(proposal_id)
+    }
+
+    // Remove the inactive member from the current members, the threshold is lowered to the
+    // remaining members if needed
+    fn process_member_removal(account_id: &T::AccountId) -> Result<(), Error<T>> {
+      let remaining_members: Vec<T::AccountId> = Members::<T>::iter_keys()
+        .filter(|member| member != account_id)
+        .collect();
+      let remaining_weight = Self::members_vote_weight(remaining_members.iter().cloned());
+      let threshold = Self::proposal_thresholds()
+        .update_configuration
+        .min(remaining_weight);
+      Self::process_update_configuration(&remaining_members, threshold)
+    }
+
+    // Votes weight needed to approve the proposal, by type of proposal. The mints without price
+    // are valued above the large mint value. The removal of an inactive member doesn't wait for
+    // its vote to stay reachable.
+    fn proposal_threshold(proposal_id: Hash) -> u16 {
+      let thresholds = Self::proposal_thresholds();
+      let threshold = match Self::get_proposal(proposal_id) {
+        Ok(ProposalType::Mint(mint)) => {
+          let is_large_mint = thresholds.large_mint > thresholds.mint
+            && T::Fees::get_usdt_value(mint.currency_id, mint.mint_amount)
+              .map(|value| value > thresholds.large_mint_value)
+              .unwrap_or(true);
+          if is_large_mint {
+            thresholds.large_mint
+          } else {
+            thresholds.mint
+          }
+        }
+        Ok(ProposalType::Withdrawal(_)) => thresholds.withdrawal,
+        Ok(ProposalType::UpdateConfiguration(_, _)) => thresholds.update_configuration,
+        // unknown proposals need the highest threshold
+        Err(_) => thresholds
+          .mint
+          .max(thresholds.large_mint)
+          .max(thresholds.withdrawal)
+          .max(thresholds.update_configuration),
+      };
+
+      match Self::inactive_member_removals(proposal_id) {
+        Some(inactive_member) => {
+          let active_weight = Self::members_vote_weight(
+            Members::<T>::iter_keys().filter(|account_id| *account_id != inactive_member),
+          );
+          threshold.min(active_weight).max(1)
+        }
+        None => threshold,
+      }
+    }
+
+    // Thresholds of each type of proposal, the single threshold until the storage is migrated
+    pub fn proposal_thresholds() -> ProposalThresholds {
+      Self::thresholds().unwrap_or_else(|| ProposalThresholds::from_threshold(Self::threshold()))
+    }
+
+    // Vote weight of the members among `accounts`, the other accounts don't count
+    fn members_vote_weight(accounts: impl Iterator<Item = T::AccountId>) -> u16 {
+      accounts
+        .filter(|account_id| Self::is_member(account_id))
+        .map(|account_id| Self::member_vote_weight(account_id).unwrap_or(1))
+        .fold(0, |total_weight, weight| {
+          total_weight.saturating_add(weight)
+        })
+    }
+
+    // Count the finalized proposal, submitted at `submitted_at`, as missed for the members who
+    // didn't vote. The liveness of the voters is only written when it changed.
+    fn record_missed_votes(
+      votes_for: &BoundedVec<T::AccountId, T::VotesLimit>,
+      votes_against: &BoundedVec<T::AccountId, T::VotesLimit>,
+      submitted_at: T::BlockNumber,
+    ) {
+      let current_block = T::Security::get_current_block_count();
+      for account_id in Members::<T>::iter_keys() {
+        let mut liveness = Self::get_member_liveness(&account_id, current_block);
+        if votes_for.contains(&account_id) || votes_against.contains(&account_id) {
+          if liveness.missed_proposals == 0 && liveness.missed_since.is_none() {
+            continue;
+          }
+          liveness.missed_proposals = 0;
+          liveness.missed_since = None;
+        } else {
+          liveness.missed_proposals = liveness.missed_proposals.saturating_add(1);
+          liveness.missed_since = liveness.missed_since.or(Some(submitted_at));
+        }
+        MembersLiveness::<T>::insert(&account_id, liveness);
+      }
+    }
+
+    // Weight of `record_missed_votes`, the liveness of each member is read and written
+    pub(crate) fn record_missed_votes_weight() -> Weight {
+      let members = T::VotesLimit::get() as Weight;
+      <T as frame_system::Config>::DbWeight::get().reads_writes(members, members)
+    }
+
+    // Liveness of a member tracked from `block_number`
+    fn new_member_liveness(block_number: T::BlockNumber) -> MemberLiveness<T> {
+      MemberLiveness {
+        last_vote: block_number,
+        last_keys_submission: block_number,
+        missed_proposals: 0,
+        missed_since: None,
+        removal_proposal: None,
+      }
+    }
+
+    // Get the liveness of a member, tracked from `block_number` if it's not tracked yet
+    fn get_member_liveness(
+      account_id: &T::AccountId,
+      block_number: T::BlockNumber,
+    ) -> MemberLiveness<T> {
+      Self::members_liveness(account_id).unwrap_or_else(|| Self::new_member_liveness(block_number))
+    }
+
+    // Make sure the account id is part of the quorum set list
+    fn is_member(who: &T::AccountId) -> bool {
+      Self::members(who).unwrap_or(false)
+    }
+
+    // Make sure the account id is part of the quorum set list and have public key set
+    fn is_member_and_ready(who: &T::AccountId) -> bool {
+      let at_least_one_public_key = PublicKeys::<T>::iter_values()
+        .find(|assets| {
+          assets
+            .iter()
+            .find(|(account_id, _)| account_id == who)
+            .is_some()
+        })
+        .is_some();
+
+      Self::members(who).unwrap_or(false) && at_least_one_public_key
+    }
+
+    // Register a vote for the proposal
+    fn vote_for(who: T::AccountId, proposal_id: Hash) -> Result<(), DispatchError> {
+      Self::commit_vote(who, proposal_id, true)?;
+      Self::try_resolve_proposal(proposal_id)?;
+      Ok(())
+    }
+
+    // Register a vote against the proposal
+    fn vote_against(who: T::AccountId, proposal_id: Hash) -> Result<(), DispatchError> {
+      Self::commit_vote(who, proposal_id, false)?;
+      Self::try_resolve_proposal(proposal_id)?;
+      Ok(())
+    }
+
+    // Record the vote in the storage
+    fn commit_vote(who: T::AccountId, proposal_id: Hash, in_favour: bool) -> DispatchResult {
+      let block_number = T::Security::get_current_block_count();
+      let mut votes = Votes::<T>::get(proposal_id).unwrap_or_else(|| {
+        let mut v =
+          ProposalVotes::<T::BlockNumber, BoundedVec<T::AccountId, T::VotesLimit>>::default();
+        v.expiry = block_number + T::ProposalLifetime::get();
+        v
+      });
+
+      ensure!(
+        votes.status == ProposalStatus::Initiated,
+        Error::<T>::ProposalAlreadyComplete
+      );
+      ensure!(votes.expiry >= block_number, Error::<T>::ProposalExpired);
+      ensure!(
+        !votes.votes_for.contains(&who) && !votes.votes_against.contains(&who),
+        Error::<T>::MemberAlreadyVoted
+      );
+
+      // record the liveness of the member
+      let mut liveness = Self::get_member_liveness(&who, block_number);
+      liveness.last_vote = block_number;
+      liveness.missed_since = None;
+      MembersLiveness::<T>::insert(&who, liveness);
+
+      if in_favour {
+        votes
+          .votes_for
+          .try_push(who.clone())
+          .map_err(|_| Error::<T>::VotesOverflow)?;
+        Self::deposit_event(Event::<T>::VoteFor {
+          account_id: who,
+          proposal_id,
+        });
+      } else {
+        votes
+          .votes_against
+          .try_push(who.clone())
+          .map_err(|_| Error::<T>::VotesOverflow)?;
+
+        Self::deposit_event(Event::<T>::VoteAgainst {
+          account_id: who,
+          proposal_id,
+        });
+      }
+
+      Votes::<T>::insert(proposal_id, votes);
+
+      Ok(())
+    }
+
+    // Process proposal
+    //
+    // A proposal with enough votes will be either executed or cancelled, and the status
+    // will be updated accordingly.
+    fn try_resolve_proposal(proposal_id: Hash) -> Result<(), Error<T>> {
+      Votes::<T>::mutate_exists(proposal_id, |proposal_votes| match proposal_votes {
+        Some(votes) => {
+          let block_number = T::Security::get_current_block_count();
+          ensure!(
+            votes.status == ProposalStatus::Initiated,
+            Error::<T>::ProposalAlreadyComplete
+          );
+          ensure!(votes.expiry >= block_number, Error::<T>::ProposalExpired);
+
+          let threshold = Self::proposal_threshold(proposal_id);
+          let total_weight = Self::members_vote_weight(Members::<T>::iter_keys());
+          let votes_for = Self::members_vote_weight(votes.votes_for.iter().cloned());
+          let votes_against = Self::members_vote_weight(votes.votes_against.iter().cloned());
+          let status = if votes_for >= threshold {
+            votes.status = ProposalStatus::Approved;
+            ProposalStatus::Approved
+          } else if total_weight >= threshold
+            && votes_against.saturating_add(threshold) > total_weight
+          {
+            votes.status = ProposalStatus::Rejected;
+            ProposalStatus::Rejected
+          } else {
+            ProposalStatus::Initiated
+          };
+
+          *proposal_votes = Some(votes.clone());
+          match status {
+            ProposalStatus::Approved => {
+              Self::deposit_event(Event::<T>::ProposalApproved { proposal_id });
+              Self::process_proposal(proposal_id)?;
+              Self::archive_proposal(
+                proposal_id,
+                ProposalResolution::Approved,
+                votes.votes_for.clone(),
+                votes.votes_against.clone(),
+              );
+              Self::delete_proposal(proposal_id)?;
+              *proposal_votes = None;
+              Ok(())
+            }
+            ProposalStatus::Rejected => {
+              // FIXME: Maybe add some slashing for the proposer?
+              Self::deposit_event(Event::<T>::ProposalRejected { proposal_id });
+              Self::refund_withdrawal_fee(proposal_id);
+              Self::archive_proposal(
+                proposal_id,
+                ProposalResolution::Rejected,
+                votes.votes_for.clone(),
+                votes.votes_against.clone(),
+              );
+              Self::delete_proposal(proposal_id)?;
+              *proposal_votes = None;
+              Ok(())
+            }
+            _ => Ok(()),
+          }
+        }
+        None => Err(Error::<T>::ProposalDoesNotExist),
+      })
+    }
+
+    // Process the original proposal call
+    fn process_proposal(proposal_id: Hash) -> Result<(), Error<T>> {
+      match Self::get_proposal(proposal_id)? {
+        // mint token
+        ProposalType::Mint(mint) => Self::process_mint(proposal_id, &mint)?,
+        // withdraw (burn)
+        ProposalType::Withdrawal(withdrawal) => Self::process_withdrawal(proposal_id, &withdrawal)?,
+        // update quorum configuration (threshold & member set), the removal of an inactive
+        // member is applied to the current members
+        ProposalType::UpdateConfiguration(members, threshold) => {
+          match Self::inactive_member_removals(proposal_id) {
+            Some(inactive_member) => Self::process_member_removal(&inactive_member)?,
+            None => Self::process_update_configuration(&members, threshold)?,
+          }
+        }
+      };
+      Self::deposit_event(Event::<T>::ProposalProcessed { proposal_id });
+      Ok(())
+    }
+
+    // Process the original proposal call
+    pub fn get_proposal(
+      proposal_id: Hash,
+    ) -> Result<
+      ProposalType<
+        T::AccountId,
+        T::BlockNumber,
+        BoundedVec<u8, <T as pallet::Config>::StringLimit>,
+        BoundedVec<T::AccountId, <T as pallet::Config>::VotesLimit>,
+      >,
+      Error<T>,
+    > {
+      let proposals = Proposals::<T>::get();
+      let proposal = proposals
+        .iter()
+        .find(|(hash, _, _)| *hash == proposal_id)
+        .ok_or(Error::<T>::ProposalDoesNotExist)?;
+      // FIXME: would be great to add a lifetime to the pallelt
+      Ok(proposal.2.clone())
+    }
+
+    // Process withdrawal
+    fn process_withdrawal(
+      proposal_id: Hash,
+      item: &Withdrawal<
+        T::AccountId,
+        T::BlockNumber,
+        BoundedVec<u8, <T as pallet::Config>::StringLimit>,
+      >,
+    ) -> Result<(), Error<T>> {
+      // 1. Make sure the currency_id exist and is enabled
+      ensure!(
+        T::AssetRegistry::is_currency_enabled(item.asset_id),
+        Error::<T>::AssetDisabled
+      );
+
+      // 2. Remove the token from the account, the fee has been collected when queued
+      T::CurrencyTidefi::burn_from(item.asset_id, &item.account_id, item.amount)
+        .map_err(|_| Error::<T>::BurnFailed)?;
+      let fee = WithdrawalProposalFees::<T>::take(proposal_id).unwrap_or_default();
+
+      // 3. Add to burned queue, the quorum can poll and initiate the chain deposit, then confirm
+      // the external transfer
+      BurnedQueue::<T>::try_mutate(|burned_queue| {
+        burned_queue
+          .try_push((proposal_id, item.clone()))
+          .map_err(|_| Error::<T>::BurnedQueueOverflow)
+      })?;
+      BurnedConfirmations::<T>::insert(
+        proposal_id,
+        Self::new_burned_confirmation(T::Security::get_current_block_count(), fee),
+      );
+
+      // 4. Emit the event on chain
+      Self::deposit_event(Event::<T>::BurnedInitialized {
+        proposal_id,
+        account_id: item.account_id.clone(),
+        currency_id: item.asset_id,
+        amount: item.amount,
+        fee,
+      });
+
+      Ok(())
+    }
+
+    // Process mint
+    fn process_mint(
+      proposal_id: Hash,
+      item: &Mint<T::AccountId, BoundedVec<u8, <T as pallet::Config>::StringLimit>>,
+    ) -> Result<(), Error<T>> {
+      // 1. Make sure the currency_id exist and is enabled
+      ensure!(
+        T::AssetRegistry::is_currency_enabled(item.currency_id),
+        Error::<T>::AssetDisabled
+      );
+
+      // 2. Make sure the deposit has not been processed yet
+      ensure!(
+        !ProcessedMints::<T>::contains_key(item.currency_id, &item.transaction_id),
+        Error::<T>::MintAlreadySubmitted
+      );
+
+      // 3. Add `Amber` and `Red` to watch list
+      if item.compliance_level == ComplianceLevel::Amber
+        || item.compliance_level == ComplianceLevel::Red
+      {
+        Self::add_account_watch_list(
+          &item.account_id,
+          item.currency_id,
+          item.mint_amount,
+          item.compliance_level.clone(),
+          item.transaction_id.clone().to_vec(),
+          WatchListAction::Mint,
+        )?;
+      }
+
+      // 4. Mint `Green` and `Amber`
+      if item.compliance_level == ComplianceLevel::Green
+        || item.compliance_level == ComplianceLevel::Amber
+      {
+        T::CurrencyTidefi::mint_into(item.currency_id, &item.account_id, item.mint_amount)
+          .map_err(|_| Error::<T>::MintFailed)?;
+
+        Self::deposit_event(Event::<T>::Minted {
+          proposal_id,
+          account_id: item.account_id.clone(),
+          currency_id: item.currency_id,
+          amount: item.mint_amount,
+          transaction_id: item.transaction_id.clone().to_vec(),
+          compliance_level: item.compliance_level.clone(),
+        });
+      }
+
+      // 5. Register the deposit as processed, `Red` mints are held in the watch list
+      Self::register_processed_mint(item.currency_id, item.transaction_id.clone());
+
+      Ok(())
+    }
+
+    // The deposit is already processed, or pending in a mint proposal
+    fn is_mint_submitted(
+      currency_id: CurrencyId,
+      transaction_id: &BoundedVec<u8, <T as pallet::Config>::StringLimit>,
+    ) -> bool {
+      ProcessedMints::<T>::contains_key(currency_id, transaction_id)
+        || Self::proposals()
+          .iter()
+          .any(|(_, _, proposal)| match proposal {
+            ProposalType::Mint(mint) => {
+              mint.currency_id == currency_id && mint.transaction_id == *transaction_id
+            }
+            _ => false,
+          })
+    }
+
+    // Keep the processed deposit until the replay horizon
+    fn register_processed_mint(
+      currency_id: CurrencyId,
+      transaction_id: BoundedVec<u8, <T as pallet::Config>::StringLimit>,
+    ) {
+      let index = ProcessedMintsHead::<T>::mutate(|head| {
+        let index = *head;
+        *head = head.saturating_add(1);
+        index
+      });
+
+      ProcessedMints::<T>::insert(
+        currency_id,
+        &transaction_id,
+        T::Security::get_current_block_count(),
+      );
+      ProcessedMintsQueue::<T>::insert(index, (currency_id, transaction_id));
+    }
+
+    // Remove the processed deposits older than the replay horizon, oldest first
+    fn prune_processed_mints_with_max_weight(max_weight: Weight) -> Weight {
+      let db_weight = <T as frame_system::Config>::DbWeight::get();
+      let mut weight_used = db_weight.reads(2);
+
+      let head = Self::processed_mints_head();
+      let mut tail = Self::processed_mints_tail();
+      if tail >= head {
+        return weight_used;
       }
-      shuffled
+
+      let current_block = T::Security::get_current_block_count();
+      let horizon = T::MintReplayHorizon::get();
+      let prune_weight = db_weight.reads_writes(2, 2);
+
+      while tail < head && max_weight.saturating_sub(weight_used) >= prune_weight {
+        weight_used = weight_used.saturating_add(prune_weight);
+        if let Some((currency_id, transaction_id)) = ProcessedMintsQueue::<T>::get(tail) {
+          // processed deposits are sorted by block, stop at the first one to keep
+          if let Some(processed_at) = ProcessedMints::<T>::get(currency_id, &transaction_id) {
+            if processed_at.saturating_add(horizon) > current_block {
+              break;
+            }
+          }
+          ProcessedMints::<T>::remove(currency_id, &transaction_id);
+          ProcessedMintsQueue::<T>::remove(tail);
+        }
+        tail = tail.saturating_add(1);
+      }
+
+      ProcessedMintsTail::<T>::put(tail);
+      weight_used.saturating_add(db_weight.writes(1))
     }
 
-    // Make sure the account id is part of the quorum set list
-    fn is_member(who: &T::AccountId) -> bool {
-      Self::members(who).unwrap_or(false)
+    // Add the delayed withdrawals at the end of their delay in the proposals queue, oldest first
+    fn release_delayed_withdrawals_with_max_weight(max_weight: Weight) -> Weight {
+      let db_weight = <T as frame_system::Config>::DbWeight::get();
+      let mut weight_used = db_weight.reads(2);
+
+      let head = Self::delayed_withdrawals_head();
+      let mut tail = Self::delayed_withdrawals_tail();
+      if tail >= head {
+        return weight_used;
+      }
+
+      let current_block = T::Security::get_current_block_count();
+      // release the withdrawal, or release the funds and the withdrawal usage when it fails
+      let release_weight = db_weight.reads_writes(8, 7);
+
+      while tail < head && max_weight.saturating_sub(weight_used) >= release_weight {
+        weight_used = weight_used.saturating_add(release_weight);
+        if let Some(withdrawal_id) = DelayedWithdrawalsQueue::<T>::get(tail) {
+          // delayed withdrawals are sorted by release block, stop at the first one to keep
+          if let Some(withdrawal) = DelayedWithdrawals::<T>::get(withdrawal_id) {
+            if withdrawal.release_at > current_block {
+              break;
+            }
+            match Self::release_delayed_withdrawal(withdrawal_id, &withdrawal) {
+              // the proposals queue is full, try again on the next block
+              Err(error) if error == DispatchError::from(Error::<T>::ProposalsCapExceeded) => break,
+              // the funds are released, the withdrawal is dropped
+              Err(release_error) => {
+                Self::fail_delayed_withdrawal(withdrawal_id, &withdrawal, release_error)
+              }
+              Ok(_) => {}
+            }
+            DelayedWithdrawals::<T>::remove(withdrawal_id);
+          }
+          DelayedWithdrawalsQueue::<T>::remove(tail);
+        }
+        tail = tail.saturating_add(1);
+      }
+
+      DelayedWithdrawalsTail::<T>::put(tail);
+      weight_used.saturating_add(db_weight.writes(1))
     }
 
-    // Make sure the account id is part of the quorum set list and have public key set
-    fn is_member_and_ready(who: &T::AccountId) -> bool {
-      let at_least_one_public_key = PublicKeys::<T>::iter_values()
-        .find(|assets| {
-          assets
-            .iter()
-            .find(|(account_id, _)| account_id == who)
-            .is_some()
-        })
-        .is_some();
+    fn escalate_burned_with_max_weight(max_weight: Weight) -> Weight {
+      let db_weight = <T as frame_system::Config>::DbWeight::get();
+      let mut weight_used = db_weight.reads(2);
 
-      Self::members(who).unwrap_or(false) && at_least_one_public_key
+      let current_block = T::Security::get_current_block_count();
+      let escalate_weight = db_weight.reads_writes(1, 1);
+
+      for (proposal_id, withdrawal) in Self::burned_queue() {
+        if max_weight.saturating_sub(weight_used) < escalate_weight {
+          break;
+        }
+        weight_used = weight_used.saturating_add(escalate_weight);
+
+        // the withdrawals burned before the confirmations were recorded start from their request
+        // block, they are not sorted by it
+        let (mut confirmation, is_sorted) = match Self::burned_confirmations(proposal_id) {
+          Some(confirmation) => (confirmation, true),
+          None => (
+            Self::new_burned_confirmation(withdrawal.block_number, 0),
+            false,
+          ),
+        };
+        if confirmation.escalated {
+          continue;
+        }
+        if confirmation
+          .burned_at
+          .saturating_add(T::BurnedConfirmationTimeout::get())
+          > current_block
+        {
+          // the recorded burned withdrawals are sorted by burned block, stop at the first one in
+          // time
+          if is_sorted {
+            break;
+          }
+          continue;
+        }
+        confirmation.escalated = true;
+        BurnedConfirmations::<T>::insert(proposal_id, confirmation);
+        Self::deposit_event(Event::<T>::BurnedEscalated { proposal_id });
+      }
+
+      weight_used
     }
 
-    // Register a vote for the proposal
-    fn vote_for(who: T::AccountId, proposal_id: Hash) -> Result<(), DispatchError> {
-      Self::commit_vote(who, proposal_id, true)?;
-      Self::try_resolve_proposal(proposal_id)?;
+    // Confirmation of a burned withdrawal without vote
+    fn new_burned_confirmation(burned_at: T::BlockNumber, fee: Balance) -> BurnedConfirmation<T> {
+      BurnedConfirmation {
+        burned_at,
+        fee,
+        acknowledged_by: None,
+        confirmations: BoundedVec::default(),
+        failures: BoundedVec::default(),
+        escalated: false,
+      }
+    }
+
+    // Get the confirmation of a burned withdrawal in queue, the withdrawals burned before the
+    // confirmations were recorded start from their request block, without fee to refund
+    fn get_burned_confirmation(proposal_id: Hash) -> Option<BurnedConfirmation<T>> {
+      Self::burned_confirmations(proposal_id).or_else(|| {
+        Self::burned_queue()
+          .into_iter()
+          .find(|(burned_proposal_id, _)| *burned_proposal_id == proposal_id)
+          .map(|(_, withdrawal)| Self::new_burned_confirmation(withdrawal.block_number, 0))
+      })
+    }
+
+    // Check if the member already confirmed or failed the burned withdrawal
+    fn has_voted_burned(confirmation: &BurnedConfirmation<T>, who: &T::AccountId) -> bool {
+      confirmation
+        .confirmations
+        .iter()
+        .any(|(account_id, _)| account_id == who)
+        || confirmation.failures.contains(who)
+    }
+
+    // Remove the burned withdrawal from the queue once the external transfer is confirmed
+    fn confirm_burned_withdrawal(
+      proposal_id: Hash,
+      external_tx_id: BoundedVec<u8, <T as pallet::Config>::StringLimit>,
+    ) {
+      BurnedQueue::<T>::mutate(|burned_queue| {
+        burned_queue.retain(|(burned_proposal_id, _)| *burned_proposal_id != proposal_id);
+      });
+      BurnedConfirmations::<T>::remove(proposal_id);
+
+      Self::deposit_event(Event::<T>::BurnedConfirmed {
+        proposal_id,
+        external_tx_id: external_tx_id.to_vec(),
+      });
+    }
+
+    // Mint the burned funds back to the account and refund the fee, the external transfer failed
+    fn fail_burned_withdrawal(proposal_id: Hash, fee: Balance) -> Result<(), DispatchError> {
+      // 1. Remove the burned withdrawal from the queue
+      let mut burned_queue = Self::burned_queue();
+      let position = burned_queue
+        .iter()
+        .position(|(burned_proposal_id, _)| *burned_proposal_id == proposal_id)
+        .ok_or(Error::<T>::BurnedNotFound)?;
+      let (_, withdrawal) = burned_queue.remove(position);
+      BurnedQueue::<T>::put(burned_queue);
+      BurnedConfirmations::<T>::remove(proposal_id);
+
+      // 2. Mint the burned funds back to the account
+      T::CurrencyTidefi::mint_into(
+        withdrawal.asset_id,
+        &withdrawal.account_id,
+        withdrawal.amount,
+      )
+      .map_err(|_| Error::<T>::MintFailed)?;
+
+      // 3. Refund the withdrawal fee
+      if fee > 0 {
+        T::CurrencyTidefi::transfer(
+          withdrawal.asset_id,
+          &T::WithdrawalFeesAccount::get(),
+          &withdrawal.account_id,
+          fee,
+          false,
+        )?;
+      }
+
+      // 4. Emit the event on chain
+      Self::deposit_event(Event::<T>::BurnedFailed {
+        proposal_id,
+        account_id: withdrawal.account_id,
+        currency_id: withdrawal.asset_id,
+        amount: withdrawal.amount,
+        fee,
+      });
+
       Ok(())
     }
 
-    // Register a vote against the proposal
-    fn vote_against(who: T::AccountId, proposal_id: Hash) -> Result<(), DispatchError> {
-      Self::commit_vote(who, proposal_id, false)?;
-      Self::try_resolve_proposal(proposal_id)?;
+    // Release the funds of the delayed withdrawal and add it in the proposals queue, nothing is
+    // committed if it fails
+    #[transactional]
+    fn release_delayed_withdrawal(
+      withdrawal_id: Hash,
+      withdrawal: &DelayedWithdrawal<T>,
+    ) -> Result<(), DispatchError> {
+      // 1. Make sure the proposals queue can take the withdrawal before releasing the funds
+      ensure!(
+        (Self::proposals().len() as u32) < T::ProposalsCap::get(),
+        Error::<T>::ProposalsCapExceeded
+      );
+
+      // 2. Release the funds, they are burned once the quorum process the withdrawal
+      T::CurrencyTidefi::release(
+        withdrawal.asset_id,
+        &withdrawal.account_id,
+        withdrawal.amount,
+        false,
+      )
+      .map_err(|_| Error::<T>::HoldFailed)?;
+
+      // 3. Add the withdrawal in the proposals queue with the fee quoted at the request
+      Self::queue_withdrawal(
+        withdrawal.account_id.clone(),
+        withdrawal.asset_id,
+        withdrawal.amount,
+        withdrawal.fee,
+        withdrawal.external_address.clone(),
+      )?;
+
+      // 4. Emit event on chain
+      Self::deposit_event(Event::<T>::DelayedWithdrawalReleased {
+        withdrawal_id,
+        account_id: withdrawal.account_id.clone(),
+        currency_id: withdrawal.asset_id,
+        amount: withdrawal.amount,
+      });
+
       Ok(())
     }
 
-    // Record the vote in the storage
-    fn commit_vote(who: T::AccountId, proposal_id: Hash, in_favour: bool) -> DispatchResult {
-      let block_number = T::Security::get_current_block_count();
-      let mut votes = Votes::<T>::get(proposal_id).unwrap_or_else(|| {
-        let mut v =
-          ProposalVotes::<T::BlockNumber, BoundedVec<T::AccountId, T::VotesLimit>>::default();
-        v.expiry = block_number + T::ProposalLifetime::get();
-        v
+    // Release the funds of the delayed withdrawal who can't be added in the proposals queue, the
+    // withdrawal doesn't count in the withdrawal caps
+    fn fail_delayed_withdrawal(
+      withdrawal_id: Hash,
+      withdrawal: &DelayedWithdrawal<T>,
+      error: DispatchError,
+    ) {
+      log!(
+        error,
+        "Unable to release the delayed withdrawal {:?}: {:?}",
+        withdrawal_id,
+        error
+      );
+
+      if T::CurrencyTidefi::release(
+        withdrawal.asset_id,
+        &withdrawal.account_id,
+        withdrawal.amount,
+        false,
+      )
+      .is_err()
+      {
+        log!(
+          error,
+          "Unable to release the funds of the delayed withdrawal {:?}",
+          withdrawal_id
+        );
+      }
+      T::AssetRegistry::unregister_withdrawal(
+        &withdrawal.account_id,
+        withdrawal.asset_id,
+        withdrawal.amount,
+      );
+
+      Self::deposit_event(Event::<T>::DelayedWithdrawalFailed {
+        withdrawal_id,
+        account_id: withdrawal.account_id.clone(),
+        currency_id: withdrawal.asset_id,
+        amount: withdrawal.amount,
       });
+    }
+
+    // Collect the withdrawal fee and add the withdrawal of the amount left in the proposals queue
+    #[transactional]
+    fn queue_withdrawal(
+      account_id: T::AccountId,
+      asset_id: CurrencyId,
+      amount: Balance,
+      fee: Balance,
+      external_address: BoundedVec<u8, <T as pallet::Config>::StringLimit>,
+    ) -> Result<(), DispatchError> {
+      // 1. Make sure the amount cover the fee
+      ensure!(amount > fee, Error::<T>::WithdrawalBelowFee);
+
+      // 2. Collect the fee
+      if fee > 0 {
+        T::CurrencyTidefi::transfer(
+          asset_id,
+          &account_id,
+          &T::WithdrawalFeesAccount::get(),
+          fee,
+          true,
+        )?;
+      }
+
+      // 3. Add the withdrawal in the proposals queue
+      let unique_id = T::Security::get_unique_id(account_id.clone());
+      let block_number = T::Security::get_current_block_count();
+
+      Proposals::<T>::try_append((
+        unique_id,
+        block_number,
+        ProposalType::Withdrawal(Withdrawal {
+          account_id,
+          amount: amount.saturating_sub(fee),
+          asset_id,
+          external_address,
+          block_number,
+        }),
+      ))
+      .map_err(|_| Error::<T>::ProposalsCapExceeded)?;
+
+      if fee > 0 {
+        WithdrawalProposalFees::<T>::insert(unique_id, fee);
+      }
+
+      Ok(())
+    }
+
+    // Refund the fee of a withdrawal proposal rejected or expired, the funds were not burned
+    fn refund_withdrawal_fee(proposal_id: Hash) {
+      let fee = match WithdrawalProposalFees::<T>::take(proposal_id) {
+        Some(fee) => fee,
+        None => return,
+      };
+
+      if let Some((_, _, ProposalType::Withdrawal(withdrawal))) = Self::proposals()
+        .into_iter()
+        .find(|(found_proposal_id, _, _)| *found_proposal_id == proposal_id)
+      {
+        if let Err(refund_error) = T::CurrencyTidefi::transfer(
+          withdrawal.asset_id,
+          &T::WithdrawalFeesAccount::get(),
+          &withdrawal.account_id,
+          fee,
+          false,
+        ) {
+          log!(
+            error,
+            "Can't refund the withdrawal fee of proposal {}: {:?}",
+            proposal_id,
+            refund_error
+          );
+        }
+      }
+    }
+
+    // Quote the withdrawal fee and the amount burned, fails if the amount doesn't cover the fee
+    pub fn quote_withdrawal(
+      asset_id: CurrencyId,
+      amount: Balance,
+    ) -> Result<(Balance, Balance), DispatchError> {
+      let fee = <Self as WithdrawalFeeExt>::quote_withdrawal_fee(asset_id, amount);
+      ensure!(amount > fee, Error::<T>::WithdrawalBelowFee);
+      Ok((fee, amount.saturating_sub(fee)))
+    }
 
+    // Make sure the configuration has at least one member, no duplicate, and a reachable threshold
+    fn ensure_valid_configuration(
+      members: &[T::AccountId],
+      threshold: u16,
+    ) -> Result<(), Error<T>> {
+      let mut unique_members = members.to_vec();
+      unique_members.sort();
+      unique_members.dedup();
       ensure!(
-        votes.status == ProposalStatus::Initiated,
-        Error::<T>::ProposalAlreadyComplete
+        unique_members.len() == members.len(),
+        Error::<T>::DuplicateMember
       );
-      ensure!(votes.expiry >= block_number, Error::<T>::ProposalExpired);
+      // the threshold is a votes weight, the members not weighted yet count for 1
+      let total_weight = members
+        .iter()
+        .map(|account_id| Self::member_vote_weight(account_id).unwrap_or(1))
+        .fold(0, |total_weight: u16, weight| {
+          total_weight.saturating_add(weight)
+        });
       ensure!(
-        !votes.votes_for.contains(&who) || !votes.votes_against.contains(&who),
-        Error::<T>::MemberAlreadyVoted
+        threshold > 0 && threshold <= total_weight,
+        Error::<T>::BadThreshold
       );
+      Ok(())
+    }
 
-      if in_favour {
-        votes
-          .votes_for
-          .try_push(who.clone())
-          .map_err(|_| Error::<T>::VotesOverflow)?;
-        Self::deposit_event(Event::<T>::VoteFor {
-          account_id: who,
-          proposal_id,
+    // Process configuration update, the members who stay keep their public keys and liveness
+    fn process_update_configuration(
+      members: &Vec<T::AccountId>,
+      threshold: u16,
+    ) -> Result<(), Error<T>> {
+      // 1. Make sure the configuration is valid before updating anything
+      Self::ensure_valid_configuration(members, threshold)?;
+
+      // 2. Remove the members not in the new set, their public keys stay valid until the end
+      // of the grace period
+      let current_block = T::Security::get_current_block_count();
+      let keys_expiry = current_block.saturating_add(T::MemberKeysGracePeriod::get());
+      let removed: Vec<T::AccountId> = Members::<T>::iter_keys()
+        .filter(|account_id| !members.contains(account_id))
+        .collect();
+      for account_id in removed.iter() {
+        Members::<T>::remove(account_id);
+        MembersLiveness::<T>::remove(account_id);
+        RetiringMembers::<T>::insert(account_id, keys_expiry);
+      }
+
+      // 3. Add the new members, the liveness is tracked from now, a member added back during
+      // the grace period keeps its public keys
+      let mut added = Vec::new();
+      for account_id in members {
+        if Members::<T>::contains_key(account_id) {
+          continue;
+        }
+        Members::<T>::insert(account_id, true);
+        MembersLiveness::<T>::insert(account_id, Self::new_member_liveness(current_block));
+        RetiringMembers::<T>::remove(account_id);
+        added.push(account_id.clone());
+      }
+
+      // 4. Update thresholds, the thresholds of each type of proposal are at least `threshold`
+      // and stay reachable by the new members
+      Threshold::<T>::put(threshold);
+      let removed_members: Vec<T::AccountId> = MemberVoteWeights::<T>::iter_keys()
+        .filter(|account_id| !members.contains(account_id))
+        .collect();
+      for account_id in removed_members {
+        MemberVoteWeights::<T>::remove(account_id);
+      }
+      let total_weight = Self::members_vote_weight(members.iter().cloned());
+      let new_threshold = |current: u16| current.max(threshold).min(total_weight).max(1);
+      let thresholds = Self::proposal_thresholds();
+      Thresholds::<T>::put(ProposalThresholds {
+        mint: new_threshold(thresholds.mint),
+        large_mint: new_threshold(thresholds.large_mint),
+        large_mint_value: thresholds.large_mint_value,
+        withdrawal: new_threshold(thresholds.withdrawal),
+        update_configuration: new_threshold(thresholds.update_configuration),
+      });
+
+      // 5. Emit event
+      Self::deposit_event(Event::<T>::ConfigurationUpdated {
+        threshold,
+        members: members.clone(),
+        added,
+        removed,
+      });
+
+      // 6. Rotate the group keys with the new member set
+      let mut asset_ids: Vec<AssetId> = GroupPublicKeys::<T>::iter_keys()
+        .chain(KeygenRounds::<T>::iter_keys())
+        .collect();
+      asset_ids.sort_unstable();
+      asset_ids.dedup();
+      for asset_id in asset_ids {
+        if let Err(error) = Self::start_keygen_round(asset_id) {
+          log!(
+            error,
+            "Can't start key rotation for asset {}: {:?}",
+            asset_id,
+            error
+          );
+        }
+      }
+
+      Ok(())
+    }
+
+    // Start a new key generation round with the current member set, the group key signs the
+    // withdrawals so the round needs the withdrawal threshold, a share for each participant. The
+    // active round of the asset, if any, is cancelled
+    fn start_keygen_round(asset_id: AssetId) -> Result<(), Error<T>> {
+      let participants: KeygenParticipants<T> = Members::<T>::iter()
+        .filter(|(_, is_member)| *is_member)
+        .map(|(account_id, _)| account_id)
+        .collect::<Vec<T::AccountId>>()
+        .try_into()
+        .map_err(|_| Error::<T>::MembersOverflow)?;
+
+      ensure!(
+        Self::members_vote_weight(participants.iter().cloned())
+          >= Self::proposal_thresholds().withdrawal,
+        Error::<T>::KeygenNotEnoughParticipants
+      );
+      let threshold = Self::proposal_thresholds()
+        .withdrawal
+        .min(participants.len() as u16);
+      ensure!(threshold > 0, Error::<T>::KeygenNotEnoughParticipants);
+
+      if let Some(keygen_round) = KeygenRounds::<T>::get(asset_id) {
+        Self::close_keygen_round(asset_id);
+        Self::deposit_event(Event::<T>::KeygenFailed {
+          asset_id,
+          round: keygen_round.round,
         });
-      } else {
-        votes
-          .votes_against
-          .try_push(who.clone())
-          .map_err(|_| Error::<T>::VotesOverflow)?;
+      }
+
+      let round = KeygenRoundIndex::<T>::mutate(asset_id, |index| {
+        *index = index.saturating_add(1);
+        *index
+      });
+      let phase_end = T::Security::get_current_block_count() + T::KeygenPhaseTimeout::get();
+
+      KeygenRounds::<T>::insert(
+        asset_id,
+        KeygenRound {
+          round,
+          phase: KeygenPhase::Commitment,
+          phase_end,
+          threshold,
+          participants: participants.clone(),
+        },
+      );
+      KeygenDeadlines::<T>::insert(phase_end, asset_id, ());
+
+      Self::deposit_event(Event::<T>::KeygenStarted {
+        asset_id,
+        round,
+        participants: participants.into_inner(),
+        threshold,
+        phase_end,
+      });
+
+      Ok(())
+    }
+
+    // Make sure the account is a participant of the active round, in the expected phase
+    fn ensure_keygen_participant(
+      who: &T::AccountId,
+      asset_id: AssetId,
+      round: u32,
+      phase: KeygenPhase,
+    ) -> Result<KeygenRound<T>, Error<T>> {
+      let keygen_round = KeygenRounds::<T>::get(asset_id).ok_or(Error::<T>::KeygenNotInProgress)?;
+      ensure!(
+        keygen_round.round == round && keygen_round.phase == phase,
+        Error::<T>::KeygenBadRound
+      );
+      ensure!(
+        keygen_round.participants.contains(who),
+        Error::<T>::KeygenNotParticipant
+      );
+      Ok(keygen_round)
+    }
+
+    // Participants who published their commitments, distributed their shares and didn't receive
+    // complaints from `threshold` participants
+    fn keygen_qualified_participants(
+      asset_id: AssetId,
+      keygen_round: &KeygenRound<T>,
+    ) -> Vec<T::AccountId> {
+      keygen_round
+        .participants
+        .iter()
+        .filter(|account_id| {
+          KeygenCommitments::<T>::contains_key(asset_id, account_id)
+            && KeygenShares::<T>::contains_key(asset_id, account_id)
+            && Self::keygen_complaints(asset_id, account_id).len() < keygen_round.threshold as usize
+        })
+        .cloned()
+        .collect()
+    }
+
+    // Move the round to the next phase when all the participants submitted, or when the phase
+    // `timed_out`. The round fails when there is not enough participants to reach the threshold.
+    // The complaint phase always last until the timeout, then the round is completed.
+    fn try_advance_keygen_round(
+      asset_id: AssetId,
+      mut keygen_round: KeygenRound<T>,
+      timed_out: bool,
+    ) {
+      let threshold = keygen_round.threshold as usize;
+      let total_participants = keygen_round.participants.len();
+
+      let (submitted, next_phase) = match keygen_round.phase {
+        KeygenPhase::Commitment => (
+          keygen_round
+            .participants
+            .iter()
+            .filter(|account_id| KeygenCommitments::<T>::contains_key(asset_id, account_id))
+            .count(),
+          KeygenPhase::Share,
+        ),
+        KeygenPhase::Share => (
+          keygen_round
+            .participants
+            .iter()
+            .filter(|account_id| KeygenShares::<T>::contains_key(asset_id, account_id))
+            .count(),
+          KeygenPhase::Complaint,
+        ),
+        KeygenPhase::Complaint => {
+          if timed_out {
+            let qualified = Self::keygen_qualified_participants(asset_id, &keygen_round);
+            Self::try_complete_keygen_round(asset_id, keygen_round, qualified);
+          }
+          return;
+        }
+      };
+
+      if !timed_out && submitted < total_participants {
+        return;
+      }
 
-        Self::deposit_event(Event::<T>::VoteAgainst {
-          account_id: who,
-          proposal_id,
+      if submitted < threshold {
+        Self::close_keygen_round(asset_id);
+        Self::deposit_event(Event::<T>::KeygenFailed {
+          asset_id,
+          round: keygen_round.round,
         });
+        return;
       }
 
-      Votes::<T>::insert(proposal_id, votes);
+      KeygenDeadlines::<T>::remove(keygen_round.phase_end, asset_id);
+      keygen_round.phase = next_phase;
+      keygen_round.phase_end =
+        T::Security::get_current_block_count() + T::KeygenPhaseTimeout::get();
+      KeygenDeadlines::<T>::insert(keygen_round.phase_end, asset_id, ());
+
+      Self::deposit_event(Event::<T>::KeygenPhaseChanged {
+        asset_id,
+        round: keygen_round.round,
+        phase: keygen_round.phase.clone(),
+        phase_end: keygen_round.phase_end,
+      });
 
-      Ok(())
+      KeygenRounds::<T>::insert(asset_id, keygen_round);
     }
 
-    // Process proposal
-    //
-    // A proposal with enough votes will be either executed or cancelled, and the status
-    // will be updated accordingly.
-    fn try_resolve_proposal(proposal_id: Hash) -> Result<(), Error<T>> {
-      Votes::<T>::mutate_exists(proposal_id, |proposal_votes| match proposal_votes {
-        Some(votes) => {
-          let block_number = T::Security::get_current_block_count();
-          ensure!(
-            votes.status == ProposalStatus::Initiated,
-            Error::<T>::ProposalAlreadyComplete
-          );
-          ensure!(votes.expiry >= block_number, Error::<T>::ProposalExpired);
-
-          let threshold = Self::threshold();
-          let total_members = Members::<T>::count() as u16;
-          let status = if votes.votes_for.len() >= threshold as usize {
-            votes.status = ProposalStatus::Approved;
-            ProposalStatus::Approved
-          } else if total_members >= threshold
-            && votes.votes_against.len() as u16 + threshold > total_members
-          {
-            votes.status = ProposalStatus::Rejected;
-            ProposalStatus::Rejected
-          } else {
-            ProposalStatus::Initiated
-          };
-
-          *proposal_votes = Some(votes.clone());
-          match status {
-            ProposalStatus::Approved => {
-              Self::deposit_event(Event::<T>::ProposalApproved { proposal_id });
-              Self::process_proposal(proposal_id)?;
-              Self::delete_proposal(proposal_id)?;
-              *proposal_votes = None;
-              Ok(())
-            }
-            ProposalStatus::Rejected => {
-              // FIXME: Maybe add some slashing for the proposer?
-              Self::deposit_event(Event::<T>::ProposalRejected { proposal_id });
-              Self::delete_proposal(proposal_id)?;
-              *proposal_votes = None;
-              Ok(())
-            }
-            _ => Ok(()),
+    // Store the group public key confirmed by at least `threshold` qualified participants
+    fn try_complete_keygen_round(
+      asset_id: AssetId,
+      keygen_round: KeygenRound<T>,
+      qualified: Vec<T::AccountId>,
+    ) {
+      // count the confirmations of each group public key
+      let mut public_keys: Vec<(BoundedVec<u8, <T as pallet::Config>::StringLimit>, usize)> =
+        Vec::new();
+      for account_id in qualified.iter() {
+        if let Some(public_key) = Self::keygen_confirmations(asset_id, account_id) {
+          match public_keys.iter_mut().find(|(key, _)| *key == public_key) {
+            Some((_, confirmations)) => *confirmations += 1,
+            None => public_keys.push((public_key, 1)),
           }
         }
-        None => Err(Error::<T>::ProposalDoesNotExist),
-      })
-    }
+      }
 
-    // Process the original proposal call
-    fn process_proposal(proposal_id: Hash) -> Result<(), Error<T>> {
-      match Self::get_proposal(proposal_id)? {
-        // mint token
-        ProposalType::Mint(mint) => Self::process_mint(proposal_id, &mint)?,
-        // withdraw (burn)
-        ProposalType::Withdrawal(withdrawal) => Self::process_withdrawal(proposal_id, &withdrawal)?,
-        // update quorum configuration (threshold & member set)
-        ProposalType::UpdateConfiguration(members, threshold) => {
-          Self::process_update_configuration(&members, threshold)?
+      Self::close_keygen_round(asset_id);
+
+      let public_key = public_keys
+        .into_iter()
+        .max_by_key(|(_, confirmations)| *confirmations)
+        .filter(|(_, confirmations)| *confirmations >= keygen_round.threshold as usize)
+        .map(|(public_key, _)| public_key);
+
+      match (public_key, KeygenParticipants::<T>::try_from(qualified)) {
+        (Some(public_key), Ok(participants)) => {
+          Self::deposit_event(Event::<T>::KeygenCompleted {
+            asset_id,
+            round: keygen_round.round,
+            public_key: public_key.to_vec(),
+            participants: participants.to_vec(),
+            threshold: keygen_round.threshold,
+          });
+          GroupPublicKeys::<T>::insert(
+            asset_id,
+            GroupPublicKey {
+              round: keygen_round.round,
+              threshold: keygen_round.threshold,
+              public_key,
+              participants,
+              block_number: T::Security::get_current_block_count(),
+            },
+          );
         }
-      };
-      Self::deposit_event(Event::<T>::ProposalProcessed { proposal_id });
-      Ok(())
+        _ => {
+          Self::deposit_event(Event::<T>::KeygenFailed {
+            asset_id,
+            round: keygen_round.round,
+          });
+        }
+      }
     }
 
-    // Process the original proposal call
-    pub fn get_proposal(
-      proposal_id: Hash,
-    ) -> Result<
-      ProposalType<
-        T::AccountId,
-        T::BlockNumber,
-        BoundedVec<u8, <T as pallet::Config>::StringLimit>,
-        BoundedVec<T::AccountId, <T as pallet::Config>::VotesLimit>,
-      >,
-      Error<T>,
-    > {
-      let proposals = Proposals::<T>::get();
-      let proposal = proposals
-        .iter()
-        .find(|(hash, _, _)| *hash == proposal_id)
-        .ok_or(Error::<T>::ProposalDoesNotExist)?;
-      // FIXME: would be great to add a lifetime to the pallelt
-      Ok(proposal.2.clone())
+    // Delete the active round of the asset with all the participants submissions
+    fn close_keygen_round(asset_id: AssetId) {
+      if let Some(keygen_round) = KeygenRounds::<T>::take(asset_id) {
+        KeygenDeadlines::<T>::remove(keygen_round.phase_end, asset_id);
+      }
+      KeygenCommitments::<T>::remove_prefix(asset_id, None);
+      KeygenShares::<T>::remove_prefix(asset_id, None);
+      KeygenComplaints::<T>::remove_prefix(asset_id, None);
+      KeygenConfirmations::<T>::remove_prefix(asset_id, None);
     }
 
-    // Process withdrawal
-    fn process_withdrawal(
+    // Archive the finalized proposal with its votes, the proposal is still in queue
+    fn archive_proposal(
       proposal_id: Hash,
-      item: &Withdrawal<
-        T::AccountId,
-        T::BlockNumber,
-        BoundedVec<u8, <T as pallet::Config>::StringLimit>,
-      >,
-    ) -> Result<(), Error<T>> {
-      // 1. Make sure the currency_id exist and is enabled
-      ensure!(
-        T::AssetRegistry::is_currency_enabled(item.asset_id),
-        Error::<T>::AssetDisabled
-      );
-
-      // 2. Remove the token from the account
-      T::CurrencyTidefi::burn_from(item.asset_id, &item.account_id, item.amount)
-        .map_err(|_| Error::<T>::BurnFailed)?;
-
-      // 3. Add to burned queue, the quorum can poll and initiate the chain deposit
-      BurnedQueue::<T>::try_mutate(|burned_queue| {
-        burned_queue
-          .try_push((proposal_id, item.clone()))
-          .map_err(|_| Error::<T>::BurnedQueueOverflow)
-      })?;
+      status: ProposalResolution,
+      votes_for: BoundedVec<T::AccountId, T::VotesLimit>,
+      votes_against: BoundedVec<T::AccountId, T::VotesLimit>,
+    ) {
+      let (submitted_at, proposal) = match Self::proposals()
+        .into_iter()
+        .find(|(found_proposal_id, _, _)| *found_proposal_id == proposal_id)
+      {
+        Some((_, submitted_at, proposal)) => (submitted_at, proposal),
+        None => {
+          log!(error, "Can't archive proposal {}", proposal_id);
+          return;
+        }
+      };
 
-      // 4. Emit the event on chain
-      Self::deposit_event(Event::<T>::BurnedInitialized {
-        proposal_id,
-        account_id: item.account_id.clone(),
-        currency_id: item.asset_id,
-        amount: item.amount,
+      let index = ProposalArchiveHead::<T>::mutate(|head| {
+        let index = *head;
+        *head = head.saturating_add(1);
+        index
       });
 
-      Ok(())
-    }
+      Self::record_missed_votes(&votes_for, &votes_against, submitted_at);
 
-    // Process mint
-    fn process_mint(
-      proposal_id: Hash,
-      item: &Mint<T::AccountId, BoundedVec<u8, <T as pallet::Config>::StringLimit>>,
-    ) -> Result<(), Error<T>> {
-      // 1. Make sure the currency_id exist and is enabled
-      ensure!(
-        T::AssetRegistry::is_currency_enabled(item.currency_id),
-        Error::<T>::AssetDisabled
+      ProposalArchive::<T>::insert(
+        index,
+        ArchivedProposal {
+          proposal_id,
+          proposal,
+          status,
+          votes_for,
+          votes_against,
+          submitted_at,
+          resolved_at: T::Security::get_current_block_count(),
+        },
       );
+    }
 
-      // 2. Add `Amber` and `Red` to watch list
-      if item.compliance_level == ComplianceLevel::Amber
-        || item.compliance_level == ComplianceLevel::Red
-      {
-        Self::add_account_watch_list(
-          &item.account_id,
-          item.currency_id,
-          item.mint_amount,
-          item.compliance_level.clone(),
-          item.transaction_id.clone().to_vec(),
-          WatchListAction::Mint,
-        )?;
+    // Remove the archived proposals resolved before the retention window, oldest first
+    fn prune_proposal_archive_with_max_weight(max_weight: Weight) -> Weight {
+      let db_weight = <T as frame_system::Config>::DbWeight::get();
+      let mut weight_used = db_weight.reads(2);
+
+      let head = Self::proposal_archive_head();
+      let mut tail = Self::proposal_archive_tail();
+      if tail >= head {
+        return weight_used;
       }
 
-      // 3. Mint `Green` and `Amber`
-      if item.compliance_level == ComplianceLevel::Green
-        || item.compliance_level == ComplianceLevel::Amber
-      {
-        T::CurrencyTidefi::mint_into(item.currency_id, &item.account_id, item.mint_amount)
-          .map_err(|_| Error::<T>::MintFailed)?;
+      let current_block = T::Security::get_current_block_count();
+      let retention = T::ProposalArchiveRetention::get();
+      let prune_weight = db_weight.reads_writes(1, 1);
+
+      while tail < head && max_weight.saturating_sub(weight_used) >= prune_weight {
+        weight_used = weight_used.saturating_add(prune_weight);
+        match ProposalArchive::<T>::get(tail) {
+          // archived proposals are sorted by resolution block, stop at the first one to keep
+          Some(archived) if archived.resolved_at.saturating_add(retention) > current_block => break,
+          _ => ProposalArchive::<T>::remove(tail),
+        }
+        tail = tail.saturating_add(1);
+      }
 
-        Self::deposit_event(Event::<T>::Minted {
-          proposal_id,
-          account_id: item.account_id.clone(),
-          currency_id: item.currency_id,
-          amount: item.mint_amount,
-          transaction_id: item.transaction_id.clone().to_vec(),
-          compliance_level: item.compliance_level.clone(),
+      ProposalArchiveTail::<T>::put(tail);
+      weight_used.saturating_add(db_weight.writes(1))
+    }
+
+    // The archived proposal match all the filters set
+    fn is_archived_proposal_matching(
+      archived: &ArchivedProposal<T>,
+      filter: &ProposalArchiveFilter<T::AccountId>,
+    ) -> bool {
+      let (account_id, currency_id) = match &archived.proposal {
+        ProposalType::Mint(mint) => (Some(&mint.account_id), Some(mint.currency_id)),
+        ProposalType::Withdrawal(withdrawal) => {
+          (Some(&withdrawal.account_id), Some(withdrawal.asset_id))
+        }
+        ProposalType::UpdateConfiguration(_, _) => (None, None),
+      };
+
+      let is_account_matching = filter
+        .account_id
+        .as_ref()
+        .map_or(true, |filter_account_id| {
+          account_id == Some(filter_account_id)
+            || archived.votes_for.contains(filter_account_id)
+            || archived.votes_against.contains(filter_account_id)
         });
-      }
+      let is_currency_matching = filter.currency_id.map_or(true, |filter_currency_id| {
+        currency_id == Some(filter_currency_id)
+      });
+      let is_status_matching = filter
+        .status
+        .map_or(true, |filter_status| archived.status == filter_status);
 
-      Ok(())
+      is_account_matching && is_currency_matching && is_status_matching
     }
 
-    // Process configuration update
-    fn process_update_configuration(
-      members: &Vec<T::AccountId>,
-      threshold: u16,
-    ) -> Result<(), Error<T>> {
-      // 1. Remove all members existing
-      Members::<T>::remove_all();
+    // Page of the archived proposals matching the filter, newest first, starting before the
+    // `cursor` index. Returns the cursor of the next page, if any.
+    pub fn get_proposal_archive(
+      filter: ProposalArchiveFilter<T::AccountId>,
+      limit: u32,
+      cursor: Option<u32>,
+    ) -> (
+      Vec<(u32, ArchivedProposalInfo<T::AccountId, T::BlockNumber>)>,
+      Option<u32>,
+    ) {
+      let limit = limit.min(PROPOSAL_ARCHIVE_PAGE_LIMIT) as usize;
+      let head = Self::proposal_archive_head();
+      let start = cursor.map_or(head, |cursor| cursor.min(head));
+
+      let mut archived_proposals: Vec<(u32, ArchivedProposalInfo<T::AccountId, T::BlockNumber>)> =
+        (Self::proposal_archive_tail()..start)
+          .rev()
+          .filter_map(|index| ProposalArchive::<T>::get(index).map(|archived| (index, archived)))
+          .filter(|(_, archived)| Self::is_archived_proposal_matching(archived, &filter))
+          .take(limit.saturating_add(1))
+          .map(|(index, archived)| (index, archived.into()))
+          .collect();
+
+      // we fetched one more proposal to know if there is a next page
+      let next_cursor = if archived_proposals.len() > limit {
+        archived_proposals.truncate(limit);
+        archived_proposals.last().map(|(index, _)| *index)
+      } else {
+        None
+      };
+
+      (archived_proposals, next_cursor)
+    }
+
+    // Watch list entry of the account with this currency and transaction ID, still under review
+    // and with the expected compliance level
+    fn ensure_open_watch_list_entry(
+      account_id: &T::AccountId,
+      currency_id: CurrencyId,
+      transaction_id: Vec<u8>,
+      compliance_level: ComplianceLevel,
+    ) -> Result<
+      (
+        BoundedVec<u8, <T as pallet::Config>::StringLimit>,
+        WatchList<T::BlockNumber, BoundedVec<u8, <T as pallet::Config>::StringLimit>>,
+      ),
+      Error<T>,
+    > {
+      let transaction_id: BoundedVec<u8, <T as pallet::Config>::StringLimit> = transaction_id
+        .try_into()
+        .map_err(|_| Error::<T>::BadTransactionId)?;
+
+      let watch_list = Self::account_watch_list(account_id)
+        .and_then(|account_watch_list| {
+          account_watch_list.into_iter().find(|watch_list| {
+            watch_list.currency_id == currency_id && watch_list.transaction_id == transaction_id
+          })
+        })
+        .ok_or(Error::<T>::WatchListEntryNotFound)?;
+
+      ensure!(
+        !WatchListDecisions::<T>::contains_key(account_id, (currency_id, &transaction_id)),
+        Error::<T>::WatchListEntryAlreadyDecided
+      );
+      ensure!(
+        watch_list.compliance_level == compliance_level,
+        Error::<T>::WatchListBadDecision
+      );
 
-      // 2. Remove all public keys
-      //
-      // FIXME: We need to validate if we want to have quorum to resubmit keys?
-      PublicKeys::<T>::remove_all(None);
+      Ok((transaction_id, watch_list))
+    }
+
+    // The account has at least one watch list entry without compliance decision
+    fn has_open_watch_list_review(account_id: &T::AccountId) -> bool {
+      Self::account_watch_list(account_id)
+        .unwrap_or_default()
+        .iter()
+        .any(|watch_list| {
+          !WatchListDecisions::<T>::contains_key(
+            account_id,
+            (watch_list.currency_id, &watch_list.transaction_id),
+          )
+        })
+    }
 
-      // 3. Add new set
-      for account in members {
-        Members::<T>::insert(account, true);
+    // Thaw the wrapped assets frozen by the compliance, once all the reviews are closed
+    fn try_thaw_watched_account(account_id: &T::AccountId) {
+      if Self::has_open_watch_list_review(account_id) {
+        return;
+      }
+      if FrozenWatchedAccounts::<T>::take(account_id).is_none() {
+        return;
       }
 
-      // 4. Update threshold
-      Threshold::<T>::put(threshold);
+      let asset_ids: Vec<AssetId> = FrozenAccountAssets::<T>::iter_prefix(account_id)
+        .map(|(asset_id, _)| asset_id)
+        .collect();
+
+      for asset_id in asset_ids.iter() {
+        // the asset account may have been removed since the freeze
+        if let Err(error) = pallet_assets::Pallet::<T>::do_thaw(*asset_id, account_id) {
+          log!(
+            error,
+            "Can't thaw asset {} of {:?}: {:?}",
+            asset_id,
+            account_id,
+            error
+          );
+        }
+      }
+      FrozenAccountAssets::<T>::remove_prefix(account_id, None);
 
-      // 5. Emit event
-      Self::deposit_event(Event::<T>::ConfigurationUpdated {
-        threshold,
-        members: members.clone(),
+      Self::deposit_event(Event::<T>::WatchListAccountThawed {
+        account_id: account_id.clone(),
+        asset_ids,
       });
+    }
 
-      Ok(())
+    // Watch list of the account with the compliance decisions and the frozen assets
+    pub fn get_account_watch_list(
+      account_id: &T::AccountId,
+    ) -> AccountWatchListInfo<T::BlockNumber> {
+      let entries = Self::account_watch_list(account_id)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|watch_list| WatchListEntryInfo {
+          currency_id: watch_list.currency_id,
+          amount: BalanceInfo {
+            amount: watch_list.amount,
+          },
+          compliance_level: watch_list.compliance_level,
+          watch_action: watch_list.watch_action,
+          transaction_id: watch_list.transaction_id.to_vec(),
+          block_number: watch_list.block_number,
+          decision: WatchListDecisions::<T>::get(
+            account_id,
+            (watch_list.currency_id, &watch_list.transaction_id),
+          ),
+        })
+        .collect();
+
+      // all the wrapped assets held by a frozen account are locked, including the assets
+      // received after the freeze
+      let frozen_assets = if FrozenWatchedAccounts::<T>::contains_key(account_id) {
+        pallet_assets::Account::<T>::iter_prefix(account_id)
+          .map(|(asset_id, _)| asset_id)
+          .collect()
+      } else {
+        Vec::new()
+      };
+
+      AccountWatchListInfo {
+        entries,
+        frozen_assets,
+      }
     }
 
     // Delete specific proposal
     fn delete_proposal(proposal_id: Hash) -> Result<(), Error<T>> {
+      InactiveMemberRemovals::<T>::remove(proposal_id);
       Proposals::<T>::mutate(|proposals| {
         proposals.retain(|(found_proposal_id, _, _)| *found_proposal_id != proposal_id);
         Ok(())
@@ -1015,27 +4071,81 @@ pub mod pallet {
       amount: Balance,
       external_address: Vec<u8>,
     ) -> Result<(), DispatchError> {
-      let unique_id = T::Security::get_unique_id(account_id.clone());
-      let block_number = T::Security::get_current_block_count();
+      let external_address: BoundedVec<u8, <T as pallet::Config>::StringLimit> = external_address
+        .try_into()
+        .map_err(|_| Error::<T>::BadExternalAddress)?;
 
+      let fee = <Self as WithdrawalFeeExt>::quote_withdrawal_fee(asset_id, amount);
+      Self::queue_withdrawal(account_id, asset_id, amount, fee, external_address)
+    }
+  }
+
+  // delayed withdrawals extension exposed in other pallets
+  impl<T: Config> DelayedWithdrawalExt<T::AccountId, T::BlockNumber> for Pallet<T> {
+    fn add_new_delayed_withdrawal(
+      account_id: T::AccountId,
+      asset_id: CurrencyId,
+      amount: Balance,
+      external_address: Vec<u8>,
+    ) -> Result<(Hash, T::BlockNumber), DispatchError> {
       let external_address: BoundedVec<u8, <T as pallet::Config>::StringLimit> = external_address
         .try_into()
         .map_err(|_| Error::<T>::BadExternalAddress)?;
 
-      Proposals::<T>::try_append((
-        unique_id,
-        block_number,
-        ProposalType::Withdrawal(Withdrawal {
+      // make sure the amount cover the fee before holding the funds
+      let fee = <Self as WithdrawalFeeExt>::quote_withdrawal_fee(asset_id, amount);
+      ensure!(amount > fee, Error::<T>::WithdrawalBelowFee);
+
+      // hold the funds until the end of the delay
+      T::CurrencyTidefi::hold(asset_id, &account_id, amount).map_err(|_| Error::<T>::HoldFailed)?;
+
+      let withdrawal_id = T::Security::get_unique_id(account_id.clone());
+      let requested_at = T::Security::get_current_block_count();
+      let release_at = requested_at.saturating_add(T::WithdrawalDelay::get());
+      let index = DelayedWithdrawalsHead::<T>::mutate(|head| {
+        let index = *head;
+        *head = head.saturating_add(1);
+        index
+      });
+
+      DelayedWithdrawals::<T>::insert(
+        withdrawal_id,
+        DelayedWithdrawal {
           account_id,
-          amount,
           asset_id,
+          amount,
+          fee,
           external_address,
-          block_number,
-        }),
-      ))
-      .map_err(|_| Error::<T>::ProposalsCapExceeded)?;
+          requested_at,
+          release_at,
+        },
+      );
+      DelayedWithdrawalsQueue::<T>::insert(index, withdrawal_id);
 
-      Ok(())
+      Ok((withdrawal_id, release_at))
+    }
+  }
+
+  // withdrawal fees extension exposed in other pallets
+  impl<T: Config> WithdrawalFeeExt for Pallet<T> {
+    fn quote_withdrawal_fee(asset_id: CurrencyId, amount: Balance) -> Balance {
+      Self::withdrawal_fees(asset_id)
+        .map(|fee| fee.flat.saturating_add(fee.percentage * amount))
+        .unwrap_or_default()
+    }
+  }
+
+  // lock the wrapped assets received by the accounts frozen by the compliance, the assets held
+  // at the freeze are also frozen in `pallet_assets`
+  impl<T: Config> pallet_assets::FrozenBalance<AssetId, T::AccountId, Balance> for Pallet<T> {
+    fn frozen_balance(asset_id: AssetId, who: &T::AccountId) -> Option<Balance> {
+      if FrozenWatchedAccounts::<T>::contains_key(who) {
+        Some(pallet_assets::Pallet::<T>::balance(asset_id, who))
+      } else {
+        None
+      }
     }
+
+    fn died(_asset_id: AssetId, _who: &T::AccountId) {}
   }
 }
@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::pallet as pallet_quorum;
+use crate::{pallet as pallet_quorum, ProposalThresholds};
 use frame_benchmarking::frame_support::traits::tokens::{DepositConsequence, WithdrawConsequence};
 use frame_support::{
   parameter_types,
@@ -29,6 +29,7 @@ use frame_support::{
   PalletId,
 };
 use frame_system as system;
+use pallet_fees::UsdtValueExt;
 use sp_core::H256;
 use sp_runtime::{
   testing::Header,
@@ -115,6 +116,20 @@ parameter_types! {
   pub const WatchListLimit: u32 = 10000;
   // The maximum number of pubkey each asset can have, should alway be more more than the current quorum active member set
   pub const PubkeyLimitPerAsset: u32 = 10;
+  // The number of blocks of each phase of a key generation round
+  pub const KeygenPhaseTimeout: u64 = 10;
+  // The maximum length of the key generation commitments and shares
+  pub const KeygenPayloadLimit: u32 = 1024;
+  // The number of blocks a finalized proposal is kept in the archive
+  pub const ProposalArchiveRetention: u64 = 100;
+  // The number of blocks a processed mint is kept to reject the replays of its deposit
+  pub const MintReplayHorizon: u64 = 1_000;
+  pub const WithdrawalDelay: u64 = 10;
+  pub const BurnedConfirmationTimeout: u64 = 20;
+  pub const MaxMissedProposals: u32 = 2;
+  pub const MemberInactivityPeriod: u64 = 1_000;
+  pub const MemberKeysGracePeriod: u64 = 10;
+  pub const WithdrawalFeesAccount: u64 = 99;
 }
 
 impl pallet_assets::Config for Test {
@@ -128,7 +143,7 @@ impl pallet_assets::Config for Test {
   type MetadataDepositPerByte = MetadataDepositPerByte;
   type ApprovalDeposit = ApprovalDeposit;
   type StringLimit = StringLimit;
-  type Freezer = ();
+  type Freezer = Quorum;
   type Extra = ();
   type WeightInfo = ();
   type AssetAccountDeposit = ConstU128<0>;
@@ -149,6 +164,7 @@ impl pallet_balances::Config for Test {
 parameter_types! {
   pub const TidefiPalletId: PalletId = PalletId(*b"wrpr*pal");
   pub const AssetRegistryPalletId: PalletId = PalletId(*b"asst*pal");
+  pub const WithdrawalPeriod: u64 = 100;
 }
 
 impl pallet_quorum::Config for Test {
@@ -158,6 +174,7 @@ impl pallet_quorum::Config for Test {
   type Security = Security;
   type CurrencyTidefi = Adapter<AccountId>;
   type AssetRegistry = AssetRegistry;
+  type Fees = UsdtValue;
   type ProposalsCap = ProposalsCap;
   type BurnedCap = BurnedCap;
   type ProposalLifetime = ProposalLifetime;
@@ -165,6 +182,18 @@ impl pallet_quorum::Config for Test {
   type VotesLimit = VotesLimit;
   type WatchListLimit = WatchListLimit;
   type PubkeyLimitPerAsset = PubkeyLimitPerAsset;
+  type KeygenPhaseTimeout = KeygenPhaseTimeout;
+  type KeygenPayloadLimit = KeygenPayloadLimit;
+  type ProposalArchiveRetention = ProposalArchiveRetention;
+  type ComplianceOrigin = EnsureRoot<Self::AccountId>;
+  type MintReplayHorizon = MintReplayHorizon;
+  type WithdrawalDelay = WithdrawalDelay;
+  type BurnedConfirmationTimeout = BurnedConfirmationTimeout;
+  type MaxMissedProposals = MaxMissedProposals;
+  type MemberInactivityPeriod = MemberInactivityPeriod;
+  type MemberKeysGracePeriod = MemberKeysGracePeriod;
+  type ForceOrigin = EnsureRoot<Self::AccountId>;
+  type WithdrawalFeesAccount = WithdrawalFeesAccount;
 }
 
 impl pallet_security::Config for Test {
@@ -176,6 +205,7 @@ impl pallet_asset_registry::Config for Test {
   type WeightInfo = pallet_asset_registry::weights::SubstrateWeight<Test>;
   type AssetRegistryPalletId = AssetRegistryPalletId;
   type CurrencyTidefi = Adapter<AccountId>;
+  type WithdrawalPeriod = WithdrawalPeriod;
 }
 
 // this is only the mock for benchmarking, it's implemented directly in the runtime
@@ -331,6 +361,15 @@ where
   }
 }
 
+// the assets are valued 1:1 in USDT, it's implemented by the fees pallet in the runtime
+pub struct UsdtValue;
+
+impl UsdtValueExt for UsdtValue {
+  fn get_usdt_value(_currency_id: CurrencyId, amount: Balance) -> Result<Balance, DispatchError> {
+    Ok(amount)
+  }
+}
+
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
   let mut t = system::GenesisConfig::default()
@@ -343,6 +382,8 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
     enabled: false,
     members: vec![1],
     threshold: 1,
+    proposal_thresholds: ProposalThresholds::from_threshold(1),
+    vote_weights: Vec::new(),
   }
   .assimilate_storage(&mut t)
   .unwrap();
@@ -16,14 +16,24 @@
 
 use crate::{
   mock::{
-    new_test_ext, Origin, ProposalLifetime, PubkeyLimitPerAsset, Quorum, Security, StringLimit,
-    System, Test,
+    new_test_ext, Assets, Balances, BurnedConfirmationTimeout, Event as MockEvent,
+    KeygenPhaseTimeout, MemberInactivityPeriod, MemberKeysGracePeriod, Origin,
+    ProposalArchiveRetention, ProposalLifetime, PubkeyLimitPerAsset, Quorum, Security, StringLimit,
+    System, Test, WithdrawalFeesAccount,
   },
   pallet::*,
+  ArchivedProposalBody, ProposalArchiveFilter, ProposalResolution, ProposalThresholds,
+  WatchListDecision, WithdrawalFee,
 };
-use frame_support::{assert_ok, traits::Hooks, BoundedVec};
+use frame_support::{
+  assert_err, assert_noop, assert_ok,
+  traits::{Hooks, StorageVersion},
+  BoundedVec,
+};
+use sp_runtime::{traits::BadOrigin, Permill};
 use tidefi_primitives::{
-  pallet::SecurityExt, ComplianceLevel, CurrencyId, Hash, Mint, ProposalType,
+  pallet::{QuorumExt, SecurityExt},
+  BalanceInfo, ComplianceLevel, CurrencyId, Hash, Mint, ProposalType, Withdrawal,
 };
 
 #[test]
@@ -106,6 +116,152 @@ pub fn should_remove_expired() {
   });
 }
 
+#[test]
+pub fn should_archive_finalized_proposals() {
+  new_test_ext().execute_with(|| {
+    let alice = Origin::signed(1u64);
+    let public_key: BoundedVec<u8, StringLimit> = "pubkey".as_bytes().to_vec().try_into().unwrap();
+    let public_keys: BoundedVec<(u64, BoundedVec<u8, StringLimit>), PubkeyLimitPerAsset> =
+      vec![(1u64, public_key)].try_into().unwrap();
+    PublicKeys::<Test>::insert(1, public_keys);
+
+    let mint_proposal = |account_id: u64, currency_id: CurrencyId, transaction_id: &str| {
+      ProposalType::Mint(Mint {
+        account_id,
+        currency_id,
+        mint_amount: 1_000_000_000_000,
+        transaction_id: transaction_id.as_bytes().to_vec(),
+        compliance_level: ComplianceLevel::Green,
+      })
+    };
+    let no_filter = ProposalArchiveFilter {
+      account_id: None,
+      currency_id: None,
+      status: None,
+    };
+    let archive_indexes = |filter: ProposalArchiveFilter<u64>| {
+      Quorum::get_proposal_archive(filter, 10, None)
+        .0
+        .iter()
+        .map(|(index, _)| *index)
+        .collect::<Vec<u32>>()
+    };
+
+    // approved by the only member
+    assert_ok!(Quorum::submit_proposal(
+      alice.clone(),
+      mint_proposal(2, CurrencyId::Tifi, "approved_tx")
+    ));
+    let approved_proposal_id = Quorum::proposals().last().unwrap().0;
+    assert_ok!(Quorum::acknowledge_proposal(
+      alice.clone(),
+      approved_proposal_id
+    ));
+
+    // rejected by the only member
+    assert_ok!(Quorum::submit_proposal(
+      alice.clone(),
+      mint_proposal(3, CurrencyId::Wrapped(4), "rejected_tx")
+    ));
+    let rejected_proposal_id = Quorum::proposals().last().unwrap().0;
+    assert_ok!(Quorum::reject_proposal(alice.clone(), rejected_proposal_id));
+
+    // expired without any vote
+    assert_ok!(Quorum::submit_proposal(
+      alice,
+      mint_proposal(3, CurrencyId::Tifi, "expired_tx")
+    ));
+    let expired_proposal_id = Quorum::proposals().last().unwrap().0;
+    let expired_at = ProposalLifetime::get() + 2;
+    pallet_security::CurrentBlockCount::<Test>::put(expired_at);
+    Quorum::on_idle(0, 1_000_000_000_000);
+
+    assert!(Quorum::proposals().is_empty());
+    assert_eq!(Quorum::proposal_archive_head(), 3);
+
+    let approved = Quorum::proposal_archive(0).unwrap();
+    assert_eq!(approved.proposal_id, approved_proposal_id);
+    assert_eq!(approved.status, ProposalResolution::Approved);
+    assert_eq!(approved.votes_for.to_vec(), vec![1]);
+    assert!(approved.votes_against.is_empty());
+    assert_eq!(approved.resolved_at, 0);
+
+    let rejected = Quorum::proposal_archive(1).unwrap();
+    assert_eq!(rejected.proposal_id, rejected_proposal_id);
+    assert_eq!(rejected.status, ProposalResolution::Rejected);
+    assert!(rejected.votes_for.is_empty());
+    assert_eq!(rejected.votes_against.to_vec(), vec![1]);
+
+    let expired = Quorum::proposal_archive(2).unwrap();
+    assert_eq!(expired.proposal_id, expired_proposal_id);
+    assert_eq!(expired.status, ProposalResolution::Expired);
+    assert!(expired.votes_for.is_empty() && expired.votes_against.is_empty());
+    assert_eq!(expired.submitted_at, 0);
+    assert_eq!(expired.resolved_at, expired_at);
+
+    // newest first, paged with the cursor
+    let (archived_proposals, cursor) = Quorum::get_proposal_archive(no_filter.clone(), 2, None);
+    assert_eq!(archived_proposals.len(), 2);
+    assert_eq!(archived_proposals[0].0, 2);
+    assert_eq!(archived_proposals[1].0, 1);
+    assert_eq!(cursor, Some(1));
+    let (archived_proposals, cursor) = Quorum::get_proposal_archive(no_filter.clone(), 2, cursor);
+    assert_eq!(archived_proposals.len(), 1);
+    assert_eq!(archived_proposals[0].1.proposal_id, approved_proposal_id);
+    assert_eq!(
+      archived_proposals[0].1.proposal,
+      ArchivedProposalBody::Mint {
+        account_id: 2,
+        currency_id: CurrencyId::Tifi,
+        amount: BalanceInfo {
+          amount: 1_000_000_000_000
+        },
+        transaction_id: "approved_tx".as_bytes().to_vec(),
+      }
+    );
+    assert_eq!(cursor, None);
+
+    // filter by account of the mint or by voter, asset and status
+    assert_eq!(
+      archive_indexes(ProposalArchiveFilter {
+        account_id: Some(3),
+        ..no_filter.clone()
+      }),
+      vec![2, 1]
+    );
+    assert_eq!(
+      archive_indexes(ProposalArchiveFilter {
+        account_id: Some(1),
+        ..no_filter.clone()
+      }),
+      vec![1, 0]
+    );
+    assert_eq!(
+      archive_indexes(ProposalArchiveFilter {
+        currency_id: Some(CurrencyId::Tifi),
+        ..no_filter.clone()
+      }),
+      vec![2, 0]
+    );
+    assert_eq!(
+      archive_indexes(ProposalArchiveFilter {
+        account_id: Some(3),
+        status: Some(ProposalResolution::Rejected),
+        ..no_filter.clone()
+      }),
+      vec![1]
+    );
+
+    // the proposals resolved before the retention window are pruned
+    pallet_security::CurrentBlockCount::<Test>::put(ProposalArchiveRetention::get());
+    Quorum::on_idle(0, 1_000_000_000_000);
+    assert_eq!(Quorum::proposal_archive_tail(), 2);
+    assert!(Quorum::proposal_archive(0).is_none());
+    assert!(Quorum::proposal_archive(1).is_none());
+    assert_eq!(archive_indexes(no_filter), vec![2]);
+  });
+}
+
 #[test]
 pub fn test_vec_shuffle() {
   // switching block hash should give new shuffle
@@ -123,3 +279,1109 @@ pub fn test_vec_shuffle() {
     assert_eq!(Quorum::create_shuffle(4), vec![3, 2, 0, 1]);
   });
 }
+
+#[test]
+pub fn keygen_round_with_timeout_complaints_and_rotation() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let asset_id = 1;
+    for account_id in [1_u64, 2, 3] {
+      Members::<Test>::insert(account_id, true);
+    }
+    Threshold::<Test>::put(2);
+    Thresholds::<Test>::put(ProposalThresholds::from_threshold(2));
+
+    // only the quorum members can start a round
+    assert_noop!(
+      Quorum::start_keygen(Origin::signed(4u64), asset_id),
+      Error::<Test>::AccessDenied
+    );
+    assert_ok!(Quorum::start_keygen(Origin::signed(1u64), asset_id));
+    assert_noop!(
+      Quorum::start_keygen(Origin::signed(2u64), asset_id),
+      Error::<Test>::KeygenInProgress
+    );
+
+    let keygen_round = Quorum::keygen_rounds(asset_id).unwrap();
+    assert_eq!(keygen_round.round, 1);
+    assert_eq!(keygen_round.threshold, 2);
+    assert_eq!(keygen_round.participants.len(), 3);
+
+    // all the participants published their commitments, move to the share phase
+    for account_id in [1_u64, 2, 3] {
+      assert_ok!(Quorum::submit_keygen_commitment(
+        Origin::signed(account_id),
+        asset_id,
+        1,
+        "commitment".as_bytes().to_vec()
+      ));
+    }
+    assert_eq!(
+      Quorum::keygen_rounds(asset_id).unwrap().phase,
+      KeygenPhase::Share
+    );
+
+    // a share is missing
+    assert_noop!(
+      Quorum::submit_keygen_shares(
+        Origin::signed(1u64),
+        asset_id,
+        1,
+        vec![(2, "share".as_bytes().to_vec())]
+      ),
+      Error::<Test>::KeygenBadShares
+    );
+    for (dealer, recipients) in [(1_u64, [2_u64, 3]), (2, [1, 3])] {
+      assert_ok!(Quorum::submit_keygen_shares(
+        Origin::signed(dealer),
+        asset_id,
+        1,
+        recipients
+          .iter()
+          .map(|recipient| (*recipient, "share".as_bytes().to_vec()))
+          .collect()
+      ));
+    }
+
+    // the third participant didn't distribute the shares before the timeout
+    let phase_end = Quorum::keygen_rounds(asset_id).unwrap().phase_end;
+    pallet_security::CurrentBlockCount::<Test>::put(phase_end);
+    Quorum::on_initialize(phase_end);
+    assert_eq!(
+      Quorum::keygen_rounds(asset_id).unwrap().phase,
+      KeygenPhase::Complaint
+    );
+    assert_eq!(
+      Quorum::keygen_rounds(asset_id).unwrap().phase_end,
+      phase_end + KeygenPhaseTimeout::get()
+    );
+
+    // a single complaint doesn't disqualify with a threshold of 2
+    assert_ok!(Quorum::submit_keygen_complaint(
+      Origin::signed(1u64),
+      asset_id,
+      1,
+      2
+    ));
+    assert_noop!(
+      Quorum::submit_keygen_complaint(Origin::signed(1u64), asset_id, 1, 2),
+      Error::<Test>::KeygenAlreadySubmitted
+    );
+    assert_noop!(
+      Quorum::confirm_keygen(
+        Origin::signed(3u64),
+        asset_id,
+        1,
+        "group_pubkey".as_bytes().to_vec()
+      ),
+      Error::<Test>::KeygenNotParticipant
+    );
+
+    // all the qualified participants confirmed the group key
+    for account_id in [1_u64, 2] {
+      assert_ok!(Quorum::confirm_keygen(
+        Origin::signed(account_id),
+        asset_id,
+        1,
+        "group_pubkey".as_bytes().to_vec()
+      ));
+    }
+
+    // the complaints are open until the end of the complaint phase
+    let phase_end = Quorum::keygen_rounds(asset_id).unwrap().phase_end;
+    assert!(Quorum::keygen_deadlines(phase_end, asset_id).is_some());
+    pallet_security::CurrentBlockCount::<Test>::put(phase_end - 1);
+    Quorum::on_initialize(phase_end - 1);
+    assert!(Quorum::keygen_rounds(asset_id).is_some());
+
+    pallet_security::CurrentBlockCount::<Test>::put(phase_end);
+    Quorum::on_initialize(phase_end);
+    assert!(Quorum::keygen_rounds(asset_id).is_none());
+    assert!(Quorum::keygen_deadlines(phase_end, asset_id).is_none());
+    assert!(Quorum::keygen_commitments(asset_id, 1).is_none());
+    let group_public_key = Quorum::group_public_keys(asset_id).unwrap();
+    assert_eq!(group_public_key.round, 1);
+    assert_eq!(group_public_key.threshold, 2);
+    assert_eq!(
+      group_public_key.public_key.to_vec(),
+      b"group_pubkey".to_vec()
+    );
+    let mut participants = group_public_key.participants.to_vec();
+    participants.sort_unstable();
+    assert_eq!(participants, vec![1, 2]);
+
+    // the member set update start the rotation of the group key
+    for account_id in [1_u64, 2] {
+      let public_key: BoundedVec<u8, StringLimit> =
+        "pubkey".as_bytes().to_vec().try_into().unwrap();
+      let public_keys: BoundedVec<(u64, BoundedVec<u8, StringLimit>), PubkeyLimitPerAsset> =
+        vec![(account_id, public_key)].try_into().unwrap();
+      PublicKeys::<Test>::insert(account_id as u32, public_keys);
+    }
+    assert_ok!(Quorum::submit_proposal(
+      Origin::signed(1u64),
+      ProposalType::UpdateConfiguration(vec![1, 2], 2)
+    ));
+    let proposal_id = Quorum::proposals()[0].0;
+    assert_ok!(Quorum::acknowledge_proposal(
+      Origin::signed(1u64),
+      proposal_id
+    ));
+    assert_ok!(Quorum::acknowledge_proposal(
+      Origin::signed(2u64),
+      proposal_id
+    ));
+
+    let keygen_round = Quorum::keygen_rounds(asset_id).unwrap();
+    assert_eq!(keygen_round.round, 2);
+    assert_eq!(keygen_round.phase, KeygenPhase::Commitment);
+    assert_eq!(keygen_round.participants.len(), 2);
+    System::assert_has_event(MockEvent::Quorum(Event::KeygenStarted {
+      asset_id,
+      round: 2,
+      participants: keygen_round.participants.to_vec(),
+      threshold: 2,
+      phase_end: keygen_round.phase_end,
+    }));
+
+    // the previous key is kept until the rotation is completed
+    assert_eq!(Quorum::group_public_keys(asset_id).unwrap().round, 1);
+  });
+}
+
+#[test]
+pub fn compliance_decide_watch_list_entries_and_freeze_account() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let alice = Origin::signed(1u64);
+    let public_key: BoundedVec<u8, StringLimit> = "pubkey".as_bytes().to_vec().try_into().unwrap();
+    let public_keys: BoundedVec<(u64, BoundedVec<u8, StringLimit>), PubkeyLimitPerAsset> =
+      vec![(1u64, public_key)].try_into().unwrap();
+    PublicKeys::<Test>::insert(1, public_keys);
+
+    // the account hold a wrapped asset
+    let asset_id = 4;
+    assert_ok!(Assets::force_create(Origin::root(), asset_id, 1, true, 1));
+    assert_ok!(Assets::mint(Origin::signed(1), asset_id, 2, 1_000));
+
+    // `Red` mint is held, `Amber` mint is minted
+    let amount = 1_000_000_000_000;
+    for (transaction_id, compliance_level) in [
+      ("red_tx", ComplianceLevel::Red),
+      ("amber_tx", ComplianceLevel::Amber),
+    ] {
+      assert_ok!(Quorum::submit_proposal(
+        alice.clone(),
+        ProposalType::Mint(Mint {
+          account_id: 2,
+          currency_id: CurrencyId::Tifi,
+          mint_amount: amount,
+          transaction_id: transaction_id.as_bytes().to_vec(),
+          compliance_level,
+        })
+      ));
+      let proposal_id = Quorum::proposals().last().unwrap().0;
+      assert_ok!(Quorum::acknowledge_proposal(alice.clone(), proposal_id));
+    }
+    assert_eq!(Quorum::account_watch_list(2).unwrap().len(), 2);
+    assert_eq!(Balances::free_balance(2), amount);
+
+    // freeze the wrapped assets while the reviews are open
+    assert_noop!(
+      Quorum::freeze_watched_account(Origin::signed(1u64), 2, 1),
+      BadOrigin
+    );
+    assert_noop!(
+      Quorum::freeze_watched_account(Origin::root(), 3, 1),
+      Error::<Test>::WatchListNoOpenReview
+    );
+    assert_noop!(
+      Quorum::freeze_watched_account(Origin::root(), 2, 0),
+      Error::<Test>::WatchListBadAssetCount
+    );
+    assert_ok!(Quorum::freeze_watched_account(Origin::root(), 2, 1));
+    System::assert_has_event(MockEvent::Quorum(Event::WatchListAccountFrozen {
+      account_id: 2,
+      asset_ids: vec![asset_id],
+    }));
+    assert_noop!(
+      Quorum::freeze_watched_account(Origin::root(), 2, 1),
+      Error::<Test>::WatchListAccountAlreadyFrozen
+    );
+    assert_noop!(
+      Assets::transfer(Origin::signed(2), asset_id, 3, 100),
+      pallet_assets::Error::<Test>::Frozen
+    );
+
+    // the wrapped assets received after the freeze are locked too
+    let received_asset_id = 5;
+    assert_ok!(Assets::force_create(
+      Origin::root(),
+      received_asset_id,
+      1,
+      true,
+      1
+    ));
+    assert_ok!(Assets::mint(Origin::signed(1), received_asset_id, 2, 1_000));
+    assert_noop!(
+      Assets::transfer(Origin::signed(2), received_asset_id, 3, 100),
+      pallet_assets::Error::<Test>::BalanceLow
+    );
+    let mut frozen_assets = Quorum::get_account_watch_list(&2).frozen_assets;
+    frozen_assets.sort_unstable();
+    assert_eq!(frozen_assets, vec![asset_id, received_asset_id]);
+
+    // the decisions must match the compliance level of the entry
+    assert_noop!(
+      Quorum::approve_watch_list_mint(
+        Origin::root(),
+        2,
+        CurrencyId::Tifi,
+        "amber_tx".as_bytes().to_vec()
+      ),
+      Error::<Test>::WatchListBadDecision
+    );
+    assert_noop!(
+      Quorum::review_watch_list_entry(
+        Origin::root(),
+        2,
+        CurrencyId::Tifi,
+        "red_tx".as_bytes().to_vec()
+      ),
+      Error::<Test>::WatchListBadDecision
+    );
+    assert_noop!(
+      Quorum::reject_watch_list_mint(
+        Origin::root(),
+        2,
+        CurrencyId::Tifi,
+        "unknown_tx".as_bytes().to_vec()
+      ),
+      Error::<Test>::WatchListEntryNotFound
+    );
+    assert_noop!(
+      Quorum::reject_watch_list_mint(
+        Origin::root(),
+        2,
+        CurrencyId::Wrapped(asset_id),
+        "red_tx".as_bytes().to_vec()
+      ),
+      Error::<Test>::WatchListEntryNotFound
+    );
+
+    // the account stay frozen while the `Red` mint is under review
+    assert_ok!(Quorum::review_watch_list_entry(
+      Origin::root(),
+      2,
+      CurrencyId::Tifi,
+      "amber_tx".as_bytes().to_vec()
+    ));
+    assert!(Quorum::frozen_account_assets(2, asset_id).is_some());
+
+    // approving the `Red` mint close the last review, mint and thaw the account
+    assert_ok!(Quorum::approve_watch_list_mint(
+      Origin::root(),
+      2,
+      CurrencyId::Tifi,
+      "red_tx".as_bytes().to_vec()
+    ));
+    assert_eq!(Balances::free_balance(2), amount * 2);
+    System::assert_has_event(MockEvent::Quorum(Event::WatchListMintApproved {
+      account_id: 2,
+      currency_id: CurrencyId::Tifi,
+      amount,
+      transaction_id: "red_tx".as_bytes().to_vec(),
+    }));
+    System::assert_has_event(MockEvent::Quorum(Event::WatchListAccountThawed {
+      account_id: 2,
+      asset_ids: vec![asset_id],
+    }));
+    assert_ok!(Assets::transfer(Origin::signed(2), asset_id, 3, 100));
+    assert_ok!(Assets::transfer(
+      Origin::signed(2),
+      received_asset_id,
+      3,
+      100
+    ));
+
+    assert_noop!(
+      Quorum::reject_watch_list_mint(
+        Origin::root(),
+        2,
+        CurrencyId::Tifi,
+        "red_tx".as_bytes().to_vec()
+      ),
+      Error::<Test>::WatchListEntryAlreadyDecided
+    );
+
+    let account_watch_list = Quorum::get_account_watch_list(&2);
+    assert!(account_watch_list.frozen_assets.is_empty());
+    assert_eq!(
+      account_watch_list
+        .entries
+        .iter()
+        .map(|entry| entry.decision)
+        .collect::<Vec<_>>(),
+      vec![
+        Some((WatchListDecision::Approved, 0)),
+        Some((WatchListDecision::Reviewed, 0))
+      ]
+    );
+  });
+}
+
+#[test]
+pub fn should_reject_replayed_mint() {
+  new_test_ext().execute_with(|| {
+    let alice = Origin::signed(1u64);
+    let public_key: BoundedVec<u8, StringLimit> = "pubkey".as_bytes().to_vec().try_into().unwrap();
+    let public_keys: BoundedVec<(u64, BoundedVec<u8, StringLimit>), PubkeyLimitPerAsset> =
+      vec![(1u64, public_key)].try_into().unwrap();
+    PublicKeys::<Test>::insert(1, public_keys);
+
+    let amount = 1_000_000_000_000;
+    let mint_proposal = |currency_id: CurrencyId| {
+      ProposalType::Mint(Mint {
+        account_id: 2,
+        currency_id,
+        mint_amount: amount,
+        transaction_id: "deposit_tx".as_bytes().to_vec(),
+        compliance_level: ComplianceLevel::Green,
+      })
+    };
+    let transaction_id: BoundedVec<u8, StringLimit> =
+      "deposit_tx".as_bytes().to_vec().try_into().unwrap();
+
+    // the same deposit can't be pending in two proposals
+    assert_ok!(Quorum::submit_proposal(
+      alice.clone(),
+      mint_proposal(CurrencyId::Tifi)
+    ));
+    assert_noop!(
+      Quorum::submit_proposal(alice.clone(), mint_proposal(CurrencyId::Tifi)),
+      Error::<Test>::MintAlreadySubmitted
+    );
+
+    // the same transaction ID on another currency is another deposit
+    assert_ok!(Quorum::submit_proposal(
+      alice.clone(),
+      mint_proposal(CurrencyId::Wrapped(4))
+    ));
+
+    // the deposit can't be minted twice once processed
+    let proposal_id = Quorum::proposals().first().unwrap().0;
+    assert_ok!(Quorum::acknowledge_proposal(alice.clone(), proposal_id));
+    assert_eq!(Balances::free_balance(2), amount);
+    assert_eq!(
+      Quorum::processed_mints(CurrencyId::Tifi, &transaction_id),
+      Some(0)
+    );
+    assert_noop!(
+      Quorum::submit_proposal(alice.clone(), mint_proposal(CurrencyId::Tifi)),
+      Error::<Test>::MintAlreadySubmitted
+    );
+
+    // a proposal already in queue is not processed twice
+    let replayed_proposal_id = Hash::zero();
+    assert_ok!(Proposals::<Test>::try_append((
+      replayed_proposal_id,
+      Security::get_current_block_count(),
+      ProposalType::Mint(Mint {
+        account_id: 2,
+        currency_id: CurrencyId::Tifi,
+        mint_amount: amount,
+        transaction_id: transaction_id.clone(),
+        compliance_level: ComplianceLevel::Green,
+      })
+    )));
+    assert_err!(
+      Quorum::acknowledge_proposal(alice.clone(), replayed_proposal_id),
+      Error::<Test>::MintAlreadySubmitted
+    );
+    assert_eq!(Balances::free_balance(2), amount);
+
+    // the processed deposits expire after the replay horizon
+    Proposals::<Test>::kill();
+    pallet_security::CurrentBlockCount::<Test>::put(MintReplayHorizon::get() - 1);
+    Quorum::on_idle(0, 1_000_000_000_000);
+    assert!(Quorum::processed_mints(CurrencyId::Tifi, &transaction_id).is_some());
+
+    pallet_security::CurrentBlockCount::<Test>::put(MintReplayHorizon::get());
+    Quorum::on_idle(0, 1_000_000_000_000);
+    assert!(Quorum::processed_mints(CurrencyId::Tifi, &transaction_id).is_none());
+    assert_eq!(Quorum::processed_mints_tail(), 1);
+    assert_ok!(Quorum::submit_proposal(
+      alice,
+      mint_proposal(CurrencyId::Tifi)
+    ));
+  });
+}
+
+#[test]
+pub fn should_collect_and_refund_withdrawal_fee() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let alice = Origin::signed(1u64);
+    let public_key: BoundedVec<u8, StringLimit> = "pubkey".as_bytes().to_vec().try_into().unwrap();
+    let public_keys: BoundedVec<(u64, BoundedVec<u8, StringLimit>), PubkeyLimitPerAsset> =
+      vec![(1u64, public_key)].try_into().unwrap();
+    PublicKeys::<Test>::insert(1, public_keys);
+
+    let asset_id = 4;
+    assert_ok!(Assets::force_create(Origin::root(), asset_id, 1, true, 1));
+    assert_ok!(Assets::mint(Origin::signed(1), asset_id, 2, 1_000_000));
+
+    // no fee until the quorum vote a fee schedule
+    assert_eq!(
+      Quorum::quote_withdrawal(CurrencyId::Wrapped(asset_id), 100_000),
+      Ok((0, 100_000))
+    );
+
+    let fee = WithdrawalFee {
+      flat: 1_000,
+      percentage: Permill::from_percent(1),
+    };
+    assert_ok!(Quorum::submit_withdrawal_fee(
+      alice.clone(),
+      CurrencyId::Wrapped(asset_id),
+      fee
+    ));
+    System::assert_has_event(MockEvent::Quorum(Event::WithdrawalFeeUpdated {
+      currency_id: CurrencyId::Wrapped(asset_id),
+      fee,
+    }));
+    assert_eq!(
+      Quorum::quote_withdrawal(CurrencyId::Wrapped(asset_id), 100_000),
+      Ok((2_000, 98_000))
+    );
+    assert_noop!(
+      Quorum::quote_withdrawal(CurrencyId::Wrapped(asset_id), 1_000),
+      Error::<Test>::WithdrawalBelowFee
+    );
+
+    // the fee is collected when the withdrawal is queued
+    assert_ok!(<Quorum as QuorumExt<_, _>>::add_new_withdrawal_in_queue(
+      2,
+      CurrencyId::Wrapped(asset_id),
+      100_000,
+      vec![1; 32]
+    ));
+    assert_eq!(
+      Assets::balance(asset_id, WithdrawalFeesAccount::get()),
+      2_000
+    );
+    assert_eq!(Assets::balance(asset_id, 2), 998_000);
+    let (proposal_id, _, proposal) = Quorum::proposals().first().cloned().unwrap();
+    match proposal {
+      ProposalType::Withdrawal(withdrawal) => assert_eq!(withdrawal.amount, 98_000),
+      _ => panic!("Expected a withdrawal proposal"),
+    }
+
+    // the fee is refunded when the proposal is rejected
+    assert_ok!(Quorum::reject_proposal(alice.clone(), proposal_id));
+    assert_eq!(Assets::balance(asset_id, WithdrawalFeesAccount::get()), 0);
+    assert_eq!(Assets::balance(asset_id, 2), 1_000_000);
+
+    // the fee is kept when the withdrawal is burned
+    assert_ok!(<Quorum as QuorumExt<_, _>>::add_new_withdrawal_in_queue(
+      2,
+      CurrencyId::Wrapped(asset_id),
+      100_000,
+      vec![1; 32]
+    ));
+    let proposal_id = Quorum::proposals().first().unwrap().0;
+    assert_ok!(Quorum::acknowledge_proposal(alice, proposal_id));
+    System::assert_has_event(MockEvent::Quorum(Event::BurnedInitialized {
+      proposal_id,
+      account_id: 2,
+      currency_id: CurrencyId::Wrapped(asset_id),
+      amount: 98_000,
+      fee: 2_000,
+    }));
+    assert_eq!(
+      Assets::balance(asset_id, WithdrawalFeesAccount::get()),
+      2_000
+    );
+    assert_eq!(Assets::balance(asset_id, 2), 900_000);
+  });
+}
+
+#[test]
+pub fn burned_withdrawal_confirmation_failure_and_escalation() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let alice = Origin::signed(1u64);
+    let public_key: BoundedVec<u8, StringLimit> = "pubkey".as_bytes().to_vec().try_into().unwrap();
+    let public_keys: BoundedVec<(u64, BoundedVec<u8, StringLimit>), PubkeyLimitPerAsset> =
+      vec![(1u64, public_key)].try_into().unwrap();
+    PublicKeys::<Test>::insert(1, public_keys);
+
+    let asset_id = 4;
+    assert_ok!(Assets::force_create(Origin::root(), asset_id, 1, true, 1));
+    assert_ok!(Assets::mint(Origin::signed(1), asset_id, 2, 1_000_000));
+    assert_ok!(Quorum::submit_withdrawal_fee(
+      alice.clone(),
+      CurrencyId::Wrapped(asset_id),
+      WithdrawalFee {
+        flat: 1_000,
+        percentage: Permill::zero(),
+      }
+    ));
+
+    let burn_withdrawal = || {
+      assert_ok!(<Quorum as QuorumExt<_, _>>::add_new_withdrawal_in_queue(
+        2,
+        CurrencyId::Wrapped(asset_id),
+        100_000,
+        vec![1; 32]
+      ));
+      let proposal_id = Quorum::proposals().first().unwrap().0;
+      assert_ok!(Quorum::acknowledge_proposal(
+        Origin::signed(1u64),
+        proposal_id
+      ));
+      proposal_id
+    };
+
+    // the burned withdrawal stays in queue until the external transfer is confirmed
+    let proposal_id = burn_withdrawal();
+    assert_ok!(Quorum::acknowledge_burned(alice.clone(), proposal_id));
+    assert_eq!(Quorum::burned_queue().len(), 1);
+    assert_eq!(
+      Quorum::burned_confirmations(proposal_id)
+        .unwrap()
+        .acknowledged_by,
+      Some(1)
+    );
+    assert_noop!(
+      Quorum::acknowledge_burned(alice.clone(), proposal_id),
+      Error::<Test>::BurnedAlreadyAcknowledged
+    );
+    assert_noop!(
+      Quorum::confirm_burned(
+        Origin::root(),
+        proposal_id,
+        "external_tx".as_bytes().to_vec()
+      ),
+      Error::<Test>::BurnedNotEscalated
+    );
+    assert_ok!(Quorum::confirm_burned(
+      alice.clone(),
+      proposal_id,
+      "external_tx".as_bytes().to_vec()
+    ));
+    System::assert_has_event(MockEvent::Quorum(Event::BurnedConfirmed {
+      proposal_id,
+      external_tx_id: "external_tx".as_bytes().to_vec(),
+    }));
+    assert!(Quorum::burned_queue().is_empty());
+    assert!(Quorum::burned_confirmations(proposal_id).is_none());
+    assert_eq!(Assets::balance(asset_id, 2), 900_000);
+
+    // the funds and the fee are returned when the external transfer failed
+    let proposal_id = burn_withdrawal();
+    assert_eq!(Assets::balance(asset_id, 2), 800_000);
+    assert_ok!(Quorum::fail_burned(alice.clone(), proposal_id));
+    System::assert_has_event(MockEvent::Quorum(Event::BurnedFailed {
+      proposal_id,
+      account_id: 2,
+      currency_id: CurrencyId::Wrapped(asset_id),
+      amount: 99_000,
+      fee: 1_000,
+    }));
+    assert!(Quorum::burned_queue().is_empty());
+    assert_eq!(Assets::balance(asset_id, 2), 900_000);
+    assert_eq!(
+      Assets::balance(asset_id, WithdrawalFeesAccount::get()),
+      1_000
+    );
+    assert_noop!(
+      Quorum::fail_burned(alice.clone(), proposal_id),
+      Error::<Test>::BurnedNotFound
+    );
+
+    // the burned withdrawal not confirmed in time is escalated to the force origin
+    let proposal_id = burn_withdrawal();
+    pallet_security::CurrentBlockCount::<Test>::put(BurnedConfirmationTimeout::get() - 1);
+    Quorum::on_idle(0, 1_000_000_000_000);
+    assert!(!Quorum::burned_confirmations(proposal_id).unwrap().escalated);
+
+    pallet_security::CurrentBlockCount::<Test>::put(BurnedConfirmationTimeout::get());
+    Quorum::on_idle(0, 1_000_000_000_000);
+    assert!(Quorum::burned_confirmations(proposal_id).unwrap().escalated);
+    System::assert_has_event(MockEvent::Quorum(Event::BurnedEscalated { proposal_id }));
+
+    assert_noop!(
+      Quorum::fail_burned(Origin::signed(3u64), proposal_id),
+      Error::<Test>::AccessDenied
+    );
+    assert_ok!(Quorum::fail_burned(Origin::root(), proposal_id));
+    assert!(Quorum::burned_queue().is_empty());
+    assert_eq!(Assets::balance(asset_id, 2), 900_000);
+  });
+}
+
+#[test]
+pub fn escalate_legacy_burned_withdrawals() {
+  new_test_ext().execute_with(|| {
+    let withdrawal = |block_number| Withdrawal {
+      account_id: 2u64,
+      asset_id: CurrencyId::Wrapped(4),
+      amount: 100_000,
+      external_address: vec![1; 32].try_into().unwrap(),
+      block_number,
+    };
+    let first_proposal_id = Hash::from_low_u64_be(1);
+    let second_proposal_id = Hash::from_low_u64_be(2);
+    let third_proposal_id = Hash::from_low_u64_be(3);
+
+    // the withdrawals burned before the confirmations were recorded are not sorted by request
+    // block, they are followed by the recorded ones
+    BurnedQueue::<Test>::put(
+      BoundedVec::try_from(vec![
+        (first_proposal_id, withdrawal(50)),
+        (second_proposal_id, withdrawal(10)),
+        (third_proposal_id, withdrawal(60)),
+      ])
+      .unwrap(),
+    );
+    BurnedConfirmations::<Test>::insert(
+      third_proposal_id,
+      BurnedConfirmation {
+        burned_at: 60,
+        fee: 0,
+        acknowledged_by: None,
+        confirmations: BoundedVec::default(),
+        failures: BoundedVec::default(),
+        escalated: false,
+      },
+    );
+
+    pallet_security::CurrentBlockCount::<Test>::put(10 + BurnedConfirmationTimeout::get());
+    Quorum::on_idle(0, 1_000_000_000_000);
+    assert!(Quorum::burned_confirmations(first_proposal_id).is_none());
+    assert!(
+      Quorum::burned_confirmations(second_proposal_id)
+        .unwrap()
+        .escalated
+    );
+    assert!(
+      !Quorum::burned_confirmations(third_proposal_id)
+        .unwrap()
+        .escalated
+    );
+  });
+}
+
+#[test]
+pub fn inactive_member_removal_proposal() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let public_key: BoundedVec<u8, StringLimit> = "pubkey".as_bytes().to_vec().try_into().unwrap();
+    let public_keys: BoundedVec<(u64, BoundedVec<u8, StringLimit>), PubkeyLimitPerAsset> = vec![
+      (1u64, public_key.clone()),
+      (2u64, public_key.clone()),
+      (3u64, public_key),
+    ]
+    .try_into()
+    .unwrap();
+    PublicKeys::<Test>::insert(1, public_keys);
+    for account_id in [2, 3] {
+      Members::<Test>::insert(account_id, true);
+    }
+    Threshold::<Test>::put(3);
+    Thresholds::<Test>::put(ProposalThresholds::from_threshold(3));
+
+    // the liveness is tracked from the first block
+    Quorum::on_idle(0, 1_000_000_000_000);
+    assert_eq!(Quorum::members_liveness(3).unwrap().missed_proposals, 0);
+
+    // the member 3 doesn't vote, the proposals can't reach the threshold and expire
+    for transaction_id in ["deposit_1", "deposit_2"] {
+      let submitted_at = Security::get_current_block_count();
+      assert_ok!(Quorum::submit_proposal(
+        Origin::signed(1u64),
+        ProposalType::Mint(Mint {
+          account_id: 4,
+          currency_id: CurrencyId::Tifi,
+          mint_amount: 1_000_000_000_000,
+          transaction_id: transaction_id.as_bytes().to_vec(),
+          compliance_level: ComplianceLevel::Green,
+        })
+      ));
+      let proposal_id = Quorum::proposals().first().unwrap().0;
+      assert_ok!(Quorum::acknowledge_proposal(
+        Origin::signed(1u64),
+        proposal_id
+      ));
+      assert_ok!(Quorum::acknowledge_proposal(
+        Origin::signed(2u64),
+        proposal_id
+      ));
+
+      pallet_security::CurrentBlockCount::<Test>::put(submitted_at + ProposalLifetime::get() + 1);
+      Quorum::on_idle(0, 1_000_000_000_000);
+    }
+    assert_eq!(Quorum::members_liveness(1).unwrap().missed_proposals, 0);
+    assert_eq!(Quorum::members_liveness(3).unwrap().missed_proposals, 2);
+
+    // the member 3 is proposed for removal once
+    let (proposal_id, _, proposal) = Quorum::proposals().first().cloned().unwrap();
+    match proposal {
+      ProposalType::UpdateConfiguration(members, threshold) => {
+        assert_eq!(members.len(), 2);
+        assert!(!members.contains(&3));
+        assert_eq!(threshold, 2);
+      }
+      _ => panic!("Expected an update configuration proposal"),
+    }
+    System::assert_has_event(MockEvent::Quorum(Event::InactiveMemberRemovalProposed {
+      account_id: 3,
+      proposal_id,
+      threshold: 2,
+    }));
+    assert_eq!(Quorum::inactive_member_removals(proposal_id), Some(3));
+    Quorum::on_idle(0, 1_000_000_000_000);
+    assert_eq!(Quorum::proposals().len(), 1);
+
+    // the removal is approved without the vote of the inactive member
+    assert_ok!(Quorum::acknowledge_proposal(
+      Origin::signed(1u64),
+      proposal_id
+    ));
+    assert!(Members::<Test>::contains_key(3));
+    assert_ok!(Quorum::acknowledge_proposal(
+      Origin::signed(2u64),
+      proposal_id
+    ));
+    assert!(!Members::<Test>::contains_key(3));
+    assert_eq!(Quorum::threshold(), 2);
+    assert!(Quorum::members_liveness(3).is_none());
+    assert!(Quorum::inactive_member_removals(proposal_id).is_none());
+  });
+}
+
+#[test]
+pub fn inactive_member_removal_one_at_a_time() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let public_key: BoundedVec<u8, StringLimit> = "pubkey".as_bytes().to_vec().try_into().unwrap();
+    let public_keys: BoundedVec<(u64, BoundedVec<u8, StringLimit>), PubkeyLimitPerAsset> = vec![
+      (1u64, public_key.clone()),
+      (2u64, public_key.clone()),
+      (3u64, public_key.clone()),
+      (4u64, public_key),
+    ]
+    .try_into()
+    .unwrap();
+    PublicKeys::<Test>::insert(1, public_keys);
+    for account_id in [2, 3, 4] {
+      Members::<Test>::insert(account_id, true);
+    }
+    Threshold::<Test>::put(3);
+    Thresholds::<Test>::put(ProposalThresholds::from_threshold(3));
+    Quorum::on_idle(0, 1_000_000_000_000);
+
+    // a quiet period without proposals doesn't make the members inactive
+    pallet_security::CurrentBlockCount::<Test>::put(MemberInactivityPeriod::get() + 10);
+    Quorum::on_idle(0, 1_000_000_000_000);
+    assert!(Quorum::proposals().is_empty());
+
+    // the members 3 and 4 don't vote, the proposals expire
+    for transaction_id in ["deposit_1", "deposit_2"] {
+      let submitted_at = Security::get_current_block_count();
+      assert_ok!(Quorum::submit_proposal(
+        Origin::signed(1u64),
+        ProposalType::Mint(Mint {
+          account_id: 5,
+          currency_id: CurrencyId::Tifi,
+          mint_amount: 1_000_000_000_000,
+          transaction_id: transaction_id.as_bytes().to_vec(),
+          compliance_level: ComplianceLevel::Green,
+        })
+      ));
+      let proposal_id = Quorum::proposals().first().unwrap().0;
+      for account_id in [1u64, 2u64] {
+        assert_ok!(Quorum::acknowledge_proposal(
+          Origin::signed(account_id),
+          proposal_id
+        ));
+      }
+
+      pallet_security::CurrentBlockCount::<Test>::put(submitted_at + ProposalLifetime::get() + 1);
+      Quorum::on_idle(0, 1_000_000_000_000);
+    }
+    assert_eq!(Quorum::members_liveness(3).unwrap().missed_proposals, 2);
+    assert_eq!(Quorum::members_liveness(4).unwrap().missed_proposals, 2);
+
+    // a single removal is pending at a time
+    assert_eq!(Quorum::proposals().len(), 1);
+    let proposal_id = Quorum::proposals().first().unwrap().0;
+    assert_eq!(Quorum::inactive_member_removals(proposal_id), Some(3));
+    Quorum::on_idle(0, 1_000_000_000_000);
+    assert_eq!(Quorum::proposals().len(), 1);
+
+    // the removal is applied to the current members
+    Members::<Test>::insert(6, true);
+    for account_id in [1u64, 2u64, 4u64] {
+      assert_ok!(Quorum::acknowledge_proposal(
+        Origin::signed(account_id),
+        proposal_id
+      ));
+    }
+    assert!(!Members::<Test>::contains_key(3));
+    assert!(Members::<Test>::contains_key(4));
+    assert!(Members::<Test>::contains_key(6));
+
+    // the member 4 voted on the removal and is active again
+    Quorum::on_idle(0, 1_000_000_000_000);
+    assert!(Quorum::proposals().is_empty());
+    assert_eq!(Quorum::members_liveness(4).unwrap().missed_proposals, 0);
+    assert_eq!(Quorum::members_liveness(4).unwrap().missed_since, None);
+  });
+}
+
+#[test]
+pub fn weighted_votes_and_proposal_thresholds() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let public_key: BoundedVec<u8, StringLimit> = "pubkey".as_bytes().to_vec().try_into().unwrap();
+    let public_keys: BoundedVec<(u64, BoundedVec<u8, StringLimit>), PubkeyLimitPerAsset> = vec![
+      (1u64, public_key.clone()),
+      (2u64, public_key.clone()),
+      (3u64, public_key),
+    ]
+    .try_into()
+    .unwrap();
+    PublicKeys::<Test>::insert(1, public_keys);
+    for account_id in [2, 3] {
+      Members::<Test>::insert(account_id, true);
+    }
+    Threshold::<Test>::put(2);
+
+    let asset_id = 4;
+    assert_ok!(Assets::force_create(Origin::root(), asset_id, 1, true, 1));
+
+    // the mints valued above 1_000 USDT need a higher threshold
+    let thresholds = ProposalThresholds {
+      mint: 2,
+      large_mint: 3,
+      large_mint_value: 1_000,
+      withdrawal: 2,
+      update_configuration: 3,
+    };
+    assert_noop!(
+      Quorum::set_proposal_thresholds(
+        Origin::root(),
+        ProposalThresholds {
+          withdrawal: 0,
+          ..thresholds
+        }
+      ),
+      Error::<Test>::BadThreshold
+    );
+    assert_noop!(
+      Quorum::set_proposal_thresholds(Origin::signed(1u64), thresholds),
+      BadOrigin
+    );
+    assert_ok!(Quorum::set_proposal_thresholds(Origin::root(), thresholds));
+    System::assert_has_event(MockEvent::Quorum(Event::ProposalThresholdsChanged {
+      thresholds,
+    }));
+
+    // the member 1 votes with a weight of 2
+    assert_noop!(
+      Quorum::set_member_vote_weight(Origin::root(), 4, Some(2)),
+      Error::<Test>::MemberNotFound
+    );
+    assert_ok!(Quorum::set_member_vote_weight(Origin::root(), 1, Some(2)));
+    assert_eq!(Quorum::member_vote_weight(1), Some(2));
+
+    let submit_mint = |transaction_id: &str, mint_amount: u128| {
+      assert_ok!(Quorum::submit_proposal(
+        Origin::signed(1u64),
+        ProposalType::Mint(Mint {
+          account_id: 5,
+          currency_id: CurrencyId::Wrapped(asset_id),
+          mint_amount,
+          transaction_id: transaction_id.as_bytes().to_vec(),
+          compliance_level: ComplianceLevel::Green,
+        })
+      ));
+      Quorum::proposals().last().unwrap().0
+    };
+
+    // the weight of the member 1 reaches the mint threshold
+    let proposal_id = submit_mint("deposit_1", 500);
+    assert_ok!(Quorum::acknowledge_proposal(
+      Origin::signed(1u64),
+      proposal_id
+    ));
+    assert_eq!(Assets::balance(asset_id, 5), 500);
+
+    // the large mint needs another vote
+    let proposal_id = submit_mint("deposit_2", 5_000);
+    assert_ok!(Quorum::acknowledge_proposal(
+      Origin::signed(1u64),
+      proposal_id
+    ));
+    assert_eq!(Assets::balance(asset_id, 5), 500);
+    assert_ok!(Quorum::acknowledge_proposal(
+      Origin::signed(2u64),
+      proposal_id
+    ));
+    assert_eq!(Assets::balance(asset_id, 5), 5_500);
+  });
+}
+
+#[test]
+pub fn member_cant_vote_twice() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let public_key: BoundedVec<u8, StringLimit> = "pubkey".as_bytes().to_vec().try_into().unwrap();
+    let public_keys: BoundedVec<(u64, BoundedVec<u8, StringLimit>), PubkeyLimitPerAsset> =
+      vec![(1u64, public_key.clone()), (2u64, public_key)]
+        .try_into()
+        .unwrap();
+    PublicKeys::<Test>::insert(1, public_keys);
+    Members::<Test>::insert(2, true);
+    Threshold::<Test>::put(2);
+    Thresholds::<Test>::put(ProposalThresholds::from_threshold(2));
+
+    let asset_id = 4;
+    assert_ok!(Assets::force_create(Origin::root(), asset_id, 1, true, 1));
+    assert_ok!(Quorum::submit_proposal(
+      Origin::signed(1u64),
+      ProposalType::Mint(Mint {
+        account_id: 5,
+        currency_id: CurrencyId::Wrapped(asset_id),
+        mint_amount: 500,
+        transaction_id: "deposit_1".as_bytes().to_vec(),
+        compliance_level: ComplianceLevel::Green,
+      })
+    ));
+    let proposal_id = Quorum::proposals().first().unwrap().0;
+    assert_ok!(Quorum::acknowledge_proposal(
+      Origin::signed(1u64),
+      proposal_id
+    ));
+
+    // the member can't vote again, for or against the proposal
+    assert_noop!(
+      Quorum::acknowledge_proposal(Origin::signed(1u64), proposal_id),
+      Error::<Test>::MemberAlreadyVoted
+    );
+    assert_noop!(
+      Quorum::reject_proposal(Origin::signed(1u64), proposal_id),
+      Error::<Test>::MemberAlreadyVoted
+    );
+    assert_eq!(Assets::balance(asset_id, 5), 0);
+
+    // the vote of another member reaches the threshold
+    assert_ok!(Quorum::acknowledge_proposal(
+      Origin::signed(2u64),
+      proposal_id
+    ));
+    assert_eq!(Assets::balance(asset_id, 5), 500);
+  });
+}
+
+#[test]
+pub fn migrate_single_threshold_to_proposal_thresholds() {
+  new_test_ext().execute_with(|| {
+    Threshold::<Test>::put(3);
+    Thresholds::<Test>::kill();
+    StorageVersion::new(0).put::<Quorum>();
+
+    crate::migrations::migrate_to_v1::<Test, Quorum>();
+    assert_eq!(
+      Quorum::thresholds(),
+      Some(ProposalThresholds::from_threshold(3))
+    );
+    assert_eq!(StorageVersion::get::<Quorum>(), 1);
+  });
+}
+
+#[test]
+pub fn incremental_configuration_update_keep_public_keys() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let public_key: BoundedVec<u8, StringLimit> = "pubkey".as_bytes().to_vec().try_into().unwrap();
+    let public_keys: BoundedVec<(u64, BoundedVec<u8, StringLimit>), PubkeyLimitPerAsset> = vec![
+      (1u64, public_key.clone()),
+      (2u64, public_key.clone()),
+      (3u64, public_key),
+    ]
+    .try_into()
+    .unwrap();
+    PublicKeys::<Test>::insert(1, public_keys);
+    for account_id in [2, 3] {
+      Members::<Test>::insert(account_id, true);
+    }
+    Threshold::<Test>::put(2);
+    Thresholds::<Test>::put(ProposalThresholds::from_threshold(2));
+
+    // the configuration must be applicable
+    assert_noop!(
+      Quorum::submit_proposal(
+        Origin::signed(1u64),
+        ProposalType::UpdateConfiguration(vec![1, 4], 3)
+      ),
+      Error::<Test>::BadThreshold
+    );
+    assert_noop!(
+      Quorum::submit_proposal(
+        Origin::signed(1u64),
+        ProposalType::UpdateConfiguration(vec![1, 1], 1)
+      ),
+      Error::<Test>::DuplicateMember
+    );
+
+    // replace the member 3 by the member 4
+    assert_ok!(Quorum::submit_proposal(
+      Origin::signed(1u64),
+      ProposalType::UpdateConfiguration(vec![1, 2, 4], 2)
+    ));
+    let proposal_id = Quorum::proposals()[0].0;
+    for account_id in [1u64, 2] {
+      assert_ok!(Quorum::acknowledge_proposal(
+        Origin::signed(account_id),
+        proposal_id
+      ));
+    }
+    System::assert_has_event(MockEvent::Quorum(Event::ConfigurationUpdated {
+      members: vec![1, 2, 4],
+      threshold: 2,
+      added: vec![4],
+      removed: vec![3],
+    }));
+    assert!(!Members::<Test>::contains_key(3));
+    let keys_expiry = Security::get_current_block_count() + MemberKeysGracePeriod::get();
+    assert_eq!(Quorum::retiring_members(3), Some(keys_expiry));
+
+    // the members who stay can submit proposals without resubmitting their public keys
+    assert_eq!(Quorum::public_keys(1).len(), 3);
+    assert_ok!(Quorum::submit_proposal(
+      Origin::signed(2u64),
+      ProposalType::UpdateConfiguration(vec![1, 2], 2)
+    ));
+    assert_noop!(
+      Quorum::submit_proposal(
+        Origin::signed(4u64),
+        ProposalType::UpdateConfiguration(vec![1, 2], 2)
+      ),
+      Error::<Test>::AccessDenied
+    );
+
+    // the public keys of the member 3 expire at the end of the grace period
+    Quorum::on_idle(0, 1_000_000_000_000);
+    assert_eq!(Quorum::public_keys(1).len(), 3);
+    pallet_security::CurrentBlockCount::<Test>::put(keys_expiry);
+    Quorum::on_idle(0, 1_000_000_000_000);
+    assert_eq!(Quorum::public_keys(1).len(), 2);
+    assert!(Quorum::retiring_members(3).is_none());
+    System::assert_has_event(MockEvent::Quorum(Event::MemberKeysExpired {
+      account_id: 3,
+    }));
+  });
+}
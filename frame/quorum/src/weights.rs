@@ -29,6 +29,21 @@ pub trait WeightInfo {
    fn reject_proposal() -> Weight;
    fn eval_proposal_state() -> Weight;
    fn submit_public_keys(_k: u32) -> Weight;
+   fn start_keygen() -> Weight;
+   fn submit_keygen_commitment() -> Weight;
+   fn submit_keygen_shares(_s: u32) -> Weight;
+   fn submit_keygen_complaint() -> Weight;
+   fn confirm_keygen() -> Weight;
+   fn approve_watch_list_mint() -> Weight;
+   fn reject_watch_list_mint() -> Weight;
+   fn review_watch_list_entry() -> Weight;
+   fn freeze_watched_account(a: u32) -> Weight;
+   fn cancel_delayed_withdrawal() -> Weight;
+   fn submit_withdrawal_fee() -> Weight;
+   fn confirm_burned() -> Weight;
+   fn fail_burned() -> Weight;
+   fn set_proposal_thresholds() -> Weight;
+   fn set_member_vote_weight() -> Weight;
 }
 
 /// Weights for `pallet_tidefi` using the Substrate node and recommended hardware.
@@ -36,7 +51,7 @@ pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
    fn submit_proposal() -> Weight {
       61_000_300_u64
-          .saturating_add(T::DbWeight::get().reads(6_u64))
+          .saturating_add(T::DbWeight::get().reads(7_u64))
           .saturating_add(T::DbWeight::get().writes(5_u64))
   }
   fn acknowledge_proposal() -> Weight {
@@ -65,4 +80,82 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
          .saturating_add(T::DbWeight::get().reads(6_u64))
          .saturating_add(T::DbWeight::get().writes(5_u64))
    }
+   fn start_keygen() -> Weight {
+      52_000_300_u64
+         .saturating_add(T::DbWeight::get().reads(16_u64))
+         .saturating_add(T::DbWeight::get().writes(3_u64))
+   }
+   fn submit_keygen_commitment() -> Weight {
+      48_000_300_u64
+         .saturating_add(T::DbWeight::get().reads(14_u64))
+         .saturating_add(T::DbWeight::get().writes(2_u64))
+   }
+   fn submit_keygen_shares(s: u32) -> Weight {
+      50_000_300_u64
+         .saturating_add((1_500_000 as Weight).saturating_mul(s as Weight))
+         .saturating_add(T::DbWeight::get().reads(14_u64))
+         .saturating_add(T::DbWeight::get().writes(2_u64))
+   }
+   fn submit_keygen_complaint() -> Weight {
+      45_000_300_u64
+         .saturating_add(T::DbWeight::get().reads(4_u64))
+         .saturating_add(T::DbWeight::get().writes(1_u64))
+   }
+   fn confirm_keygen() -> Weight {
+      60_000_300_u64
+         .saturating_add(T::DbWeight::get().reads(44_u64))
+         .saturating_add(T::DbWeight::get().writes(7_u64))
+   }
+   fn approve_watch_list_mint() -> Weight {
+      58_000_300_u64
+         .saturating_add(T::DbWeight::get().reads(10_u64))
+         .saturating_add(T::DbWeight::get().writes(7_u64))
+   }
+   fn reject_watch_list_mint() -> Weight {
+      40_000_300_u64
+         .saturating_add(T::DbWeight::get().reads(7_u64))
+         .saturating_add(T::DbWeight::get().writes(5_u64))
+   }
+   fn review_watch_list_entry() -> Weight {
+      40_000_300_u64
+         .saturating_add(T::DbWeight::get().reads(7_u64))
+         .saturating_add(T::DbWeight::get().writes(5_u64))
+   }
+   fn freeze_watched_account(a: u32) -> Weight {
+      38_000_300_u64
+         .saturating_add((4_000_000 as Weight).saturating_mul(a as Weight))
+         .saturating_add(T::DbWeight::get().reads(5_u64))
+         .saturating_add(T::DbWeight::get().writes(1_u64))
+         .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(a as Weight)))
+         .saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(a as Weight)))
+   }
+   fn cancel_delayed_withdrawal() -> Weight {
+      38_000_300_u64
+         .saturating_add(T::DbWeight::get().reads(6_u64))
+         .saturating_add(T::DbWeight::get().writes(4_u64))
+   }
+   fn submit_withdrawal_fee() -> Weight {
+      30_000_300_u64
+         .saturating_add(T::DbWeight::get().reads(5_u64))
+         .saturating_add(T::DbWeight::get().writes(2_u64))
+   }
+   fn confirm_burned() -> Weight {
+      36_000_300_u64
+         .saturating_add(T::DbWeight::get().reads(6_u64))
+         .saturating_add(T::DbWeight::get().writes(2_u64))
+   }
+   fn fail_burned() -> Weight {
+      54_000_300_u64
+         .saturating_add(T::DbWeight::get().reads(9_u64))
+         .saturating_add(T::DbWeight::get().writes(6_u64))
+   }
+   fn set_proposal_thresholds() -> Weight {
+      18_000_300_u64
+         .saturating_add(T::DbWeight::get().writes(1_u64))
+   }
+   fn set_member_vote_weight() -> Weight {
+      22_000_300_u64
+         .saturating_add(T::DbWeight::get().reads(1_u64))
+         .saturating_add(T::DbWeight::get().writes(1_u64))
+   }
 }
@@ -15,13 +15,61 @@
 // along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
 
 #![cfg_attr(not(feature = "std"), no_std)]
-use codec::Codec;
+use codec::{Codec, Decode, Encode};
 use frame_support::inherent::Vec;
-use sp_runtime::DispatchError;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{DispatchError, Permill, RuntimeDebug};
 use tidefi_primitives::{
-  BalanceInfo, BlockNumber, CurrencyBalance, CurrencyId, CurrencyMetadata, Stake,
+  Balance, BalanceInfo, BlockNumber, CurrencyBalance, CurrencyId, CurrencyMetadata, Hash, Stake,
 };
 
+pub use pallet_oracle::{SwapFillInfo, SwapInfo, SwapSide};
+pub use pallet_quorum::{
+  AccountWatchListInfo, ArchivedProposalBody, ArchivedProposalInfo, ProposalArchiveFilter,
+  ProposalResolution, WatchListDecision, WatchListEntryInfo,
+};
+
+/// Oracle heartbeat watchdog state
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct OracleWatchdog {
+  /// Oracle status set by the oracle account
+  pub is_enabled: bool,
+  /// The oracle sent a heartbeat within `MaxOracleSilence` blocks
+  pub is_responsive: bool,
+  /// Block of the last oracle heartbeat
+  pub last_seen: BlockNumber,
+  /// Block the oracle was flagged as unresponsive
+  pub unresponsive_since: Option<BlockNumber>,
+}
+
+/// Simulation of a swap request, before it's submitted
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct SwapQuote {
+  /// The account is a market maker and get the discounted fee
+  pub is_market_maker: bool,
+  /// Amount of the source asset held when the swap is requested, with the slippage and the fee
+  pub amount_held: BalanceInfo,
+  /// Network fee in the source asset
+  pub fee: BalanceInfo,
+  /// Network fee in USDT
+  pub fee_usdt: BalanceInfo,
+  /// Sunrise pool ID and TIFI reward earned by the swap, if any pool is eligible
+  pub sunrise_reward: Option<(u8, BalanceInfo)>,
+}
+
+/// Quote of a withdrawal, before it's requested
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct WithdrawalQuote {
+  /// Withdrawal fee in the withdrawn asset
+  pub fee: BalanceInfo,
+  /// Amount burned and sent to the external address, after the fee
+  pub amount_burned: BalanceInfo,
+}
+
 sp_api::decl_runtime_apis! {
     pub trait TidefiApi<AccountId> where AccountId: Codec,
         {
@@ -29,5 +77,14 @@ sp_api::decl_runtime_apis! {
             fn get_account_balances(account_id: AccountId) -> Result<Vec<(CurrencyId, CurrencyBalance<BalanceInfo>)>, DispatchError>;
             fn get_account_stakes(account_id: AccountId) -> Result<Vec<(CurrencyId, Stake<BalanceInfo, BlockNumber>)>, DispatchError>;
             fn get_assets() -> Result<Vec<(CurrencyId, CurrencyMetadata<Vec<u8>>)>, DispatchError>;
+            fn get_oracle_watchdog() -> Result<OracleWatchdog, DispatchError>;
+            fn get_swap_with_fills(request_id: Hash) -> Result<(Option<SwapInfo<AccountId, BlockNumber>>, Vec<SwapFillInfo<BlockNumber>>), DispatchError>;
+            fn get_account_swaps(account_id: AccountId) -> Result<Vec<(Hash, SwapInfo<AccountId, BlockNumber>)>, DispatchError>;
+            fn get_swap(request_id: Hash) -> Result<Option<SwapInfo<AccountId, BlockNumber>>, DispatchError>;
+            fn get_open_swaps(pair: (CurrencyId, CurrencyId), side: SwapSide, limit: u32, cursor: Option<Hash>) -> Result<(Vec<(Hash, SwapInfo<AccountId, BlockNumber>)>, Option<Hash>), DispatchError>;
+            fn quote_swap(account_id: AccountId, currency_id_from: CurrencyId, amount_from: Balance, currency_id_to: CurrencyId, amount_to: Balance, slippage: Permill) -> Result<SwapQuote, DispatchError>;
+            fn get_proposal_archive(filter: ProposalArchiveFilter<AccountId>, limit: u32, cursor: Option<u32>) -> Result<(Vec<(u32, ArchivedProposalInfo<AccountId, BlockNumber>)>, Option<u32>), DispatchError>;
+            fn get_account_watch_list(account_id: AccountId) -> Result<AccountWatchListInfo<BlockNumber>, DispatchError>;
+            fn quote_withdrawal(currency_id: CurrencyId, amount: Balance) -> Result<WithdrawalQuote, DispatchError>;
         }
 }
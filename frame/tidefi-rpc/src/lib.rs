@@ -18,13 +18,16 @@ use codec::Codec;
 use frame_support::inherent::Vec;
 use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
 use jsonrpc_derive::rpc;
-pub use pallet_tidefi_rpc_runtime_api::TidefiApi as TidefiRuntimeApi;
+pub use pallet_tidefi_rpc_runtime_api::{
+  AccountWatchListInfo, ArchivedProposalInfo, OracleWatchdog, ProposalArchiveFilter, SwapFillInfo,
+  SwapInfo, SwapQuote, SwapSide, TidefiApi as TidefiRuntimeApi, WithdrawalQuote,
+};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
-use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use sp_runtime::{generic::BlockId, traits::Block as BlockT, Permill};
 use std::sync::Arc;
 use tidefi_primitives::{
-  BalanceInfo, BlockNumber, CurrencyBalance, CurrencyId, CurrencyMetadata, Stake,
+  Balance, BalanceInfo, BlockNumber, CurrencyBalance, CurrencyId, CurrencyMetadata, Hash, Stake,
 };
 
 #[rpc]
@@ -56,6 +59,82 @@ pub trait TidefiApi<BlockHash, AccountId> {
     account_id: AccountId,
     at: Option<BlockHash>,
   ) -> Result<Vec<(CurrencyId, Stake<BalanceInfo, BlockNumber>)>>;
+
+  #[rpc(name = "tidefi_getOracleWatchdog")]
+  fn get_oracle_watchdog(&self, at: Option<BlockHash>) -> Result<OracleWatchdog>;
+
+  #[rpc(name = "tidefi_getSwapWithFills")]
+  fn get_swap_with_fills(
+    &self,
+    request_id: Hash,
+    at: Option<BlockHash>,
+  ) -> Result<(
+    Option<SwapInfo<AccountId, BlockNumber>>,
+    Vec<SwapFillInfo<BlockNumber>>,
+  )>;
+
+  #[rpc(name = "tidefi_getAccountSwaps")]
+  fn get_account_swaps(
+    &self,
+    account_id: AccountId,
+    at: Option<BlockHash>,
+  ) -> Result<Vec<(Hash, SwapInfo<AccountId, BlockNumber>)>>;
+
+  #[rpc(name = "tidefi_getSwap")]
+  fn get_swap(
+    &self,
+    request_id: Hash,
+    at: Option<BlockHash>,
+  ) -> Result<Option<SwapInfo<AccountId, BlockNumber>>>;
+
+  #[rpc(name = "tidefi_getOpenSwaps")]
+  fn get_open_swaps(
+    &self,
+    pair: (CurrencyId, CurrencyId),
+    side: SwapSide,
+    limit: u32,
+    cursor: Option<Hash>,
+    at: Option<BlockHash>,
+  ) -> Result<(Vec<(Hash, SwapInfo<AccountId, BlockNumber>)>, Option<Hash>)>;
+
+  #[rpc(name = "tidefi_quoteSwap")]
+  fn quote_swap(
+    &self,
+    account_id: AccountId,
+    currency_id_from: CurrencyId,
+    amount_from: Balance,
+    currency_id_to: CurrencyId,
+    amount_to: Balance,
+    slippage: Permill,
+    at: Option<BlockHash>,
+  ) -> Result<SwapQuote>;
+
+  #[rpc(name = "tidefi_getProposalArchive")]
+  fn get_proposal_archive(
+    &self,
+    filter: ProposalArchiveFilter<AccountId>,
+    limit: u32,
+    cursor: Option<u32>,
+    at: Option<BlockHash>,
+  ) -> Result<(
+    Vec<(u32, ArchivedProposalInfo<AccountId, BlockNumber>)>,
+    Option<u32>,
+  )>;
+
+  #[rpc(name = "tidefi_getAccountWatchList")]
+  fn get_account_watch_list(
+    &self,
+    account_id: AccountId,
+    at: Option<BlockHash>,
+  ) -> Result<AccountWatchListInfo<BlockNumber>>;
+
+  #[rpc(name = "tidefi_quoteWithdrawal")]
+  fn quote_withdrawal(
+    &self,
+    currency_id: CurrencyId,
+    amount: Balance,
+    at: Option<BlockHash>,
+  ) -> Result<WithdrawalQuote>;
 }
 
 /// A struct that implements the [`TidefiApi`].
@@ -78,6 +157,12 @@ pub enum Error {
   RuntimeError,
   AccountBalanceError,
   AccountStakesError,
+  OracleWatchdogError,
+  SwapError,
+  SwapQuoteError,
+  ProposalArchiveError,
+  WatchListError,
+  WithdrawalQuoteError,
 }
 
 impl From<Error> for i64 {
@@ -86,6 +171,12 @@ impl From<Error> for i64 {
       Error::RuntimeError => 1,
       Error::AccountBalanceError => 2,
       Error::AccountStakesError => 3,
+      Error::OracleWatchdogError => 4,
+      Error::SwapError => 5,
+      Error::SwapQuoteError => 6,
+      Error::ProposalArchiveError => 7,
+      Error::WatchListError => 8,
+      Error::WithdrawalQuoteError => 9,
     }
   }
 }
@@ -162,6 +253,171 @@ where
       .map_err(runtime_error_into_rpc_error)?
       .map_err(account_balance_error_into_rpc_error)
   }
+
+  fn get_oracle_watchdog(&self, at: Option<<Block as BlockT>::Hash>) -> Result<OracleWatchdog> {
+    let api = self.client.runtime_api();
+    let at = BlockId::hash(at.unwrap_or(
+      // If the block hash is not supplied assume the best block.
+      self.client.info().best_hash,
+    ));
+    api
+      .get_oracle_watchdog(&at)
+      .map_err(runtime_error_into_rpc_error)?
+      .map_err(oracle_watchdog_error_into_rpc_error)
+  }
+
+  fn get_swap_with_fills(
+    &self,
+    request_id: Hash,
+    at: Option<<Block as BlockT>::Hash>,
+  ) -> Result<(
+    Option<SwapInfo<AccountId, BlockNumber>>,
+    Vec<SwapFillInfo<BlockNumber>>,
+  )> {
+    let api = self.client.runtime_api();
+    let at = BlockId::hash(at.unwrap_or(
+      // If the block hash is not supplied assume the best block.
+      self.client.info().best_hash,
+    ));
+    api
+      .get_swap_with_fills(&at, request_id)
+      .map_err(runtime_error_into_rpc_error)?
+      .map_err(swap_error_into_rpc_error)
+  }
+
+  fn get_account_swaps(
+    &self,
+    account_id: AccountId,
+    at: Option<<Block as BlockT>::Hash>,
+  ) -> Result<Vec<(Hash, SwapInfo<AccountId, BlockNumber>)>> {
+    let api = self.client.runtime_api();
+    let at = BlockId::hash(at.unwrap_or(
+      // If the block hash is not supplied assume the best block.
+      self.client.info().best_hash,
+    ));
+    api
+      .get_account_swaps(&at, account_id)
+      .map_err(runtime_error_into_rpc_error)?
+      .map_err(swap_error_into_rpc_error)
+  }
+
+  fn get_swap(
+    &self,
+    request_id: Hash,
+    at: Option<<Block as BlockT>::Hash>,
+  ) -> Result<Option<SwapInfo<AccountId, BlockNumber>>> {
+    let api = self.client.runtime_api();
+    let at = BlockId::hash(at.unwrap_or(
+      // If the block hash is not supplied assume the best block.
+      self.client.info().best_hash,
+    ));
+    api
+      .get_swap(&at, request_id)
+      .map_err(runtime_error_into_rpc_error)?
+      .map_err(swap_error_into_rpc_error)
+  }
+
+  fn get_open_swaps(
+    &self,
+    pair: (CurrencyId, CurrencyId),
+    side: SwapSide,
+    limit: u32,
+    cursor: Option<Hash>,
+    at: Option<<Block as BlockT>::Hash>,
+  ) -> Result<(Vec<(Hash, SwapInfo<AccountId, BlockNumber>)>, Option<Hash>)> {
+    let api = self.client.runtime_api();
+    let at = BlockId::hash(at.unwrap_or(
+      // If the block hash is not supplied assume the best block.
+      self.client.info().best_hash,
+    ));
+    api
+      .get_open_swaps(&at, pair, side, limit, cursor)
+      .map_err(runtime_error_into_rpc_error)?
+      .map_err(swap_error_into_rpc_error)
+  }
+
+  fn quote_swap(
+    &self,
+    account_id: AccountId,
+    currency_id_from: CurrencyId,
+    amount_from: Balance,
+    currency_id_to: CurrencyId,
+    amount_to: Balance,
+    slippage: Permill,
+    at: Option<<Block as BlockT>::Hash>,
+  ) -> Result<SwapQuote> {
+    let api = self.client.runtime_api();
+    let at = BlockId::hash(at.unwrap_or(
+      // If the block hash is not supplied assume the best block.
+      self.client.info().best_hash,
+    ));
+    api
+      .quote_swap(
+        &at,
+        account_id,
+        currency_id_from,
+        amount_from,
+        currency_id_to,
+        amount_to,
+        slippage,
+      )
+      .map_err(runtime_error_into_rpc_error)?
+      .map_err(swap_quote_error_into_rpc_error)
+  }
+
+  fn get_proposal_archive(
+    &self,
+    filter: ProposalArchiveFilter<AccountId>,
+    limit: u32,
+    cursor: Option<u32>,
+    at: Option<<Block as BlockT>::Hash>,
+  ) -> Result<(
+    Vec<(u32, ArchivedProposalInfo<AccountId, BlockNumber>)>,
+    Option<u32>,
+  )> {
+    let api = self.client.runtime_api();
+    let at = BlockId::hash(at.unwrap_or(
+      // If the block hash is not supplied assume the best block.
+      self.client.info().best_hash,
+    ));
+    api
+      .get_proposal_archive(&at, filter, limit, cursor)
+      .map_err(runtime_error_into_rpc_error)?
+      .map_err(proposal_archive_error_into_rpc_error)
+  }
+
+  fn get_account_watch_list(
+    &self,
+    account_id: AccountId,
+    at: Option<<Block as BlockT>::Hash>,
+  ) -> Result<AccountWatchListInfo<BlockNumber>> {
+    let api = self.client.runtime_api();
+    let at = BlockId::hash(at.unwrap_or(
+      // If the block hash is not supplied assume the best block.
+      self.client.info().best_hash,
+    ));
+    api
+      .get_account_watch_list(&at, account_id)
+      .map_err(runtime_error_into_rpc_error)?
+      .map_err(watch_list_error_into_rpc_error)
+  }
+
+  fn quote_withdrawal(
+    &self,
+    currency_id: CurrencyId,
+    amount: Balance,
+    at: Option<<Block as BlockT>::Hash>,
+  ) -> Result<WithdrawalQuote> {
+    let api = self.client.runtime_api();
+    let at = BlockId::hash(at.unwrap_or(
+      // If the block hash is not supplied assume the best block.
+      self.client.info().best_hash,
+    ));
+    api
+      .quote_withdrawal(&at, currency_id, amount)
+      .map_err(runtime_error_into_rpc_error)?
+      .map_err(withdrawal_quote_error_into_rpc_error)
+  }
 }
 
 /// Converts a runtime trap into an RPC error.
@@ -199,3 +455,57 @@ fn assets_error_into_rpc_error(err: impl std::fmt::Debug) -> RpcError {
     data: Some(format!("{:?}", err).into()),
   }
 }
+
+/// Converts an oracle watchdog error into an RPC error.
+fn oracle_watchdog_error_into_rpc_error(err: impl std::fmt::Debug) -> RpcError {
+  RpcError {
+    code: ErrorCode::ServerError(Error::OracleWatchdogError.into()),
+    message: "Not able to get oracle watchdog".into(),
+    data: Some(format!("{:?}", err).into()),
+  }
+}
+
+/// Converts a swap error into an RPC error.
+fn swap_error_into_rpc_error(err: impl std::fmt::Debug) -> RpcError {
+  RpcError {
+    code: ErrorCode::ServerError(Error::SwapError.into()),
+    message: "Not able to get swap".into(),
+    data: Some(format!("{:?}", err).into()),
+  }
+}
+
+/// Converts a swap quote error into an RPC error.
+fn swap_quote_error_into_rpc_error(err: impl std::fmt::Debug) -> RpcError {
+  RpcError {
+    code: ErrorCode::ServerError(Error::SwapQuoteError.into()),
+    message: "Not able to quote swap".into(),
+    data: Some(format!("{:?}", err).into()),
+  }
+}
+
+/// Converts a proposal archive error into an RPC error.
+fn proposal_archive_error_into_rpc_error(err: impl std::fmt::Debug) -> RpcError {
+  RpcError {
+    code: ErrorCode::ServerError(Error::ProposalArchiveError.into()),
+    message: "Not able to get proposal archive".into(),
+    data: Some(format!("{:?}", err).into()),
+  }
+}
+
+/// Converts a watch list error into an RPC error.
+fn watch_list_error_into_rpc_error(err: impl std::fmt::Debug) -> RpcError {
+  RpcError {
+    code: ErrorCode::ServerError(Error::WatchListError.into()),
+    message: "Not able to get account watch list".into(),
+    data: Some(format!("{:?}", err).into()),
+  }
+}
+
+/// Converts a withdrawal quote error into an RPC error.
+fn withdrawal_quote_error_into_rpc_error(err: impl std::fmt::Debug) -> RpcError {
+  RpcError {
+    code: ErrorCode::ServerError(Error::WithdrawalQuoteError.into()),
+    message: "Not able to quote withdrawal".into(),
+    data: Some(format!("{:?}", err).into()),
+  }
+}
@@ -16,9 +16,10 @@
 
 //! Benchmarking setup for pallet-tidefi
 use crate::*;
-use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
-use frame_support::traits::fungibles::Mutate;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::traits::{fungibles::Mutate, EnsureOrigin, UnfilteredDispatchable};
 use frame_system::RawOrigin;
+use sp_runtime::Percent;
 use tidefi_primitives::CurrencyId;
 
 const INITIAL_AMOUNT: u128 = 500_000_000;
@@ -33,12 +34,116 @@ benchmarks! {
    }: _(RawOrigin::Signed(caller), CurrencyId::Wrapped(TEST_TOKEN), INITIAL_AMOUNT, (14400_u32 * 15_u32).into())
 
    unstake {
+      let caller: T::AccountId = whitelisted_caller();
+      let balance = INITIAL_AMOUNT.saturating_mul(IA_MULTIPLIER.into());
+      // the unstake fee is paid in TIFI
+      <T as Config>::CurrencyTidefi::mint_into(CurrencyId::Tifi, &caller, balance as u128).expect("Unable to mint token");
+
+      let stake_id = Pallet::<T>::add_account_stake(&caller, CurrencyId::Tifi, INITIAL_AMOUNT, (14400_u32 * 15_u32).into()).expect("Unable to stake asset");
+   }: _(RawOrigin::Signed(caller), stake_id, true)
+
+   cancel_queued_unstake {
+      let caller: T::AccountId = whitelisted_caller();
+      let balance = INITIAL_AMOUNT.saturating_mul(IA_MULTIPLIER.into());
+      <T as Config>::CurrencyTidefi::mint_into(CurrencyId::Wrapped(TEST_TOKEN), &caller, balance as u128).expect("Unable to mint token");
+
+      let stake_id = Pallet::<T>::add_account_stake(&caller, CurrencyId::Wrapped(TEST_TOKEN), INITIAL_AMOUNT, (14400_u32 * 15_u32).into()).expect("Unable to stake asset");
+      UnstakeQueue::<T>::try_append((caller.clone(), stake_id, T::BlockNumber::from(256_u32))).expect("Unable to queue unstake");
+   }: _(RawOrigin::Signed(caller), stake_id)
+
+   increase_stake {
+      let caller: T::AccountId = whitelisted_caller();
+      let balance = INITIAL_AMOUNT.saturating_mul(IA_MULTIPLIER.into());
+      <T as Config>::CurrencyTidefi::mint_into(CurrencyId::Wrapped(TEST_TOKEN), &caller, balance as u128).expect("Unable to mint token");
+
+      let stake_id = Pallet::<T>::add_account_stake(&caller, CurrencyId::Wrapped(TEST_TOKEN), INITIAL_AMOUNT / 2, (14400_u32 * 15_u32).into()).expect("Unable to stake asset");
+   }: _(RawOrigin::Signed(caller), stake_id, INITIAL_AMOUNT / 2)
+
+   partial_unstake {
+      let caller: T::AccountId = whitelisted_caller();
+      let balance = INITIAL_AMOUNT.saturating_mul(IA_MULTIPLIER.into());
+      <T as Config>::CurrencyTidefi::mint_into(CurrencyId::Wrapped(TEST_TOKEN), &caller, balance as u128).expect("Unable to mint token");
+
+      let stake_id = Pallet::<T>::add_account_stake(&caller, CurrencyId::Wrapped(TEST_TOKEN), INITIAL_AMOUNT, (14400_u32 * 15_u32).into()).expect("Unable to stake asset");
+      // only the expired stakes can be partially unstaked
+      Pallet::<T>::update_account_stake(&caller, stake_id, |stake| stake.duration = T::BlockNumber::from(0_u32)).expect("Unable to expire stake");
+   }: _(RawOrigin::Signed(caller), stake_id, INITIAL_AMOUNT / 2)
+
+   set_stake_auto_renew {
       let caller: T::AccountId = whitelisted_caller();
       let balance = INITIAL_AMOUNT.saturating_mul(IA_MULTIPLIER.into());
       <T as Config>::CurrencyTidefi::mint_into(CurrencyId::Wrapped(TEST_TOKEN), &caller, balance as u128).expect("Unable to mint token");
 
       let stake_id = Pallet::<T>::add_account_stake(&caller, CurrencyId::Wrapped(TEST_TOKEN), INITIAL_AMOUNT, (14400_u32 * 15_u32).into()).expect("Unable to stake asset");
    }: _(RawOrigin::Signed(caller), stake_id, true)
+
+   transfer_stake {
+      let caller: T::AccountId = whitelisted_caller();
+      let destination: T::AccountId = account("destination", 0, 0);
+      let balance = INITIAL_AMOUNT.saturating_mul(IA_MULTIPLIER.into());
+      <T as Config>::CurrencyTidefi::mint_into(CurrencyId::Wrapped(TEST_TOKEN), &caller, balance as u128).expect("Unable to mint token");
+
+      let stake_id = Pallet::<T>::add_account_stake(&caller, CurrencyId::Wrapped(TEST_TOKEN), INITIAL_AMOUNT, (14400_u32 * 15_u32).into()).expect("Unable to stake asset");
+   }: _(RawOrigin::Signed(caller), stake_id, destination)
+
+   cancel_stake_transfer {
+      let caller: T::AccountId = whitelisted_caller();
+      let destination: T::AccountId = account("destination", 0, 0);
+      let balance = INITIAL_AMOUNT.saturating_mul(IA_MULTIPLIER.into());
+      <T as Config>::CurrencyTidefi::mint_into(CurrencyId::Wrapped(TEST_TOKEN), &caller, balance as u128).expect("Unable to mint token");
+
+      let stake_id = Pallet::<T>::add_account_stake(&caller, CurrencyId::Wrapped(TEST_TOKEN), INITIAL_AMOUNT, (14400_u32 * 15_u32).into()).expect("Unable to stake asset");
+      PendingStakeTransfers::<T>::insert(stake_id, (caller.clone(), destination));
+   }: _(RawOrigin::Signed(caller), stake_id)
+
+   accept_stake_transfer {
+      let caller: T::AccountId = whitelisted_caller();
+      let owner: T::AccountId = account("owner", 0, 0);
+      let balance = INITIAL_AMOUNT.saturating_mul(IA_MULTIPLIER.into());
+      <T as Config>::CurrencyTidefi::mint_into(CurrencyId::Wrapped(TEST_TOKEN), &owner, balance as u128).expect("Unable to mint token");
+
+      let stake_id = Pallet::<T>::add_account_stake(&owner, CurrencyId::Wrapped(TEST_TOKEN), INITIAL_AMOUNT, (14400_u32 * 15_u32).into()).expect("Unable to stake asset");
+      PendingStakeTransfers::<T>::insert(stake_id, (owner, caller.clone()));
+   }: _(RawOrigin::Signed(caller), stake_id)
+
+   set_stake_reward_beneficiary {
+      let caller: T::AccountId = whitelisted_caller();
+      let beneficiary: T::AccountId = account("beneficiary", 0, 0);
+      let balance = INITIAL_AMOUNT.saturating_mul(IA_MULTIPLIER.into());
+      <T as Config>::CurrencyTidefi::mint_into(CurrencyId::Wrapped(TEST_TOKEN), &caller, balance as u128).expect("Unable to mint token");
+
+      let stake_id = Pallet::<T>::add_account_stake(&caller, CurrencyId::Wrapped(TEST_TOKEN), INITIAL_AMOUNT, (14400_u32 * 15_u32).into()).expect("Unable to stake asset");
+   }: _(RawOrigin::Signed(caller), stake_id, Some(beneficiary))
+
+   claim_beneficiary_rewards {
+      let caller: T::AccountId = whitelisted_caller();
+      let balance = INITIAL_AMOUNT.saturating_mul(IA_MULTIPLIER.into());
+      <T as Config>::CurrencyTidefi::mint_into(CurrencyId::Wrapped(TEST_TOKEN), &caller, balance as u128).expect("Unable to mint token");
+
+      // the staked funds cover the rewards in the staking pool
+      Pallet::<T>::add_account_stake(&caller, CurrencyId::Wrapped(TEST_TOKEN), INITIAL_AMOUNT, (14400_u32 * 15_u32).into()).expect("Unable to stake asset");
+      BeneficiaryRewards::<T>::insert(&caller, CurrencyId::Wrapped(TEST_TOKEN), INITIAL_AMOUNT / 2);
+   }: _(RawOrigin::Signed(caller), CurrencyId::Wrapped(TEST_TOKEN))
+
+   set_staking_period {
+      let origin = T::ForceOrigin::successful_origin();
+      let call = Call::<T>::set_staking_period { duration: (14400_u32 * 120_u32).into(), reward: Percent::from_parts(6) };
+   }: { call.dispatch_bypass_filter(origin)? }
+
+   remove_staking_period {
+      let origin = T::ForceOrigin::successful_origin();
+      let call = Call::<T>::remove_staking_period { duration: (14400_u32 * 15_u32).into() };
+   }: { call.dispatch_bypass_filter(origin)? }
+
+   set_staking_currency_meta {
+      let origin = T::ForceOrigin::successful_origin();
+      let call = Call::<T>::set_staking_currency_meta { currency_id: CurrencyId::Wrapped(TEST_TOKEN), minimum_amount: INITIAL_AMOUNT, maximum_amount: INITIAL_AMOUNT.saturating_mul(IA_MULTIPLIER.into()) };
+   }: { call.dispatch_bypass_filter(origin)? }
+
+   set_unstake_fee {
+      let origin = T::ForceOrigin::successful_origin();
+      let call = Call::<T>::set_unstake_fee { unstake_fee: Percent::from_parts(2) };
+   }: { call.dispatch_bypass_filter(origin)? }
 }
 
 impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
@@ -31,8 +31,19 @@ pub use weights::*;
 // Re-export pallet items so that they can be accessed from the crate namespace.
 pub use pallet::*;
 
+use sp_runtime::DispatchError;
+use tidefi_primitives::{Balance, CurrencyId};
+
 pub(crate) const LOG_TARGET: &str = "tidefi::staking";
 
+/// Fees pallet receiving the fees of the forced unstakes, valued in TIFI
+pub trait StakingFeesExt<AccountId> {
+  /// Account receiving the unstake fees
+  fn account_id() -> AccountId;
+  /// Value `amount` of `currency_id` in TIFI, based on the order book prices
+  fn get_tifi_value(currency_id: CurrencyId, amount: Balance) -> Result<Balance, DispatchError>;
+}
+
 mod migrations;
 
 // syntactic sugar for logging.
@@ -57,17 +68,31 @@ pub mod pallet {
       tokens::fungibles::{Inspect, Mutate, Transfer},
       StorageVersion,
     },
-    BoundedVec, PalletId,
+    transactional, BoundedVec, PalletId,
   };
   use frame_system::pallet_prelude::*;
-  use sp_runtime::{traits::AccountIdConversion, ArithmeticError, Percent, Perquintill};
+  use sp_runtime::{
+    traits::{AccountIdConversion, Saturating, Zero},
+    ArithmeticError, FixedPointNumber, FixedU128, Percent,
+  };
   use tidefi_primitives::{
     pallet::{AssetRegistryExt, SecurityExt, StakingExt},
     Balance, BalanceInfo, CurrencyId, Hash, SessionIndex, Stake, StakeCurrencyMeta,
   };
 
   /// The current storage version.
-  const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+  const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
+
+  /// Terms of a stake, snapshotted when the stake is opened
+  #[derive(
+    Clone, Copy, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+  )]
+  pub struct StakeTerms {
+    /// Reward percentage of the staking period
+    pub reward: Percent,
+    /// Fee taken when unstaking before the end of the stake
+    pub unstake_fee: Percent,
+  }
 
   #[pallet::config]
   /// Configure the pallet by specifying the parameters and types on which it depends.
@@ -95,6 +120,9 @@ pub mod pallet {
     #[pallet::constant]
     type BlocksForceUnstake: Get<Self::BlockNumber>;
 
+    /// The origin which may manage the staking periods, the currency limits and the unstake fee
+    type ForceOrigin: EnsureOrigin<Self::Origin>;
+
     /// Weight information for extrinsics in this pallet.
     type WeightInfo: WeightInfo;
 
@@ -104,6 +132,10 @@ pub mod pallet {
     /// Security traits
     type Security: SecurityExt<Self::AccountId, Self::BlockNumber>;
 
+    /// Fees traits, the fees of the forced unstakes are converted to TIFI and sent to the fees
+    /// pallet
+    type Fees: StakingFeesExt<Self::AccountId>;
+
     /// Tidechain currency wrapper
     type CurrencyTidefi: Inspect<Self::AccountId, AssetId = CurrencyId, Balance = Balance>
       + Mutate<Self::AccountId, AssetId = CurrencyId, Balance = Balance>
@@ -137,6 +169,11 @@ pub mod pallet {
   #[pallet::getter(fn unstake_fee)]
   pub type UnstakeFee<T: Config> = StorageValue<_, Percent, ValueQuery>;
 
+  /// Terms of each stake when it was opened, by stake ID
+  #[pallet::storage]
+  #[pallet::getter(fn stake_terms)]
+  pub type StakesTerms<T: Config> = StorageMap<_, Blake2_128Concat, Hash, StakeTerms>;
+
   /// The last session we should compound the account interests.
   #[pallet::storage]
   #[pallet::getter(fn interest_compound_last_session)]
@@ -152,8 +189,8 @@ pub mod pallet {
   >;
 
   /// Map from all pending stored sessions.
-  // When all stake that are bounded for this sessions are compounded, they got removed from the map.
-  // When the map is empty, the `do_next_compound_interest_operation` is not triggered.
+  // The sessions were compounded by walking all the stakes before the reward index, the pending
+  // sessions are distributed and drained by the migration to v3.
   #[pallet::storage]
   #[pallet::getter(fn pending_sessions)]
   pub type PendingStoredSessions<T: Config> =
@@ -173,6 +210,90 @@ pub mod pallet {
     ValueQuery,
   >;
 
+  /// Stakes renewed for a new period with their principal when they expire
+  #[pallet::storage]
+  #[pallet::getter(fn stake_auto_renew)]
+  pub type StakesAutoRenew<T: Config> = StorageMap<_, Blake2_128Concat, Hash, bool, ValueQuery>;
+
+  /// Accounts receiving the rewards of the stakes, instead of compounding them in the stake
+  #[pallet::storage]
+  #[pallet::getter(fn stake_reward_beneficiary)]
+  pub type StakesRewardBeneficiary<T: Config> = StorageMap<_, Blake2_128Concat, Hash, T::AccountId>;
+
+  /// Rewards of the delegated stakes to claim, by beneficiary and currency
+  #[pallet::storage]
+  #[pallet::getter(fn beneficiary_rewards)]
+  pub type BeneficiaryRewards<T: Config> = StorageDoubleMap<
+    _,
+    Blake2_128Concat,
+    T::AccountId,
+    Blake2_128Concat,
+    CurrencyId,
+    Balance,
+    ValueQuery,
+  >;
+
+  /// Owner of the transferred stakes, the expiry queue keeps the account which opened the stake
+  #[pallet::storage]
+  #[pallet::getter(fn transferred_stake_owner)]
+  pub type TransferredStakes<T: Config> = StorageMap<_, Blake2_128Concat, Hash, T::AccountId>;
+
+  /// Stake transfers waiting for the acceptance of the destination, with the owner and the
+  /// destination of the stake
+  #[pallet::storage]
+  #[pallet::getter(fn pending_stake_transfer)]
+  pub type PendingStakeTransfers<T: Config> =
+    StorageMap<_, Blake2_128Concat, Hash, (T::AccountId, T::AccountId)>;
+
+  /// Cumulative reward per staked unit of the active stakes, by currency and duration
+  #[pallet::storage]
+  #[pallet::getter(fn reward_per_share)]
+  pub type RewardPerShare<T: Config> = StorageDoubleMap<
+    _,
+    Blake2_128Concat,
+    CurrencyId,
+    Blake2_128Concat,
+    T::BlockNumber,
+    FixedU128,
+    ValueQuery,
+  >;
+
+  /// Staked amount of the active stakes, by currency and duration
+  #[pallet::storage]
+  #[pallet::getter(fn reward_shares)]
+  pub type RewardShares<T: Config> = StorageDoubleMap<
+    _,
+    Blake2_128Concat,
+    CurrencyId,
+    Blake2_128Concat,
+    T::BlockNumber,
+    Balance,
+    ValueQuery,
+  >;
+
+  /// Reward index of each active stake at its last settlement, removed once the stake expired
+  #[pallet::storage]
+  #[pallet::getter(fn stake_reward_index)]
+  pub type StakeRewardIndex<T: Config> = StorageMap<_, Blake2_128Concat, Hash, FixedU128>;
+
+  /// Expiry queue of the active stakes by currency and duration, the stakes of the same currency
+  /// and duration expire in the order they are opened
+  #[pallet::storage]
+  pub type StakeExpiryQueue<T: Config> = StorageDoubleMap<
+    _,
+    Blake2_128Concat,
+    (CurrencyId, T::BlockNumber),
+    Blake2_128Concat,
+    u64,
+    (T::AccountId, Hash, T::BlockNumber),
+  >;
+
+  /// Head and tail of the expiry queue of each currency and duration
+  #[pallet::storage]
+  #[pallet::getter(fn stake_expiry_queue_bounds)]
+  pub type StakeExpiryQueueBounds<T: Config> =
+    StorageMap<_, Blake2_128Concat, (CurrencyId, T::BlockNumber), (u64, u64), ValueQuery>;
+
   /// Account staking by CurrencyID
   #[pallet::storage]
   #[pallet::getter(fn account_stakes)]
@@ -257,10 +378,22 @@ pub mod pallet {
       amount: Balance,
       duration: T::BlockNumber,
     },
-    /// The assets unstaking has been queued
+    /// The assets unstaking has been queued, the fee is paid in TIFI
     UnstakeQueued {
       request_id: Hash,
       account_id: T::AccountId,
+      fee: Balance,
+    },
+    /// The queued unstake has been cancelled, the fee is not refunded
+    QueuedUnstakeCancelled {
+      request_id: Hash,
+      account_id: T::AccountId,
+    },
+    /// The queued unstake failed and has been removed from the queue, the stake is kept
+    QueuedUnstakeFailed {
+      request_id: Hash,
+      account_id: T::AccountId,
+      error: DispatchError,
     },
     /// The assets get `unstaked` successfully
     Unstaked {
@@ -270,6 +403,74 @@ pub mod pallet {
       initial_balance: Balance,
       final_balance: Balance,
     },
+    /// Funds have been added to the stake
+    StakeIncreased {
+      request_id: Hash,
+      account_id: T::AccountId,
+      amount: Balance,
+    },
+    /// A part of the expired stake has been unstaked
+    StakePartiallyUnstaked {
+      request_id: Hash,
+      account_id: T::AccountId,
+      amount: Balance,
+    },
+    /// The auto-renew of the stake has been updated
+    StakeAutoRenewSet {
+      request_id: Hash,
+      account_id: T::AccountId,
+      auto_renew: bool,
+    },
+    /// The expired stake has been renewed for a new period with its principal
+    StakeRenewed {
+      request_id: Hash,
+      account_id: T::AccountId,
+      principal: Balance,
+    },
+    /// The transfer of the stake has been proposed, and waits for the acceptance of the destination
+    StakeTransferProposed {
+      request_id: Hash,
+      account_id: T::AccountId,
+      destination_id: T::AccountId,
+    },
+    /// The pending transfer of the stake has been cancelled by its owner
+    StakeTransferCancelled {
+      request_id: Hash,
+      account_id: T::AccountId,
+    },
+    /// The stake has been transferred to another account
+    StakeTransferred {
+      request_id: Hash,
+      account_id: T::AccountId,
+      destination_id: T::AccountId,
+    },
+    /// The account receiving the rewards of the stake has been updated
+    StakeRewardBeneficiarySet {
+      request_id: Hash,
+      account_id: T::AccountId,
+      beneficiary: Option<T::AccountId>,
+    },
+    /// The rewards of the delegated stakes have been claimed
+    BeneficiaryRewardsClaimed {
+      account_id: T::AccountId,
+      currency_id: CurrencyId,
+      amount: Balance,
+    },
+    /// The reward of a staking period has been added or updated
+    StakingPeriodSet {
+      duration: T::BlockNumber,
+      reward: Percent,
+    },
+    /// The staking period has been removed, the opened stakes keep their terms
+    StakingPeriodRemoved { duration: T::BlockNumber },
+    /// The minimum and maximum stake amount of the currency has been updated
+    StakingCurrencyMetaSet {
+      currency_id: CurrencyId,
+      minimum_amount: Balance,
+      maximum_amount: Balance,
+    },
+    /// The fee of the unstake before the end of the stake has been updated
+    UnstakeFeeSet { unstake_fee: Percent },
   }
 
   // Errors inform users that something went wrong.
@@ -293,44 +494,50 @@ pub mod pallet {
     AmountTooSmall,
     /// The staked amount is above the maximum stake amount for this currency.
     AmountTooLarge,
+    /// Exceeded the staking periods capacity
+    StakingRewardCapExceeded,
+    /// The minimum stake amount is above the maximum stake amount
+    InvalidCurrencyMeta,
+    /// The stake is expired and doesn't accrue rewards anymore
+    StakeNotActive,
+    /// The stake is already queued for unstake
+    UnstakeAlreadyQueued,
+    /// The stake is not queued for unstake
+    UnstakeNotQueued,
+    /// The stake can't be transferred to its owner
+    InvalidStakeTransfer,
+    /// Exceeded the stakes capacity of the account
+    StakeAccountCapExceeded,
+    /// The account has no rewards to claim
+    NoBeneficiaryRewards,
+    /// The stake is not expired, it can only be unstaked entirely
+    StakeNotExpired,
+    /// No pending transfer of the stake for the account
+    StakeTransferNotFound,
   }
 
   #[pallet::hooks]
   impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
     fn on_runtime_upgrade() -> frame_support::weights::Weight {
       migrations::migrate_to_v1::<T, Self>()
+        .saturating_add(migrations::migrate_to_v2::<T, Self>())
+        .saturating_add(migrations::migrate_to_v3::<T, Self>())
     }
 
     /// Try to compute when chain is idle
     fn on_idle(_n: BlockNumberFor<T>, mut remaining_weight: Weight) -> Weight {
-      let do_next_compound_interest_operation_weight =
-        <T as frame_system::Config>::DbWeight::get().reads_writes(6, 6);
-      let do_next_unstake_operation_weight =
-        <T as frame_system::Config>::DbWeight::get().reads_writes(6, 6);
+      let do_next_unstake_operation_weight = Self::queued_unstake_weight();
+
+      // stop the reward accrual of the expired stakes
+      remaining_weight =
+        remaining_weight.saturating_sub(Self::expire_stakes_with_max_weight(remaining_weight));
 
       // security if loop is get jammed somehow or prevent any overflow in tests
       let max_iter = 100;
       let mut current_iter = 0;
 
       loop {
-        if remaining_weight > do_next_compound_interest_operation_weight
-          && PendingStoredSessions::<T>::count() > 0
-        {
-          match Self::do_next_compound_interest_operation(remaining_weight) {
-            Ok((real_weight_consumed, should_continue)) => {
-              remaining_weight -= real_weight_consumed;
-
-              if !should_continue {
-                break;
-              }
-            }
-            Err(err) => {
-              log!(error, "Interest compounding failed {:?}", err);
-              break;
-            }
-          };
-        } else if remaining_weight > do_next_unstake_operation_weight
-          && !Self::unstake_queue().is_empty()
+        if remaining_weight > do_next_unstake_operation_weight && !Self::unstake_queue().is_empty()
         {
           match Self::do_next_unstake_operation(remaining_weight) {
             Ok((real_weight_consumed, should_continue)) => {
@@ -430,6 +637,7 @@ pub mod pallet {
     ///
     /// Weight: `O(1)`
     #[pallet::weight(<T as pallet::Config>::WeightInfo::unstake())]
+    #[transactional]
     pub fn unstake(
       origin: OriginFor<T>,
       stake_id: Hash,
@@ -450,268 +658,1070 @@ pub mod pallet {
         expected_block_expiration > T::Security::get_current_block_count() && force_unstake;
 
       ensure!(staking_is_ready, Error::<T>::StakingNotReady);
-
-      // FIXME: Validate not already queued
+      ensure!(
+        !Self::is_unstake_queued(stake_id),
+        Error::<T>::UnstakeAlreadyQueued
+      );
 
       // we should add to unstaking queue and take immeditately the extra fees
       // for the queue storage
       if staking_is_forced {
-        // take the fee
-        // FIXME: would be great to convert to TIFI
-        let unstaking_fee = Self::unstake_fee() * stake.initial_balance;
-        T::CurrencyTidefi::can_withdraw(stake.currency_id, &account_id, unstaking_fee)
+        // take the fee of the terms the stake was opened under, valued in TIFI
+        let unstaking_fee =
+          Self::get_stake_terms(stake_id, stake.duration).unstake_fee * stake.initial_balance;
+        let unstaking_fee = if stake.currency_id == CurrencyId::Tifi {
+          unstaking_fee
+        } else {
+          T::Fees::get_tifi_value(stake.currency_id, unstaking_fee)?
+        };
+        T::CurrencyTidefi::can_withdraw(CurrencyId::Tifi, &account_id, unstaking_fee)
           .into_result()
           .map_err(|_| Error::<T>::InsufficientBalance)?;
 
-        UnstakeQueue::<T>::try_append((
-          account_id.clone(),
-          stake_id,
-          T::Security::get_current_block_count() + T::BlocksForceUnstake::get(),
-        ))
-        .map_err(|_| Error::<T>::UnstakeQueueCapExceeded)?;
-
-        // FIXME: transfer to DEX wallet
+        // the fee is collected before queueing, a failed transfer doesn't leave a queued unstake
         T::CurrencyTidefi::transfer(
-          stake.currency_id,
+          CurrencyId::Tifi,
           &account_id,
-          &Self::account_id(),
+          &T::Fees::account_id(),
           unstaking_fee,
           true,
         )
         .map_err(|_| Error::<T>::TransferFeesFailed)?;
 
+        UnstakeQueue::<T>::try_append((
+          account_id.clone(),
+          stake_id,
+          T::Security::get_current_block_count() + T::BlocksForceUnstake::get(),
+        ))
+        .map_err(|_| Error::<T>::UnstakeQueueCapExceeded)?;
+
         Self::deposit_event(Event::<T>::UnstakeQueued {
           request_id: stake_id,
           account_id,
+          fee: unstaking_fee,
         });
       } else {
         // we can process to unstaking immediately
-        Self::process_unstake(&account_id, stake_id)?;
+        let settled_stake = Self::process_unstake(&account_id, stake_id)?;
         Self::deposit_event(Event::<T>::Unstaked {
           request_id: stake_id,
           account_id,
           currency_id: stake.currency_id,
           initial_balance: stake.initial_balance,
-          final_balance: stake.principal,
+          final_balance: settled_stake.principal,
         });
       }
 
       Ok(().into())
     }
-  }
-
-  // helper functions (not dispatchable)
-  impl<T: Config> Pallet<T> {
-    pub fn account_id() -> T::AccountId {
-      <T as pallet::Config>::StakePalletId::get().into_account()
-    }
-
-    pub fn add_account_stake(
-      account_id: &T::AccountId,
-      currency_id: CurrencyId,
-      amount: Balance,
-      duration: T::BlockNumber,
-    ) -> Result<Hash, DispatchError> {
-      // create unique hash
-      let unique_stake_request_id = <T as Config>::Security::get_unique_id(account_id.clone());
-
-      // 3. Transfer the funds into the staking pool
-      T::CurrencyTidefi::can_withdraw(currency_id, account_id, amount)
-        .into_result()
-        .map_err(|_| Error::<T>::InsufficientBalance)?;
 
-      T::CurrencyTidefi::transfer(currency_id, account_id, &Self::account_id(), amount, false)?;
+    /// Cancel a queued unstake, the stake is kept and the unstake fee is not refunded
+    ///
+    /// - `stake_id`: Unique Stake ID
+    ///
+    /// Emits `QueuedUnstakeCancelled` event when successful.
+    ///
+    /// Weight: `O(1)`
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::cancel_queued_unstake())]
+    pub fn cancel_queued_unstake(
+      origin: OriginFor<T>,
+      stake_id: Hash,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the transaction is signed
+      let account_id = ensure_signed(origin)?;
 
-      // 4. Update our `StakingPool` storage
-      StakingPool::<T>::try_mutate(currency_id, |balance| -> DispatchResult {
-        if let Some(b) = balance {
-          *balance = Some(b.checked_add(amount).ok_or(ArithmeticError::Overflow)?);
-        } else {
-          *balance = Some(amount)
-        }
+      // 2. Remove the unstake of the account from the queue
+      UnstakeQueue::<T>::try_mutate(|unstake_queue| -> DispatchResult {
+        let position = unstake_queue
+          .iter()
+          .position(|(queued_account_id, queued_stake_id, _)| {
+            *queued_account_id == account_id && *queued_stake_id == stake_id
+          })
+          .ok_or(Error::<T>::UnstakeNotQueued)?;
+        unstake_queue.remove(position);
         Ok(())
       })?;
 
-      // 5. Insert the new staking
-      let initial_block = T::Security::get_current_block_count();
-      AccountStakes::<T>::mutate(account_id.clone(), |stake| -> DispatchResult {
-        stake
-          .try_push(Stake {
-            currency_id,
-            unique_id: unique_stake_request_id,
-            last_session_index_compound: InterestCompoundLastSession::<T>::get(),
-            initial_block,
-            initial_balance: amount,
-            principal: amount,
-            duration,
-          })
-          .map_err(|_| DispatchError::Other("Invalid stake; eqd"))
-      })?;
+      // 3. Emit event on chain
+      Self::deposit_event(Event::<T>::QueuedUnstakeCancelled {
+        request_id: stake_id,
+        account_id,
+      });
 
-      Ok(unique_stake_request_id)
+      Ok(().into())
     }
 
-    fn get_account_stake(
-      account_id: &T::AccountId,
+    /// Add funds to an active stake
+    ///
+    /// - `stake_id`: Unique Stake ID
+    /// - `amount`: The amount to add to the stake
+    ///
+    /// Emits `StakeIncreased` event when successful.
+    ///
+    /// Weight: `O(1)`
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::increase_stake())]
+    #[transactional]
+    pub fn increase_stake(
+      origin: OriginFor<T>,
       stake_id: Hash,
-    ) -> Option<Stake<Balance, T::BlockNumber>> {
-      AccountStakes::<T>::get(account_id)
-        .into_iter()
-        .find(|stake| stake.unique_id == stake_id)
-    }
+      amount: Balance,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the transaction is signed
+      let account_id = ensure_signed(origin)?;
 
-    fn process_unstake(account_id: &T::AccountId, stake_id: Hash) -> DispatchResult {
-      let current_stake =
+      // 2. Make sure the stake is still accruing rewards and not queued for unstake
+      let stake =
         Self::get_account_stake(&account_id, stake_id).ok_or(Error::<T>::InvalidStakeId)?;
-      AccountStakes::<T>::try_mutate_exists(account_id, |account_stakes| match account_stakes {
-        None => Err(Error::<T>::InvalidStakeId),
-        Some(stakes) => {
-          T::CurrencyTidefi::can_withdraw(
-            current_stake.currency_id,
-            &Self::account_id(),
-            current_stake.principal,
-          )
-          .into_result()
-          .map_err(|_| Error::<T>::InsufficientBalance)?;
-
-          T::CurrencyTidefi::transfer(
-            current_stake.currency_id,
-            &Self::account_id(),
-            &account_id,
-            current_stake.principal,
-            false,
-          )
-          .map_err(|_| Error::<T>::TransferFailed)?;
+      ensure!(
+        StakeRewardIndex::<T>::contains_key(stake_id)
+          && stake.initial_block.saturating_add(stake.duration)
+            > T::Security::get_current_block_count(),
+        Error::<T>::StakeNotActive
+      );
+      ensure!(
+        !Self::is_unstake_queued(stake_id),
+        Error::<T>::UnstakeAlreadyQueued
+      );
 
-          if stakes.len() > 1 {
-            stakes.retain(|stake| stake.unique_id != stake_id);
-          } else {
-            *account_stakes = None;
-          }
+      // 3. If we have the metadata of this currency, make sure the stake isn't too high
+      let new_initial_balance = stake
+        .initial_balance
+        .checked_add(amount)
+        .ok_or(ArithmeticError::Overflow)?;
+      if let Some(currency_meta) = StakingCurrencyMeta::<T>::get(stake.currency_id) {
+        ensure!(
+          new_initial_balance <= currency_meta.maximum_amount,
+          Error::<T>::AmountTooLarge
+        );
+      }
 
-          Ok(())
-        }
+      // 4. Transfer the funds into the staking pool
+      T::CurrencyTidefi::can_withdraw(stake.currency_id, &account_id, amount)
+        .into_result()
+        .map_err(|_| Error::<T>::InsufficientBalance)?;
+      T::CurrencyTidefi::transfer(
+        stake.currency_id,
+        &account_id,
+        &Self::account_id(),
+        amount,
+        false,
+      )?;
+
+      // 5. Settle the rewards of the previous amount, and add the funds to the stake
+      Self::settle_stake(&account_id, stake_id)?;
+      Self::update_account_stake(&account_id, stake_id, |stake| {
+        stake.initial_balance = new_initial_balance;
+        stake.principal = stake.principal.saturating_add(amount);
       })?;
+      Self::increase_staked_amount(stake.currency_id, stake.duration, amount, true);
 
-      Ok(())
-    }
+      // 6. Emit event on chain
+      Self::deposit_event(Event::<T>::StakeIncreased {
+        request_id: stake_id,
+        account_id,
+        amount,
+      });
 
-    // FIXME: require more tests to prevent any blocking on-chain
-    #[inline]
-    pub fn do_next_compound_interest_operation(
-      max_weight: Weight,
-    ) -> Result<(Weight, bool), DispatchError> {
-      let weight_per_iteration = <T as frame_system::Config>::DbWeight::get().reads_writes(2, 2);
-      let max_iterations = if weight_per_iteration == 0 {
-        100
-      } else {
-        max_weight / weight_per_iteration
-      };
+      Ok(().into())
+    }
 
-      let pending_session_to_compound = PendingStoredSessions::<T>::count();
-      if pending_session_to_compound == 0 {
-        return Ok((weight_per_iteration, false));
-      }
+    /// Unstake a part of the expired stake, the rewards are unstaked first
+    ///
+    /// - `stake_id`: Unique Stake ID
+    /// - `amount`: The amount to unstake
+    ///
+    /// The stake that is not expired can only be unstaked entirely with `unstake`, through the
+    /// unstake queue.
+    ///
+    /// Emits `StakePartiallyUnstaked` event when successful.
+    ///
+    /// Weight: `O(1)`
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::partial_unstake())]
+    #[transactional]
+    pub fn partial_unstake(
+      origin: OriginFor<T>,
+      stake_id: Hash,
+      amount: Balance,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the transaction is signed
+      let account_id = ensure_signed(origin)?;
 
-      let all_pending_sessions: Vec<SessionIndex> = PendingStoredSessions::<T>::iter()
-        .map(|(key, _)| key)
+      // 2. Make sure the stake is expired and not queued for unstake
+      let stake =
+        Self::get_account_stake(&account_id, stake_id).ok_or(Error::<T>::InvalidStakeId)?;
+      ensure!(
+        stake.initial_block.saturating_add(stake.duration)
+          <= T::Security::get_current_block_count(),
+        Error::<T>::StakeNotExpired
+      );
+      ensure!(
+        !Self::is_unstake_queued(stake_id),
+        Error::<T>::UnstakeAlreadyQueued
+      );
+
+      // 3. Settle the rewards, and make sure the remaining stake is above the minimum
+      Self::settle_stake(&account_id, stake_id)?;
+      let stake =
+        Self::get_account_stake(&account_id, stake_id).ok_or(Error::<T>::InvalidStakeId)?;
+      ensure!(!amount.is_zero(), Error::<T>::AmountTooSmall);
+      ensure!(amount < stake.principal, Error::<T>::AmountTooLarge);
+      let remaining_principal = stake.principal.saturating_sub(amount);
+      if let Some(currency_meta) = StakingCurrencyMeta::<T>::get(stake.currency_id) {
+        ensure!(
+          remaining_principal >= currency_meta.minimum_amount,
+          Error::<T>::AmountTooSmall
+        );
+      }
+
+      // 4. Release the amount from the staking pool
+      T::CurrencyTidefi::can_withdraw(stake.currency_id, &Self::account_id(), amount)
+        .into_result()
+        .map_err(|_| Error::<T>::InsufficientBalance)?;
+      T::CurrencyTidefi::transfer(
+        stake.currency_id,
+        &Self::account_id(),
+        &account_id,
+        amount,
+        false,
+      )
+      .map_err(|_| Error::<T>::TransferFailed)?;
+
+      // 5. Remove the amount from the stake
+      let new_initial_balance = stake.initial_balance.min(remaining_principal);
+      Self::update_account_stake(&account_id, stake_id, |stake| {
+        stake.initial_balance = new_initial_balance;
+        stake.principal = remaining_principal;
+      })?;
+      Self::decrease_staked_amount(
+        stake.currency_id,
+        stake.duration,
+        stake.initial_balance.saturating_sub(new_initial_balance),
+        StakeRewardIndex::<T>::contains_key(stake_id),
+      );
+
+      // 6. Emit event on chain
+      Self::deposit_event(Event::<T>::StakePartiallyUnstaked {
+        request_id: stake_id,
+        account_id,
+        amount,
+      });
+
+      Ok(().into())
+    }
+
+    /// Set the auto-renew of the stake, the stake is renewed for a new period with its principal
+    /// when it expires
+    ///
+    /// - `stake_id`: Unique Stake ID
+    /// - `auto_renew`: Renew the stake when it expires
+    ///
+    /// Emits `StakeAutoRenewSet` event when successful.
+    ///
+    /// Weight: `O(1)`
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::set_stake_auto_renew())]
+    pub fn set_stake_auto_renew(
+      origin: OriginFor<T>,
+      stake_id: Hash,
+      auto_renew: bool,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the transaction is signed
+      let account_id = ensure_signed(origin)?;
+
+      // 2. Make sure the stake belong to the account
+      ensure!(
+        Self::get_account_stake(&account_id, stake_id).is_some(),
+        Error::<T>::InvalidStakeId
+      );
+
+      // 3. Update the auto-renew
+      if auto_renew {
+        StakesAutoRenew::<T>::insert(stake_id, true);
+      } else {
+        StakesAutoRenew::<T>::remove(stake_id);
+      }
+
+      // 4. Emit event on chain
+      Self::deposit_event(Event::<T>::StakeAutoRenewSet {
+        request_id: stake_id,
+        account_id,
+        auto_renew,
+      });
+
+      Ok(().into())
+    }
+
+    /// Propose the transfer of the stake to another account, with its terms and its rewards, the
+    /// stake is moved once the destination accepts the transfer
+    ///
+    /// - `stake_id`: Unique Stake ID
+    /// - `destination_id`: The new owner of the stake
+    ///
+    /// A new transfer replaces the pending transfer of the stake.
+    ///
+    /// Emits `StakeTransferProposed` event when successful.
+    ///
+    /// Weight: `O(1)`
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::transfer_stake())]
+    pub fn transfer_stake(
+      origin: OriginFor<T>,
+      stake_id: Hash,
+      destination_id: T::AccountId,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the transaction is signed
+      let account_id = ensure_signed(origin)?;
+
+      // 2. Make sure the stake is not queued for unstake
+      ensure!(
+        account_id != destination_id,
+        Error::<T>::InvalidStakeTransfer
+      );
+      ensure!(
+        Self::get_account_stake(&account_id, stake_id).is_some(),
+        Error::<T>::InvalidStakeId
+      );
+      ensure!(
+        !Self::is_unstake_queued(stake_id),
+        Error::<T>::UnstakeAlreadyQueued
+      );
+
+      // 3. Wait for the acceptance of the destination
+      PendingStakeTransfers::<T>::insert(stake_id, (account_id.clone(), destination_id.clone()));
+
+      // 4. Emit event on chain
+      Self::deposit_event(Event::<T>::StakeTransferProposed {
+        request_id: stake_id,
+        account_id,
+        destination_id,
+      });
+
+      Ok(().into())
+    }
+
+    /// Cancel the pending transfer of the stake
+    ///
+    /// - `stake_id`: Unique Stake ID
+    ///
+    /// Emits `StakeTransferCancelled` event when successful.
+    ///
+    /// Weight: `O(1)`
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::cancel_stake_transfer())]
+    pub fn cancel_stake_transfer(
+      origin: OriginFor<T>,
+      stake_id: Hash,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the transaction is signed
+      let account_id = ensure_signed(origin)?;
+
+      // 2. Make sure the pending transfer has been proposed by the account
+      let (owner_id, _) =
+        Self::pending_stake_transfer(stake_id).ok_or(Error::<T>::StakeTransferNotFound)?;
+      ensure!(owner_id == account_id, Error::<T>::StakeTransferNotFound);
+
+      // 3. Remove the pending transfer
+      PendingStakeTransfers::<T>::remove(stake_id);
+
+      // 4. Emit event on chain
+      Self::deposit_event(Event::<T>::StakeTransferCancelled {
+        request_id: stake_id,
+        account_id,
+      });
+
+      Ok(().into())
+    }
+
+    /// Accept the pending transfer of the stake to the account
+    ///
+    /// - `stake_id`: Unique Stake ID
+    ///
+    /// The reward beneficiary of the stake is removed.
+    ///
+    /// Emits `StakeTransferred` event when successful.
+    ///
+    /// Weight: `O(1)`
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::accept_stake_transfer())]
+    #[transactional]
+    pub fn accept_stake_transfer(
+      origin: OriginFor<T>,
+      stake_id: Hash,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the transaction is signed
+      let destination_id = ensure_signed(origin)?;
+
+      // 2. Make sure the transfer is pending for the account, and the stake is not queued for
+      // unstake
+      let (account_id, pending_destination_id) =
+        Self::pending_stake_transfer(stake_id).ok_or(Error::<T>::StakeTransferNotFound)?;
+      ensure!(
+        pending_destination_id == destination_id,
+        Error::<T>::StakeTransferNotFound
+      );
+      ensure!(
+        Self::get_account_stake(&account_id, stake_id).is_some(),
+        Error::<T>::InvalidStakeId
+      );
+      ensure!(
+        !Self::is_unstake_queued(stake_id),
+        Error::<T>::UnstakeAlreadyQueued
+      );
+
+      // 3. Settle the rewards for the current beneficiary
+      Self::settle_stake(&account_id, stake_id)?;
+      let stake =
+        Self::get_account_stake(&account_id, stake_id).ok_or(Error::<T>::InvalidStakeId)?;
+
+      // 4. Move the stake to the destination account
+      AccountStakes::<T>::try_mutate_exists(&account_id, |account_stakes| -> DispatchResult {
+        let stakes = account_stakes.as_mut().ok_or(Error::<T>::InvalidStakeId)?;
+        if stakes.len() > 1 {
+          stakes.retain(|stake| stake.unique_id != stake_id);
+        } else {
+          *account_stakes = None;
+        }
+        Ok(())
+      })?;
+      AccountStakes::<T>::try_mutate(&destination_id, |stakes| {
+        stakes
+          .try_push(stake)
+          .map_err(|_| Error::<T>::StakeAccountCapExceeded)
+      })?;
+      PendingStakeTransfers::<T>::remove(stake_id);
+      StakesRewardBeneficiary::<T>::remove(stake_id);
+      TransferredStakes::<T>::insert(stake_id, destination_id.clone());
+
+      // 5. Emit event on chain
+      Self::deposit_event(Event::<T>::StakeTransferred {
+        request_id: stake_id,
+        account_id,
+        destination_id,
+      });
+
+      Ok(().into())
+    }
+
+    /// Set the account receiving the rewards of the stake, the principal stay in the stake
+    ///
+    /// - `stake_id`: Unique Stake ID
+    /// - `beneficiary`: The account claiming the rewards, `None` compound the rewards in the stake
+    ///
+    /// Emits `StakeRewardBeneficiarySet` event when successful.
+    ///
+    /// Weight: `O(1)`
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::set_stake_reward_beneficiary())]
+    pub fn set_stake_reward_beneficiary(
+      origin: OriginFor<T>,
+      stake_id: Hash,
+      beneficiary: Option<T::AccountId>,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the transaction is signed
+      let account_id = ensure_signed(origin)?;
+
+      // 2. Settle the rewards accrued for the previous beneficiary
+      Self::settle_stake(&account_id, stake_id)?;
+
+      // 3. Update the beneficiary
+      match beneficiary.clone() {
+        Some(beneficiary) => StakesRewardBeneficiary::<T>::insert(stake_id, beneficiary),
+        None => StakesRewardBeneficiary::<T>::remove(stake_id),
+      }
+
+      // 4. Emit event on chain
+      Self::deposit_event(Event::<T>::StakeRewardBeneficiarySet {
+        request_id: stake_id,
+        account_id,
+        beneficiary,
+      });
+
+      Ok(().into())
+    }
+
+    /// Claim the rewards of the stakes delegated to the account
+    ///
+    /// - `currency_id`: The currency of the rewards
+    ///
+    /// Emits `BeneficiaryRewardsClaimed` event when successful.
+    ///
+    /// Weight: `O(1)`
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::claim_beneficiary_rewards())]
+    pub fn claim_beneficiary_rewards(
+      origin: OriginFor<T>,
+      currency_id: CurrencyId,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the transaction is signed
+      let account_id = ensure_signed(origin)?;
+
+      // 2. Make sure the account has rewards to claim
+      let amount = Self::beneficiary_rewards(&account_id, currency_id);
+      ensure!(!amount.is_zero(), Error::<T>::NoBeneficiaryRewards);
+
+      // 3. Transfer the rewards from the staking pool
+      T::CurrencyTidefi::transfer(currency_id, &Self::account_id(), &account_id, amount, false)
+        .map_err(|_| Error::<T>::TransferFailed)?;
+      BeneficiaryRewards::<T>::remove(&account_id, currency_id);
+
+      // 4. Emit event on chain
+      Self::deposit_event(Event::<T>::BeneficiaryRewardsClaimed {
+        account_id,
+        currency_id,
+        amount,
+      });
+
+      Ok(().into())
+    }
+
+    /// Add a staking period or update its reward, the opened stakes keep their reward
+    ///
+    /// - `duration`: The duration of the period in numbers of blocks
+    /// - `reward`: The reward percentage of the period
+    ///
+    /// Emits `StakingPeriodSet` event when successful.
+    ///
+    /// Weight: `O(1)`
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::set_staking_period())]
+    pub fn set_staking_period(
+      origin: OriginFor<T>,
+      duration: T::BlockNumber,
+      reward: Percent,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the origin is allowed
+      T::ForceOrigin::ensure_origin(origin)?;
+
+      // 2. Update the reward of the period, or add the period
+      StakingPeriodRewards::<T>::try_mutate(|periods| -> DispatchResult {
+        match periods
+          .iter_mut()
+          .find(|(period_duration, _)| *period_duration == duration)
+        {
+          Some(period) => period.1 = reward,
+          None => periods
+            .try_push((duration, reward))
+            .map_err(|_| Error::<T>::StakingRewardCapExceeded)?,
+        }
+        Ok(())
+      })?;
+
+      // 3. Emit event on chain
+      Self::deposit_event(Event::<T>::StakingPeriodSet { duration, reward });
+
+      Ok(().into())
+    }
+
+    /// Remove a staking period, the opened stakes keep their terms until they are unstaked
+    ///
+    /// - `duration`: The duration of the period in numbers of blocks
+    ///
+    /// Emits `StakingPeriodRemoved` event when successful.
+    ///
+    /// Weight: `O(1)`
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::remove_staking_period())]
+    pub fn remove_staking_period(
+      origin: OriginFor<T>,
+      duration: T::BlockNumber,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the origin is allowed
+      T::ForceOrigin::ensure_origin(origin)?;
+
+      // 2. Remove the period
+      StakingPeriodRewards::<T>::try_mutate(|periods| -> DispatchResult {
+        let periods_count = periods.len();
+        periods.retain(|(period_duration, _)| *period_duration != duration);
+        ensure!(periods.len() < periods_count, Error::<T>::InvalidDuration);
+        Ok(())
+      })?;
+
+      // 3. Emit event on chain
+      Self::deposit_event(Event::<T>::StakingPeriodRemoved { duration });
+
+      Ok(().into())
+    }
+
+    /// Set the minimum and maximum stake amount of a currency
+    ///
+    /// - `currency_id`: The currency to update
+    /// - `minimum_amount`: The minimum amount of a stake
+    /// - `maximum_amount`: The maximum amount of a stake
+    ///
+    /// Emits `StakingCurrencyMetaSet` event when successful.
+    ///
+    /// Weight: `O(1)`
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::set_staking_currency_meta())]
+    pub fn set_staking_currency_meta(
+      origin: OriginFor<T>,
+      currency_id: CurrencyId,
+      minimum_amount: Balance,
+      maximum_amount: Balance,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the origin is allowed
+      T::ForceOrigin::ensure_origin(origin)?;
+
+      // 2. Make sure the limits are consistent
+      ensure!(
+        minimum_amount <= maximum_amount,
+        Error::<T>::InvalidCurrencyMeta
+      );
+
+      // 3. Update the limits of the currency
+      StakingCurrencyMeta::<T>::insert(
+        currency_id,
+        StakeCurrencyMeta {
+          minimum_amount,
+          maximum_amount,
+        },
+      );
+
+      // 4. Emit event on chain
+      Self::deposit_event(Event::<T>::StakingCurrencyMetaSet {
+        currency_id,
+        minimum_amount,
+        maximum_amount,
+      });
+
+      Ok(().into())
+    }
+
+    /// Set the fee taken when unstaking before the end of the stake, the opened stakes keep
+    /// their unstake fee
+    ///
+    /// - `unstake_fee`: The percentage of the initial balance taken as fee
+    ///
+    /// Emits `UnstakeFeeSet` event when successful.
+    ///
+    /// Weight: `O(1)`
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::set_unstake_fee())]
+    pub fn set_unstake_fee(
+      origin: OriginFor<T>,
+      unstake_fee: Percent,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the origin is allowed
+      T::ForceOrigin::ensure_origin(origin)?;
+
+      // 2. Update the unstake fee
+      UnstakeFee::<T>::put(unstake_fee);
+
+      // 3. Emit event on chain
+      Self::deposit_event(Event::<T>::UnstakeFeeSet { unstake_fee });
+
+      Ok(().into())
+    }
+  }
+
+  // helper functions (not dispatchable)
+  impl<T: Config> Pallet<T> {
+    pub fn account_id() -> T::AccountId {
+      <T as pallet::Config>::StakePalletId::get().into_account()
+    }
+
+    pub fn add_account_stake(
+      account_id: &T::AccountId,
+      currency_id: CurrencyId,
+      amount: Balance,
+      duration: T::BlockNumber,
+    ) -> Result<Hash, DispatchError> {
+      // create unique hash
+      let unique_stake_request_id = <T as Config>::Security::get_unique_id(account_id.clone());
+
+      // 3. Transfer the funds into the staking pool
+      T::CurrencyTidefi::can_withdraw(currency_id, account_id, amount)
+        .into_result()
+        .map_err(|_| Error::<T>::InsufficientBalance)?;
+
+      T::CurrencyTidefi::transfer(currency_id, account_id, &Self::account_id(), amount, false)?;
+
+      // 4. Update our `StakingPool` storage
+      StakingPool::<T>::try_mutate(currency_id, |balance| -> DispatchResult {
+        if let Some(b) = balance {
+          *balance = Some(b.checked_add(amount).ok_or(ArithmeticError::Overflow)?);
+        } else {
+          *balance = Some(amount)
+        }
+        Ok(())
+      })?;
+
+      // 5. Insert the new staking with a snapshot of the current terms
+      StakesTerms::<T>::insert(unique_stake_request_id, Self::current_stake_terms(duration));
+      let new_stake = Stake {
+        currency_id,
+        unique_id: unique_stake_request_id,
+        last_session_index_compound: InterestCompoundLastSession::<T>::get(),
+        initial_block: T::Security::get_current_block_count(),
+        initial_balance: amount,
+        principal: amount,
+        duration,
+      };
+      AccountStakes::<T>::mutate(account_id.clone(), |stake| -> DispatchResult {
+        stake
+          .try_push(new_stake.clone())
+          .map_err(|_| Error::<T>::StakeAccountCapExceeded.into())
+      })?;
+
+      // 6. Accrue the rewards until the end of the stake
+      Self::activate_stake(account_id, &new_stake);
+
+      Ok(unique_stake_request_id)
+    }
+
+    // Terms of a new stake of `duration` under the current staking periods and unstake fee
+    pub fn current_stake_terms(duration: T::BlockNumber) -> StakeTerms {
+      StakeTerms {
+        reward: StakingPeriodRewards::<T>::get()
+          .into_iter()
+          .find(|(period_duration, _)| *period_duration == duration)
+          .map(|(_, reward)| reward)
+          .unwrap_or_else(Percent::zero),
+        unstake_fee: Self::unstake_fee(),
+      }
+    }
+
+    // Terms the stake was opened under, the stakes opened before the snapshots use the
+    // current terms
+    pub fn get_stake_terms(stake_id: Hash, duration: T::BlockNumber) -> StakeTerms {
+      Self::stake_terms(stake_id).unwrap_or_else(|| Self::current_stake_terms(duration))
+    }
+
+    fn get_account_stake(
+      account_id: &T::AccountId,
+      stake_id: Hash,
+    ) -> Option<Stake<Balance, T::BlockNumber>> {
+      AccountStakes::<T>::get(account_id)
+        .into_iter()
+        .find(|stake| stake.unique_id == stake_id)
+    }
+
+    // Settle the stake and transfer its principal back to the account
+    #[transactional]
+    fn process_unstake(
+      account_id: &T::AccountId,
+      stake_id: Hash,
+    ) -> Result<Stake<Balance, T::BlockNumber>, DispatchError> {
+      Self::deactivate_stake(account_id, stake_id)?;
+      let current_stake =
+        Self::get_account_stake(&account_id, stake_id).ok_or(Error::<T>::InvalidStakeId)?;
+      AccountStakes::<T>::try_mutate_exists(account_id, |account_stakes| match account_stakes {
+        None => Err(Error::<T>::InvalidStakeId),
+        Some(stakes) => {
+          T::CurrencyTidefi::can_withdraw(
+            current_stake.currency_id,
+            &Self::account_id(),
+            current_stake.principal,
+          )
+          .into_result()
+          .map_err(|_| Error::<T>::InsufficientBalance)?;
+
+          T::CurrencyTidefi::transfer(
+            current_stake.currency_id,
+            &Self::account_id(),
+            &account_id,
+            current_stake.principal,
+            false,
+          )
+          .map_err(|_| Error::<T>::TransferFailed)?;
+
+          if stakes.len() > 1 {
+            stakes.retain(|stake| stake.unique_id != stake_id);
+          } else {
+            *account_stakes = None;
+          }
+          StakesTerms::<T>::remove(stake_id);
+          StakesAutoRenew::<T>::remove(stake_id);
+          StakesRewardBeneficiary::<T>::remove(stake_id);
+          TransferredStakes::<T>::remove(stake_id);
+          PendingStakeTransfers::<T>::remove(stake_id);
+
+          Ok(())
+        }
+      })?;
+
+      // the unstaked amount doesn't share the session fees anymore
+      StakingPool::<T>::mutate_exists(current_stake.currency_id, |balance| {
+        *balance = balance
+          .map(|balance| balance.saturating_sub(current_stake.initial_balance))
+          .filter(|balance| !balance.is_zero());
+      });
+
+      Ok(current_stake)
+    }
+
+    // Start the reward accrual of a new stake, until it expires
+    pub(crate) fn activate_stake(
+      account_id: &T::AccountId,
+      stake: &Stake<Balance, T::BlockNumber>,
+    ) {
+      StakeRewardIndex::<T>::insert(
+        stake.unique_id,
+        RewardPerShare::<T>::get(stake.currency_id, stake.duration),
+      );
+      RewardShares::<T>::mutate(stake.currency_id, stake.duration, |shares| {
+        *shares = shares.saturating_add(stake.initial_balance)
+      });
+
+      let bucket = (stake.currency_id, stake.duration);
+      let (head, tail) = StakeExpiryQueueBounds::<T>::get(bucket);
+      StakeExpiryQueue::<T>::insert(
+        bucket,
+        tail,
+        (
+          account_id.clone(),
+          stake.unique_id,
+          stake.initial_block.saturating_add(stake.duration),
+        ),
+      );
+      StakeExpiryQueueBounds::<T>::insert(bucket, (head, tail.saturating_add(1)));
+    }
+
+    // Reward accrued by the stake since its last settlement, with the reward of the terms the
+    // stake was opened under
+    pub fn pending_stake_reward(stake: &Stake<Balance, T::BlockNumber>) -> Balance {
+      match Self::stake_reward_index(stake.unique_id) {
+        Some(stake_index) => {
+          let reward_per_share =
+            RewardPerShare::<T>::get(stake.currency_id, stake.duration).saturating_sub(stake_index);
+          Self::get_stake_terms(stake.unique_id, stake.duration).reward
+            * reward_per_share.saturating_mul_int(stake.initial_balance)
+        }
+        None => Zero::zero(),
+      }
+    }
+
+    // Add the reward accrued by the stake to its principal
+    fn settle_stake(account_id: &T::AccountId, stake_id: Hash) -> DispatchResult {
+      AccountStakes::<T>::try_mutate(account_id, |stakes| -> DispatchResult {
+        let stake = stakes
+          .iter_mut()
+          .find(|stake| stake.unique_id == stake_id)
+          .ok_or(Error::<T>::InvalidStakeId)?;
+        if StakeRewardIndex::<T>::contains_key(stake_id) {
+          let reward = Self::pending_stake_reward(stake);
+          // the delegated rewards are claimed by the beneficiary, the others are compounded
+          match Self::stake_reward_beneficiary(stake_id) {
+            Some(beneficiary) => {
+              BeneficiaryRewards::<T>::mutate(beneficiary, stake.currency_id, |rewards| {
+                *rewards = rewards.saturating_add(reward)
+              })
+            }
+            None => stake.principal = stake.principal.saturating_add(reward),
+          }
+          StakeRewardIndex::<T>::insert(
+            stake_id,
+            RewardPerShare::<T>::get(stake.currency_id, stake.duration),
+          );
+        }
+        stake.last_session_index_compound = InterestCompoundLastSession::<T>::get();
+        Ok(())
+      })
+    }
+
+    // Settle the stake and stop its reward accrual
+    fn deactivate_stake(account_id: &T::AccountId, stake_id: Hash) -> DispatchResult {
+      Self::settle_stake(account_id, stake_id)?;
+      if StakeRewardIndex::<T>::take(stake_id).is_some() {
+        let stake =
+          Self::get_account_stake(account_id, stake_id).ok_or(Error::<T>::InvalidStakeId)?;
+        RewardShares::<T>::mutate_exists(stake.currency_id, stake.duration, |shares| {
+          *shares = shares
+            .map(|shares| shares.saturating_sub(stake.initial_balance))
+            .filter(|shares| !shares.is_zero());
+        });
+      }
+      Ok(())
+    }
+
+    // Distribute the fees of the session to the active stakes of the currency, the reward per
+    // share of each duration with active stakes is increased
+    pub(crate) fn distribute_session_fees(currency_id: CurrencyId, session_fees: Balance) {
+      let staking_pool = StakingPool::<T>::get(currency_id).unwrap_or(0);
+      if staking_pool.is_zero() || session_fees.is_zero() {
+        return;
+      }
+
+      let reward_per_share = FixedU128::saturating_from_rational(session_fees, staking_pool);
+      let durations: Vec<T::BlockNumber> = RewardShares::<T>::iter_prefix(currency_id)
+        .map(|(duration, _)| duration)
         .collect();
+      for duration in durations {
+        RewardPerShare::<T>::mutate(currency_id, duration, |index| {
+          *index = index.saturating_add(reward_per_share)
+        });
+      }
+    }
 
-      let mut current_iterations = 1;
-      let last_session = InterestCompoundLastSession::<T>::get();
+    // Make sure the stake is in the unstake queue
+    fn is_unstake_queued(stake_id: Hash) -> bool {
+      Self::unstake_queue()
+        .iter()
+        .any(|(_, queued_stake_id, _)| *queued_stake_id == stake_id)
+    }
 
-      log!(
-        trace,
-        "Running next compound interest operation for {} sessions.",
-        all_pending_sessions.len()
+    // Update a stake of the account
+    pub(crate) fn update_account_stake(
+      account_id: &T::AccountId,
+      stake_id: Hash,
+      update: impl FnOnce(&mut Stake<Balance, T::BlockNumber>),
+    ) -> Result<Stake<Balance, T::BlockNumber>, DispatchError> {
+      AccountStakes::<T>::try_mutate(account_id, |stakes| -> Result<_, DispatchError> {
+        let stake = stakes
+          .iter_mut()
+          .find(|stake| stake.unique_id == stake_id)
+          .ok_or(Error::<T>::InvalidStakeId)?;
+        update(stake);
+        Ok(stake.clone())
+      })
+    }
+
+    // Add the amount to the staking pool, and to the shares of the active stakes
+    fn increase_staked_amount(
+      currency_id: CurrencyId,
+      duration: T::BlockNumber,
+      amount: Balance,
+      is_active: bool,
+    ) {
+      StakingPool::<T>::mutate(currency_id, |balance| {
+        *balance = Some(balance.unwrap_or(0).saturating_add(amount))
+      });
+      if is_active {
+        RewardShares::<T>::mutate(currency_id, duration, |shares| {
+          *shares = shares.saturating_add(amount)
+        });
+      }
+    }
+
+    // Remove the amount from the staking pool, and from the shares of the active stakes
+    fn decrease_staked_amount(
+      currency_id: CurrencyId,
+      duration: T::BlockNumber,
+      amount: Balance,
+      is_active: bool,
+    ) {
+      StakingPool::<T>::mutate_exists(currency_id, |balance| {
+        *balance = balance
+          .map(|balance| balance.saturating_sub(amount))
+          .filter(|balance| !balance.is_zero());
+      });
+      if is_active {
+        RewardShares::<T>::mutate_exists(currency_id, duration, |shares| {
+          *shares = shares
+            .map(|shares| shares.saturating_sub(amount))
+            .filter(|shares| !shares.is_zero());
+        });
+      }
+    }
+
+    // Renew the expired stake when the auto-renew is set and its staking period still exist,
+    // otherwise stop its reward accrual
+    fn expire_stake(account_id: &T::AccountId, stake_id: Hash) -> DispatchResult {
+      if Self::stake_auto_renew(stake_id) && !Self::is_unstake_queued(stake_id) {
+        let stake =
+          Self::get_account_stake(account_id, stake_id).ok_or(Error::<T>::InvalidStakeId)?;
+        if StakingPeriodRewards::<T>::get()
+          .iter()
+          .any(|(duration, _)| *duration == stake.duration)
+        {
+          return Self::renew_stake(account_id, stake_id);
+        }
+      }
+      Self::deactivate_stake(account_id, stake_id)
+    }
+
+    // Renew the stake for a new period from now with its principal, under the current terms
+    fn renew_stake(account_id: &T::AccountId, stake_id: Hash) -> DispatchResult {
+      Self::deactivate_stake(account_id, stake_id)?;
+      let current_block = T::Security::get_current_block_count();
+      let mut accrued_rewards = Zero::zero();
+      let renewed_stake = Self::update_account_stake(account_id, stake_id, |stake| {
+        accrued_rewards = stake.principal.saturating_sub(stake.initial_balance);
+        stake.initial_balance = stake.principal;
+        stake.initial_block = current_block;
+      })?;
+
+      Self::increase_staked_amount(
+        renewed_stake.currency_id,
+        renewed_stake.duration,
+        accrued_rewards,
+        false,
       );
+      StakesTerms::<T>::insert(stake_id, Self::current_stake_terms(renewed_stake.duration));
+      Self::activate_stake(account_id, &renewed_stake);
 
-      // loop all stakes
-      //for (account_id, currency_id, stake_details) in AccountStakes::<T>::iter() {
-      for (account_id, account_stakes) in AccountStakes::<T>::iter() {
-        //for (_, last_session_indexed, _) in account_stakes {
-        for current_stake in account_stakes {
-          let currency_id = current_stake.currency_id;
-          let staking_pool_for_this_currency = StakingPool::<T>::get(currency_id).unwrap_or(0);
-          if current_stake.last_session_index_compound <= last_session {
-            let mut keep_going_in_loop = Some(current_stake.last_session_index_compound + 1);
-            while let Some(session_to_index) = keep_going_in_loop {
-              if all_pending_sessions.contains(&session_to_index) {
-                let session_fee_for_currency =
-                  SessionTotalFees::<T>::get(session_to_index, currency_id);
-                AccountStakes::<T>::mutate(
-                  account_id.clone(),
-                  |staking_details| -> DispatchResult {
-                    // loop trough all stake for this currency for this account
-                    let mut final_stake = staking_details.clone();
-                    for active_stake in final_stake.as_mut().iter_mut() {
-                      if T::Security::get_current_block_count()
-                        <= current_stake.initial_block + current_stake.duration
-                      {
-                        // FIXME: we could probably find the closest reward
-                        // but in theory this should never happens
-                        let available_reward = StakingPeriodRewards::<T>::get()
-                          .into_iter()
-                          .find(|(duration, _)| *duration == active_stake.duration)
-                          .map(|(_, reward)| reward)
-                          .unwrap_or_else(Percent::zero)
-                          * session_fee_for_currency;
-
-                        // calculate proportional reward base on the stake pool
-                        let staking_pool_percentage = Perquintill::from_rational(
-                          active_stake.initial_balance,
-                          staking_pool_for_this_currency,
-                        );
-
-                        let proportional_reward = staking_pool_percentage * available_reward;
-                        active_stake.principal =
-                          active_stake.principal.saturating_add(proportional_reward);
-                      }
-                      // update the last session index for this stake
-                      active_stake.last_session_index_compound = session_to_index;
-
-                      // increment our weighting
-                      current_iterations += 1;
-
-                      log!(
-                        trace,
-                        "Recomputed rewards for {:?} with new balance: {} (initial: {}) - i{}",
-                        account_id,
-                        active_stake.principal,
-                        active_stake.initial_balance,
-                        current_iterations,
-                      )
-                    }
-
-                    *staking_details = final_stake;
-                    Ok(())
-                  },
-                )?;
-              }
-              if session_to_index < last_session {
-                keep_going_in_loop = Some(session_to_index + 1);
-              } else {
-                keep_going_in_loop = None;
-              }
+      Self::deposit_event(Event::<T>::StakeRenewed {
+        request_id: stake_id,
+        account_id: account_id.clone(),
+        principal: renewed_stake.principal,
+      });
+
+      Ok(())
+    }
+
+    // Stop the reward accrual of the expired stakes, or renew them, the stakes of each currency
+    // and duration expire in the order they were opened
+    fn expire_stakes_with_max_weight(max_weight: Weight) -> Weight {
+      let db_weight = <T as frame_system::Config>::DbWeight::get();
+      let mut weight_used = db_weight.reads(1);
+
+      let current_block = T::Security::get_current_block_count();
+      let expire_weight = db_weight.reads_writes(5, 5);
+      // read the bounds and the head of the queue
+      let bucket_weight = db_weight.reads(2);
+      // read the tail and write the bounds once the head moved
+      let bounds_weight = db_weight.reads_writes(1, 1);
+      // only the bounds of the current bucket are updated while iterating
+      let mut buckets = StakeExpiryQueueBounds::<T>::iter();
 
-              if current_iterations >= max_iterations {
-                keep_going_in_loop = None;
+      loop {
+        // make sure the budget covers the bucket and the update of its bounds
+        if max_weight.saturating_sub(weight_used) < bucket_weight.saturating_add(bounds_weight) {
+          break;
+        }
+        let (bucket, (start, tail)) = match buckets.next() {
+          Some(bucket_bounds) => bucket_bounds,
+          None => break,
+        };
+        weight_used = weight_used.saturating_add(bucket_weight);
+
+        let mut head = start;
+        while head < tail
+          && max_weight.saturating_sub(weight_used) >= expire_weight.saturating_add(bounds_weight)
+        {
+          if let Some((account_id, stake_id, expiry)) = StakeExpiryQueue::<T>::get(bucket, head) {
+            if expiry > current_block {
+              break;
+            }
+            let account_id = Self::transferred_stake_owner(stake_id).unwrap_or(account_id);
+            weight_used = weight_used.saturating_add(expire_weight);
+            // the stakes already unstaked are not active anymore
+            if StakeRewardIndex::<T>::contains_key(stake_id) {
+              if let Err(expire_error) = Self::expire_stake(&account_id, stake_id) {
+                log!(
+                  error,
+                  "Can't settle the expired stake {:?}: {:?}",
+                  stake_id,
+                  expire_error
+                );
               }
             }
+            StakeExpiryQueue::<T>::remove(bucket, head);
           }
+          head = head.saturating_add(1);
         }
-      }
 
-      // FIXME: we should have a better draining
-      PendingStoredSessions::<T>::remove_all();
+        // the bounds are unchanged when no stake expired
+        if head == start {
+          continue;
+        }
 
-      // FIXME: implement maximum iteration / should_continue = true
-      Ok((current_iterations * weight_per_iteration, false))
+        // the renewed stakes are added at the tail of the queue
+        weight_used = weight_used.saturating_add(bounds_weight);
+        let tail = Self::stake_expiry_queue_bounds(bucket).1;
+        if head >= tail {
+          StakeExpiryQueueBounds::<T>::remove(bucket);
+        } else {
+          StakeExpiryQueueBounds::<T>::insert(bucket, (head, tail));
+        }
+      }
+
+      weight_used
     }
 
     #[inline]
     pub fn do_next_unstake_operation(max_weight: Weight) -> Result<(Weight, bool), DispatchError> {
-      let weight_per_iteration = <T as frame_system::Config>::DbWeight::get().reads_writes(2, 2);
+      let weight_per_iteration = Self::queued_unstake_weight();
       let unstake_queue = Self::unstake_queue();
       if unstake_queue.is_empty() {
         return Ok((weight_per_iteration, false));
@@ -746,20 +1756,43 @@ pub mod pallet {
       Ok((total_weight, false))
     }
 
+    // Weight of a queued unstake: the queue and the current block, the stake settlement and its
+    // terms, the principal transfer and the removal of the stake storage and pool share
+    fn queued_unstake_weight() -> Weight {
+      <T as frame_system::Config>::DbWeight::get().reads_writes(15, 14)
+    }
+
     fn do_unstake_queue_front() -> Result<(), DispatchError> {
       let unstake_queue = Self::unstake_queue();
       if unstake_queue.is_empty() {
         return Ok(());
       }
 
-      let (account_id, stake_id, expiration) = &unstake_queue[0];
+      let (account_id, stake_id, expiration) = unstake_queue[0].clone();
 
-      if T::Security::get_current_block_count() < *expiration {
+      if T::Security::get_current_block_count() < expiration {
         return Ok(());
       }
 
-      Self::process_unstake(&account_id, *stake_id)?;
+      // the entry is removed even if the unstake fail, so it can't block the queue
       UnstakeQueue::<T>::mutate(|v| v.remove(0));
+      match Self::process_unstake(&account_id, stake_id) {
+        Ok(settled_stake) => Self::deposit_event(Event::<T>::Unstaked {
+          request_id: stake_id,
+          account_id,
+          currency_id: settled_stake.currency_id,
+          initial_balance: settled_stake.initial_balance,
+          final_balance: settled_stake.principal,
+        }),
+        Err(error) => {
+          log!(warn, "Queued unstake {:?} failed {:?}", stake_id, error);
+          Self::deposit_event(Event::<T>::QueuedUnstakeFailed {
+            request_id: stake_id,
+            account_id,
+            error,
+          });
+        }
+      }
 
       Ok(())
     }
@@ -770,8 +1803,14 @@ pub mod pallet {
     ) -> Vec<(CurrencyId, Stake<BalanceInfo, T::BlockNumber>)> {
       let mut final_stakes = Vec::new();
 
-      // we need to re-organize as our storage use a unique AccountId / CurrencyId key
+      // we need to re-organize as our storage use a unique AccountId / CurrencyId key, the
+      // principal include the rewards not settled yet, unless they are delegated
       for account_stake in Self::account_stakes(account_id) {
+        let pending_reward = if Self::stake_reward_beneficiary(account_stake.unique_id).is_some() {
+          Zero::zero()
+        } else {
+          Self::pending_stake_reward(&account_stake)
+        };
         final_stakes.push((
           account_stake.currency_id,
           Stake {
@@ -780,7 +1819,7 @@ pub mod pallet {
             unique_id: account_stake.unique_id,
             initial_block: account_stake.initial_block,
             principal: BalanceInfo {
-              amount: account_stake.principal,
+              amount: account_stake.principal.saturating_add(pending_reward),
             },
             initial_balance: BalanceInfo {
               amount: account_stake.initial_balance,
@@ -805,9 +1844,9 @@ pub mod pallet {
       session_trade_values: Vec<(CurrencyId, Balance)>,
     ) -> Result<(), DispatchError> {
       InterestCompoundLastSession::<T>::put(session_index);
-      PendingStoredSessions::<T>::insert(session_index, ());
       for (currency_id, total_fees_for_the_session) in session_trade_values {
         SessionTotalFees::<T>::insert(session_index, currency_id, total_fees_for_the_session);
+        Self::distribute_session_fees(currency_id, total_fees_for_the_session);
       }
       Ok(())
     }
@@ -21,8 +21,8 @@ use frame_support::{
   BoundedVec,
 };
 use sp_runtime::Percent;
-use sp_std::vec;
-use tidefi_primitives::pallet::SecurityExt;
+use sp_std::{vec, vec::Vec};
+use tidefi_primitives::{pallet::SecurityExt, SessionIndex};
 
 /// Migrate the pallet storage to v1.
 pub fn migrate_to_v1<T: Config, P: GetStorageVersion + PalletInfoAccess>(
@@ -84,3 +84,118 @@ pub fn migrate_to_v1<T: Config, P: GetStorageVersion + PalletInfoAccess>(
     T::DbWeight::get().reads(1)
   }
 }
+
+/// Migrate the pallet storage to v2, the opened stakes get a snapshot of the current terms.
+pub fn migrate_to_v2<T: Config, P: GetStorageVersion + PalletInfoAccess>(
+) -> frame_support::weights::Weight {
+  let on_chain_storage_version = <P as GetStorageVersion>::on_chain_storage_version();
+  log!(
+    info,
+    "Running migration storage v2 with storage version {:?}",
+    on_chain_storage_version,
+  );
+
+  if on_chain_storage_version < 2 {
+    let mut stakes_count: u64 = 0;
+    for (_, account_stakes) in AccountStakes::<T>::iter() {
+      for stake in account_stakes {
+        StakesTerms::<T>::insert(
+          stake.unique_id,
+          Pallet::<T>::current_stake_terms(stake.duration),
+        );
+        stakes_count = stakes_count.saturating_add(1);
+      }
+    }
+
+    // update on-chain storage version
+    StorageVersion::new(2).put::<P>();
+    log!(
+      info,
+      "Running migration storage v2 with storage version {:?} was complete",
+      on_chain_storage_version,
+    );
+    // return migration weights
+    T::DbWeight::get().reads_writes(
+      stakes_count.saturating_add(3),
+      stakes_count.saturating_add(1),
+    )
+  } else {
+    log!(
+      info,
+      "Attempted to apply migration to v2 but failed because storage version is {:?}",
+      on_chain_storage_version,
+    );
+    T::DbWeight::get().reads(1)
+  }
+}
+
+/// Migrate the pallet storage to v3, the opened stakes accrue their rewards with the reward index.
+///
+/// The principals already compounded are kept, the active stakes start at the current reward index
+/// and the sessions pending compounding are distributed to them. The staking pools are recomputed
+/// from the opened stakes.
+pub fn migrate_to_v3<T: Config, P: GetStorageVersion + PalletInfoAccess>(
+) -> frame_support::weights::Weight {
+  let on_chain_storage_version = <P as GetStorageVersion>::on_chain_storage_version();
+  log!(
+    info,
+    "Running migration storage v3 with storage version {:?}",
+    on_chain_storage_version,
+  );
+
+  if on_chain_storage_version < 3 {
+    let current_block = T::Security::get_current_block_count();
+    let mut reads: u64 = 2;
+    let mut writes: u64 = 1;
+
+    // 1. Recompute the staking pools and activate the stakes not expired yet, by opening order
+    StakingPool::<T>::remove_all(None);
+    let mut active_stakes = Vec::new();
+    for (account_id, account_stakes) in AccountStakes::<T>::iter() {
+      for stake in account_stakes {
+        reads = reads.saturating_add(1);
+        writes = writes.saturating_add(1);
+        StakingPool::<T>::mutate(stake.currency_id, |balance| {
+          *balance = Some(balance.unwrap_or(0).saturating_add(stake.initial_balance))
+        });
+        if stake.initial_block.saturating_add(stake.duration) > current_block {
+          active_stakes.push((account_id.clone(), stake));
+        }
+      }
+    }
+    active_stakes.sort_by_key(|(_, stake)| stake.initial_block);
+    for (account_id, stake) in active_stakes {
+      writes = writes.saturating_add(5);
+      Pallet::<T>::activate_stake(&account_id, &stake);
+    }
+
+    // 2. Distribute the sessions pending compounding to the active stakes
+    let mut pending_sessions: Vec<SessionIndex> = PendingStoredSessions::<T>::iter_keys().collect();
+    pending_sessions.sort_unstable();
+    for session_index in pending_sessions {
+      for (currency_id, session_fees) in SessionTotalFees::<T>::iter_prefix(session_index) {
+        reads = reads.saturating_add(2);
+        writes = writes.saturating_add(1);
+        Pallet::<T>::distribute_session_fees(currency_id, session_fees);
+      }
+    }
+    PendingStoredSessions::<T>::remove_all();
+
+    // update on-chain storage version
+    StorageVersion::new(3).put::<P>();
+    log!(
+      info,
+      "Running migration storage v3 with storage version {:?} was complete",
+      on_chain_storage_version,
+    );
+    // return migration weights
+    T::DbWeight::get().reads_writes(reads, writes)
+  } else {
+    log!(
+      info,
+      "Attempted to apply migration to v3 but failed because storage version is {:?}",
+      on_chain_storage_version,
+    );
+    T::DbWeight::get().reads(1)
+  }
+}
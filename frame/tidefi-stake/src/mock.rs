@@ -33,7 +33,7 @@ use frame_system as system;
 use sp_core::H256;
 use sp_runtime::{
   generic::Header,
-  traits::{BlakeTwo256, IdentityLookup},
+  traits::{AccountIdConversion, BlakeTwo256, IdentityLookup},
   DispatchError, DispatchResult, Percent,
 };
 use std::marker::PhantomData;
@@ -112,6 +112,8 @@ parameter_types! {
   pub const TidefiPalletId: PalletId = PalletId(*b"wrpr*pal");
   pub const QuorumPalletId: PalletId = PalletId(*b"qurm*pal");
   pub const AssetRegistryPalletId: PalletId = PalletId(*b"asst*pal");
+  pub const FeesPalletId: PalletId = PalletId(*b"fees*pal");
+  pub const WithdrawalPeriod: BlockNumber = 100;
   pub const BlocksForceUnstake: BlockNumber = 256;
   pub const MinimumPeriod: u64 = 5;
   pub const StakeAccountCap: u32 = 10;
@@ -168,8 +170,25 @@ impl pallet_tidefi_stake::Config for Test {
   type UnstakeQueueCap = UnstakeQueueCap;
   type BlocksForceUnstake = BlocksForceUnstake;
   type StakingRewardCap = StakingRewardCap;
+  type ForceOrigin = EnsureRoot<Self::AccountId>;
   type AssetRegistry = AssetRegistry;
   type Security = Security;
+  type Fees = MockFees;
+}
+
+// the wrapped assets are valued 2 TIFI in the mock
+pub struct MockFees;
+impl crate::StakingFeesExt<AccountId> for MockFees {
+  fn account_id() -> AccountId {
+    FeesPalletId::get().into_account()
+  }
+
+  fn get_tifi_value(currency_id: CurrencyId, amount: Balance) -> Result<Balance, DispatchError> {
+    match currency_id {
+      CurrencyId::Tifi => Ok(amount),
+      CurrencyId::Wrapped(_) => Ok(amount.saturating_mul(2)),
+    }
+  }
 }
 
 impl pallet_asset_registry::Config for Test {
@@ -177,6 +196,7 @@ impl pallet_asset_registry::Config for Test {
   type WeightInfo = pallet_asset_registry::weights::SubstrateWeight<Test>;
   type AssetRegistryPalletId = AssetRegistryPalletId;
   type CurrencyTidefi = Adapter<AccountId>;
+  type WithdrawalPeriod = WithdrawalPeriod;
 }
 
 // this is only the mock for benchmarking, it's implemented directly in the runtime
@@ -338,9 +358,12 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
     .build_storage::<Test>()
     .unwrap();
 
-  pallet_balances::GenesisConfig::<Test>::default()
-    .assimilate_storage(&mut t)
-    .unwrap();
+  // the fees account receive the unstake fees
+  pallet_balances::GenesisConfig::<Test> {
+    balances: vec![(FeesPalletId::get().into_account(), TIFI)],
+  }
+  .assimilate_storage(&mut t)
+  .unwrap();
 
   pallet_tidefi_stake::GenesisConfig::<Test> {
     unstake_fee: Percent::from_parts(1),
@@ -15,10 +15,16 @@
 // along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
 
 use crate::{
-  mock::{new_test_ext, Adapter, Origin, Security, System, Test, TidefiStaking},
-  Error,
+  mock::{
+    new_test_ext, Adapter, Event as MockEvent, MockFees, Origin, Security, System, Test,
+    TidefiStaking,
+  },
+  AccountStakes, BeneficiaryRewards, Error, Event, PendingStoredSessions, RewardPerShare,
+  RewardShares, SessionTotalFees, StakeExpiryQueue, StakeExpiryQueueBounds, StakeRewardIndex,
+  StakeTerms, StakesTerms, StakingFeesExt, StakingPool, UnstakeQueue,
 };
-use sp_runtime::Percent;
+use frame_support::traits::StorageVersion;
+use sp_runtime::{traits::BadOrigin, Percent};
 use tidefi_primitives::BlockNumber;
 
 use frame_support::{
@@ -519,18 +525,14 @@ pub fn should_calculate_rewards() {
       vec![(CurrencyId::Tifi, 100_000_000_000_000)]
     ));
 
-    // run on idle hook
-    assert_eq!(
-      TidefiStaking::on_idle(1, 1_000_000_000_000_000),
-      1_000_000_000_000_000
-    );
-
-    // started with 100, now should have 102 tides
+    // the rewards are settled lazily, started with 100, now should have 102 tides
     assert_eq!(
-      TidefiStaking::account_stakes(alice)
+      TidefiStaking::get_account_stakes(&alice)
         .first()
         .unwrap()
-        .principal,
+        .1
+        .principal
+        .amount,
       102_000_000_000_000
     );
 
@@ -561,18 +563,22 @@ pub fn should_calculate_rewards() {
     );
 
     assert_eq!(
-      TidefiStaking::account_stakes(alice)
+      TidefiStaking::get_account_stakes(&alice)
         .first()
         .unwrap()
-        .principal,
+        .1
+        .principal
+        .amount,
       103_000_000_000_000
     );
 
     assert_eq!(
-      TidefiStaking::account_stakes(bob)
+      TidefiStaking::get_account_stakes(&bob)
         .first()
         .unwrap()
-        .principal,
+        .1
+        .principal
+        .amount,
       101_000_000_000_000
     );
 
@@ -585,18 +591,22 @@ pub fn should_calculate_rewards() {
     );
 
     assert_eq!(
-      TidefiStaking::account_stakes(alice)
+      TidefiStaking::get_account_stakes(&alice)
         .first()
         .unwrap()
-        .principal,
+        .1
+        .principal
+        .amount,
       103_000_000_000_000
     );
 
     assert_eq!(
-      TidefiStaking::account_stakes(bob)
+      TidefiStaking::get_account_stakes(&bob)
         .first()
         .unwrap()
-        .principal,
+        .1
+        .principal
+        .amount,
       101_000_000_000_000
     );
 
@@ -618,27 +628,928 @@ pub fn should_calculate_rewards() {
     );
 
     assert_eq!(
-      TidefiStaking::account_stakes(alice)
+      TidefiStaking::get_account_stakes(&alice)
         .first()
         .unwrap()
-        .principal,
+        .1
+        .principal
+        .amount,
       103_333_333_333_333
     );
 
     assert_eq!(
-      TidefiStaking::account_stakes(bob)
+      TidefiStaking::get_account_stakes(&bob)
         .first()
         .unwrap()
-        .principal,
+        .1
+        .principal
+        .amount,
       101_333_333_333_333
     );
 
     assert_eq!(
-      TidefiStaking::account_stakes(charlie)
+      TidefiStaking::get_account_stakes(&charlie)
         .first()
         .unwrap()
-        .principal,
+        .1
+        .principal
+        .amount,
       401_333_333_333_333
     );
   });
 }
+
+#[test]
+pub fn governance_update_terms_keep_opened_stakes() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let alice = 1u64;
+    let alice_origin = Origin::signed(alice);
+    let initial_stake = 1_000_000_000_000;
+    let initial_mint = 1_000_000_000_000_000;
+    Adapter::mint_into(CurrencyId::Tifi, &alice, initial_mint).expect("Unable to mint token");
+
+    // the stake is opened under the genesis terms
+    assert_ok!(TidefiStaking::stake(
+      alice_origin.clone(),
+      CurrencyId::Tifi,
+      initial_stake,
+      FIFTEEN_DAYS
+    ));
+    let stake_id = TidefiStaking::account_stakes(alice)
+      .first()
+      .unwrap()
+      .unique_id;
+    let genesis_terms = StakeTerms {
+      reward: Percent::from_parts(2),
+      unstake_fee: Percent::from_parts(1),
+    };
+    assert_eq!(TidefiStaking::stake_terms(stake_id), Some(genesis_terms));
+
+    // update the terms
+    assert_noop!(
+      TidefiStaking::set_unstake_fee(alice_origin.clone(), Percent::from_parts(10)),
+      BadOrigin
+    );
+    assert_ok!(TidefiStaking::set_unstake_fee(
+      Origin::root(),
+      Percent::from_parts(10)
+    ));
+    System::assert_has_event(MockEvent::TidefiStaking(Event::UnstakeFeeSet {
+      unstake_fee: Percent::from_parts(10),
+    }));
+    assert_ok!(TidefiStaking::set_staking_period(
+      Origin::root(),
+      FIFTEEN_DAYS,
+      Percent::from_parts(7)
+    ));
+    System::assert_has_event(MockEvent::TidefiStaking(Event::StakingPeriodSet {
+      duration: FIFTEEN_DAYS,
+      reward: Percent::from_parts(7),
+    }));
+    assert_ok!(TidefiStaking::set_staking_period(
+      Origin::root(),
+      14400 * 120,
+      Percent::from_parts(6)
+    ));
+    assert_eq!(TidefiStaking::staking_rewards().len(), 6);
+
+    // the new stakes get the new terms, the opened stake keeps its terms
+    assert_ok!(TidefiStaking::stake(
+      alice_origin.clone(),
+      CurrencyId::Tifi,
+      initial_stake,
+      FIFTEEN_DAYS
+    ));
+    let new_stake_id = TidefiStaking::account_stakes(alice)
+      .last()
+      .unwrap()
+      .unique_id;
+    assert_eq!(
+      TidefiStaking::stake_terms(new_stake_id),
+      Some(StakeTerms {
+        reward: Percent::from_parts(7),
+        unstake_fee: Percent::from_parts(10),
+      })
+    );
+    assert_eq!(TidefiStaking::stake_terms(stake_id), Some(genesis_terms));
+
+    // the forced unstake takes the fee of the opened stake
+    assert_ok!(TidefiStaking::unstake(alice_origin, stake_id, true));
+    assert_eq!(
+      Adapter::balance(CurrencyId::Tifi, &alice),
+      initial_mint - initial_stake * 2 - Percent::from_parts(1) * initial_stake
+    );
+
+    // the removed period can't be staked anymore
+    assert_ok!(TidefiStaking::remove_staking_period(
+      Origin::root(),
+      14400 * 120
+    ));
+    System::assert_has_event(MockEvent::TidefiStaking(Event::StakingPeriodRemoved {
+      duration: 14400 * 120,
+    }));
+    assert_noop!(
+      TidefiStaking::remove_staking_period(Origin::root(), 14400 * 120),
+      Error::<Test>::InvalidDuration
+    );
+    assert_noop!(
+      TidefiStaking::stake(
+        Origin::signed(alice),
+        CurrencyId::Tifi,
+        initial_stake,
+        14400 * 120
+      ),
+      Error::<Test>::InvalidDuration
+    );
+  });
+}
+
+#[test]
+pub fn governance_set_staking_currency_meta() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let alice = 1u64;
+    Adapter::mint_into(CurrencyId::Tifi, &alice, 1_000_000_000_000_000)
+      .expect("Unable to mint token");
+
+    assert_noop!(
+      TidefiStaking::set_staking_currency_meta(
+        Origin::root(),
+        CurrencyId::Tifi,
+        10_000_000_000_000,
+        1_000_000_000_000
+      ),
+      Error::<Test>::InvalidCurrencyMeta
+    );
+    assert_ok!(TidefiStaking::set_staking_currency_meta(
+      Origin::root(),
+      CurrencyId::Tifi,
+      10_000_000_000_000,
+      100_000_000_000_000
+    ));
+    System::assert_has_event(MockEvent::TidefiStaking(Event::StakingCurrencyMetaSet {
+      currency_id: CurrencyId::Tifi,
+      minimum_amount: 10_000_000_000_000,
+      maximum_amount: 100_000_000_000_000,
+    }));
+
+    assert_noop!(
+      TidefiStaking::stake(
+        Origin::signed(alice),
+        CurrencyId::Tifi,
+        1_000_000_000_000,
+        FIFTEEN_DAYS
+      ),
+      Error::<Test>::AmountTooSmall
+    );
+    assert_noop!(
+      TidefiStaking::stake(
+        Origin::signed(alice),
+        CurrencyId::Tifi,
+        1_000_000_000_000_000,
+        FIFTEEN_DAYS
+      ),
+      Error::<Test>::AmountTooLarge
+    );
+  });
+}
+
+#[test]
+pub fn migrate_opened_stakes_terms() {
+  new_test_ext().execute_with(|| {
+    let alice = 1u64;
+    Adapter::mint_into(CurrencyId::Tifi, &alice, 1_000_000_000_000_000)
+      .expect("Unable to mint token");
+    assert_ok!(TidefiStaking::stake(
+      Origin::signed(alice),
+      CurrencyId::Tifi,
+      1_000_000_000_000,
+      FIFTEEN_DAYS
+    ));
+    let stake_id = TidefiStaking::account_stakes(alice)
+      .first()
+      .unwrap()
+      .unique_id;
+
+    // the stakes opened before the snapshots get the current terms
+    StakesTerms::<Test>::remove(stake_id);
+    StorageVersion::new(1).put::<TidefiStaking>();
+    crate::migrations::migrate_to_v2::<Test, TidefiStaking>();
+    assert_eq!(
+      TidefiStaking::stake_terms(stake_id),
+      Some(StakeTerms {
+        reward: Percent::from_parts(2),
+        unstake_fee: Percent::from_parts(1),
+      })
+    );
+    assert_eq!(StorageVersion::get::<TidefiStaking>(), 2);
+  });
+}
+
+#[test]
+pub fn expired_stakes_stop_accruing_rewards() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let alice = 1u64;
+    let bob = 2u64;
+    let initial_stake = 100_000_000_000_000;
+    let initial_mint = 1_000_000_000_000_000;
+    Adapter::mint_into(CurrencyId::Tifi, &alice, initial_mint).expect("Unable to mint token");
+    Adapter::mint_into(CurrencyId::Tifi, &bob, initial_mint).expect("Unable to mint token");
+
+    // alice stake for 150 blocks at 1%, bob for 15 days at 2%
+    assert_ok!(TidefiStaking::stake(
+      Origin::signed(alice),
+      CurrencyId::Tifi,
+      initial_stake,
+      150
+    ));
+    assert_ok!(TidefiStaking::stake(
+      Origin::signed(bob),
+      CurrencyId::Tifi,
+      initial_stake,
+      FIFTEEN_DAYS
+    ));
+    let stake_id = TidefiStaking::account_stakes(alice)
+      .first()
+      .unwrap()
+      .unique_id;
+
+    // each stake share half of the session fees
+    assert_ok!(TidefiStaking::on_session_end(
+      1,
+      vec![(CurrencyId::Tifi, 100_000_000_000_000)]
+    ));
+    assert_eq!(
+      TidefiStaking::get_account_stakes(&alice)[0]
+        .1
+        .principal
+        .amount,
+      100_500_000_000_000
+    );
+    assert_eq!(
+      TidefiStaking::get_account_stakes(&bob)[0]
+        .1
+        .principal
+        .amount,
+      101_000_000_000_000
+    );
+
+    // the expired stake of alice is settled and doesn't accrue rewards anymore
+    <pallet_security::CurrentBlockCount<Test>>::put(150);
+    TidefiStaking::on_idle(1, 1_000_000_000_000_000);
+    assert!(TidefiStaking::stake_reward_index(stake_id).is_none());
+    assert_eq!(TidefiStaking::reward_shares(CurrencyId::Tifi, 150), 0);
+    assert_eq!(
+      TidefiStaking::account_stakes(alice)[0].principal,
+      100_500_000_000_000
+    );
+
+    assert_ok!(TidefiStaking::on_session_end(
+      2,
+      vec![(CurrencyId::Tifi, 100_000_000_000_000)]
+    ));
+    assert_eq!(
+      TidefiStaking::get_account_stakes(&alice)[0]
+        .1
+        .principal
+        .amount,
+      100_500_000_000_000
+    );
+    assert_eq!(
+      TidefiStaking::get_account_stakes(&bob)[0]
+        .1
+        .principal
+        .amount,
+      102_000_000_000_000
+    );
+
+    // the settled principal is returned
+    assert_ok!(TidefiStaking::unstake(
+      Origin::signed(alice),
+      stake_id,
+      false
+    ));
+    System::assert_has_event(MockEvent::TidefiStaking(Event::Unstaked {
+      request_id: stake_id,
+      account_id: alice,
+      currency_id: CurrencyId::Tifi,
+      initial_balance: initial_stake,
+      final_balance: 100_500_000_000_000,
+    }));
+    assert_eq!(
+      Adapter::balance(CurrencyId::Tifi, &alice),
+      initial_mint + 500_000_000_000
+    );
+    assert_eq!(
+      TidefiStaking::staking_pool(CurrencyId::Tifi),
+      Some(initial_stake)
+    );
+  });
+}
+
+#[test]
+pub fn migrate_stakes_to_reward_index() {
+  new_test_ext().execute_with(|| {
+    let alice = 1u64;
+    let initial_stake = 100_000_000_000_000;
+    Adapter::mint_into(CurrencyId::Tifi, &alice, 1_000_000_000_000_000)
+      .expect("Unable to mint token");
+    assert_ok!(TidefiStaking::stake(
+      Origin::signed(alice),
+      CurrencyId::Tifi,
+      initial_stake,
+      FIFTEEN_DAYS
+    ));
+    let stake_id = TidefiStaking::account_stakes(alice)
+      .first()
+      .unwrap()
+      .unique_id;
+
+    // the stake was opened before the reward index, with a session pending compounding
+    let bucket = (CurrencyId::Tifi, FIFTEEN_DAYS);
+    StakeRewardIndex::<Test>::remove(stake_id);
+    RewardShares::<Test>::remove(CurrencyId::Tifi, FIFTEEN_DAYS);
+    RewardPerShare::<Test>::remove(CurrencyId::Tifi, FIFTEEN_DAYS);
+    StakeExpiryQueue::<Test>::remove(bucket, 0);
+    StakeExpiryQueueBounds::<Test>::remove(bucket);
+    StakingPool::<Test>::insert(CurrencyId::Tifi, initial_stake * 5);
+    PendingStoredSessions::<Test>::insert(1, ());
+    SessionTotalFees::<Test>::insert(1, CurrencyId::Tifi, 100_000_000_000_000);
+    StorageVersion::new(2).put::<TidefiStaking>();
+
+    crate::migrations::migrate_to_v3::<Test, TidefiStaking>();
+    assert_eq!(StorageVersion::get::<TidefiStaking>(), 3);
+    assert_eq!(
+      TidefiStaking::staking_pool(CurrencyId::Tifi),
+      Some(initial_stake)
+    );
+    assert_eq!(TidefiStaking::pending_sessions(1), None);
+    assert_eq!(
+      TidefiStaking::reward_shares(CurrencyId::Tifi, FIFTEEN_DAYS),
+      initial_stake
+    );
+    assert_eq!(TidefiStaking::stake_expiry_queue_bounds(bucket), (0, 1));
+
+    // the pending session is distributed to the stake
+    assert_eq!(
+      TidefiStaking::get_account_stakes(&alice)[0]
+        .1
+        .principal
+        .amount,
+      102_000_000_000_000
+    );
+  });
+}
+
+#[test]
+pub fn increase_and_partial_unstake_stake() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let alice = 1u64;
+    let bob = 2u64;
+    let initial_stake = 100_000_000_000_000;
+    let initial_mint = 1_000_000_000_000_000;
+    Adapter::mint_into(CurrencyId::Tifi, &alice, initial_mint).expect("Unable to mint token");
+    assert_ok!(TidefiStaking::stake(
+      Origin::signed(alice),
+      CurrencyId::Tifi,
+      initial_stake,
+      FIFTEEN_DAYS
+    ));
+    let stake_id = TidefiStaking::account_stakes(alice)
+      .first()
+      .unwrap()
+      .unique_id;
+    assert_ok!(TidefiStaking::on_session_end(
+      1,
+      vec![(CurrencyId::Tifi, 100_000_000_000_000)]
+    ));
+
+    // the rewards are settled before the funds are added
+    assert_noop!(
+      TidefiStaking::increase_stake(Origin::signed(bob), stake_id, 50_000_000_000_000),
+      Error::<Test>::InvalidStakeId
+    );
+    assert_ok!(TidefiStaking::increase_stake(
+      Origin::signed(alice),
+      stake_id,
+      50_000_000_000_000
+    ));
+    System::assert_has_event(MockEvent::TidefiStaking(Event::StakeIncreased {
+      request_id: stake_id,
+      account_id: alice,
+      amount: 50_000_000_000_000,
+    }));
+    let stake = TidefiStaking::account_stakes(alice)[0].clone();
+    assert_eq!(stake.initial_balance, 150_000_000_000_000);
+    assert_eq!(stake.principal, 152_000_000_000_000);
+    assert_eq!(
+      TidefiStaking::staking_pool(CurrencyId::Tifi),
+      Some(150_000_000_000_000)
+    );
+    assert_eq!(
+      TidefiStaking::reward_shares(CurrencyId::Tifi, FIFTEEN_DAYS),
+      150_000_000_000_000
+    );
+
+    // the stake queued for unstake can't be increased, and the stake that is not expired can't be
+    // partially unstaked
+    UnstakeQueue::<Test>::try_append((alice, stake_id, FIFTEEN_DAYS)).unwrap();
+    assert_noop!(
+      TidefiStaking::increase_stake(Origin::signed(alice), stake_id, 50_000_000_000_000),
+      Error::<Test>::UnstakeAlreadyQueued
+    );
+    UnstakeQueue::<Test>::kill();
+    assert_noop!(
+      TidefiStaking::partial_unstake(Origin::signed(alice), stake_id, 52_000_000_000_000),
+      Error::<Test>::StakeNotExpired
+    );
+
+    // the rewards are unstaked first once the stake is expired
+    <pallet_security::CurrentBlockCount<Test>>::put(FIFTEEN_DAYS + 1);
+    assert_noop!(
+      TidefiStaking::partial_unstake(Origin::signed(alice), stake_id, 0),
+      Error::<Test>::AmountTooSmall
+    );
+    assert_noop!(
+      TidefiStaking::partial_unstake(Origin::signed(alice), stake_id, 152_000_000_000_000),
+      Error::<Test>::AmountTooLarge
+    );
+    assert_ok!(TidefiStaking::partial_unstake(
+      Origin::signed(alice),
+      stake_id,
+      52_000_000_000_000
+    ));
+    System::assert_has_event(MockEvent::TidefiStaking(Event::StakePartiallyUnstaked {
+      request_id: stake_id,
+      account_id: alice,
+      amount: 52_000_000_000_000,
+    }));
+    let stake = TidefiStaking::account_stakes(alice)[0].clone();
+    assert_eq!(stake.initial_balance, initial_stake);
+    assert_eq!(stake.principal, initial_stake);
+    assert_eq!(
+      Adapter::balance(CurrencyId::Tifi, &alice),
+      initial_mint - 150_000_000_000_000 + 52_000_000_000_000
+    );
+    assert_eq!(
+      TidefiStaking::staking_pool(CurrencyId::Tifi),
+      Some(initial_stake)
+    );
+    assert_eq!(
+      TidefiStaking::reward_shares(CurrencyId::Tifi, FIFTEEN_DAYS),
+      initial_stake
+    );
+
+    // the stake queued for unstake can't be partially unstaked
+    UnstakeQueue::<Test>::try_append((alice, stake_id, FIFTEEN_DAYS + 1)).unwrap();
+    assert_noop!(
+      TidefiStaking::partial_unstake(Origin::signed(alice), stake_id, 50_000_000_000_000),
+      Error::<Test>::UnstakeAlreadyQueued
+    );
+  });
+}
+
+#[test]
+pub fn auto_renew_expired_stake() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let alice = 1u64;
+    let initial_stake = 100_000_000_000_000;
+    Adapter::mint_into(CurrencyId::Tifi, &alice, 1_000_000_000_000_000)
+      .expect("Unable to mint token");
+    assert_ok!(TidefiStaking::stake(
+      Origin::signed(alice),
+      CurrencyId::Tifi,
+      initial_stake,
+      150
+    ));
+    let stake_id = TidefiStaking::account_stakes(alice)
+      .first()
+      .unwrap()
+      .unique_id;
+    assert_ok!(TidefiStaking::set_stake_auto_renew(
+      Origin::signed(alice),
+      stake_id,
+      true
+    ));
+    System::assert_has_event(MockEvent::TidefiStaking(Event::StakeAutoRenewSet {
+      request_id: stake_id,
+      account_id: alice,
+      auto_renew: true,
+    }));
+    assert_ok!(TidefiStaking::on_session_end(
+      1,
+      vec![(CurrencyId::Tifi, 100_000_000_000_000)]
+    ));
+
+    // the expired stake is renewed with its accrued principal
+    <pallet_security::CurrentBlockCount<Test>>::put(150);
+    TidefiStaking::on_idle(1, 1_000_000_000_000_000);
+    System::assert_has_event(MockEvent::TidefiStaking(Event::StakeRenewed {
+      request_id: stake_id,
+      account_id: alice,
+      principal: 101_000_000_000_000,
+    }));
+    let stake = TidefiStaking::account_stakes(alice)[0].clone();
+    assert_eq!(stake.initial_block, 150);
+    assert_eq!(stake.initial_balance, 101_000_000_000_000);
+    assert!(TidefiStaking::stake_reward_index(stake_id).is_some());
+    assert_eq!(
+      TidefiStaking::staking_pool(CurrencyId::Tifi),
+      Some(101_000_000_000_000)
+    );
+    assert_eq!(
+      TidefiStaking::reward_shares(CurrencyId::Tifi, 150),
+      101_000_000_000_000
+    );
+    assert_eq!(
+      TidefiStaking::stake_expiry_queue_bounds((CurrencyId::Tifi, 150)),
+      (1, 2)
+    );
+
+    // the renewed stake accrue rewards on its whole principal
+    assert_ok!(TidefiStaking::on_session_end(
+      2,
+      vec![(CurrencyId::Tifi, 101_000_000_000_000)]
+    ));
+    assert_eq!(
+      TidefiStaking::get_account_stakes(&alice)[0]
+        .1
+        .principal
+        .amount,
+      102_010_000_000_000
+    );
+
+    // without auto-renew, the stake stop accruing rewards when it expires
+    assert_ok!(TidefiStaking::set_stake_auto_renew(
+      Origin::signed(alice),
+      stake_id,
+      false
+    ));
+    <pallet_security::CurrentBlockCount<Test>>::put(300);
+    TidefiStaking::on_idle(2, 1_000_000_000_000_000);
+    assert!(TidefiStaking::stake_reward_index(stake_id).is_none());
+    assert_eq!(TidefiStaking::reward_shares(CurrencyId::Tifi, 150), 0);
+    assert_eq!(
+      TidefiStaking::account_stakes(alice)[0].principal,
+      102_010_000_000_000
+    );
+  });
+}
+
+#[test]
+pub fn force_unstake_fee_in_tifi_and_cancel() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let alice = 1u64;
+    let bob = 2u64;
+    let initial_stake = 100_000_000;
+    let initial_mint = 1_000_000_000_000_000;
+    Adapter::mint_into(CurrencyId::Wrapped(TEST_TOKEN), &alice, 1_000_000_000_000)
+      .expect("Unable to mint token");
+    Adapter::mint_into(CurrencyId::Tifi, &alice, initial_mint).expect("Unable to mint token");
+    let fees_balance = Adapter::balance(CurrencyId::Tifi, &MockFees::account_id());
+
+    assert_ok!(TidefiStaking::stake(
+      Origin::signed(alice),
+      CurrencyId::Wrapped(TEST_TOKEN),
+      initial_stake,
+      FIFTEEN_DAYS
+    ));
+    let stake_id = TidefiStaking::account_stakes(alice)
+      .first()
+      .unwrap()
+      .unique_id;
+
+    // 1% of 100_000_000 valued 2 TIFI each, sent to the fees pallet
+    assert_ok!(TidefiStaking::unstake(
+      Origin::signed(alice),
+      stake_id,
+      true
+    ));
+    System::assert_has_event(MockEvent::TidefiStaking(Event::UnstakeQueued {
+      request_id: stake_id,
+      account_id: alice,
+      fee: 2_000_000,
+    }));
+    assert_eq!(
+      Adapter::balance(CurrencyId::Tifi, &alice),
+      initial_mint - 2_000_000
+    );
+    assert_eq!(
+      Adapter::balance(CurrencyId::Wrapped(TEST_TOKEN), &alice),
+      1_000_000_000_000 - initial_stake
+    );
+    assert_eq!(
+      Adapter::balance(CurrencyId::Tifi, &MockFees::account_id()),
+      fees_balance + 2_000_000
+    );
+
+    // the stake can't be queued twice
+    assert_noop!(
+      TidefiStaking::unstake(Origin::signed(alice), stake_id, true),
+      Error::<Test>::UnstakeAlreadyQueued
+    );
+
+    // only the owner can cancel the queued unstake, the fee is not refunded
+    assert_noop!(
+      TidefiStaking::cancel_queued_unstake(Origin::signed(bob), stake_id),
+      Error::<Test>::UnstakeNotQueued
+    );
+    assert_ok!(TidefiStaking::cancel_queued_unstake(
+      Origin::signed(alice),
+      stake_id
+    ));
+    System::assert_has_event(MockEvent::TidefiStaking(Event::QueuedUnstakeCancelled {
+      request_id: stake_id,
+      account_id: alice,
+    }));
+    assert!(TidefiStaking::unstake_queue().is_empty());
+    assert_eq!(TidefiStaking::account_stakes(alice).len(), 1);
+    assert_eq!(
+      Adapter::balance(CurrencyId::Tifi, &alice),
+      initial_mint - 2_000_000
+    );
+    assert_noop!(
+      TidefiStaking::cancel_queued_unstake(Origin::signed(alice), stake_id),
+      Error::<Test>::UnstakeNotQueued
+    );
+  });
+}
+
+#[test]
+pub fn failing_queued_unstake_doesnt_block_the_queue() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let alice = 1u64;
+    let bob = 2u64;
+    let initial_stake = 1_000_000_000_000;
+    let initial_mint = 1_000_000_000_000_000;
+    Adapter::mint_into(CurrencyId::Tifi, &alice, initial_mint).expect("Unable to mint token");
+    Adapter::mint_into(CurrencyId::Tifi, &bob, initial_mint).expect("Unable to mint token");
+
+    assert_ok!(TidefiStaking::stake(
+      Origin::signed(alice),
+      CurrencyId::Tifi,
+      initial_stake,
+      FIFTEEN_DAYS
+    ));
+    assert_ok!(TidefiStaking::stake(
+      Origin::signed(bob),
+      CurrencyId::Tifi,
+      initial_stake,
+      FIFTEEN_DAYS
+    ));
+    let alice_stake_id = TidefiStaking::account_stakes(alice)
+      .first()
+      .unwrap()
+      .unique_id;
+    let bob_stake_id = TidefiStaking::account_stakes(bob)
+      .first()
+      .unwrap()
+      .unique_id;
+    assert_ok!(TidefiStaking::unstake(
+      Origin::signed(alice),
+      alice_stake_id,
+      true
+    ));
+    assert_ok!(TidefiStaking::unstake(
+      Origin::signed(bob),
+      bob_stake_id,
+      true
+    ));
+
+    // the stake of alice is lost, its unstake fail
+    AccountStakes::<Test>::remove(alice);
+
+    <pallet_security::CurrentBlockCount<Test>>::put(BLOCKS_FORCE_UNLOCK + 1);
+    TidefiStaking::on_idle(1, 1_000_000_000_000_000);
+
+    // the failing entry is removed, and the unstake of bob is processed
+    assert!(TidefiStaking::unstake_queue().is_empty());
+    System::assert_has_event(MockEvent::TidefiStaking(Event::QueuedUnstakeFailed {
+      request_id: alice_stake_id,
+      account_id: alice,
+      error: Error::<Test>::InvalidStakeId.into(),
+    }));
+    System::assert_has_event(MockEvent::TidefiStaking(Event::Unstaked {
+      request_id: bob_stake_id,
+      account_id: bob,
+      currency_id: CurrencyId::Tifi,
+      initial_balance: initial_stake,
+      final_balance: initial_stake,
+    }));
+    assert!(TidefiStaking::account_stakes(bob).is_empty());
+    assert_eq!(
+      Adapter::balance(CurrencyId::Tifi, &bob),
+      initial_mint - TidefiStaking::unstake_fee() * initial_stake
+    );
+  });
+}
+
+#[test]
+pub fn transfer_stake_to_another_account() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let alice = 1u64;
+    let bob = 2u64;
+    let initial_stake = 100_000_000_000_000;
+    Adapter::mint_into(CurrencyId::Tifi, &alice, 1_000_000_000_000_000)
+      .expect("Unable to mint token");
+    assert_ok!(TidefiStaking::stake(
+      Origin::signed(alice),
+      CurrencyId::Tifi,
+      initial_stake,
+      FIFTEEN_DAYS
+    ));
+    let stake_id = TidefiStaking::account_stakes(alice)
+      .first()
+      .unwrap()
+      .unique_id;
+
+    // the session fees are sent to the staking pool
+    Adapter::mint_into(
+      CurrencyId::Tifi,
+      &TidefiStaking::account_id(),
+      100_000_000_000_000,
+    )
+    .expect("Unable to mint token");
+    assert_ok!(TidefiStaking::on_session_end(
+      1,
+      vec![(CurrencyId::Tifi, 100_000_000_000_000)]
+    ));
+
+    assert_noop!(
+      TidefiStaking::transfer_stake(Origin::signed(alice), stake_id, alice),
+      Error::<Test>::InvalidStakeTransfer
+    );
+    assert_noop!(
+      TidefiStaking::transfer_stake(Origin::signed(bob), stake_id, alice),
+      Error::<Test>::InvalidStakeId
+    );
+
+    // the transfer waits for the acceptance of bob, and can be cancelled by alice
+    assert_ok!(TidefiStaking::transfer_stake(
+      Origin::signed(alice),
+      stake_id,
+      bob
+    ));
+    System::assert_has_event(MockEvent::TidefiStaking(Event::StakeTransferProposed {
+      request_id: stake_id,
+      account_id: alice,
+      destination_id: bob,
+    }));
+    assert_eq!(TidefiStaking::account_stakes(alice).len(), 1);
+    assert_noop!(
+      TidefiStaking::cancel_stake_transfer(Origin::signed(bob), stake_id),
+      Error::<Test>::StakeTransferNotFound
+    );
+    assert_ok!(TidefiStaking::cancel_stake_transfer(
+      Origin::signed(alice),
+      stake_id
+    ));
+    assert_noop!(
+      TidefiStaking::accept_stake_transfer(Origin::signed(bob), stake_id),
+      Error::<Test>::StakeTransferNotFound
+    );
+
+    // the stake is moved with its rewards once bob accepts
+    assert_ok!(TidefiStaking::transfer_stake(
+      Origin::signed(alice),
+      stake_id,
+      bob
+    ));
+    assert_noop!(
+      TidefiStaking::accept_stake_transfer(Origin::signed(3u64), stake_id),
+      Error::<Test>::StakeTransferNotFound
+    );
+    assert_ok!(TidefiStaking::accept_stake_transfer(
+      Origin::signed(bob),
+      stake_id
+    ));
+    System::assert_has_event(MockEvent::TidefiStaking(Event::StakeTransferred {
+      request_id: stake_id,
+      account_id: alice,
+      destination_id: bob,
+    }));
+    assert!(TidefiStaking::account_stakes(alice).is_empty());
+    assert_eq!(
+      TidefiStaking::account_stakes(bob)[0].principal,
+      102_000_000_000_000
+    );
+    assert_eq!(TidefiStaking::transferred_stake_owner(stake_id), Some(bob));
+    assert!(TidefiStaking::pending_stake_transfer(stake_id).is_none());
+
+    // the stake of bob expires, and is unstaked by bob
+    <pallet_security::CurrentBlockCount<Test>>::put(FIFTEEN_DAYS);
+    TidefiStaking::on_idle(1, 1_000_000_000_000_000);
+    assert!(TidefiStaking::stake_reward_index(stake_id).is_none());
+    assert_eq!(
+      TidefiStaking::reward_shares(CurrencyId::Tifi, FIFTEEN_DAYS),
+      0
+    );
+
+    assert_ok!(TidefiStaking::unstake(Origin::signed(bob), stake_id, false));
+    assert_eq!(
+      Adapter::balance(CurrencyId::Tifi, &bob),
+      102_000_000_000_000
+    );
+    assert!(TidefiStaking::account_stakes(bob).is_empty());
+    assert!(TidefiStaking::transferred_stake_owner(stake_id).is_none());
+  });
+}
+
+#[test]
+pub fn delegate_stake_rewards_to_beneficiary() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let alice = 1u64;
+    let charlie = 3u64;
+    let initial_stake = 100_000_000_000_000;
+    Adapter::mint_into(CurrencyId::Tifi, &alice, 1_000_000_000_000_000)
+      .expect("Unable to mint token");
+    assert_ok!(TidefiStaking::stake(
+      Origin::signed(alice),
+      CurrencyId::Tifi,
+      initial_stake,
+      FIFTEEN_DAYS
+    ));
+    let stake_id = TidefiStaking::account_stakes(alice)
+      .first()
+      .unwrap()
+      .unique_id;
+
+    assert_ok!(TidefiStaking::set_stake_reward_beneficiary(
+      Origin::signed(alice),
+      stake_id,
+      Some(charlie)
+    ));
+    System::assert_has_event(MockEvent::TidefiStaking(Event::StakeRewardBeneficiarySet {
+      request_id: stake_id,
+      account_id: alice,
+      beneficiary: Some(charlie),
+    }));
+
+    // the delegated rewards are not compounded
+    assert_ok!(TidefiStaking::on_session_end(
+      1,
+      vec![(CurrencyId::Tifi, 100_000_000_000_000)]
+    ));
+    assert_eq!(
+      TidefiStaking::get_account_stakes(&alice)[0]
+        .1
+        .principal
+        .amount,
+      initial_stake
+    );
+
+    // the rewards accrued are settled for charlie when the beneficiary is removed
+    assert_ok!(TidefiStaking::set_stake_reward_beneficiary(
+      Origin::signed(alice),
+      stake_id,
+      None
+    ));
+    assert_eq!(
+      BeneficiaryRewards::<Test>::get(charlie, CurrencyId::Tifi),
+      2_000_000_000_000
+    );
+    assert_eq!(
+      TidefiStaking::account_stakes(alice)[0].principal,
+      initial_stake
+    );
+
+    assert_ok!(TidefiStaking::claim_beneficiary_rewards(
+      Origin::signed(charlie),
+      CurrencyId::Tifi
+    ));
+    System::assert_has_event(MockEvent::TidefiStaking(Event::BeneficiaryRewardsClaimed {
+      account_id: charlie,
+      currency_id: CurrencyId::Tifi,
+      amount: 2_000_000_000_000,
+    }));
+    assert_eq!(
+      Adapter::balance(CurrencyId::Tifi, &charlie),
+      2_000_000_000_000
+    );
+    assert_noop!(
+      TidefiStaking::claim_beneficiary_rewards(Origin::signed(charlie), CurrencyId::Tifi),
+      Error::<Test>::NoBeneficiaryRewards
+    );
+
+    // without beneficiary, the rewards are compounded again
+    assert_ok!(TidefiStaking::on_session_end(
+      2,
+      vec![(CurrencyId::Tifi, 100_000_000_000_000)]
+    ));
+    assert_eq!(
+      TidefiStaking::get_account_stakes(&alice)[0]
+        .1
+        .principal
+        .amount,
+      102_000_000_000_000
+    );
+  });
+}
@@ -25,6 +25,19 @@ use sp_std::marker::PhantomData;
 pub trait WeightInfo {
    fn stake() -> Weight;
    fn unstake() -> Weight;
+   fn set_staking_period() -> Weight;
+   fn remove_staking_period() -> Weight;
+   fn set_staking_currency_meta() -> Weight;
+   fn set_unstake_fee() -> Weight;
+   fn increase_stake() -> Weight;
+   fn partial_unstake() -> Weight;
+   fn set_stake_auto_renew() -> Weight;
+   fn cancel_queued_unstake() -> Weight;
+   fn transfer_stake() -> Weight;
+   fn cancel_stake_transfer() -> Weight;
+   fn accept_stake_transfer() -> Weight;
+   fn set_stake_reward_beneficiary() -> Weight;
+   fn claim_beneficiary_rewards() -> Weight;
 }
 
 /// Weights for `pallet_tidefi` using the Substrate node and recommended hardware.
@@ -33,12 +46,75 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 
    fn stake() -> Weight {
       14_100_000_u64
-      .saturating_add(T::DbWeight::get().reads(6_u64))
-      .saturating_add(T::DbWeight::get().writes(5_u64))
+      .saturating_add(T::DbWeight::get().reads(9_u64))
+      .saturating_add(T::DbWeight::get().writes(9_u64))
    }
    fn unstake() -> Weight {
       18_100_000_u64
       .saturating_add(T::DbWeight::get().reads(6_u64))
       .saturating_add(T::DbWeight::get().writes(5_u64))
    }
+   fn set_staking_period() -> Weight {
+      12_000_000_u64
+      .saturating_add(T::DbWeight::get().reads(1_u64))
+      .saturating_add(T::DbWeight::get().writes(1_u64))
+   }
+   fn remove_staking_period() -> Weight {
+      12_000_000_u64
+      .saturating_add(T::DbWeight::get().reads(1_u64))
+      .saturating_add(T::DbWeight::get().writes(1_u64))
+   }
+   fn set_staking_currency_meta() -> Weight {
+      10_000_000_u64
+      .saturating_add(T::DbWeight::get().writes(1_u64))
+   }
+   fn set_unstake_fee() -> Weight {
+      9_000_000_u64
+      .saturating_add(T::DbWeight::get().writes(1_u64))
+   }
+   fn increase_stake() -> Weight {
+      60_000_000_u64
+      .saturating_add(T::DbWeight::get().reads(10_u64))
+      .saturating_add(T::DbWeight::get().writes(7_u64))
+   }
+   fn partial_unstake() -> Weight {
+      60_000_000_u64
+      .saturating_add(T::DbWeight::get().reads(11_u64))
+      .saturating_add(T::DbWeight::get().writes(7_u64))
+   }
+   fn set_stake_auto_renew() -> Weight {
+      10_000_000_u64
+      .saturating_add(T::DbWeight::get().reads(1_u64))
+      .saturating_add(T::DbWeight::get().writes(1_u64))
+   }
+   fn cancel_queued_unstake() -> Weight {
+      10_000_000_u64
+      .saturating_add(T::DbWeight::get().reads(1_u64))
+      .saturating_add(T::DbWeight::get().writes(1_u64))
+   }
+   fn transfer_stake() -> Weight {
+      15_000_000_u64
+      .saturating_add(T::DbWeight::get().reads(2_u64))
+      .saturating_add(T::DbWeight::get().writes(1_u64))
+   }
+   fn cancel_stake_transfer() -> Weight {
+      10_000_000_u64
+      .saturating_add(T::DbWeight::get().reads(1_u64))
+      .saturating_add(T::DbWeight::get().writes(1_u64))
+   }
+   fn accept_stake_transfer() -> Weight {
+      40_000_000_u64
+      .saturating_add(T::DbWeight::get().reads(9_u64))
+      .saturating_add(T::DbWeight::get().writes(8_u64))
+   }
+   fn set_stake_reward_beneficiary() -> Weight {
+      30_000_000_u64
+      .saturating_add(T::DbWeight::get().reads(6_u64))
+      .saturating_add(T::DbWeight::get().writes(3_u64))
+   }
+   fn claim_beneficiary_rewards() -> Weight {
+      40_000_000_u64
+      .saturating_add(T::DbWeight::get().reads(3_u64))
+      .saturating_add(T::DbWeight::get().writes(3_u64))
+   }
 }
@@ -15,10 +15,12 @@
 // along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
 
 use crate::*;
-use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, vec, whitelisted_caller};
 use frame_support::{inherent::Vec, traits::fungibles::Mutate};
 use frame_system::RawOrigin;
-use tidefi_primitives::{CurrencyId, SwapType};
+use pallet_oracle::SwapRouteExt;
+use sp_runtime::Permill;
+use tidefi_primitives::{pallet::OracleExt, CurrencyId, SwapType};
 const INITIAL_AMOUNT: u128 = 500_000_000_000_000;
 const IA_MULTIPLIER: u32 = 2;
 const TEST_TOKEN: u32 = 2;
@@ -34,7 +36,48 @@ benchmarks! {
       let balance = INITIAL_AMOUNT.saturating_mul(IA_MULTIPLIER.into());
       T::CurrencyTidefi::mint_into(CurrencyId::Wrapped(TEST_TOKEN), &caller, balance as u128).expect("Unable to mint token");
       T::CurrencyTidefi::mint_into(CurrencyId::Tifi, &caller, balance as u128).expect("Unable to mint token");
+      // the limit order is inserted in a full order book
+      T::Oracle::set_full_order_book(CurrencyId::Tifi, CurrencyId::Wrapped(TEST_TOKEN));
    }: _(RawOrigin::Signed(caller), CurrencyId::Tifi, INITIAL_AMOUNT.into(), CurrencyId::Wrapped(TEST_TOKEN), INITIAL_AMOUNT.into(), SwapType::Limit, None)
+   cancel_swap {
+      let caller: T::AccountId = whitelisted_caller();
+      let balance = INITIAL_AMOUNT.saturating_mul(IA_MULTIPLIER.into());
+      T::CurrencyTidefi::mint_into(CurrencyId::Tifi, &caller, balance as u128).expect("Unable to mint token");
+      // the limit order is removed from a full order book
+      T::Oracle::set_full_order_book(CurrencyId::Tifi, CurrencyId::Wrapped(TEST_TOKEN));
+      let (request_id, _) = T::Oracle::add_new_swap_in_queue(
+         caller.clone(),
+         CurrencyId::Tifi,
+         INITIAL_AMOUNT,
+         CurrencyId::Wrapped(TEST_TOKEN),
+         INITIAL_AMOUNT,
+         <frame_system::Pallet<T>>::block_number(),
+         [0; 32],
+         false,
+         SwapType::Limit,
+         Permill::zero(),
+      ).expect("Unable to add swap");
+   }: _(RawOrigin::Signed(caller), request_id)
+   swap_route {
+      let caller: T::AccountId = whitelisted_caller();
+      let balance = INITIAL_AMOUNT.saturating_mul(IA_MULTIPLIER.into());
+      T::CurrencyTidefi::mint_into(CurrencyId::Tifi, &caller, balance as u128).expect("Unable to mint token");
+   }: _(RawOrigin::Signed(caller), vec![CurrencyId::Tifi, CurrencyId::Wrapped(TEST_TOKEN)], INITIAL_AMOUNT.into(), INITIAL_AMOUNT.into(), None)
+   cancel_swap_route {
+      let caller: T::AccountId = whitelisted_caller();
+      let balance = INITIAL_AMOUNT.saturating_mul(IA_MULTIPLIER.into());
+      T::CurrencyTidefi::mint_into(CurrencyId::Tifi, &caller, balance as u128).expect("Unable to mint token");
+      let request_id = T::Oracle::add_new_route_in_queue(
+         caller.clone(),
+         vec![CurrencyId::Tifi, CurrencyId::Wrapped(TEST_TOKEN)],
+         INITIAL_AMOUNT,
+         INITIAL_AMOUNT,
+         <frame_system::Pallet<T>>::block_number(),
+         [0; 32],
+         false,
+         Permill::zero(),
+      ).expect("Unable to add routed swap");
+   }: _(RawOrigin::Signed(caller), request_id)
    transfer {
       let caller: T::AccountId = whitelisted_caller();
    }: _(RawOrigin::Signed(caller.clone()), caller.clone(), CurrencyId::Tifi, INITIAL_AMOUNT)
@@ -41,8 +41,12 @@ pub mod pallet {
       fungibles::{Inspect, InspectHold, Mutate, MutateHold, Transfer},
       WithdrawConsequence,
     },
+    transactional,
   };
   use frame_system::pallet_prelude::*;
+  use pallet_asset_registry::WithdrawalLimitsExt;
+  use pallet_oracle::SwapRouteExt;
+  use pallet_quorum::{DelayedWithdrawalExt, WithdrawalFeeExt};
   use sp_io::hashing::blake2_256;
   use sp_runtime::Permill;
   use tidefi_primitives::{
@@ -60,13 +64,16 @@ pub mod pallet {
     type WeightInfo: WeightInfo;
 
     /// Quorum traits
-    type Quorum: QuorumExt<Self::AccountId, Self::BlockNumber>;
+    type Quorum: QuorumExt<Self::AccountId, Self::BlockNumber>
+      + DelayedWithdrawalExt<Self::AccountId, Self::BlockNumber>
+      + WithdrawalFeeExt;
 
     /// Oracle traits
-    type Oracle: OracleExt<Self::AccountId, Self::BlockNumber>;
+    type Oracle: OracleExt<Self::AccountId, Self::BlockNumber>
+      + SwapRouteExt<Self::AccountId, Self::BlockNumber>;
 
     /// Asset registry traits
-    type AssetRegistry: AssetRegistryExt;
+    type AssetRegistry: AssetRegistryExt + WithdrawalLimitsExt<Self::AccountId>;
 
     /// Tidechain currency wrapper
     type CurrencyTidefi: Inspect<Self::AccountId, AssetId = CurrencyId, Balance = Balance>
@@ -95,8 +102,19 @@ pub mod pallet {
       account: T::AccountId,
       currency_id: CurrencyId,
       amount: Balance,
+      fee: Balance,
       external_address: Vec<u8>,
     },
+    /// Event emitted when a large withdrawal is held until the end of its delay.
+    WithdrawalDelayed {
+      withdrawal_id: Hash,
+      account: T::AccountId,
+      currency_id: CurrencyId,
+      amount: Balance,
+      fee: Balance,
+      external_address: Vec<u8>,
+      release_at: T::BlockNumber,
+    },
     /// Event emitted when swap is initialized.
     Swap {
       request_id: Hash,
@@ -112,6 +130,19 @@ pub mod pallet {
     },
     /// User cancelled the initial swap and the funds has been released
     SwapCancelled { request_id: Hash },
+    /// Event emitted when a swap routed through intermediate assets is initialized.
+    SwapRoute {
+      request_id: Hash,
+      account: T::AccountId,
+      path: Vec<CurrencyId>,
+      amount_from: Balance,
+      min_amount_to: Balance,
+      extrinsic_hash: [u8; 32],
+      slippage_tolerance: Permill,
+      is_market_maker: bool,
+    },
+    /// User cancelled the routed swap and the funds has been released
+    SwapRouteCancelled { request_id: Hash },
   }
 
   // Errors inform users that something went wrong.
@@ -173,14 +204,21 @@ pub mod pallet {
 
     /// Submit asset withdrawal to the Quorum.
     ///
+    /// The withdrawal must be within the rolling caps of the asset registry. Withdrawals
+    /// above the delay threshold of the asset are held until the end of the delay, and can
+    /// be cancelled by the governance or the quorum in the meantime.
+    ///
+    /// The withdrawal fee of the asset is deducted from the amount sent to the external address.
+    ///
     /// - `currency_id`: The currency to withdraw.
-    /// - `amount`: The amount to transfer
+    /// - `amount`: The amount to transfer, including the withdrawal fee
     /// - `external_address`: External address where to send funds.
     ///
-    /// Emits `Withdrawal` event when successful.
+    /// Emits `Withdrawal` event when successful, or `WithdrawalDelayed` for large withdrawals.
     ///
     /// Weight: `O(1)`
     #[pallet::weight(<T as pallet::Config>::WeightInfo::withdrawal())]
+    #[transactional]
     pub fn withdrawal(
       origin: OriginFor<T>,
       currency_id: CurrencyId,
@@ -205,20 +243,45 @@ pub mod pallet {
       // 5. Make sure the account have enough funds
       match T::CurrencyTidefi::can_withdraw(currency_id, &account_id, amount) {
         WithdrawConsequence::Success => {
-          // Add withdrawal in queue
-          T::Quorum::add_new_withdrawal_in_queue(
-            account_id.clone(),
-            currency_id,
-            amount,
-            external_address.clone(),
-          )?;
-          // Send event to the chain
-          Self::deposit_event(Event::<T>::Withdrawal {
-            account: account_id,
-            currency_id,
-            amount,
-            external_address,
-          });
+          // 5. a) Make sure the withdrawal is within the rolling caps and quote the fee
+          let is_delayed = T::AssetRegistry::register_withdrawal(&account_id, currency_id, amount)?;
+          let fee = T::Quorum::quote_withdrawal_fee(currency_id, amount);
+
+          if is_delayed {
+            // 5. b) Hold the funds until the end of the delay
+            let (withdrawal_id, release_at) = T::Quorum::add_new_delayed_withdrawal(
+              account_id.clone(),
+              currency_id,
+              amount,
+              external_address.clone(),
+            )?;
+            // 5. c) Send event to the chain
+            Self::deposit_event(Event::<T>::WithdrawalDelayed {
+              withdrawal_id,
+              account: account_id,
+              currency_id,
+              amount,
+              fee,
+              external_address,
+              release_at,
+            });
+          } else {
+            // 5. b) Add withdrawal in queue
+            T::Quorum::add_new_withdrawal_in_queue(
+              account_id.clone(),
+              currency_id,
+              amount,
+              external_address.clone(),
+            )?;
+            // 5. c) Send event to the chain
+            Self::deposit_event(Event::<T>::Withdrawal {
+              account: account_id,
+              currency_id,
+              amount,
+              fee,
+              external_address,
+            });
+          }
 
           Ok(().into())
         }
@@ -327,19 +390,121 @@ pub mod pallet {
     /// Emits `SwapCancelled` event when successful.
     ///
     /// Weight: `O(1)`
-    #[pallet::weight(<T as pallet::Config>::WeightInfo::swap())]
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::cancel_swap())]
     pub fn cancel_swap(origin: OriginFor<T>, request_id: Hash) -> DispatchResultWithPostInfo {
       // 1. Make sure the transaction is signed
       let account_id = ensure_signed(origin)?;
 
+      // 2. Remove the swap request and release funds if needed, the oracle allow the
+      // cancellation while it is unresponsive
+      T::Oracle::remove_swap_from_queue(account_id, request_id)?;
+
+      // 3. Emit event on chain
+      Self::deposit_event(Event::<T>::SwapCancelled { request_id });
+
+      Ok(().into())
+    }
+
+    /// Swap through the market makers, routed through intermediate assets.
+    ///
+    /// This will register a new request and will be queued for the oracle, do
+    /// not expect an immediate response. All the legs of the route are settled at
+    /// once, or the funds are released.
+    ///
+    /// - `path`: The currencies of the route, from the currency to send to the currency to receive.
+    /// - `amount_from`: The amount to send.
+    /// - `min_amount_to`: The minimum amount to receive at the end of the route.
+    /// - `slippage_tolerance`: The slippage tolerance of the amount sent.
+    ///
+    /// Emits `SwapRoute` event when successful.
+    ///
+    /// Weight: `O(1)`
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::swap_route())]
+    pub fn swap_route(
+      origin: OriginFor<T>,
+      path: Vec<CurrencyId>,
+      amount_from: Balance,
+      min_amount_to: Balance,
+      slippage_tolerance: Option<Permill>,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the transaction is signed
+      let account_id = ensure_signed(origin)?;
+
       // 2. Make sure the oracle is enabled
       ensure!(T::Oracle::is_oracle_enabled(), Error::<T>::OraclePaused);
 
-      // 3. Remove the swap request and release funds if needed
-      T::Oracle::remove_swap_from_queue(account_id, request_id)?;
+      // 3. Make sure all the currencies of the path are enabled
+      ensure!(
+        path
+          .iter()
+          .all(|currency_id| T::AssetRegistry::is_currency_enabled(*currency_id)),
+        Error::<T>::AssetDisabled
+      );
+      let currency_id_from = *path.first().ok_or(Error::<T>::UnknownAsset)?;
 
-      // 4. Emit event on chain
-      Self::deposit_event(Event::<T>::SwapCancelled { request_id });
+      // 4. Grab the extrinsic hash of the current extrinsic for better traceability
+      let extrinsic_hash = blake2_256(&<frame_system::Pallet<T>>::extrinsic_data(
+        <frame_system::Pallet<T>>::extrinsic_index().ok_or(Error::<T>::UnknownError)?,
+      ));
+
+      // 5. Validate if the user is a market maker to allocate the correct fees
+      let is_market_maker = T::Oracle::is_market_maker(account_id.clone())?;
+
+      // 6. Make sure the account have enough funds for the first currency of the path
+      match T::CurrencyTidefi::can_withdraw(currency_id_from, &account_id, amount_from) {
+        WithdrawConsequence::Success => {
+          let real_slippage_tolerance = slippage_tolerance.unwrap_or(Permill::zero());
+
+          // 6. a) Add the routed swap in queue
+          let request_id = T::Oracle::add_new_route_in_queue(
+            account_id.clone(),
+            path.clone(),
+            amount_from,
+            min_amount_to,
+            <frame_system::Pallet<T>>::block_number(),
+            extrinsic_hash,
+            is_market_maker,
+            real_slippage_tolerance,
+          )?;
+
+          // 6. b) Send event to the chain
+          Self::deposit_event(Event::<T>::SwapRoute {
+            request_id,
+            account: account_id,
+            path,
+            amount_from,
+            min_amount_to,
+            extrinsic_hash,
+            is_market_maker,
+            slippage_tolerance: real_slippage_tolerance,
+          });
+
+          Ok(().into())
+        }
+        WithdrawConsequence::NoFunds => Err(Error::<T>::NoFunds.into()),
+        WithdrawConsequence::UnknownAsset => Err(Error::<T>::UnknownAsset.into()),
+        _ => Err(Error::<T>::UnknownError.into()),
+      }
+    }
+
+    /// Cancel a routed swap and release funds.
+    ///
+    /// - `request_id`: The request ID to cancel.
+    ///
+    /// Emits `SwapRouteCancelled` event when successful.
+    ///
+    /// Weight: `O(1)`
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::cancel_swap_route())]
+    pub fn cancel_swap_route(origin: OriginFor<T>, request_id: Hash) -> DispatchResultWithPostInfo {
+      // 1. Make sure the transaction is signed
+      let account_id = ensure_signed(origin)?;
+
+      // 2. Remove the routed swap and release funds, the oracle allow the cancellation while
+      // it is unresponsive
+      T::Oracle::remove_route_from_queue(account_id, request_id)?;
+
+      // 3. Emit event on chain
+      Self::deposit_event(Event::<T>::SwapRouteCancelled { request_id });
 
       Ok(().into())
     }
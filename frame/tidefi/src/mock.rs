@@ -33,7 +33,7 @@ use sp_core::H256;
 use sp_runtime::{
   testing::Header,
   traits::{BlakeTwo256, IdentityLookup},
-  DispatchError, DispatchResult, Permill,
+  DispatchError, DispatchResult, FixedU128, Permill,
 };
 use std::marker::PhantomData;
 use system::EnsureRoot;
@@ -60,6 +60,7 @@ frame_support::construct_runtime!(
     Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
     Tidefi: pallet_tidefi::{Pallet, Call, Storage, Event<T>},
     TidefiStaking: pallet_tidefi_stake::{Pallet, Call, Storage, Event<T>},
+    PriceFeed: pallet_price_feed::{Pallet, Call, Config<T>, Storage, Event<T>},
     Fees: pallet_fees::{Pallet, Storage, Event<T>},
     Quorum: pallet_quorum::{Pallet, Call, Config<T>, Storage, Event<T>},
     Oracle: pallet_oracle::{Pallet, Call, Config<T>, Storage, Event<T>},
@@ -147,6 +148,7 @@ parameter_types! {
   pub const QuorumPalletId: PalletId = PalletId(*b"qurm*pal");
   pub const OraclePalletId: PalletId = PalletId(*b"orcl*pal");
   pub const AssetRegistryPalletId: PalletId = PalletId(*b"asst*pal");
+  pub const WithdrawalPeriod: u64 = 100;
   pub const FeesPalletId: PalletId = PalletId(*b"fees*pal");
   pub const StakePalletId: PalletId = PalletId(*b"stak*pal");
   pub const SessionsPerEra: SessionIndex = 10;
@@ -174,8 +176,34 @@ parameter_types! {
   pub const WatchListLimit: u32 = 10000;
   // The maximum number of pubkey each asset can have, should alway be more more than the current quorum active member set
   pub const PubkeyLimitPerAsset: u32 = 10;
+  // The number of blocks of each phase of a key generation round
+  pub const KeygenPhaseTimeout: BlockNumber = 10;
+  // The maximum length of the key generation commitments and shares
+  pub const KeygenPayloadLimit: u32 = 1024;
+  // The number of blocks a finalized proposal is kept in the archive
+  pub const ProposalArchiveRetention: BlockNumber = 100;
+  // The number of blocks a processed mint is kept to reject the replays of its deposit
+  pub const MintReplayHorizon: BlockNumber = 1_000;
+  pub const WithdrawalDelay: BlockNumber = 10;
+  pub const BurnedConfirmationTimeout: BlockNumber = 20;
+  pub const MaxMissedProposals: u32 = 2;
+  pub const MemberInactivityPeriod: BlockNumber = 1_000;
+  pub const MemberKeysGracePeriod: BlockNumber = 10;
+  pub const WithdrawalFeesAccount: u64 = 99;
   // The number of swap each account can have in queue
   pub const SwapLimitByAccount: u32 = 100;
+  pub const SwapLifetime: u64 = 100;
+  pub const OrderBookLimit: u32 = 100;
+  pub const SwapFillLimit: u32 = 10;
+  pub const SwapFillRetention: u64 = 100;
+  pub const MaxOracleSilence: u64 = 100;
+  pub const SwapPathLimit: u32 = 4;
+  pub const MarketMakerBond: Balance = 10 * TIFI;
+  pub const MarketMakerUnbondingPeriod: u64 = 10;
+  pub const MarketMakerNameLimit: u32 = 32;
+  pub const MarketMakerPairsLimit: u32 = 5;
+  pub const MaxReporters: u32 = 5;
+  pub const PriceValidity: u64 = 100;
   // Maximum number of staking period the chain can support
   pub const StakingRewardCap: u32 = 10;
   pub const BlocksSunriseClaims: BlockNumber = 10;
@@ -197,6 +225,7 @@ impl pallet_quorum::Config for Test {
   type CurrencyTidefi = Adapter<AccountId>;
   type Security = Security;
   type AssetRegistry = AssetRegistry;
+  type Fees = Fees;
   type ProposalsCap = ProposalsCap;
   type BurnedCap = BurnedCap;
   type ProposalLifetime = ProposalLifetime;
@@ -204,6 +233,18 @@ impl pallet_quorum::Config for Test {
   type VotesLimit = VotesLimit;
   type WatchListLimit = WatchListLimit;
   type PubkeyLimitPerAsset = PubkeyLimitPerAsset;
+  type KeygenPhaseTimeout = KeygenPhaseTimeout;
+  type KeygenPayloadLimit = KeygenPayloadLimit;
+  type ProposalArchiveRetention = ProposalArchiveRetention;
+  type ComplianceOrigin = EnsureRoot<Self::AccountId>;
+  type MintReplayHorizon = MintReplayHorizon;
+  type WithdrawalDelay = WithdrawalDelay;
+  type BurnedConfirmationTimeout = BurnedConfirmationTimeout;
+  type MaxMissedProposals = MaxMissedProposals;
+  type MemberInactivityPeriod = MemberInactivityPeriod;
+  type MemberKeysGracePeriod = MemberKeysGracePeriod;
+  type ForceOrigin = EnsureRoot<Self::AccountId>;
+  type WithdrawalFeesAccount = WithdrawalFeesAccount;
 }
 
 impl pallet_timestamp::Config for Test {
@@ -220,7 +261,19 @@ impl pallet_oracle::Config for Test {
   type CurrencyTidefi = Adapter<AccountId>;
   type Security = Security;
   type SwapLimitByAccount = SwapLimitByAccount;
+  type SwapLifetime = SwapLifetime;
+  type OrderBookLimit = OrderBookLimit;
+  type SwapFillLimit = SwapFillLimit;
+  type SwapFillRetention = SwapFillRetention;
+  type MaxOracleSilence = MaxOracleSilence;
+  type SwapPathLimit = SwapPathLimit;
+  type MarketMakerBond = MarketMakerBond;
+  type MarketMakerUnbondingPeriod = MarketMakerUnbondingPeriod;
+  type MarketMakerNameLimit = MarketMakerNameLimit;
+  type MarketMakerPairsLimit = MarketMakerPairsLimit;
+  type ForceOrigin = EnsureRoot<Self::AccountId>;
   type Fees = Fees;
+  type PriceFeed = PriceFeed;
 }
 
 impl pallet_security::Config for Test {
@@ -232,6 +285,7 @@ impl pallet_asset_registry::Config for Test {
   type WeightInfo = pallet_asset_registry::weights::SubstrateWeight<Test>;
   type AssetRegistryPalletId = AssetRegistryPalletId;
   type CurrencyTidefi = Adapter<AccountId>;
+  type WithdrawalPeriod = WithdrawalPeriod;
 }
 
 impl pallet_sudo::Config for Test {
@@ -254,6 +308,17 @@ impl pallet_fees::Config for Test {
   type MarketMakerFeeAmount = MarketMakerFeeAmount;
   type BlocksSunriseClaims = BlocksSunriseClaims;
   type Staking = TidefiStaking;
+  type PriceFeed = PriceFeed;
+}
+
+impl pallet_price_feed::Config for Test {
+  type Event = Event;
+  type WeightInfo = pallet_price_feed::weights::SubstrateWeight<Test>;
+  type Security = Security;
+  type MaxReporters = MaxReporters;
+  type PriceValidity = PriceValidity;
+  type ForceOrigin = EnsureRoot<Self::AccountId>;
+  type InitialReporter = ();
 }
 
 impl pallet_tidefi_stake::Config for Test {
@@ -266,7 +331,9 @@ impl pallet_tidefi_stake::Config for Test {
   type BlocksForceUnstake = BlocksForceUnstake;
   type AssetRegistry = AssetRegistry;
   type StakingRewardCap = StakingRewardCap;
+  type ForceOrigin = EnsureRoot<Self::AccountId>;
   type Security = Security;
+  type Fees = Fees;
 }
 
 // this is only the mock for benchmarking, it's implemented directly in the runtime
@@ -439,6 +506,8 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
     enabled: true,
     members: vec![0],
     threshold: 1,
+    proposal_thresholds: pallet_quorum::ProposalThresholds::from_threshold(1),
+    vote_weights: Vec::new(),
   }
   .assimilate_storage(&mut storage)
   .unwrap();
@@ -451,6 +520,13 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
   .assimilate_storage(&mut storage)
   .unwrap();
 
+  pallet_price_feed::GenesisConfig::<Test> {
+    reporters: vec![0],
+    minimum_reports: 1,
+  }
+  .assimilate_storage(&mut storage)
+  .unwrap();
+
   pallet_asset_registry::GenesisConfig::<Test> {
     assets: vec![(
       CurrencyId::Wrapped(2),
@@ -465,6 +541,21 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
   .unwrap();
 
   let mut ext: sp_io::TestExternalities = storage.into();
-  ext.execute_with(|| System::set_block_number(1));
+  ext.execute_with(|| {
+    System::set_block_number(1);
+    // the swap fees are registered with their USDT value
+    for currency_id in [
+      CurrencyId::Tifi,
+      CurrencyId::Wrapped(1),
+      CurrencyId::Wrapped(2),
+      CurrencyId::Wrapped(3),
+    ] {
+      pallet_price_feed::Prices::<Test>::insert(
+        currency_id,
+        CurrencyId::Wrapped(4),
+        (FixedU128::from(1), 0),
+      );
+    }
+  });
   ext
 }
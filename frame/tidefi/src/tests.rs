@@ -15,13 +15,24 @@
 // along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
 
 use crate::{
-  mock::{new_test_ext, Adapter, Assets, Event as MockEvent, Origin, System, Tidefi},
+  mock::{
+    new_test_ext, Adapter, AssetRegistry, Assets, Event as MockEvent, Origin, Quorum, Security,
+    System, Test, Tidefi,
+  },
   pallet::*,
 };
-use frame_support::{assert_ok, traits::fungibles::Mutate};
+use frame_support::{
+  assert_noop, assert_ok,
+  traits::{
+    fungibles::{InspectHold, Mutate},
+    Hooks,
+  },
+};
+use pallet_asset_registry::WithdrawalLimits;
+use pallet_quorum::DelayedWithdrawals;
 use sp_runtime::Permill;
 use std::str::FromStr;
-use tidefi_primitives::{CurrencyId, Hash, SwapType};
+use tidefi_primitives::{pallet::SecurityExt, CurrencyId, Hash, SwapType};
 
 #[test]
 pub fn request_swap_event() {
@@ -96,3 +107,129 @@ pub fn request_swap_event() {
     }));
   })
 }
+
+#[test]
+pub fn withdrawal_within_limits_and_delayed() {
+  new_test_ext().execute_with(|| {
+    let currency_id = CurrencyId::Wrapped(2);
+    let external_address = vec![1; 32];
+    assert_ok!(Adapter::mint_into(currency_id, &1u64, 1_000_000));
+    assert_ok!(AssetRegistry::set_withdrawal_limits(
+      Origin::signed(0u64),
+      currency_id,
+      Some(WithdrawalLimits {
+        asset_cap: Some(600_000),
+        account_cap: Some(500_000),
+        delay_threshold: Some(100_000),
+      })
+    ));
+
+    // small withdrawal goes straight to the quorum
+    assert_ok!(Tidefi::withdrawal(
+      Origin::signed(1u64),
+      currency_id,
+      50_000,
+      external_address.clone()
+    ));
+    assert_eq!(Quorum::proposals().len(), 1);
+
+    // above the rolling cap of the account
+    assert_noop!(
+      Tidefi::withdrawal(
+        Origin::signed(1u64),
+        currency_id,
+        500_000,
+        external_address.clone()
+      ),
+      pallet_asset_registry::Error::<Test>::AccountWithdrawalCapExceeded
+    );
+
+    // large withdrawal is held until the end of the delay
+    assert_ok!(Tidefi::withdrawal(
+      Origin::signed(1u64),
+      currency_id,
+      200_000,
+      external_address.clone()
+    ));
+    assert_eq!(Quorum::proposals().len(), 1);
+    assert_eq!(Adapter::balance_on_hold(currency_id, &1u64), 200_000);
+    let (withdrawal_id, withdrawal) = DelayedWithdrawals::<Test>::iter().next().unwrap();
+    System::assert_has_event(MockEvent::Tidefi(Event::WithdrawalDelayed {
+      withdrawal_id,
+      account: 1u64,
+      currency_id,
+      amount: 200_000,
+      fee: 0,
+      external_address: external_address.clone(),
+      release_at: withdrawal.release_at,
+    }));
+
+    // only the governance or the quorum members can cancel it
+    assert_noop!(
+      Quorum::cancel_delayed_withdrawal(Origin::signed(1u64), withdrawal_id),
+      pallet_quorum::Error::<Test>::AccessDenied
+    );
+    assert_ok!(Quorum::cancel_delayed_withdrawal(
+      Origin::signed(0u64),
+      withdrawal_id
+    ));
+    assert_eq!(Adapter::balance_on_hold(currency_id, &1u64), 0);
+    assert!(Quorum::delayed_withdrawals(withdrawal_id).is_none());
+    System::assert_last_event(MockEvent::Quorum(
+      pallet_quorum::Event::DelayedWithdrawalCancelled {
+        withdrawal_id,
+        account_id: 1u64,
+        currency_id,
+        amount: 200_000,
+      },
+    ));
+
+    // the cancelled withdrawal doesn't count in the rolling caps
+    assert_eq!(
+      AssetRegistry::account_withdrawals(1u64, currency_id).amount,
+      50_000
+    );
+    assert_eq!(AssetRegistry::asset_withdrawals(currency_id).amount, 50_000);
+
+    // the next one is released in the proposals queue at the end of the delay
+    assert_ok!(Tidefi::withdrawal(
+      Origin::signed(1u64),
+      currency_id,
+      200_000,
+      external_address.clone()
+    ));
+    let (withdrawal_id, withdrawal) = DelayedWithdrawals::<Test>::iter().next().unwrap();
+    Quorum::on_idle(0, 1_000_000_000_000);
+    assert_eq!(Quorum::proposals().len(), 1);
+
+    pallet_security::CurrentBlockCount::<Test>::put(withdrawal.release_at);
+    assert_eq!(Security::get_current_block_count(), withdrawal.release_at);
+    Quorum::on_idle(0, 1_000_000_000_000);
+    assert_eq!(Quorum::proposals().len(), 2);
+    assert_eq!(Adapter::balance_on_hold(currency_id, &1u64), 0);
+    assert!(Quorum::delayed_withdrawals(withdrawal_id).is_none());
+
+    // the released withdrawals count in the rolling caps, until they decay
+    assert_eq!(
+      AssetRegistry::account_withdrawals(1u64, currency_id).amount,
+      250_000
+    );
+    assert_noop!(
+      Tidefi::withdrawal(
+        Origin::signed(1u64),
+        currency_id,
+        300_000,
+        external_address.clone()
+      ),
+      pallet_asset_registry::Error::<Test>::AccountWithdrawalCapExceeded
+    );
+    System::set_block_number(101);
+    assert_ok!(Tidefi::withdrawal(
+      Origin::signed(1u64),
+      currency_id,
+      100_000,
+      external_address
+    ));
+    assert_eq!(Quorum::proposals().len(), 3);
+  });
+}
@@ -24,6 +24,9 @@ use sp_std::marker::PhantomData;
 pub trait WeightInfo {
    fn withdrawal() -> Weight;
    fn swap() -> Weight;
+   fn cancel_swap() -> Weight;
+   fn swap_route() -> Weight;
+   fn cancel_swap_route() -> Weight;
    fn transfer() -> Weight;
 }
 
@@ -31,15 +34,30 @@ pub trait WeightInfo {
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
    fn withdrawal() -> Weight {
-      14_200_000_u64
-      .saturating_add(T::DbWeight::get().reads(6_u64))
-      .saturating_add(T::DbWeight::get().writes(5_u64))
+      21_200_000_u64
+      .saturating_add(T::DbWeight::get().reads(12_u64))
+      .saturating_add(T::DbWeight::get().writes(9_u64))
    }
    fn swap() -> Weight {
-      14_300_000_u64
+      37_300_000_u64
+      .saturating_add(T::DbWeight::get().reads(7_u64))
+      .saturating_add(T::DbWeight::get().writes(6_u64))
+   }
+   fn cancel_swap() -> Weight {
+      32_100_000_u64
+      .saturating_add(T::DbWeight::get().reads(8_u64))
+      .saturating_add(T::DbWeight::get().writes(6_u64))
+   }
+   fn swap_route() -> Weight {
+      15_300_000_u64
       .saturating_add(T::DbWeight::get().reads(6_u64))
       .saturating_add(T::DbWeight::get().writes(5_u64))
    }
+   fn cancel_swap_route() -> Weight {
+      14_800_000_u64
+      .saturating_add(T::DbWeight::get().reads(6_u64))
+      .saturating_add(T::DbWeight::get().writes(3_u64))
+   }
    fn transfer() -> Weight {
       18_400_000_u64
       .saturating_add(T::DbWeight::get().reads(6_u64))
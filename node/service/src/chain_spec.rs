@@ -138,6 +138,8 @@ fn lagoon_testnet_genesis(
 
   // default threshold set to 60%
   let quorum_threshold = (quorums.len() as f64 * 0.6).ceil() as u16;
+  // configuration updates and mints above 100k USDT need 80%
+  let quorum_high_threshold = (quorums.len() as f64 * 0.8).ceil() as u16;
 
   let mut total_claims: u128 = 0;
   for (_, balance) in &claims {
@@ -261,10 +263,18 @@ fn lagoon_testnet_genesis(
       enabled: true,
       members: quorums,
       threshold: quorum_threshold,
+      proposal_thresholds: lagoon_runtime::ProposalThresholds {
+        mint: quorum_threshold,
+        large_mint: quorum_high_threshold,
+        large_mint_value: assets::Asset::Tether.saturating_mul(100_000),
+        withdrawal: quorum_threshold,
+        update_configuration: quorum_high_threshold,
+      },
+      vote_weights: vec![],
     },
     oracle: lagoon_runtime::OracleConfig {
       enabled: true,
-      account: oracle,
+      account: oracle.clone(),
       market_makers: vec![
         //5CFsxqm4muZDTZA3vZVE8Pm9ny2XDrKvR8UAZuufxFLGoAwQ
         hex!["0885b880a6305cb19ea441fab8b5ed02cadef5cb5dafe9e9afd7c0be80046636"].into(),
@@ -299,6 +309,10 @@ fn lagoon_testnet_genesis(
     security: Default::default(),
     tidefi_staking: crate::tidefi_staking_genesis!(lagoon_runtime),
     fees: crate::tidefi_sunrise_pool_genesis!(lagoon_runtime),
+    price_feed: lagoon_runtime::PriceFeedConfig {
+      reporters: vec![oracle],
+      minimum_reports: 1,
+    },
   }
 }
 
@@ -326,6 +340,8 @@ fn tidechain_testnet_genesis(
 
   // default threshold set to 60%
   let quorum_threshold = (quorums.len() as f64 * 0.6).ceil() as u16;
+  // configuration updates and mints above 100k USDT need 80%
+  let quorum_high_threshold = (quorums.len() as f64 * 0.8).ceil() as u16;
 
   // Treasury Account Id
   let treasury_account: AccountId = tidechain_runtime::TreasuryPalletId::get().into_account();
@@ -449,10 +465,18 @@ fn tidechain_testnet_genesis(
       enabled: true,
       members: quorums,
       threshold: quorum_threshold,
+      proposal_thresholds: tidechain_runtime::ProposalThresholds {
+        mint: quorum_threshold,
+        large_mint: quorum_high_threshold,
+        large_mint_value: assets::Asset::Tether.saturating_mul(100_000),
+        withdrawal: quorum_threshold,
+        update_configuration: quorum_high_threshold,
+      },
+      vote_weights: vec![],
     },
     oracle: tidechain_runtime::OracleConfig {
       enabled: true,
-      account: oracle,
+      account: oracle.clone(),
       market_makers: Vec::new(),
     },
     asset_registry: tidechain_runtime::AssetRegistryConfig {
@@ -464,6 +488,10 @@ fn tidechain_testnet_genesis(
     security: Default::default(),
     tidefi_staking: crate::tidefi_staking_genesis!(tidechain_runtime),
     fees: crate::tidefi_sunrise_pool_genesis!(tidechain_runtime),
+    price_feed: tidechain_runtime::PriceFeedConfig {
+      reporters: vec![oracle],
+      minimum_reports: 1,
+    },
   }
 }
 
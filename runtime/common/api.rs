@@ -19,11 +19,15 @@ use crate::{
     AccountId, AuthorityDiscoveryId, Balance, Block, CurrencyId, EpochDuration, GrandpaId, Index,
     BABE_GENESIS_EPOCH_CONFIG,
   },
-  AssetRegistry, AuthorityDiscovery, Babe, Executive, Grandpa, Historical, InherentDataExt,
-  Runtime, SessionKeys, System, TidefiStaking, TransactionPayment, VERSION,
+  AssetRegistry, AuthorityDiscovery, Babe, Executive, Fees, Grandpa, Historical, InherentDataExt,
+  Oracle, Quorum, Runtime, SessionKeys, System, TidefiStaking, TransactionPayment, VERSION,
 };
 use frame_support::traits::KeyOwnerProofSystem;
 use pallet_grandpa::{fg_primitives, AuthorityList as GrandpaAuthorityList};
+use pallet_tidefi_rpc_runtime_api::{
+  AccountWatchListInfo, ArchivedProposalInfo, OracleWatchdog, ProposalArchiveFilter, SwapFillInfo,
+  SwapInfo, SwapQuote, SwapSide, WithdrawalQuote,
+};
 use pallet_transaction_payment::{FeeDetails, RuntimeDispatchInfo};
 use sp_api::{impl_runtime_apis, ApisVec};
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
@@ -32,11 +36,13 @@ use sp_inherents::{CheckInherentsResult, InherentData};
 use sp_runtime::{
   traits::{Block as BlockT, NumberFor},
   transaction_validity::{TransactionSource, TransactionValidity},
-  ApplyExtrinsicResult, DispatchError,
+  ApplyExtrinsicResult, DispatchError, Permill,
 };
 use sp_std::prelude::*;
 use sp_version::RuntimeVersion;
-use tidefi_primitives::{BalanceInfo, BlockNumber, CurrencyBalance, CurrencyMetadata, Stake};
+use tidefi_primitives::{
+  pallet::OracleExt, BalanceInfo, BlockNumber, CurrencyBalance, CurrencyMetadata, Hash, Stake,
+};
 
 // Work around the issue that RUNTIME_API_VERSIONS is not public.
 pub(crate) const PRUNTIME_API_VERSIONS: ApisVec = RUNTIME_API_VERSIONS;
@@ -235,6 +241,53 @@ impl_runtime_apis! {
      fn get_account_stakes(account_id: AccountId) -> Result<Vec<(CurrencyId, Stake<BalanceInfo, BlockNumber>)>, DispatchError> {
        Ok(TidefiStaking::get_account_stakes(&account_id))
      }
+     fn get_oracle_watchdog() -> Result<OracleWatchdog, DispatchError> {
+       Ok(OracleWatchdog {
+         is_enabled: Oracle::status(),
+         is_responsive: !Oracle::is_oracle_silent(),
+         last_seen: Oracle::last_seen(),
+         unresponsive_since: Oracle::unresponsive_since(),
+       })
+     }
+     fn get_swap_with_fills(request_id: Hash) -> Result<(Option<SwapInfo<AccountId, BlockNumber>>, Vec<SwapFillInfo<BlockNumber>>), DispatchError> {
+       Ok(Oracle::get_swap_with_fills(request_id))
+     }
+     fn get_account_swaps(account_id: AccountId) -> Result<Vec<(Hash, SwapInfo<AccountId, BlockNumber>)>, DispatchError> {
+       Ok(Oracle::get_account_swaps(&account_id))
+     }
+     fn get_swap(request_id: Hash) -> Result<Option<SwapInfo<AccountId, BlockNumber>>, DispatchError> {
+       Ok(Oracle::get_swap(request_id))
+     }
+     fn get_open_swaps(pair: (CurrencyId, CurrencyId), side: SwapSide, limit: u32, cursor: Option<Hash>) -> Result<(Vec<(Hash, SwapInfo<AccountId, BlockNumber>)>, Option<Hash>), DispatchError> {
+       Ok(Oracle::get_open_swaps(pair, side, limit, cursor))
+     }
+     fn quote_swap(account_id: AccountId, currency_id_from: CurrencyId, amount_from: Balance, currency_id_to: CurrencyId, amount_to: Balance, slippage: Permill) -> Result<SwapQuote, DispatchError> {
+       let is_market_maker = Oracle::is_market_maker(account_id.clone())?;
+       let amount_held = Oracle::quote_swap_hold(&account_id, currency_id_from, amount_from, currency_id_to, amount_to, slippage, is_market_maker)?;
+       // the fee is charged on the amount with the slippage, like the amount held, fails when the price is stale
+       let (fee, sunrise_reward) = Fees::quote_swap_fees(currency_id_from, Oracle::swap_amount_with_slippage(amount_from, slippage), is_market_maker)?;
+       Ok(SwapQuote {
+         is_market_maker,
+         amount_held: BalanceInfo { amount: amount_held },
+         fee: BalanceInfo { amount: fee.fee },
+         fee_usdt: BalanceInfo { amount: fee.fee_usdt },
+         sunrise_reward: sunrise_reward.map(|(pool_id, reward)| (pool_id, BalanceInfo { amount: reward })),
+       })
+     }
+     fn get_proposal_archive(filter: ProposalArchiveFilter<AccountId>, limit: u32, cursor: Option<u32>) -> Result<(Vec<(u32, ArchivedProposalInfo<AccountId, BlockNumber>)>, Option<u32>), DispatchError> {
+       Ok(Quorum::get_proposal_archive(filter, limit, cursor))
+     }
+     fn get_account_watch_list(account_id: AccountId) -> Result<AccountWatchListInfo<BlockNumber>, DispatchError> {
+       Ok(Quorum::get_account_watch_list(&account_id))
+     }
+
+     fn quote_withdrawal(currency_id: CurrencyId, amount: Balance) -> Result<WithdrawalQuote, DispatchError> {
+       let (fee, amount_burned) = Quorum::quote_withdrawal(currency_id, amount)?;
+       Ok(WithdrawalQuote {
+         fee: BalanceInfo { amount: fee },
+         amount_burned: BalanceInfo { amount: amount_burned },
+       })
+     }
    }
 
    #[cfg(feature = "try-runtime")]
@@ -289,6 +342,7 @@ impl_runtime_apis! {
        list_benchmark!(list, extra, pallet_oracle, crate::Oracle);
        list_benchmark!(list, extra, pallet_fees, crate::Fees);
        list_benchmark!(list, extra, pallet_asset_registry, crate::AssetRegistry);
+       list_benchmark!(list, extra, pallet_price_feed, crate::PriceFeed);
 
        let storage_info = crate::AllPalletsWithSystem::storage_info();
 
@@ -361,6 +415,7 @@ impl_runtime_apis! {
            add_benchmark!(params, batches, pallet_asset_registry, crate::AssetRegistry);
            add_benchmark!(params, batches, pallet_preimage, crate::Preimage);
            add_benchmark!(params, batches, pallet_fees, crate::Fees);
+           add_benchmark!(params, batches, pallet_price_feed, crate::PriceFeed);
 
            if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
            Ok(batches)
@@ -43,6 +43,7 @@ pub enum ProxyType {
   NonTransfer,
   Governance,
   Staking,
+  TidefiStaking,
 }
 
 impl Default for ProxyType {
@@ -51,19 +52,32 @@ impl Default for ProxyType {
   }
 }
 
+// The calls moving the stakes or their rewards to another account
+fn is_stake_transfer(c: &Call) -> bool {
+  matches!(
+    c,
+    Call::TidefiStaking(pallet_tidefi_stake::Call::transfer_stake { .. })
+      | Call::TidefiStaking(pallet_tidefi_stake::Call::set_stake_reward_beneficiary { .. })
+      | Call::TidefiStaking(pallet_tidefi_stake::Call::claim_beneficiary_rewards { .. })
+  )
+}
+
 impl InstanceFilter<Call> for ProxyType {
   fn filter(&self, c: &Call) -> bool {
     match self {
       ProxyType::Any => false,
-      ProxyType::NonTransfer => !matches!(
-        c,
-        Call::Balances(..) | Call::Indices(pallet_indices::Call::transfer { .. })
-      ),
+      ProxyType::NonTransfer => {
+        !matches!(
+          c,
+          Call::Balances(..) | Call::Indices(pallet_indices::Call::transfer { .. })
+        ) && !is_stake_transfer(c)
+      }
       ProxyType::Governance => matches!(
         c,
         Call::Council(..) | Call::TechnicalCommittee(..) | Call::Elections(..) | Call::Treasury(..)
       ),
       ProxyType::Staking => matches!(c, Call::Staking(..)),
+      ProxyType::TidefiStaking => matches!(c, Call::TidefiStaking(..)) && !is_stake_transfer(c),
     }
   }
   fn is_superset(&self, o: &Self) -> bool {
@@ -18,8 +18,8 @@ use crate::{
   constants::currency::{deposit, Adapter, CENTS, DOLLARS},
   types::{AccountId, AssetId, Balance, BlockNumber, SessionIndex},
   AssetRegistry, AssetRegistryPalletId, Balances, CouncilCollectiveInstance, Event, Fees,
-  FeesPalletId, Oracle, OraclePalletId, Origin, Quorum, QuorumPalletId, Runtime, Security,
-  TidefiStaking, TidefiStakingPalletId, Timestamp,
+  FeesPalletId, Oracle, OraclePalletId, Origin, PriceFeed, Quorum, QuorumPalletId, Runtime,
+  Security, TidefiStaking, TidefiStakingPalletId, Timestamp,
 };
 
 use frame_support::{
@@ -72,8 +72,68 @@ parameter_types! {
   pub const WatchListLimit: u32 = 10_000;
   // The maximum number of pubkey each asset can have, should alway be more more than the current quorum active member set
   pub const PubkeyLimitPerAsset: u32 = 10;
+  // The number of blocks of each phase of a key generation round
+  // ~ 30 mins
+  pub const KeygenPhaseTimeout: BlockNumber = 300;
+  // The maximum length of the key generation commitments and shares
+  pub const KeygenPayloadLimit: u32 = 4096;
+  // The number of blocks a finalized proposal is kept in the archive
+  // ~ 90 days
+  pub const ProposalArchiveRetention: BlockNumber = 1_296_000;
+  // The number of blocks a processed mint is kept to reject the replays of its deposit
+  // ~ 1 year
+  pub const MintReplayHorizon: BlockNumber = 5_256_000;
+  // The number of blocks the withdrawals above the delay threshold of the asset are held
+  // ~ 1 day
+  pub const WithdrawalDelay: BlockNumber = 14_400;
+  // The number of blocks the quorum have to confirm the external transfer of a burned withdrawal
+  // ~ 2 days
+  pub const BurnedConfirmationTimeout: BlockNumber = 28_800;
+  // The number of finalized proposals in a row a member can miss before being proposed for removal
+  pub const MaxMissedProposals: u32 = 50;
+  // The number of blocks without vote nor public keys submission before a member is proposed for removal
+  // ~ 7 days
+  pub const MemberInactivityPeriod: BlockNumber = 100_800;
+  // The number of blocks the public keys of the removed quorum members stay valid
+  // ~ 1 day
+  pub const MemberKeysGracePeriod: BlockNumber = 14_400;
+  // The number of blocks of the rolling withdrawal caps of the assets
+  // ~ 1 day
+  pub const WithdrawalPeriod: BlockNumber = 14_400;
+  // The withdrawal fees are collected in the fees pallet account
+  pub WithdrawalFeesAccount: AccountId = FeesPalletId::get().into_account();
   // The number of swap each account can have in queue
   pub const SwapLimitByAccount: u32 = 10_000;
+  // The number of blocks a swap can stay in queue before being expired
+  // ~ 1 day
+  pub const SwapLifetime: BlockNumber = 14_400;
+  // The number of limit orders each side of an order book pair can have, each side is decoded
+  // by every swap, cancellation and confirmation of the pair
+  pub const OrderBookLimit: u32 = 500;
+  // The number of fills kept in the history of each swap
+  pub const SwapFillLimit: u32 = 100;
+  // The number of blocks the fills of a closed swap are kept
+  // ~ 7 days
+  pub const SwapFillRetention: BlockNumber = 14_400 * 7;
+  // The number of blocks without oracle heartbeat before the trading is paused
+  // ~ 10 mins
+  pub const MaxOracleSilence: BlockNumber = 100;
+  // The maximum number of currencies in the path of a routed swap
+  pub const SwapPathLimit: u32 = 4;
+  // The amount of TIFI bonded by the market makers, slashed by the council when they fail to deliver funds
+  pub const MarketMakerBond: Balance = 100_000 * DOLLARS;
+  // The number of blocks the bond of a removed market maker can still be slashed
+  // ~ 7 days
+  pub const MarketMakerUnbondingPeriod: BlockNumber = 14_400 * 7;
+  // The maximum length of a market maker name
+  pub const MarketMakerNameLimit: u32 = 64;
+  // The maximum number of pairs a market maker can support
+  pub const MarketMakerPairsLimit: u32 = 100;
+  // The maximum number of accounts allowed to report prices
+  pub const MaxReporters: u32 = 10;
+  // The number of blocks a reported price is used in the median and can be consumed
+  // ~ 10 mins
+  pub const PriceValidity: BlockNumber = 100;
   // Sunrise Pool: Number of blocks to wait before they can claim the last era reward.
   // current_era.start_block + BlocksSunriseClaims < current_block to be able to claim last era sunrise reward
   pub const BlocksSunriseClaims: BlockNumber = 10;
@@ -120,7 +180,8 @@ impl pallet_assets::Config for Runtime {
   type MetadataDepositPerByte = MetadataDepositPerByte;
   type ApprovalDeposit = ApprovalDeposit;
   type StringLimit = AssetsStringLimit;
-  type Freezer = ();
+  // Lock the assets received by the accounts frozen by the compliance
+  type Freezer = Quorum;
   // The amount of funds that must be reserved for a non-provider asset account to be
   // maintained.
   type AssetAccountDeposit = ConstU128<0>;
@@ -151,6 +212,13 @@ impl pallet_tidefi_stake::Config for Runtime {
   type AssetRegistry = AssetRegistry;
   type Security = Security;
   type StakingRewardCap = StakingRewardCap;
+  // Unstake fees are converted to TIFI and sent to the fees pallet
+  type Fees = Fees;
+  // Manage the staking periods, the currency limits and the unstake fee
+  type ForceOrigin = EnsureOneOf<
+    EnsureRoot<AccountId>,
+    pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollectiveInstance, 2, 3>,
+  >;
   type WeightInfo = crate::weights::pallet_tidefi_stake::WeightInfo<Runtime>;
 }
 
@@ -163,6 +231,8 @@ impl pallet_quorum::Config for Runtime {
   type Security = Security;
   // Asset registry
   type AssetRegistry = AssetRegistry;
+  // Fees, used to value the mints in USDT
+  type Fees = Fees;
   type ProposalsCap = ProposalsCap;
   type BurnedCap = BurnedCap;
   type ProposalLifetime = ProposalLifetime;
@@ -170,6 +240,26 @@ impl pallet_quorum::Config for Runtime {
   type VotesLimit = VotesLimit;
   type WatchListLimit = WatchListLimit;
   type PubkeyLimitPerAsset = PubkeyLimitPerAsset;
+  type KeygenPhaseTimeout = KeygenPhaseTimeout;
+  type KeygenPayloadLimit = KeygenPayloadLimit;
+  type ProposalArchiveRetention = ProposalArchiveRetention;
+  // Compliance decisions on the watch list entries
+  type ComplianceOrigin = EnsureOneOf<
+    EnsureRoot<AccountId>,
+    pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollectiveInstance, 2, 3>,
+  >;
+  type MintReplayHorizon = MintReplayHorizon;
+  type WithdrawalDelay = WithdrawalDelay;
+  type BurnedConfirmationTimeout = BurnedConfirmationTimeout;
+  type MaxMissedProposals = MaxMissedProposals;
+  type MemberInactivityPeriod = MemberInactivityPeriod;
+  type MemberKeysGracePeriod = MemberKeysGracePeriod;
+  // Cancel the delayed withdrawals, along with the quorum members
+  type ForceOrigin = EnsureOneOf<
+    EnsureRoot<AccountId>,
+    pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollectiveInstance, 2, 3>,
+  >;
+  type WithdrawalFeesAccount = WithdrawalFeesAccount;
   type WeightInfo = crate::weights::pallet_quorum::WeightInfo<Runtime>;
 }
 
@@ -180,10 +270,27 @@ impl pallet_oracle::Config for Runtime {
   type CurrencyTidefi = Adapter<AccountId>;
   // Fees management
   type Fees = Fees;
+  // Prices reported by the oracle
+  type PriceFeed = PriceFeed;
   // Security utils
   type Security = Security;
   type SwapLimitByAccount = SwapLimitByAccount;
+  type SwapLifetime = SwapLifetime;
+  type OrderBookLimit = OrderBookLimit;
+  type SwapFillLimit = SwapFillLimit;
+  type SwapFillRetention = SwapFillRetention;
+  type MaxOracleSilence = MaxOracleSilence;
+  type SwapPathLimit = SwapPathLimit;
+  type MarketMakerBond = MarketMakerBond;
+  type MarketMakerUnbondingPeriod = MarketMakerUnbondingPeriod;
+  type MarketMakerNameLimit = MarketMakerNameLimit;
+  type MarketMakerPairsLimit = MarketMakerPairsLimit;
   type WeightInfo = crate::weights::pallet_oracle::WeightInfo<Runtime>;
+  // Same origin as the fees management, to slash the market makers bond
+  type ForceOrigin = EnsureOneOf<
+    EnsureRoot<AccountId>,
+    pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollectiveInstance, 2, 3>,
+  >;
 }
 
 impl pallet_security::Config for Runtime {
@@ -195,6 +302,7 @@ impl pallet_asset_registry::Config for Runtime {
   type AssetRegistryPalletId = AssetRegistryPalletId;
   // Wrapped currency
   type CurrencyTidefi = Adapter<AccountId>;
+  type WithdrawalPeriod = WithdrawalPeriod;
   type WeightInfo = crate::weights::pallet_asset_registry::WeightInfo<Runtime>;
 }
 
@@ -207,6 +315,8 @@ impl pallet_fees::Config for Runtime {
   type SessionsArchive = SessionsArchive;
   type BlocksPerSession = BlocksPerSession;
   type Staking = TidefiStaking;
+  // Prices used to value the fees and sunrise rewards
+  type PriceFeed = PriceFeed;
   // Swap fee for users
   type FeeAmount = FeeAmount;
   // Swap fees for market makers
@@ -220,3 +330,22 @@ impl pallet_fees::Config for Runtime {
     pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollectiveInstance, 2, 3>,
   >;
 }
+
+parameter_types! {
+  // Reporter seeded on runtime upgrade when the chain has no price reporters
+  pub PriceFeedInitialReporter: Option<AccountId> = Oracle::account_id();
+}
+
+impl pallet_price_feed::Config for Runtime {
+  type Event = Event;
+  // Security utils
+  type Security = Security;
+  type MaxReporters = MaxReporters;
+  type PriceValidity = PriceValidity;
+  type WeightInfo = crate::weights::pallet_price_feed::WeightInfo<Runtime>;
+  type InitialReporter = PriceFeedInitialReporter;
+  type ForceOrigin = EnsureOneOf<
+    EnsureRoot<AccountId>,
+    pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollectiveInstance, 2, 3>,
+  >;
+}
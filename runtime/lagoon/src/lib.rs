@@ -32,6 +32,8 @@ pub use frame_system::Call as SystemCall;
 #[cfg(any(feature = "std", test))]
 pub use pallet_balances::Call as BalancesCall;
 #[cfg(any(feature = "std", test))]
+pub use pallet_quorum::ProposalThresholds;
+#[cfg(any(feature = "std", test))]
 pub use pallet_staking::StakerStatus;
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
@@ -234,6 +236,9 @@ construct_runtime!(
 
         // Asset registry module
         AssetRegistry: pallet_asset_registry::{Pallet, Call, Config<T>, Storage, Event<T>} = 56,
+
+        // Price feed module
+        PriceFeed: pallet_price_feed::{Pallet, Call, Config<T>, Storage, Event<T>} = 57,
     }
 );
 
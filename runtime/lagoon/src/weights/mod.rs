@@ -39,6 +39,7 @@ pub mod pallet_membership;
 pub mod pallet_multisig;
 pub mod pallet_oracle;
 pub mod pallet_preimage;
+pub mod pallet_price_feed;
 pub mod pallet_proxy;
 pub mod pallet_quorum;
 pub mod pallet_scheduler;
@@ -0,0 +1,80 @@
+// Copyright 2021-2022 Semantic Network Ltd.
+// This file is part of Tidechain.
+
+// Tidechain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tidechain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Autogenerated weights for `pallet_price_feed`
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
+//! DATE: 2022-03-22, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("lagoon-dev"), DB CACHE: 1024
+
+// Executed Command:
+// target/production/tidechain
+// benchmark
+// --chain=lagoon-dev
+// --steps=50
+// --repeat=20
+// --pallet=pallet_price_feed
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --heap-pages=4096
+// --header=./FILE_TEMPLATE
+// --output=./runtime/lagoon/src/weights/
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for `pallet_price_feed`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> pallet_price_feed::WeightInfo for WeightInfo<T> {
+	// Storage: PriceFeed Reporters (r:1 w:0)
+	// Storage: Security CurrentBlockCount (r:1 w:0)
+	// Storage: PriceFeed MinimumReports (r:1 w:0)
+	// Storage: PriceFeed Reports (r:1 w:1)
+	// Storage: PriceFeed Prices (r:0 w:1)
+	fn submit_prices(p: u32, ) -> Weight {
+		(20_381_000 as Weight)
+			// Standard Error: 12_000
+			.saturating_add((15_894_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(p as Weight)))
+	}
+	// Storage: PriceFeed Reporters (r:1 w:1)
+	fn add_reporter() -> Weight {
+		(19_730_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: PriceFeed Reporters (r:1 w:1)
+	// Storage: PriceFeed MinimumReports (r:1 w:0)
+	fn remove_reporter() -> Weight {
+		(20_114_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: PriceFeed Reporters (r:1 w:0)
+	// Storage: PriceFeed MinimumReports (r:0 w:1)
+	fn set_minimum_reports() -> Weight {
+		(14_862_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+}
@@ -49,4 +49,12 @@ impl<T: frame_system::Config> pallet_asset_registry::WeightInfo for WeightInfo<T
 		(20_426_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
 	}
+	// Storage: AssetRegistry AssetRegistryAccountId (r:1 w:0)
+	// Storage: Assets Asset (r:1 w:0)
+	// Storage: AssetRegistry CurrencyWithdrawalLimits (r:0 w:1)
+	fn set_withdrawal_limits() -> Weight {
+		(21_817_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 }
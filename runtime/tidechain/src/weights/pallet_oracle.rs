@@ -70,10 +70,11 @@ impl<T: frame_system::Config> pallet_oracle::WeightInfo for WeightInfo<T> {
 	// Storage: Fees EraTotalFees (r:2 w:2)
 	// Storage: Fees SessionTotalFees (r:2 w:2)
 	// Storage: Fees AccountFees (r:2 w:2)
+	// Storage: Oracle OrderBook (r:2 w:2)
 	fn confirm_swap() -> Weight {
-		(317_867_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(25 as Weight))
-			.saturating_add(T::DbWeight::get().writes(19 as Weight))
+		(341_115_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(27 as Weight))
+			.saturating_add(T::DbWeight::get().writes(21 as Weight))
 	}
 	fn add_market_maker() -> Weight {
       61_000_300_u64
@@ -85,9 +86,43 @@ impl<T: frame_system::Config> pallet_oracle::WeightInfo for WeightInfo<T> {
          .saturating_add(T::DbWeight::get().reads(6_u64))
          .saturating_add(T::DbWeight::get().writes(5_u64))
    }
+   fn register_market_maker() -> Weight {
+      58_000_300_u64
+         .saturating_add(T::DbWeight::get().reads(3_u64))
+         .saturating_add(T::DbWeight::get().writes(3_u64))
+   }
+   fn slash_market_maker() -> Weight {
+      58_000_300_u64
+         .saturating_add(T::DbWeight::get().reads(5_u64))
+         .saturating_add(T::DbWeight::get().writes(5_u64))
+   }
+   fn withdraw_market_maker_bond() -> Weight {
+      58_000_300_u64
+         .saturating_add(T::DbWeight::get().reads(3_u64))
+         .saturating_add(T::DbWeight::get().writes(3_u64))
+   }
+   fn confirm_swap_route(l: u32) -> Weight {
+      63_000_400_u64
+         .saturating_add(120_000_000_u64.saturating_mul(l as Weight))
+         .saturating_add(T::DbWeight::get().reads(4_u64))
+         .saturating_add(T::DbWeight::get().reads((8_u64).saturating_mul(l as Weight)))
+         .saturating_add(T::DbWeight::get().writes((7_u64).saturating_mul(l as Weight)))
+   }
    fn im_alive() -> Weight {
       64_000_400_u64
          .saturating_add(T::DbWeight::get().reads(6_u64))
          .saturating_add(T::DbWeight::get().writes(5_u64))
    }
+   fn set_order_book_pair() -> Weight {
+      61_000_300_u64
+         .saturating_add(T::DbWeight::get().reads(3_u64))
+         .saturating_add(T::DbWeight::get().writes(5_u64))
+   }
+   // Storage: Oracle OrderBook (r:2 w:2)
+   fn match_order_book(o: u32) -> Weight {
+      18_352_000_u64
+         .saturating_add(41_000_u64.saturating_mul(o as Weight))
+         .saturating_add(T::DbWeight::get().reads(2_u64))
+         .saturating_add(T::DbWeight::get().writes(2_u64))
+   }
 }
@@ -50,9 +50,10 @@ impl<T: frame_system::Config> pallet_quorum::WeightInfo for WeightInfo<T> {
 	// Storage: Security Nonce (r:1 w:1)
 	// Storage: System ParentHash (r:1 w:0)
 	// Storage: Quorum Proposals (r:1 w:1)
+	// Storage: Quorum ProcessedMints (r:1 w:0)
 	fn submit_proposal() -> Weight {
-		(54_697_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+		(55_103_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
 			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 	}
 	// Storage: Quorum Members (r:1 w:0)
@@ -63,10 +64,12 @@ impl<T: frame_system::Config> pallet_quorum::WeightInfo for WeightInfo<T> {
 	// Storage: Quorum Threshold (r:1 w:0)
 	// Storage: Quorum CounterForMembers (r:1 w:0)
 	// Storage: Quorum Proposals (r:1 w:1)
+	// Storage: Quorum MembersLiveness (r:1 w:1)
+	// Storage: Quorum InactiveMemberRemovals (r:1 w:0)
 	fn acknowledge_proposal() -> Weight {
 		(123_195_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(9 as Weight))
-			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+			.saturating_add(T::DbWeight::get().reads(11 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
 	}
 	// Storage: Quorum Members (r:1 w:0)
 	// Storage: Quorum PublicKeys (r:2 w:0)
@@ -76,10 +79,12 @@ impl<T: frame_system::Config> pallet_quorum::WeightInfo for WeightInfo<T> {
 	// Storage: Quorum Threshold (r:1 w:0)
 	// Storage: Quorum CounterForMembers (r:1 w:0)
 	// Storage: Quorum Proposals (r:1 w:1)
+	// Storage: Quorum MembersLiveness (r:1 w:1)
+	// Storage: Quorum InactiveMemberRemovals (r:1 w:0)
 	fn reject_proposal() -> Weight {
 		(74_553_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(9 as Weight))
-			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+			.saturating_add(T::DbWeight::get().reads(11 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
 	}
 	// Storage: Quorum Votes (r:1 w:1)
 	// Storage: Security CurrentBlockCount (r:1 w:0)
@@ -92,11 +97,14 @@ impl<T: frame_system::Config> pallet_quorum::WeightInfo for WeightInfo<T> {
 	}
 	// Storage: Quorum Members (r:1 w:0)
 	// Storage: Quorum PublicKeys (r:0 w:1)
+	// Storage: Security CurrentBlockCount (r:1 w:0)
+	// Storage: Quorum MembersLiveness (r:1 w:1)
 	fn submit_public_keys(k: u32, ) -> Weight {
 		(13_828_000 as Weight)
 			// Standard Error: 11_000
 			.saturating_add((2_888_000 as Weight).saturating_mul(k as Weight))
-			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(k as Weight)))
 	}
 
@@ -105,4 +113,159 @@ impl<T: frame_system::Config> pallet_quorum::WeightInfo for WeightInfo<T> {
       .saturating_add(T::DbWeight::get().reads(6_u64))
       .saturating_add(T::DbWeight::get().writes(5_u64))
    }
+	// Storage: Quorum Members (r:11 w:0)
+	// Storage: Quorum KeygenRounds (r:1 w:1)
+	// Storage: Quorum Threshold (r:1 w:0)
+	// Storage: Quorum KeygenRoundIndex (r:1 w:1)
+	// Storage: Security CurrentBlockCount (r:1 w:0)
+	fn start_keygen() -> Weight {
+		(52_418_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(15 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Quorum KeygenRounds (r:1 w:1)
+	// Storage: Quorum KeygenCommitments (r:11 w:1)
+	// Storage: Security CurrentBlockCount (r:1 w:0)
+	fn submit_keygen_commitment() -> Weight {
+		(48_107_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(13 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Quorum KeygenRounds (r:1 w:1)
+	// Storage: Quorum KeygenCommitments (r:1 w:0)
+	// Storage: Quorum KeygenShares (r:11 w:1)
+	// Storage: Security CurrentBlockCount (r:1 w:0)
+	fn submit_keygen_shares(s: u32, ) -> Weight {
+		(50_272_000 as Weight)
+			// Standard Error: 9_000
+			.saturating_add((1_487_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(14 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Quorum KeygenRounds (r:1 w:0)
+	// Storage: Quorum KeygenShares (r:1 w:0)
+	// Storage: Quorum KeygenComplaints (r:1 w:1)
+	fn submit_keygen_complaint() -> Weight {
+		(44_913_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Quorum KeygenRounds (r:1 w:1)
+	// Storage: Quorum KeygenCommitments (r:10 w:10)
+	// Storage: Quorum KeygenShares (r:11 w:10)
+	// Storage: Quorum KeygenComplaints (r:10 w:10)
+	// Storage: Quorum KeygenConfirmations (r:21 w:10)
+	// Storage: Quorum GroupPublicKeys (r:0 w:1)
+	// Storage: Security CurrentBlockCount (r:1 w:0)
+	fn confirm_keygen() -> Weight {
+		(60_381_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(54 as Weight))
+			.saturating_add(T::DbWeight::get().writes(42 as Weight))
+	}
+	// Storage: Quorum AccountWatchList (r:1 w:0)
+	// Storage: Quorum WatchListDecisions (r:2 w:1)
+	// Storage: AssetRegistry Assets (r:1 w:0)
+	// Storage: Assets Asset (r:1 w:1)
+	// Storage: Assets Account (r:2 w:2)
+	// Storage: Quorum FrozenAccountAssets (r:1 w:1)
+	// Storage: Quorum FrozenWatchedAccounts (r:1 w:1)
+	// Storage: Security CurrentBlockCount (r:1 w:0)
+	fn approve_watch_list_mint() -> Weight {
+		(57_644_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+	// Storage: Quorum AccountWatchList (r:1 w:0)
+	// Storage: Quorum WatchListDecisions (r:2 w:1)
+	// Storage: Assets Account (r:1 w:1)
+	// Storage: Quorum FrozenAccountAssets (r:1 w:1)
+	// Storage: Quorum FrozenWatchedAccounts (r:1 w:1)
+	// Storage: Security CurrentBlockCount (r:1 w:0)
+	fn reject_watch_list_mint() -> Weight {
+		(39_870_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: Quorum AccountWatchList (r:1 w:0)
+	// Storage: Quorum WatchListDecisions (r:2 w:1)
+	// Storage: Assets Account (r:1 w:1)
+	// Storage: Quorum FrozenAccountAssets (r:1 w:1)
+	// Storage: Quorum FrozenWatchedAccounts (r:1 w:1)
+	// Storage: Security CurrentBlockCount (r:1 w:0)
+	fn review_watch_list_entry() -> Weight {
+		(39_512_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: Quorum AccountWatchList (r:1 w:0)
+	// Storage: Quorum WatchListDecisions (r:1 w:0)
+	// Storage: Quorum FrozenWatchedAccounts (r:1 w:1)
+	// Storage: Assets Account (r:2 w:1)
+	// Storage: Security CurrentBlockCount (r:1 w:0)
+	// Storage: Quorum FrozenAccountAssets (r:0 w:1)
+	fn freeze_watched_account(a: u32, ) -> Weight {
+		(37_906_000 as Weight)
+			// Standard Error: 9_000
+			.saturating_add((4_012_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(a as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(a as Weight)))
+	}
+	// Storage: Quorum DelayedWithdrawals (r:1 w:1)
+	// Storage: Assets Asset (r:1 w:0)
+	// Storage: Assets Account (r:1 w:1)
+	// Storage: AssetRegistry CurrencyWithdrawalLimits (r:1 w:0)
+	// Storage: AssetRegistry AssetWithdrawals (r:1 w:1)
+	// Storage: AssetRegistry AccountWithdrawals (r:1 w:1)
+	fn cancel_delayed_withdrawal() -> Weight {
+		(37_845_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: Quorum PublicKeys (r:1 w:0)
+	// Storage: Quorum WithdrawalFeeVotes (r:1 w:1)
+	// Storage: Quorum Members (r:2 w:0)
+	// Storage: Quorum Threshold (r:1 w:0)
+	// Storage: Quorum WithdrawalFees (r:0 w:1)
+	fn submit_withdrawal_fee() -> Weight {
+		(30_512_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Quorum BurnedConfirmations (r:1 w:1)
+	// Storage: Quorum PublicKeys (r:1 w:0)
+	// Storage: Quorum Members (r:2 w:0)
+	// Storage: Quorum Threshold (r:1 w:0)
+	// Storage: Quorum BurnedQueue (r:1 w:1)
+	fn confirm_burned() -> Weight {
+		(35_781_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Quorum BurnedConfirmations (r:1 w:1)
+	// Storage: Quorum PublicKeys (r:1 w:0)
+	// Storage: Quorum Members (r:2 w:0)
+	// Storage: Quorum Threshold (r:1 w:0)
+	// Storage: Quorum BurnedQueue (r:1 w:1)
+	// Storage: Assets Asset (r:1 w:1)
+	// Storage: Assets Account (r:2 w:2)
+	// Storage: System Account (r:0 w:1)
+	fn fail_burned() -> Weight {
+		(53_904_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: Quorum Thresholds (r:0 w:1)
+	fn set_proposal_thresholds() -> Weight {
+		(17_632_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Quorum Members (r:1 w:0)
+	// Storage: Quorum MemberVoteWeights (r:0 w:1)
+	fn set_member_vote_weight() -> Weight {
+		(21_874_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 }
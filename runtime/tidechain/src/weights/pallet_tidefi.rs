@@ -47,14 +47,21 @@ impl<T: frame_system::Config> pallet_tidefi::WeightInfo for WeightInfo<T> {
 	// Storage: Security ChainStatus (r:1 w:0)
 	// Storage: Quorum QuorumStatus (r:1 w:0)
 	// Storage: Assets Asset (r:1 w:0)
-	// Storage: Assets Account (r:1 w:0)
+	// Storage: Assets Account (r:1 w:1)
+	// Storage: AssetRegistry CurrencyWithdrawalLimits (r:1 w:0)
+	// Storage: AssetRegistry AssetWithdrawals (r:1 w:1)
+	// Storage: AssetRegistry AccountWithdrawals (r:1 w:1)
+	// Storage: Quorum WithdrawalFees (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
 	// Storage: Security Nonce (r:1 w:1)
 	// Storage: System ParentHash (r:1 w:0)
-	// Storage: Quorum Withdrawals (r:0 w:1)
+	// Storage: Quorum DelayedWithdrawalsHead (r:1 w:1)
+	// Storage: Quorum DelayedWithdrawals (r:0 w:1)
+	// Storage: Quorum DelayedWithdrawalsQueue (r:0 w:1)
 	fn withdrawal() -> Weight {
-		(56_152_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(6 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+		(86_217_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(12 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
 	}
 	// Storage: Security ChainStatus (r:1 w:0)
 	// Storage: Oracle OracleStatus (r:1 w:0)
@@ -65,11 +72,43 @@ impl<T: frame_system::Config> pallet_tidefi::WeightInfo for WeightInfo<T> {
 	// Storage: System ParentHash (r:1 w:0)
 	// Storage: Fees FeePercentageAmount (r:1 w:0)
 	// Storage: Oracle Swaps (r:0 w:1)
+	// Storage: Oracle OrderBookPairs (r:1 w:0)
+	// Storage: Oracle OrderBook (r:1 w:1)
 	fn swap() -> Weight {
-		(78_490_000 as Weight)
+		(101_873_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Security ChainStatus (r:1 w:0)
+	// Storage: Oracle Swaps (r:1 w:1)
+	// Storage: Fees FeePercentageAmount (r:1 w:0)
+	// Storage: Assets Account (r:1 w:1)
+	// Storage: Oracle AccountSwaps (r:1 w:1)
+	// Storage: Oracle OrderBook (r:1 w:1)
+	// Storage: Oracle ClosedSwapFills (r:0 w:1)
+	// Storage: Oracle ClosedSwapFillsBounds (r:1 w:1)
+	// Storage: Oracle MarketMakerRegistry (r:1 w:0)
+	fn cancel_swap() -> Weight {
+		(84_630_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	fn swap_route() -> Weight {
+		(84_120_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(8 as Weight))
 			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 	}
+	// Storage: Security ChainStatus (r:1 w:0)
+	// Storage: Oracle OracleStatus (r:1 w:0)
+	// Storage: Oracle SwapRoutes (r:1 w:1)
+	// Storage: Fees FeePercentageAmount (r:1 w:0)
+	// Storage: Assets Account (r:1 w:1)
+	// Storage: Oracle AccountSwaps (r:1 w:1)
+	fn cancel_swap_route() -> Weight {
+		(62_410_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
 	fn transfer() -> Weight {
 		(17_096_000 as Weight)
 	}
@@ -55,20 +55,143 @@ impl<T: frame_system::Config> pallet_tidefi_stake::WeightInfo for WeightInfo<T>
 	// Storage: TidefiStaking AccountStakes (r:1 w:1)
 	// Storage: TidefiStaking InterestCompoundLastSession (r:1 w:0)
 	// Storage: TidefiStaking CounterForAccountStakes (r:1 w:1)
+	// Storage: TidefiStaking UnstakeFee (r:1 w:0)
+	// Storage: TidefiStaking StakesTerms (r:0 w:1)
+	// Storage: TidefiStaking RewardPerShare (r:1 w:0)
+	// Storage: TidefiStaking StakeRewardIndex (r:0 w:1)
+	// Storage: TidefiStaking RewardShares (r:1 w:1)
+	// Storage: TidefiStaking StakeExpiryQueueBounds (r:1 w:1)
+	// Storage: TidefiStaking StakeExpiryQueue (r:0 w:1)
 	fn stake() -> Weight {
-		(126_572_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(12 as Weight))
-			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+		(134_916_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(16 as Weight))
+			.saturating_add(T::DbWeight::get().writes(13 as Weight))
 	}
 	// Storage: TidefiStaking AccountStakes (r:1 w:0)
 	// Storage: Security CurrentBlockCount (r:1 w:0)
-	// Storage: TidefiStaking UnstakeFee (r:1 w:0)
+	// Storage: TidefiStaking UnstakeQueue (r:1 w:1)
+	// Storage: TidefiStaking StakesTerms (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	fn unstake() -> Weight {
+		(97_912_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: TidefiStaking StakingPeriodRewards (r:1 w:1)
+	fn set_staking_period() -> Weight {
+		(11_843_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: TidefiStaking StakingPeriodRewards (r:1 w:1)
+	fn remove_staking_period() -> Weight {
+		(11_516_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: TidefiStaking StakingCurrencyMeta (r:0 w:1)
+	fn set_staking_currency_meta() -> Weight {
+		(9_874_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: TidefiStaking UnstakeFee (r:0 w:1)
+	fn set_unstake_fee() -> Weight {
+		(8_967_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: TidefiStaking AccountStakes (r:1 w:1)
+	// Storage: TidefiStaking StakeRewardIndex (r:1 w:1)
+	// Storage: Security CurrentBlockCount (r:1 w:0)
+	// Storage: TidefiStaking UnstakeQueue (r:1 w:0)
+	// Storage: TidefiStaking StakingCurrencyMeta (r:1 w:0)
 	// Storage: Assets Asset (r:1 w:1)
 	// Storage: Assets Account (r:2 w:2)
+	// Storage: TidefiStaking RewardPerShare (r:1 w:0)
+	// Storage: TidefiStaking StakesTerms (r:1 w:0)
+	// Storage: TidefiStaking InterestCompoundLastSession (r:1 w:0)
+	// Storage: TidefiStaking StakingPool (r:1 w:1)
+	// Storage: TidefiStaking RewardShares (r:1 w:1)
+	fn increase_stake() -> Weight {
+		(72_418_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(13 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	// Storage: TidefiStaking AccountStakes (r:1 w:1)
+	// Storage: TidefiStaking UnstakeQueue (r:1 w:0)
+	// Storage: TidefiStaking StakeRewardIndex (r:1 w:1)
+	// Storage: TidefiStaking RewardPerShare (r:1 w:0)
+	// Storage: TidefiStaking StakesTerms (r:1 w:0)
+	// Storage: TidefiStaking InterestCompoundLastSession (r:1 w:0)
+	// Storage: TidefiStaking StakingCurrencyMeta (r:1 w:0)
+	// Storage: Security CurrentBlockCount (r:1 w:0)
+	// Storage: Assets Asset (r:1 w:1)
+	// Storage: Assets Account (r:2 w:2)
+	// Storage: TidefiStaking StakingPool (r:1 w:1)
+	// Storage: TidefiStaking RewardShares (r:1 w:1)
+	fn partial_unstake() -> Weight {
+		(75_206_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(13 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	// Storage: TidefiStaking AccountStakes (r:1 w:0)
+	// Storage: TidefiStaking StakesAutoRenew (r:0 w:1)
+	fn set_stake_auto_renew() -> Weight {
+		(13_152_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 	// Storage: TidefiStaking UnstakeQueue (r:1 w:1)
-	fn unstake() -> Weight {
-		(93_547_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+	fn cancel_queued_unstake() -> Weight {
+		(12_379_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: TidefiStaking AccountStakes (r:1 w:0)
+	// Storage: TidefiStaking UnstakeQueue (r:1 w:0)
+	// Storage: TidefiStaking PendingStakeTransfers (r:0 w:1)
+	fn transfer_stake() -> Weight {
+		(17_405_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: TidefiStaking PendingStakeTransfers (r:1 w:1)
+	fn cancel_stake_transfer() -> Weight {
+		(12_116_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: TidefiStaking PendingStakeTransfers (r:1 w:1)
+	// Storage: TidefiStaking AccountStakes (r:2 w:2)
+	// Storage: TidefiStaking CounterForAccountStakes (r:1 w:1)
+	// Storage: TidefiStaking UnstakeQueue (r:1 w:0)
+	// Storage: TidefiStaking StakeRewardIndex (r:1 w:1)
+	// Storage: TidefiStaking RewardPerShare (r:1 w:0)
+	// Storage: TidefiStaking StakesTerms (r:1 w:0)
+	// Storage: TidefiStaking StakesRewardBeneficiary (r:1 w:1)
+	// Storage: TidefiStaking InterestCompoundLastSession (r:1 w:0)
+	// Storage: TidefiStaking TransferredStakes (r:0 w:1)
+	fn accept_stake_transfer() -> Weight {
+		(52_384_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+	// Storage: TidefiStaking AccountStakes (r:1 w:1)
+	// Storage: TidefiStaking StakeRewardIndex (r:1 w:1)
+	// Storage: TidefiStaking RewardPerShare (r:1 w:0)
+	// Storage: TidefiStaking StakesTerms (r:1 w:0)
+	// Storage: TidefiStaking StakesRewardBeneficiary (r:1 w:1)
+	// Storage: TidefiStaking InterestCompoundLastSession (r:1 w:0)
+	fn set_stake_reward_beneficiary() -> Weight {
+		(31_904_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: TidefiStaking BeneficiaryRewards (r:1 w:1)
+	// Storage: Assets Asset (r:1 w:1)
+	// Storage: Assets Account (r:2 w:2)
+	fn claim_beneficiary_rewards() -> Weight {
+		(58_260_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
 			.saturating_add(T::DbWeight::get().writes(4 as Weight))
 	}
 }